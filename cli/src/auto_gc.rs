@@ -0,0 +1,228 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Garbage collection of the repo, either on request or automatically after
+//! mutating commands.
+//!
+//! Automatic garbage collection runs when enough operations or loose objects
+//! have accumulated since the last collection, but at most once per
+//! `gc.auto-min-interval-hours`.
+
+use std::fs;
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::slice;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use jj_lib::backend::MillisSinceEpoch;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::op_store::OperationId;
+use jj_lib::op_walk;
+use jj_lib::repo::ReadonlyRepo;
+use jj_lib::repo::Repo as _;
+use jj_lib::settings::UserSettings;
+
+use crate::command_error::CommandError;
+use crate::command_error::print_error_sources;
+use crate::ui::Ui;
+
+/// Name of the file in the repo directory which records the head operation
+/// at the last garbage collection and its end time. The modification time of
+/// the file is the time of the collection.
+pub const LAST_GC_FILE_NAME: &str = "last_gc";
+
+/// Objects and operations newer than this are kept by default.
+pub const DEFAULT_EXPIRY: Duration = Duration::from_secs(14 * 86400);
+
+/// Thresholds which trigger automatic garbage collection.
+#[derive(Clone, Debug)]
+pub struct AutoGcConfig {
+    /// Number of operations since the last collection. 0 disables the check.
+    pub operations: u64,
+    /// Estimated number of loose objects in the backend. 0 disables the check.
+    pub loose_objects: u64,
+    /// Minimum time between two automatic collections.
+    pub min_interval: Duration,
+}
+
+impl AutoGcConfig {
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, CommandError> {
+        let hours: u64 = settings.get("gc.auto-min-interval-hours")?;
+        Ok(Self {
+            operations: settings.get("gc.auto-operations")?,
+            loose_objects: settings.get("gc.auto-loose-objects")?,
+            min_interval: Duration::from_secs(hours * 3600),
+        })
+    }
+}
+
+/// Garbage-collects operations and objects of `repo` older than
+/// `keep_newer`, and records the collection.
+pub fn gc_repo(
+    repo: &ReadonlyRepo,
+    repo_path: &Path,
+    keep_newer: SystemTime,
+) -> Result<(), CommandError> {
+    // Record the attempt first so that a failing collection isn't retried
+    // after every command.
+    let op_end_time = repo.operation().metadata().time.end.timestamp;
+    let content = format!("{}\n{}\n", repo.op_id().hex(), op_end_time.0);
+    if let Err(err) = fs::write(repo_path.join(LAST_GC_FILE_NAME), content) {
+        tracing::warn!(?err, "failed to record garbage collection");
+    }
+    repo.op_store()
+        .gc(slice::from_ref(repo.op_id()), keep_newer)?;
+    repo.store().gc(repo.index(), keep_newer)?;
+    Ok(())
+}
+
+/// Returns true if any of the `config` thresholds has been exceeded since the
+/// last garbage collection.
+pub fn needs_gc(
+    config: &AutoGcConfig,
+    repo: &ReadonlyRepo,
+    repo_path: &Path,
+) -> Result<bool, CommandError> {
+    let LastGc {
+        op_id: last_op_id,
+        op_end_time: last_op_end_time,
+        time: last_time,
+    } = read_last_gc(repo_path);
+    if let Some(last_time) = last_time
+        && SystemTime::now() < last_time + config.min_interval
+    {
+        return Ok(false);
+    }
+    if config.operations > 0 {
+        // The recorded operation may have been abandoned since, so also stop at
+        // the first operation older than it. Operations are walked newest
+        // first.
+        let mut num_ops = 0;
+        for op in op_walk::walk_ancestors(slice::from_ref(repo.operation())) {
+            let op = op?;
+            let op_end_time = op.metadata().time.end.timestamp;
+            if Some(op.id()) == last_op_id.as_ref()
+                || last_op_end_time.is_some_and(|time| op_end_time.0 < time.0)
+                || num_ops >= config.operations
+            {
+                break;
+            }
+            num_ops += 1;
+        }
+        if num_ops >= config.operations {
+            return Ok(true);
+        }
+    }
+    if config.loose_objects > 0 {
+        match estimate_loose_objects(repo) {
+            Ok(Some(count)) if count >= config.loose_objects => return Ok(true),
+            Ok(_) => {}
+            Err(err) => tracing::warn!(?err, "failed to count loose objects"),
+        }
+    }
+    Ok(false)
+}
+
+/// Runs garbage collection if it's enabled by `gc.auto` and needed.
+///
+/// Failures are reported as warnings since the operation has already been
+/// committed.
+pub fn maybe_run_auto_gc(
+    ui: &Ui,
+    settings: &UserSettings,
+    repo: &ReadonlyRepo,
+    repo_path: &Path,
+) -> io::Result<()> {
+    let result = settings
+        .get_bool("gc.auto")
+        .map_err(CommandError::from)
+        .and_then(|enabled| {
+            if !enabled {
+                return Ok(false);
+            }
+            let config = AutoGcConfig::from_settings(settings)?;
+            needs_gc(&config, repo, repo_path)
+        })
+        .and_then(|needed| {
+            if needed {
+                writeln!(ui.status(), "Running automatic garbage collection")?;
+                gc_repo(repo, repo_path, SystemTime::now() - DEFAULT_EXPIRY)?;
+            }
+            Ok(())
+        });
+    if let Err(err) = result {
+        writeln!(
+            ui.warning_default(),
+            "Automatic garbage collection failed: {}",
+            err.error
+        )?;
+        print_error_sources(ui, err.error.source())?;
+    }
+    Ok(())
+}
+
+/// Contents of the [`LAST_GC_FILE_NAME`] file.
+struct LastGc {
+    op_id: Option<OperationId>,
+    op_end_time: Option<MillisSinceEpoch>,
+    time: Option<SystemTime>,
+}
+
+fn read_last_gc(repo_path: &Path) -> LastGc {
+    let path = repo_path.join(LAST_GC_FILE_NAME);
+    let content = fs::read_to_string(&path).unwrap_or_default();
+    let mut lines = content.lines();
+    let op_id = lines
+        .next()
+        .and_then(|hex| OperationId::try_from_hex(hex.trim()));
+    let op_end_time = lines
+        .next()
+        .and_then(|millis| millis.trim().parse().ok())
+        .map(MillisSinceEpoch);
+    let time = fs::metadata(&path).and_then(|m| m.modified()).ok();
+    LastGc {
+        op_id,
+        op_end_time,
+        time,
+    }
+}
+
+/// Estimates the number of loose objects the same way as `git gc --auto`,
+/// by sampling one of the object directories.
+#[cfg(feature = "git")]
+fn estimate_loose_objects(repo: &ReadonlyRepo) -> io::Result<Option<u64>> {
+    let Ok(git_backend) = jj_lib::git::get_git_backend(repo.store()) else {
+        return Ok(None);
+    };
+    let sample_dir = git_backend.git_repo_path().join("objects").join("17");
+    let entries = match fs::read_dir(sample_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Some(0)),
+        Err(err) => return Err(err),
+    };
+    let mut count = 0;
+    for entry in entries {
+        if entry?.file_type()?.is_file() {
+            count += 1;
+        }
+    }
+    Ok(Some(count * 256))
+}
+
+#[cfg(not(feature = "git"))]
+fn estimate_loose_objects(_repo: &ReadonlyRepo) -> io::Result<Option<u64>> {
+    Ok(None)
+}
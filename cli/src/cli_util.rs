@@ -14,6 +14,7 @@
 
 use std::borrow::Cow;
 use std::cell::OnceCell;
+use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -21,11 +22,13 @@ use std::env;
 use std::ffi::OsString;
 use std::fmt;
 use std::fmt::Debug;
+use std::fs::File;
 use std::io;
 use std::io::Write as _;
 use std::mem;
 use std::path::Path;
 use std::path::PathBuf;
+use std::process::ExitStatus;
 use std::rc::Rc;
 use std::slice;
 use std::str::FromStr;
@@ -64,6 +67,7 @@ use jj_lib::config::ConfigMigrationRule;
 use jj_lib::config::ConfigNamePathBuf;
 use jj_lib::config::ConfigSource;
 use jj_lib::config::StackedConfig;
+use jj_lib::config::ToConfigNamePath;
 use jj_lib::conflicts::ConflictMarkerStyle;
 use jj_lib::fileset;
 use jj_lib::fileset::FilesetDiagnostics;
@@ -100,19 +104,22 @@ use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::repo_path::RepoPathUiConverter;
 use jj_lib::repo_path::UiPathParseError;
 use jj_lib::revset;
+use jj_lib::revset::FunctionCallNode;
+use jj_lib::revset::LoweringContext;
 use jj_lib::revset::ResolvedRevsetExpression;
 use jj_lib::revset::RevsetAliasesMap;
 use jj_lib::revset::RevsetDiagnostics;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::RevsetExtensions;
 use jj_lib::revset::RevsetFilterPredicate;
-use jj_lib::revset::RevsetFunction;
 use jj_lib::revset::RevsetIteratorExt as _;
 use jj_lib::revset::RevsetModifier;
 use jj_lib::revset::RevsetParseContext;
+use jj_lib::revset::RevsetParseError;
 use jj_lib::revset::RevsetWorkspaceContext;
 use jj_lib::revset::SymbolResolverExtension;
 use jj_lib::revset::UserRevsetExpression;
+use jj_lib::rewrite::RebaseOptions;
 use jj_lib::rewrite::restore_tree;
 use jj_lib::settings::HumanByteSize;
 use jj_lib::settings::UserSettings;
@@ -141,8 +148,11 @@ use tracing::instrument;
 use tracing_chrome::ChromeLayerBuilder;
 use tracing_subscriber::prelude::*;
 
+use crate::auto_gc;
 use crate::command_error::CommandError;
+use crate::command_error::ErrorCode;
 use crate::command_error::cli_error;
+use crate::command_error::config_error;
 use crate::command_error::config_error_with_message;
 use crate::command_error::handle_command_result;
 use crate::command_error::internal_error;
@@ -150,9 +160,12 @@ use crate::command_error::internal_error_with_message;
 use crate::command_error::print_parse_diagnostics;
 use crate::command_error::user_error;
 use crate::command_error::user_error_with_hint;
+use crate::command_error::user_error_with_message;
+use crate::commit_templater::CommitKeywordsExtension;
 use crate::commit_templater::CommitTemplateLanguage;
 use crate::commit_templater::CommitTemplateLanguageExtension;
 use crate::complete;
+use crate::config::CommandNameAndArgs;
 use crate::config::ConfigArgKind;
 use crate::config::ConfigEnv;
 use crate::config::RawConfig;
@@ -166,35 +179,62 @@ use crate::diff_util::DiffRenderer;
 use crate::formatter::FormatRecorder;
 use crate::formatter::Formatter;
 use crate::formatter::FormatterExt as _;
+use crate::hooks;
+use crate::hooks::HookCommit;
+use crate::hooks::HookKind;
+use crate::hooks::PostCheckoutInput;
+use crate::hooks::PostOperationInput;
+use crate::hooks::PreCommitRewriteInput;
 use crate::merge_tools::DiffEditor;
 use crate::merge_tools::MergeEditor;
 use crate::merge_tools::MergeToolConfigError;
+use crate::operation_notify;
 use crate::operation_templater::OperationTemplateLanguage;
 use crate::operation_templater::OperationTemplateLanguageExtension;
+use crate::revision_picker;
 use crate::revset_util;
 use crate::revset_util::RevsetExpressionEvaluator;
 use crate::template_builder;
 use crate::template_builder::TemplateLanguage;
 use crate::template_parser::TemplateAliasesMap;
 use crate::template_parser::TemplateDiagnostics;
+use crate::templater::TemplatePropertyError;
 use crate::templater::TemplateRenderer;
 use crate::templater::WrapTemplateProperty;
+use crate::test_results::TestResultStore;
 use crate::text_util;
 use crate::ui::ColorChoice;
+use crate::ui::OutputFormat;
 use crate::ui::Ui;
 
 const SHORT_CHANGE_ID_TEMPLATE_TEXT: &str = "format_short_change_id(self.change_id())";
 
+type LogLayer = tracing_subscriber::filter::Filtered<
+    tracing_subscriber::fmt::Layer<
+        tracing_subscriber::Registry,
+        tracing_subscriber::fmt::format::DefaultFields,
+        tracing_subscriber::fmt::format::Format,
+        fn() -> io::Stderr,
+    >,
+    tracing_subscriber::reload::Layer<tracing_subscriber::EnvFilter, tracing_subscriber::Registry>,
+    tracing_subscriber::Registry,
+>;
+type ChromeTracingSubscriber =
+    tracing_subscriber::layer::Layered<LogLayer, tracing_subscriber::Registry>;
+type ChromeTracingLayer = tracing_chrome::ChromeLayer<ChromeTracingSubscriber>;
+
+/// Handle to replace the Chrome tracing layer, and the guard to flush the
+/// current trace file on drop.
 #[derive(Clone)]
-struct ChromeTracingFlushGuard {
-    _inner: Option<Rc<tracing_chrome::FlushGuard>>,
+struct ChromeTracing {
+    reload_layer:
+        tracing_subscriber::reload::Handle<Option<ChromeTracingLayer>, ChromeTracingSubscriber>,
+    flush_guard: Rc<RefCell<Option<tracing_chrome::FlushGuard>>>,
 }
 
-impl Debug for ChromeTracingFlushGuard {
+impl Debug for ChromeTracing {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let Self { _inner } = self;
-        f.debug_struct("ChromeTracingFlushGuard")
-            .finish_non_exhaustive()
+        f.debug_struct("ChromeTracing").finish_non_exhaustive()
     }
 }
 
@@ -205,7 +245,20 @@ pub struct TracingSubscription {
         tracing_subscriber::EnvFilter,
         tracing_subscriber::Registry,
     >,
-    _chrome_tracing_flush_guard: ChromeTracingFlushGuard,
+    chrome_tracing: ChromeTracing,
+}
+
+fn default_trace_file_name(prefix: &str) -> String {
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    format!("{prefix}-{timestamp}.json")
+}
+
+fn chrome_tracing_layer_builder() -> ChromeLayerBuilder<ChromeTracingSubscriber> {
+    let include_args = std::env::var("JJ_TRACE_INCLUDE_ARGS").is_ok();
+    ChromeLayerBuilder::new().include_args(include_args)
 }
 
 impl TracingSubscription {
@@ -223,45 +276,59 @@ impl TracingSubscription {
         let (chrome_tracing_layer, chrome_tracing_flush_guard) = match std::env::var("JJ_TRACE") {
             Ok(filename) => {
                 let filename = if filename.is_empty() {
-                    format!(
-                        "jj-trace-{}.json",
-                        SystemTime::now()
-                            .duration_since(SystemTime::UNIX_EPOCH)
-                            .unwrap()
-                            .as_secs(),
-                    )
+                    default_trace_file_name("jj-trace")
                 } else {
                     filename
                 };
-                let include_args = std::env::var("JJ_TRACE_INCLUDE_ARGS").is_ok();
-                let (layer, guard) = ChromeLayerBuilder::new()
-                    .file(filename)
-                    .include_args(include_args)
-                    .build();
-                (
-                    Some(layer),
-                    ChromeTracingFlushGuard {
-                        _inner: Some(Rc::new(guard)),
-                    },
-                )
+                let (layer, guard) = chrome_tracing_layer_builder().file(filename).build();
+                (Some(layer), Some(guard))
             }
-            Err(_) => (None, ChromeTracingFlushGuard { _inner: None }),
+            Err(_) => (None, None),
         };
+        let (chrome_tracing_layer, reload_chrome_tracing_layer) =
+            tracing_subscriber::reload::Layer::new(chrome_tracing_layer);
 
         tracing_subscriber::registry()
             .with(
                 tracing_subscriber::fmt::Layer::default()
-                    .with_writer(std::io::stderr)
+                    .with_writer(io::stderr as fn() -> io::Stderr)
                     .with_filter(filter),
             )
             .with(chrome_tracing_layer)
             .init();
         Self {
             reload_log_filter,
-            _chrome_tracing_flush_guard: chrome_tracing_flush_guard,
+            chrome_tracing: ChromeTracing {
+                reload_layer: reload_chrome_tracing_layer,
+                flush_guard: Rc::new(RefCell::new(chrome_tracing_flush_guard)),
+            },
         }
     }
 
+    /// Starts writing timing spans in the Chrome trace format to `path`, or to
+    /// a new file in the current directory if `path` is `None`.
+    pub fn enable_profiling(&self, path: Option<&Path>) -> Result<(), CommandError> {
+        let path = path.map_or_else(
+            || PathBuf::from(default_trace_file_name("jj-profile")),
+            |path| path.to_owned(),
+        );
+        let file = File::create(&path).map_err(|err| {
+            user_error_with_message(
+                format!("Failed to create profile file {}", path.display()),
+                err,
+            )
+        })?;
+        let (layer, guard) = chrome_tracing_layer_builder().writer(file).build();
+        self.chrome_tracing
+            .reload_layer
+            .reload(Some(layer))
+            .map_err(|err| internal_error_with_message("failed to enable profiling", err))?;
+        // Dropping the previous guard (if any) flushes the previous trace.
+        *self.chrome_tracing.flush_guard.borrow_mut() = Some(guard);
+        tracing::info!(path = %path.display(), "profiling enabled");
+        Ok(())
+    }
+
     pub fn enable_debug_logging(&self) -> Result<(), CommandError> {
         self.reload_log_filter
             .modify(|filter| {
@@ -345,8 +412,9 @@ impl CommandHelper {
     /// Settings for the current command and workspace.
     ///
     /// This may be different from the settings for new workspace created by
-    /// e.g. `jj git init`. There may be conditional variables and repo config
-    /// `.jj/repo/config.toml` loaded for the cwd workspace.
+    /// e.g. `jj git init`. There may be conditional variables, repo config
+    /// `.jj/repo/config.toml`, and workspace config `.jj/config.toml` loaded for
+    /// the cwd workspace.
     pub fn settings(&self) -> &UserSettings {
         &self.data.settings
     }
@@ -360,7 +428,9 @@ impl CommandHelper {
         let mut raw_config = self.data.raw_config.clone();
         let repo_path = workspace_root.join(".jj").join("repo");
         config_env.reset_repo_path(&repo_path);
+        config_env.reset_workspace_path(workspace_root);
         config_env.reload_repo_config(&mut raw_config)?;
+        config_env.reload_workspace_config(&mut raw_config)?;
         let mut config = config_env.resolve_config(&raw_config)?;
         // No migration messages here, which would usually be emitted before.
         jj_lib::config::migrate(&mut config, &self.data.config_migrations)?;
@@ -436,7 +506,33 @@ impl CommandHelper {
         &self,
         ui: &Ui,
     ) -> Result<(WorkspaceCommandHelper, SnapshotStats), CommandError> {
-        let mut workspace_command = self.workspace_helper_no_snapshot(ui)?;
+        let (workspace_command, stats) = self.workspace_helper_with_stats_without_index(ui)?;
+        workspace_command.repo().load_index()?;
+        Ok((workspace_command, stats))
+    }
+
+    /// Loads workspace and repo, then snapshots the working copy if allowed,
+    /// but doesn't load the index unless the snapshot needs it.
+    ///
+    /// Use this for commands that don't evaluate revsets, render commit
+    /// templates, or start transactions, so they can skip the cost of loading
+    /// the index. Accessing the index without loading it with
+    /// [`ReadonlyRepo::load_index()`] first panics if it can't be read.
+    #[instrument(skip(self, ui))]
+    pub fn workspace_helper_without_index(
+        &self,
+        ui: &Ui,
+    ) -> Result<WorkspaceCommandHelper, CommandError> {
+        let (workspace_command, stats) = self.workspace_helper_with_stats_without_index(ui)?;
+        print_snapshot_stats(ui, &stats, workspace_command.env().path_converter())?;
+        Ok(workspace_command)
+    }
+
+    fn workspace_helper_with_stats_without_index(
+        &self,
+        ui: &Ui,
+    ) -> Result<(WorkspaceCommandHelper, SnapshotStats), CommandError> {
+        let mut workspace_command = self.workspace_helper_no_snapshot_without_index(ui)?;
 
         let (workspace_command, stats) = match workspace_command.maybe_snapshot_impl(ui) {
             Ok(stats) => (workspace_command, stats),
@@ -451,6 +547,10 @@ impl CommandHelper {
                 // auto-update-stale, so let's do that now. We need to do it up here, not at a
                 // lower level (e.g. inside snapshot_working_copy()) to avoid recursive locking
                 // of the working copy.
+                writeln!(
+                    ui.status(),
+                    "The working copy is stale; updating it automatically"
+                )?;
                 self.recover_stale_working_copy(ui)?
             }
         };
@@ -464,10 +564,19 @@ impl CommandHelper {
     pub fn workspace_helper_no_snapshot(
         &self,
         ui: &Ui,
+    ) -> Result<WorkspaceCommandHelper, CommandError> {
+        let workspace_command = self.workspace_helper_no_snapshot_without_index(ui)?;
+        workspace_command.repo().load_index()?;
+        Ok(workspace_command)
+    }
+
+    fn workspace_helper_no_snapshot_without_index(
+        &self,
+        ui: &Ui,
     ) -> Result<WorkspaceCommandHelper, CommandError> {
         let workspace = self.load_workspace()?;
         let op_head = self.resolve_operation(ui, workspace.repo_loader())?;
-        let repo = workspace.repo_loader().load_at(&op_head)?;
+        let repo = workspace.repo_loader().load_at_without_index(&op_head)?;
         let env = self.workspace_environment(ui, &workspace)?;
         revset_util::warn_unresolvable_trunk(ui, repo.as_ref(), &env.revset_parse_context())?;
         WorkspaceCommandHelper::new(ui, workspace, repo, env, self.is_at_head_operation())
@@ -651,7 +760,7 @@ impl CommandHelper {
                     let mut tx = start_repo_transaction(&base_repo, &self.data.string_args);
                     for other_op_head in op_heads.into_iter().skip(1) {
                         tx.merge_operation(other_op_head)?;
-                        let num_rebased = tx.repo_mut().rebase_descendants()?;
+                        let num_rebased = rebase_descendants_with_progress(ui, tx.repo_mut())?;
                         if num_rebased > 0 {
                             writeln!(
                                 ui.status(),
@@ -785,6 +894,7 @@ pub struct WorkspaceCommandEnvironment {
     immutable_heads_expression: Arc<UserRevsetExpression>,
     short_prefixes_expression: Option<Arc<UserRevsetExpression>>,
     conflict_marker_style: ConflictMarkerStyle,
+    test_results: Option<TestResultStore>,
 }
 
 impl WorkspaceCommandEnvironment {
@@ -807,6 +917,10 @@ impl WorkspaceCommandEnvironment {
             immutable_heads_expression: RevsetExpression::root(),
             short_prefixes_expression: None,
             conflict_marker_style: settings.get("ui.conflict-marker-style")?,
+            test_results: settings
+                .get::<CommandNameAndArgs>("test.command")
+                .optional()?
+                .map(|test_command| TestResultStore::new(workspace.repo_path(), &test_command)),
         };
         env.immutable_heads_expression = env.load_immutable_heads_expression(ui)?;
         env.short_prefixes_expression = env.load_short_prefixes_expression(ui)?;
@@ -982,6 +1096,7 @@ impl WorkspaceCommandEnvironment {
             id_prefix_context,
             self.immutable_expression(),
             self.conflict_marker_style,
+            self.test_results.as_ref(),
             &self.command.data.commit_template_extensions,
         )
     }
@@ -1002,6 +1117,9 @@ pub struct WorkspaceCommandHelper {
     op_summary_template_text: String,
     may_update_working_copy: bool,
     working_copy_shared_with_git: bool,
+    /// Whether the working copy is never snapshotted, and the working-copy
+    /// commit of this workspace can't be changed.
+    read_only: bool,
 }
 
 enum SnapshotWorkingCopyError {
@@ -1041,6 +1159,7 @@ impl WorkspaceCommandHelper {
             loaded_at_head && !env.command.global_args().ignore_working_copy;
         let working_copy_shared_with_git =
             crate::git_util::is_colocated_git_workspace(&workspace, &repo);
+        let read_only = settings.get_bool("workspace.read-only")?;
 
         let helper = Self {
             workspace,
@@ -1050,12 +1169,25 @@ impl WorkspaceCommandHelper {
             op_summary_template_text,
             may_update_working_copy,
             working_copy_shared_with_git,
+            read_only,
         };
         // Parse commit_summary template early to report error before starting
-        // mutable operation.
+        // mutable operation. Validation doesn't need the short-prefixes index,
+        // so don't load the repo index for it.
         helper.parse_operation_template(ui, &helper.op_summary_template_text)?;
-        helper.parse_commit_template(ui, &helper.commit_summary_template_text)?;
-        helper.parse_commit_template(ui, SHORT_CHANGE_ID_TEMPLATE_TEXT)?;
+        {
+            let id_prefix_context =
+                IdPrefixContext::new(helper.env.command.revset_extensions().clone());
+            let language = helper
+                .env
+                .commit_template_language(helper.repo().as_ref(), &id_prefix_context);
+            helper.parse_template::<Commit, _>(
+                ui,
+                &language,
+                &helper.commit_summary_template_text,
+            )?;
+            helper.parse_template::<Commit, _>(ui, &language, SHORT_CHANGE_ID_TEMPLATE_TEXT)?;
+        }
         Ok(helper)
     }
 
@@ -1085,7 +1217,7 @@ impl WorkspaceCommandHelper {
     /// this function to present possible untracked files to the user.
     #[instrument(skip_all)]
     fn maybe_snapshot_impl(&mut self, ui: &Ui) -> Result<SnapshotStats, SnapshotWorkingCopyError> {
-        if !self.may_update_working_copy {
+        if !self.may_update_working_copy || self.read_only {
             return Ok(SnapshotStats::default());
         }
 
@@ -1128,6 +1260,10 @@ impl WorkspaceCommandHelper {
     #[instrument(skip_all)]
     fn import_git_head(&mut self, ui: &Ui) -> Result<(), CommandError> {
         assert!(self.may_update_working_copy);
+        if !jj_lib::git::has_head_to_import(self.repo().as_ref())? {
+            return Ok(());
+        }
+        self.repo().load_index()?;
         let mut tx = self.start_transaction();
         jj_lib::git::import_head(tx.repo_mut())?;
         if !tx.repo().has_changes() {
@@ -1157,9 +1293,11 @@ impl WorkspaceCommandHelper {
             // HEAD, so we just need to reset our working copy
             // state to it without updating working copy files.
             locked_ws.locked_wc().reset(&new_git_head_commit)?;
-            tx.repo_mut().rebase_descendants()?;
+            rebase_descendants_with_progress(ui, tx.repo_mut())?;
+            let old_repo = tx.base_repo().clone();
             self.user_repo = ReadonlyUserRepo::new(tx.commit("import git head")?);
             locked_ws.finish(self.user_repo.repo.op_id().clone())?;
+            self.notify_operation_completed(&old_repo);
             if old_git_head.is_present() {
                 writeln!(
                     ui.status(),
@@ -1170,7 +1308,7 @@ impl WorkspaceCommandHelper {
             }
         } else {
             // Unlikely, but the HEAD ref got deleted by git?
-            self.finish_transaction(ui, tx, "import git head")?;
+            self.finish_transaction(ui, tx, "import git head", true)?;
         }
         Ok(())
     }
@@ -1187,6 +1325,10 @@ impl WorkspaceCommandHelper {
     #[instrument(skip_all)]
     fn import_git_refs(&mut self, ui: &Ui) -> Result<(), CommandError> {
         let git_settings = self.settings().git_settings()?;
+        if !jj_lib::git::has_refs_to_import(self.repo().as_ref())? {
+            return Ok(());
+        }
+        self.repo().load_index()?;
         let mut tx = self.start_transaction();
         let stats = jj_lib::git::import_refs(tx.repo_mut(), &git_settings)?;
         crate::git_util::print_git_import_stats(ui, tx.repo(), &stats, false)?;
@@ -1196,14 +1338,14 @@ impl WorkspaceCommandHelper {
 
         let mut tx = tx.into_inner();
         // Rebase here to show slightly different status message.
-        let num_rebased = tx.repo_mut().rebase_descendants()?;
+        let num_rebased = rebase_descendants_with_progress(ui, tx.repo_mut())?;
         if num_rebased > 0 {
             writeln!(
                 ui.status(),
                 "Rebased {num_rebased} descendant commits off of commits rewritten from git"
             )?;
         }
-        self.finish_transaction(ui, tx, "import git refs")?;
+        self.finish_transaction(ui, tx, "import git refs", true)?;
         writeln!(
             ui.status(),
             "Done importing changes from the underlying Git repo."
@@ -1223,6 +1365,23 @@ impl WorkspaceCommandHelper {
         &self.workspace
     }
 
+    /// Runs the configured hook of the `kind` in this workspace.
+    pub fn run_hook(
+        &self,
+        ui: &Ui,
+        kind: HookKind,
+        input: &impl serde::Serialize,
+    ) -> Result<(), CommandError> {
+        hooks::run_hook(
+            ui,
+            self.settings(),
+            self.workspace_root(),
+            self.repo_path(),
+            kind,
+            input,
+        )
+    }
+
     pub fn working_copy(&self) -> &dyn WorkingCopy {
         self.workspace.working_copy()
     }
@@ -1373,14 +1532,52 @@ to the current parents may contain changes from multiple commits.
         if max_new_file_size == 0 {
             max_new_file_size = u64::MAX;
         }
+        let max_new_file_size_overrides = self.max_new_file_size_overrides()?;
         Ok(SnapshotOptions {
             base_ignores,
             progress: None,
             start_tracking_matcher,
             max_new_file_size,
+            max_new_file_size_overrides,
         })
     }
 
+    /// Parses the `snapshot.max-new-file-size-overrides` config table.
+    fn max_new_file_size_overrides(&self) -> Result<Vec<(FilesetExpression, u64)>, CommandError> {
+        let settings = self.settings();
+        settings
+            .table_keys("snapshot.max-new-file-size-overrides")
+            // Sort keys early so errors are deterministic.
+            .sorted()
+            .map(|name| -> Result<_, CommandError> {
+                let patterns: Vec<String> =
+                    settings.get(["snapshot", "max-new-file-size-overrides", name, "patterns"])?;
+                let HumanByteSize(mut max_size) = settings.get_value_with(
+                    ["snapshot", "max-new-file-size-overrides", name, "max-size"],
+                    TryInto::try_into,
+                )?;
+                if max_size == 0 {
+                    max_size = u64::MAX;
+                }
+                let mut diagnostics = FilesetDiagnostics::new();
+                let expressions: Vec<_> = patterns
+                    .iter()
+                    .map(|pattern| {
+                        fileset::parse(
+                            &mut diagnostics,
+                            pattern,
+                            &RepoPathUiConverter::Fs {
+                                cwd: "".into(),
+                                base: "".into(),
+                            },
+                        )
+                    })
+                    .try_collect()?;
+                Ok((FilesetExpression::union_all(expressions), max_size))
+            })
+            .try_collect()
+    }
+
     pub(crate) fn path_converter(&self) -> &RepoPathUiConverter {
         self.env.path_converter()
     }
@@ -1611,6 +1808,11 @@ to the current parents may contain changes from multiple commits.
         ui: &Ui,
         revision_arg: &RevisionArg,
     ) -> Result<(RevsetExpressionEvaluator<'_>, Option<RevsetModifier>), CommandError> {
+        if revision_arg.as_ref() == revision_picker::PICK_REVISION_ARG {
+            let commit = self.pick_revision(ui)?;
+            let expression = RevsetExpression::commit(commit.id().clone());
+            return Ok((self.attach_revset_evaluator(expression), None));
+        }
         let mut diagnostics = RevsetDiagnostics::new();
         let context = self.env.revset_parse_context();
         let (expression, modifier) =
@@ -1619,6 +1821,26 @@ to the current parents may contain changes from multiple commits.
         Ok((self.attach_revset_evaluator(expression), modifier))
     }
 
+    /// Lets the user pick one of the revisions in `revsets.log`.
+    fn pick_revision(&self, ui: &Ui) -> Result<Commit, CommandError> {
+        let mut diagnostics = RevsetDiagnostics::new();
+        let context = self.env.revset_parse_context();
+        let revset_string = self.settings().get_string("revsets.log")?;
+        let expression = revset::parse(&mut diagnostics, &revset_string, &context)?;
+        print_parse_diagnostics(ui, "In revset expression", &diagnostics)?;
+        let commits: Vec<Commit> = self
+            .attach_revset_evaluator(expression)
+            .evaluate_to_commits()?
+            .try_collect()?;
+        let template = self.commit_summary_template();
+        let labels = commits
+            .iter()
+            .map(|commit| String::from_utf8_lossy(&template.format_plain_text(commit)).into_owned())
+            .collect_vec();
+        let index = revision_picker::pick(ui, &labels)?;
+        Ok(commits[index].clone())
+    }
+
     /// Parses the given revset expressions and concatenates them all.
     pub fn parse_union_revsets(
         &self,
@@ -1629,8 +1851,15 @@ to the current parents may contain changes from multiple commits.
         let context = self.env.revset_parse_context();
         let expressions: Vec<_> = revision_args
             .iter()
-            .map(|arg| revset::parse_with_modifier(&mut diagnostics, arg.as_ref(), &context))
-            .map_ok(|(expression, None | Some(RevsetModifier::All))| expression)
+            .map(|arg| {
+                if arg.as_ref() == revision_picker::PICK_REVISION_ARG {
+                    let commit = self.pick_revision(ui)?;
+                    return Ok(RevsetExpression::commit(commit.id().clone()));
+                }
+                let (expression, None | Some(RevsetModifier::All)) =
+                    revset::parse_with_modifier(&mut diagnostics, arg.as_ref(), &context)?;
+                Ok::<_, CommandError>(expression)
+            })
             .try_collect()?;
         print_parse_diagnostics(ui, "In revset expression", &diagnostics)?;
         let expression = RevsetExpression::union_all(&expressions);
@@ -1814,7 +2043,7 @@ to the current parents may contain changes from multiple commits.
 
             error
         };
-        Err(error)
+        Err(error.with_code(ErrorCode::ImmutableCommit))
     }
 
     #[instrument(skip_all)]
@@ -1875,7 +2104,8 @@ to the current parents may contain changes from multiple commits.
                             "Run `jj workspace update-stale` to update it.
 See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
                              for more information.",
-                        ),
+                        )
+                        .with_code(ErrorCode::StaleWorkingCopy),
                     ));
                 }
                 Ok(WorkingCopyFreshness::SiblingOperation) => {
@@ -1895,12 +2125,14 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
                             "Run `jj workspace update-stale` to recover.
 See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
                              for more information.",
-                        ),
+                        )
+                        .with_code(ErrorCode::StaleWorkingCopy),
                     ));
                 }
                 Err(e) => return Err(snapshot_command_error(e)),
             };
         self.user_repo = ReadonlyUserRepo::new(repo);
+        let old_repo = self.user_repo.repo.clone();
         let (new_tree_id, stats) = {
             let mut options = options;
             let progress = crate::progress::snapshot_progress(ui);
@@ -1911,6 +2143,10 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
                 .map_err(snapshot_command_error)?
         };
         if new_tree_id != *wc_commit.tree_id() {
+            self.user_repo
+                .repo
+                .load_index()
+                .map_err(snapshot_command_error)?;
             let mut tx =
                 start_repo_transaction(&self.user_repo.repo, self.env.command.string_args());
             tx.set_is_snapshot(true);
@@ -1925,9 +2161,8 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
                 .map_err(snapshot_command_error)?;
 
             // Rebase descendants
-            let num_rebased = mut_repo
-                .rebase_descendants()
-                .map_err(snapshot_command_error)?;
+            let num_rebased =
+                rebase_descendants_with_progress(ui, mut_repo).map_err(snapshot_command_error)?;
             if num_rebased > 0 {
                 writeln!(
                     ui.status(),
@@ -1960,6 +2195,7 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
         locked_ws
             .finish(self.user_repo.repo.op_id().clone())
             .map_err(snapshot_command_error)?;
+        self.notify_operation_completed(&old_repo);
         Ok(stats)
     }
 
@@ -1970,13 +2206,24 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
         new_commit: &Commit,
     ) -> Result<(), CommandError> {
         assert!(self.may_update_working_copy);
+        let spinner = crate::progress::spinner(ui, "Updating working copy");
         let stats = update_working_copy(
             &self.user_repo.repo,
             &mut self.workspace,
             maybe_old_commit,
             new_commit,
         )?;
-        self.print_updated_working_copy_stats(ui, maybe_old_commit, new_commit, &stats)
+        drop(spinner);
+        self.print_updated_working_copy_stats(ui, maybe_old_commit, new_commit, &stats)?;
+        if Some(new_commit) != maybe_old_commit {
+            let input = PostCheckoutInput {
+                workspace: self.workspace_name().as_str().to_owned(),
+                old_commit_id: maybe_old_commit.map(|commit| commit.id().clone()),
+                new_commit_id: new_commit.id().clone(),
+            };
+            self.run_hook(ui, HookKind::PostCheckout, &input)?;
+        }
+        Ok(())
     }
 
     fn print_updated_working_copy_stats(
@@ -2023,6 +2270,7 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
             helper: self,
             tx,
             id_prefix_context,
+            pre_commit_rewrite_hook_run: false,
         }
     }
 
@@ -2031,12 +2279,14 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
         ui: &Ui,
         mut tx: Transaction,
         description: impl Into<String>,
+        run_pre_commit_rewrite_hook: bool,
     ) -> Result<(), CommandError> {
+        let description = description.into();
         if !tx.repo().has_changes() {
             writeln!(ui.status(), "Nothing changed.")?;
             return Ok(());
         }
-        let num_rebased = tx.repo_mut().rebase_descendants()?;
+        let num_rebased = rebase_descendants_with_progress(ui, tx.repo_mut())?;
         if num_rebased > 0 {
             writeln!(ui.status(), "Rebased {num_rebased} descendant commits")?;
         }
@@ -2058,6 +2308,10 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
             }
         }
 
+        if run_pre_commit_rewrite_hook {
+            self.run_pre_commit_rewrite_hook(ui, &tx, &description)?;
+        }
+
         let old_repo = tx.base_repo().clone();
 
         let maybe_old_wc_commit = old_repo
@@ -2071,6 +2325,18 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
             .get_wc_commit_id(self.workspace_name())
             .map(|commit_id| tx.repo().store().get_commit(commit_id))
             .transpose()?;
+        if self.read_only
+            && let Some(new_commit) = &maybe_new_wc_commit
+            && maybe_old_wc_commit.as_ref().map(|c| c.id()) != Some(new_commit.id())
+        {
+            return Err(user_error_with_hint(
+                format!(
+                    "Workspace {name} is read-only, so its working-copy commit can't be changed",
+                    name = self.workspace_name().as_symbol()
+                ),
+                "Run the command in another workspace, or set `workspace.read-only = false`.",
+            ));
+        }
 
         #[cfg(feature = "git")]
         if self.working_copy_shared_with_git {
@@ -2091,7 +2357,8 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
             crate::git_util::print_git_export_stats(ui, &stats)?;
         }
 
-        self.user_repo = ReadonlyUserRepo::new(tx.commit(description)?);
+        self.user_repo = ReadonlyUserRepo::new(tx.commit(description.clone())?);
+        self.notify_operation_completed(&old_repo);
 
         // Update working copy before reporting repo changes, so that
         // potential errors while reporting changes (broken pipe, etc)
@@ -2107,6 +2374,16 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
 
         self.report_repo_changes(ui, &old_repo)?;
 
+        let input = PostOperationInput {
+            operation_id: self.repo().op_id().hex(),
+            description,
+        };
+        self.run_hook(ui, HookKind::PostOperation, &input)?;
+
+        if self.env.command.is_at_head_operation() {
+            auto_gc::maybe_run_auto_gc(ui, self.settings(), self.repo(), self.repo_path())?;
+        }
+
         let settings = self.settings();
         let missing_user_name = settings.user_name().is_empty();
         let missing_user_mail = settings.user_email().is_empty();
@@ -2139,6 +2416,62 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
         Ok(())
     }
 
+    /// Notifies editors and other tools that an operation was committed on top
+    /// of `old_repo`.
+    fn notify_operation_completed(&self, old_repo: &ReadonlyRepo) {
+        operation_notify::notify_operation_completed(
+            self.settings(),
+            self.repo_path(),
+            old_repo,
+            self.repo(),
+        );
+    }
+
+    /// Runs the `pre-commit-rewrite` hook if the transaction adds or hides
+    /// commits.
+    fn run_pre_commit_rewrite_hook(
+        &self,
+        ui: &Ui,
+        tx: &Transaction,
+        description: &str,
+    ) -> Result<(), CommandError> {
+        if !hooks::is_hook_enabled(self.settings(), HookKind::PreCommitRewrite)? {
+            return Ok(());
+        }
+        let repo = tx.repo();
+        let old_heads =
+            RevsetExpression::commits(tx.base_repo().view().heads().iter().cloned().collect());
+        let new_heads = RevsetExpression::commits(repo.view().heads().iter().cloned().collect());
+        let new_commits: Vec<_> = old_heads
+            .range(&new_heads)
+            .evaluate(repo)?
+            .iter()
+            .commits(repo.store())
+            .map_ok(|commit| {
+                let predecessor_ids = repo
+                    .commit_predecessors()
+                    .get(commit.id())
+                    .cloned()
+                    .unwrap_or_default();
+                HookCommit::new(&commit, predecessor_ids)
+            })
+            .try_collect()?;
+        let hidden_commit_ids: Vec<_> = new_heads
+            .range(&old_heads)
+            .evaluate(repo)?
+            .iter()
+            .try_collect()?;
+        if new_commits.is_empty() && hidden_commit_ids.is_empty() {
+            return Ok(());
+        }
+        let input = PreCommitRewriteInput {
+            operation: description.to_owned(),
+            new_commits,
+            hidden_commit_ids,
+        };
+        self.run_hook(ui, HookKind::PreCommitRewrite, &input)
+    }
+
     /// Inform the user about important changes to the repo since the previous
     /// operation (when `old_repo` was loaded).
     fn report_repo_changes(
@@ -2362,6 +2695,9 @@ pub struct WorkspaceCommandTransaction<'a> {
     tx: Transaction,
     /// Cache of index built against the current MutableRepo state.
     id_prefix_context: OnceCell<IdPrefixContext>,
+    /// Whether the `pre-commit-rewrite` hook has already been run by
+    /// [`WorkspaceCommandTransaction::run_pre_commit_rewrite_hook`].
+    pre_commit_rewrite_hook_run: bool,
 }
 
 impl WorkspaceCommandTransaction<'_> {
@@ -2400,6 +2736,11 @@ impl WorkspaceCommandTransaction<'_> {
         self.tx.repo_mut().edit(name, commit)
     }
 
+    /// Adds a tag to the metadata of the operation to be created.
+    pub fn set_tag(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.tx.set_tag(key.into(), value.into());
+    }
+
     pub fn format_commit_summary(&self, commit: &Commit) -> String {
         let output = self.commit_summary_template().format_plain_text(commit);
         output.into_string_lossy()
@@ -2443,7 +2784,27 @@ impl WorkspaceCommandTransaction<'_> {
     }
 
     pub fn finish(self, ui: &Ui, description: impl Into<String>) -> Result<(), CommandError> {
-        self.helper.finish_transaction(ui, self.tx, description)
+        let run_pre_commit_rewrite_hook = !self.pre_commit_rewrite_hook_run;
+        self.helper
+            .finish_transaction(ui, self.tx, description, run_pre_commit_rewrite_hook)
+    }
+
+    /// Runs the `pre-commit-rewrite` hook now instead of when the transaction
+    /// is finished.
+    ///
+    /// Use this before making changes outside of the repo, which couldn't be
+    /// rolled back if the hook aborted the operation. The hook isn't run again
+    /// when the transaction is finished, so the transaction shouldn't create or
+    /// rewrite commits after this.
+    pub fn run_pre_commit_rewrite_hook(
+        &mut self,
+        ui: &Ui,
+        description: &str,
+    ) -> Result<(), CommandError> {
+        self.helper
+            .run_pre_commit_rewrite_hook(ui, &self.tx, description)?;
+        self.pre_commit_rewrite_hook_run = true;
+        Ok(())
     }
 
     /// Returns the wrapped [`Transaction`] for circumstances where
@@ -2586,6 +2947,38 @@ pub fn print_updated_commits<'a>(
     Ok(())
 }
 
+/// Asks the user to confirm a destructive command affecting `count` items.
+///
+/// The confirmation is only required if `ui.confirm.<name>` is set to a
+/// non-zero threshold and `count` reaches it, and if `yes` (the `--yes` flag)
+/// isn't set. `write_summary` should describe what is going to be changed.
+pub fn confirm_destructive_command(
+    ui: &Ui,
+    settings: &UserSettings,
+    name: &str,
+    count: usize,
+    yes: bool,
+    write_summary: impl FnOnce(&mut dyn Formatter) -> io::Result<()>,
+) -> Result<(), CommandError> {
+    let threshold: usize = settings.get(["ui", "confirm", name])?;
+    if yes || threshold == 0 || count < threshold {
+        return Ok(());
+    }
+    write_summary(ui.stderr_formatter().as_mut())?;
+    if !Ui::can_prompt() {
+        return Err(user_error_with_hint(
+            "Confirmation is required, but the terminal is not interactive",
+            format!(
+                "Use `--yes` to confirm, or set `ui.confirm.{name} = 0` to disable this check."
+            ),
+        ));
+    }
+    if !ui.prompt_yes_no("Continue?", Some(false))? {
+        return Err(user_error("Canceled by user"));
+    }
+    Ok(())
+}
+
 #[instrument(skip_all)]
 pub fn print_conflicted_paths(
     conflicts: Vec<(RepoPathBuf, BackendResult<MergedTreeValue>)>,
@@ -2766,12 +3159,19 @@ pub fn print_checkout_stats(
             stats.removed_files
         )?;
     }
-    if stats.skipped_files != 0 {
+    for path in &stats.skipped_reserved_paths {
         writeln!(
             ui.warning_default(),
-            "{} of those updates were skipped because there were conflicting changes in the \
-             working copy.",
-            stats.skipped_files
+            "Skipped {} because its name is reserved by the filesystem.",
+            path.as_internal_file_string()
+        )?;
+    }
+    let conflicting_files = stats.skipped_files as usize - stats.skipped_reserved_paths.len();
+    if conflicting_files != 0 {
+        writeln!(
+            ui.warning_default(),
+            "{conflicting_files} of those updates were skipped because there were conflicting \
+             changes in the working copy."
         )?;
         writeln!(
             ui.hint_default(),
@@ -2811,6 +3211,23 @@ pub fn print_unmatched_explicit_paths<'a>(
     Ok(())
 }
 
+/// Rebases descendants of the rewritten commits in `mut_repo`, displaying the
+/// progress if it takes a while. Returns the number of rebased commits.
+pub fn rebase_descendants_with_progress(
+    ui: &Ui,
+    mut_repo: &mut MutableRepo,
+) -> BackendResult<usize> {
+    let mut progress = crate::progress::count_progress(ui, "Rebasing descendant commits");
+    let mut num_rebased = 0;
+    mut_repo.rebase_descendants_with_options(&RebaseOptions::default(), |_, _| {
+        num_rebased += 1;
+        if let Some(progress) = &mut progress {
+            progress(num_rebased);
+        }
+    })?;
+    Ok(num_rebased)
+}
+
 pub fn update_working_copy(
     repo: &Arc<ReadonlyRepo>,
     workspace: &mut Workspace,
@@ -3175,6 +3592,18 @@ pub struct GlobalArgs {
     /// `immutable_heads()` revset or the `immutable` template keyword.
     #[arg(long, global = true)]
     pub ignore_immutable: bool,
+    /// Sign the commits created or rewritten by this command
+    ///
+    /// Commits authored by you are signed by the configured signing backend,
+    /// as if `signing.behavior = "own"` were set.
+    #[arg(long, global = true, conflicts_with = "no_sign")]
+    pub sign: bool,
+    /// Don't sign the commits created or rewritten by this command
+    ///
+    /// Existing signatures of the rewritten commits are dropped, as if
+    /// `signing.behavior = "drop"` were set.
+    #[arg(long, global = true)]
+    pub no_sign: bool,
     /// Operation to load the repo at
     ///
     /// Operation to load the repo at. By default, Jujutsu loads the repo at the
@@ -3204,14 +3633,62 @@ pub struct GlobalArgs {
         add = ArgValueCandidates::new(complete::operations),
     )]
     pub at_operation: Option<String>,
+    /// Format of the command output
+    ///
+    /// With `json`, the primary output is printed as [JSON Lines] following a
+    /// stable schema, so it can be consumed by editors and scripts. Only some
+    /// commands support it; see the [documentation] for the list and the
+    /// schema.
+    ///
+    /// [JSON Lines]: https://jsonlines.org/
+    ///
+    /// [documentation]:
+    ///     https://jj-vcs.github.io/jj/latest/json-output/
+    #[arg(long, value_name = "FORMAT", global = true, default_value = "human")]
+    pub output: OutputFormat,
     /// Enable debug logging
     #[arg(long, global = true)]
     pub debug: bool,
+    /// Write timing spans of the command to a file for performance analysis
+    ///
+    /// Spans around e.g. working-copy snapshots, index loading, revset
+    /// evaluation, backend calls, and tree diffs are written in the [Chrome
+    /// trace format]. The file can be opened in <https://ui.perfetto.dev/>, or
+    /// in <https://www.speedscope.app/> to view it as a flamegraph. Please
+    /// attach it when reporting a performance problem.
+    ///
+    /// If no file is given, the profile is written to
+    /// `jj-profile-<timestamp>.json` in the current directory.
+    ///
+    /// [Chrome trace format]:
+    ///     https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU/
+    #[arg(
+        long,
+        value_name = "FILE",
+        global = true,
+        num_args = 0..=1,
+        require_equals = true,
+        value_hint = clap::ValueHint::FilePath,
+    )]
+    pub profile: Option<Option<PathBuf>>,
 
     #[command(flatten)]
     pub early_args: EarlyArgs,
 }
 
+impl GlobalArgs {
+    /// Returns the `signing.behavior` overridden by `--sign` or `--no-sign`.
+    fn sign_behavior(&self) -> Option<&'static str> {
+        if self.sign {
+            Some("own")
+        } else if self.no_sign {
+            Some("drop")
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(clap::Args, Clone, Debug)]
 pub struct EarlyArgs {
     /// When to colorize output
@@ -3242,6 +3719,16 @@ pub struct EarlyArgs {
     /// Additional configuration files (can be repeated)
     #[arg(long, value_name = "PATH", global = true, value_hint = clap::ValueHint::FilePath)]
     pub config_file: Vec<String>,
+    /// Format of error messages
+    ///
+    /// With `json`, an error is printed to stderr as a single JSON object
+    /// with a stable error code, so it can be handled by editors and scripts.
+    /// See the [documentation] for the schema and the exit codes.
+    ///
+    /// [documentation]:
+    ///     https://jj-vcs.github.io/jj/latest/json-output/#errors
+    #[arg(long, value_name = "FORMAT", global = true)]
+    pub error_format: Option<OutputFormat>,
 }
 
 impl EarlyArgs {
@@ -3326,7 +3813,7 @@ pub fn merge_args_with<'k, 'v, T, U>(
 
 fn get_string_or_array(
     config: &StackedConfig,
-    key: &'static str,
+    key: impl ToConfigNamePath + Copy,
 ) -> Result<Vec<String>, ConfigGetError> {
     config
         .get(key)
@@ -3434,6 +3921,69 @@ fn resolve_aliases(
     }
 }
 
+/// Runs the external program implementing the subcommand of `string_args` if
+/// it isn't a built-in command. The program is the one configured as
+/// `plugins.<name>`, or `jj-<name>` found in `PATH`.
+///
+/// Returns `None` if the subcommand isn't external, or if no program was
+/// found for it.
+fn run_external_subcommand(
+    app: &Command,
+    config: &StackedConfig,
+    string_args: &[String],
+    cwd: &Path,
+    workspace_loader_factory: &dyn WorkspaceLoaderFactory,
+) -> Result<Option<ExitStatus>, CommandError> {
+    let Ok(matches) = app
+        .clone()
+        .allow_external_subcommands(true)
+        .try_get_matches_from(string_args)
+    else {
+        return Ok(None);
+    };
+    let Some((name, submatches)) = matches.subcommand() else {
+        return Ok(None);
+    };
+    if app.find_subcommand(name).is_some() {
+        return Ok(None);
+    }
+    let configured_program = get_string_or_array(config, ["plugins", name]).optional()?;
+    let is_configured = configured_program.is_some();
+    let program = configured_program.unwrap_or_else(|| vec![format!("jj-{name}")]);
+    let Some((program_name, program_args)) = program.split_first() else {
+        return Err(config_error(format!("Empty command for plugin `{name}`")));
+    };
+
+    let mut cmd = std::process::Command::new(program_name);
+    cmd.args(program_args);
+    cmd.args(submatches.get_many::<OsString>("").unwrap_or_default());
+    let workspace_loader = if let Some(path) = matches.get_one::<String>("repository") {
+        let loader = workspace_loader_factory
+            .create(&cwd.join(path))
+            .map_err(|err| map_workspace_load_error(err, Some(path)))?;
+        Some(loader)
+    } else {
+        workspace_loader_factory
+            .create(find_workspace_dir(cwd))
+            .ok()
+    };
+    if let Some(loader) = &workspace_loader {
+        cmd.env("JJ_WORKSPACE_ROOT", loader.workspace_root());
+        cmd.env("JJ_REPO_PATH", loader.repo_path());
+    }
+    if let Ok(jj_path) = env::current_exe() {
+        cmd.env("JJ_EXECUTABLE_PATH", jj_path);
+    }
+    match cmd.status() {
+        Ok(status) => Ok(Some(status)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound && !is_configured => Ok(None),
+        Err(err) => Err(user_error_with_message(
+            format!("Failed to execute external command '{program_name}'"),
+            err,
+        )),
+    }
+}
+
 /// Parse args that must be interpreted early, e.g. before printing help.
 fn parse_early_args(
     app: &Command,
@@ -3616,6 +4166,7 @@ pub struct CliRunner<'a> {
     workspace_loader_factory: Box<dyn WorkspaceLoaderFactory>,
     revset_extensions: RevsetExtensions,
     commit_template_extensions: Vec<Arc<dyn CommitTemplateLanguageExtension>>,
+    commit_keywords: CommitKeywordsExtension,
     operation_template_extensions: Vec<Arc<dyn OperationTemplateLanguageExtension>>,
     dispatch_fn: CliDispatchFn<'a>,
     dispatch_hook_fns: Vec<CliDispatchHookFn<'a>>,
@@ -3647,6 +4198,7 @@ impl<'a> CliRunner<'a> {
             workspace_loader_factory: Box::new(DefaultWorkspaceLoaderFactory),
             revset_extensions: Default::default(),
             commit_template_extensions: vec![],
+            commit_keywords: CommitKeywordsExtension::new(),
             operation_template_extensions: vec![],
             dispatch_fn: Box::new(crate::commands::run_command),
             dispatch_hook_fns: vec![],
@@ -3719,11 +4271,21 @@ impl<'a> CliRunner<'a> {
         self
     }
 
-    pub fn add_revset_function_extension(
-        mut self,
-        name: &'static str,
-        func: RevsetFunction,
-    ) -> Self {
+    /// Registers revset function of the `name`.
+    ///
+    /// The function may capture state, such as a client of an external service
+    /// which provides the set of commits.
+    pub fn add_revset_function_extension<F>(mut self, name: &'static str, func: F) -> Self
+    where
+        F: Fn(
+                &mut RevsetDiagnostics,
+                &FunctionCallNode,
+                &LoweringContext,
+            ) -> Result<Arc<UserRevsetExpression>, RevsetParseError>
+            + Send
+            + Sync
+            + 'static,
+    {
         self.revset_extensions.add_custom_function(name, func);
         self
     }
@@ -3737,6 +4299,20 @@ impl<'a> CliRunner<'a> {
         self
     }
 
+    /// Registers commit template keyword of the `name`, which is evaluated by
+    /// `func`.
+    ///
+    /// The keyword can be used in `jj log -T`, and as a method of commit
+    /// objects, without implementing [`CommitTemplateLanguageExtension`].
+    pub fn add_commit_template_keyword(
+        mut self,
+        name: &'static str,
+        func: impl Fn(&Commit) -> Result<String, TemplatePropertyError> + Send + Sync + 'static,
+    ) -> Self {
+        self.commit_keywords.add_keyword(name, func);
+        self
+    }
+
     pub fn add_operation_template_extension(
         mut self,
         operation_template_extension: Box<dyn OperationTemplateLanguageExtension>,
@@ -3821,7 +4397,9 @@ impl<'a> CliRunner<'a> {
         config_env.reload_user_config(&mut raw_config)?;
         if let Ok(loader) = &maybe_cwd_workspace_loader {
             config_env.reset_repo_path(loader.repo_path());
+            config_env.reset_workspace_path(loader.workspace_root());
             config_env.reload_repo_config(&mut raw_config)?;
+            config_env.reload_workspace_config(&mut raw_config)?;
         }
         let mut config = config_env.resolve_config(&raw_config)?;
         migrate_config(&mut config)?;
@@ -3833,6 +4411,7 @@ impl<'a> CliRunner<'a> {
 
         let string_args = expand_args(ui, &self.app, env::args_os(), &config)?;
         let (args, config_layers) = parse_early_args(&self.app, &string_args)?;
+        ui.set_error_format(args.error_format.unwrap_or_default());
         if !config_layers.is_empty() {
             raw_config.as_mut().extend_layers(config_layers);
             config = config_env.resolve_config(&raw_config)?;
@@ -3844,12 +4423,37 @@ impl<'a> CliRunner<'a> {
             warn_if_args_mismatch(ui, &self.app, &config, &string_args)?;
         }
 
-        let (matches, args) = parse_args(&self.app, &string_args)
-            .map_err(|err| map_clap_cli_error(err, ui, &config))?;
+        let (matches, args) = match parse_args(&self.app, &string_args) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                if err.kind() == clap::error::ErrorKind::InvalidSubcommand
+                    && let Some(status) = run_external_subcommand(
+                        &self.app,
+                        &config,
+                        &string_args,
+                        &cwd,
+                        self.workspace_loader_factory.as_ref(),
+                    )?
+                {
+                    // Try to match the exit status of the executed process.
+                    if let Some(exit_code) = status.code() {
+                        std::process::exit(exit_code);
+                    }
+                    return Err(user_error(format!(
+                        "External command was terminated by {status}"
+                    )));
+                }
+                return Err(map_clap_cli_error(err, ui, &config));
+            }
+        };
         if args.global_args.debug {
             // TODO: set up debug logging as early as possible
             self.tracing_subscription.enable_debug_logging()?;
         }
+        if let Some(path) = &args.global_args.profile {
+            self.tracing_subscription
+                .enable_profiling(path.as_deref())?;
+        }
         for process_global_args_fn in self.process_global_args_fns {
             process_global_args_fn(ui, &matches)?;
         }
@@ -3865,16 +4469,25 @@ impl<'a> CliRunner<'a> {
                 .create(&abs_path)
                 .map_err(|err| map_workspace_load_error(err, Some(path)))?;
             config_env.reset_repo_path(loader.repo_path());
+            config_env.reset_workspace_path(loader.workspace_root());
             config_env.reload_repo_config(&mut raw_config)?;
+            config_env.reload_workspace_config(&mut raw_config)?;
             Ok(loader)
         } else {
             maybe_cwd_workspace_loader
         };
 
+        if let Some(behavior) = args.global_args.sign_behavior() {
+            let mut layer = ConfigLayer::empty(ConfigSource::CommandArg);
+            layer.set_value("signing.behavior", behavior).unwrap();
+            raw_config.as_mut().add_layer(layer);
+        }
+
         // Apply workspace configs, --config arguments, and --when.commands.
         config = config_env.resolve_config(&raw_config)?;
         migrate_config(&mut config)?;
         ui.reset(&config)?;
+        ui.set_output_format(args.global_args.output);
 
         // Print only the last migration messages to omit duplicates.
         for (source, desc) in &last_config_migration_descriptions {
@@ -3883,6 +4496,7 @@ impl<'a> CliRunner<'a> {
                 ConfigSource::EnvBase | ConfigSource::EnvOverrides => "environment-provided",
                 ConfigSource::User => "user-level",
                 ConfigSource::Repo => "repo-level",
+                ConfigSource::Workspace => "workspace-level",
                 ConfigSource::CommandArg => "CLI-provided",
             };
             writeln!(
@@ -3896,6 +4510,16 @@ impl<'a> CliRunner<'a> {
         }
 
         let settings = UserSettings::from_config(config)?;
+        if args.global_args.sign && settings.signing_backend()?.is_none() {
+            return Err(user_error_with_hint(
+                "No signing backend configured",
+                "For configuring a signing backend, see https://jj-vcs.github.io/jj/latest/config/#commit-signing",
+            ));
+        }
+        let mut commit_template_extensions = self.commit_template_extensions;
+        if !self.commit_keywords.is_empty() {
+            commit_template_extensions.push(Arc::new(self.commit_keywords));
+        }
         let command_helper_data = CommandHelperData {
             app: self.app,
             cwd,
@@ -3907,7 +4531,7 @@ impl<'a> CliRunner<'a> {
             raw_config,
             settings,
             revset_extensions: self.revset_extensions.into(),
-            commit_template_extensions: self.commit_template_extensions,
+            commit_template_extensions,
             operation_template_extensions: self.operation_template_extensions,
             maybe_workspace_loader,
             store_factories: self.store_factories,
@@ -3958,7 +4582,7 @@ fn map_clap_cli_error(err: clap::Error, ui: &Ui, config: &StackedConfig) -> Comm
         match cmd.as_str() {
             // git commands that a brand-new user might type during their first
             // experiments with `jj`
-            "clone" | "init" => {
+            "init" => {
                 let cmd = cmd.clone();
                 return CommandError::from(remove_useless_error_context(err))
                     .hinted(format!(
@@ -35,6 +35,7 @@ use jj_lib::fileset::FilesetParseError;
 use jj_lib::fileset::FilesetParseErrorKind;
 use jj_lib::fix::FixError;
 use jj_lib::gitignore::GitIgnoreError;
+use jj_lib::index::IndexReadError;
 use jj_lib::op_heads_store::OpHeadResolutionError;
 use jj_lib::op_heads_store::OpHeadsStoreError;
 use jj_lib::op_store::OpStoreError;
@@ -60,6 +61,7 @@ use jj_lib::working_copy::ResetError;
 use jj_lib::working_copy::SnapshotError;
 use jj_lib::working_copy::WorkingCopyStateError;
 use jj_lib::workspace::WorkspaceInitError;
+use jj_lib::workspace_store::WorkspaceStoreError;
 use thiserror::Error;
 
 use crate::cli_util::short_operation_hash;
@@ -78,6 +80,7 @@ use crate::revset_util::BookmarkNameParseError;
 use crate::revset_util::UserRevsetEvaluationError;
 use crate::template_parser::TemplateParseError;
 use crate::template_parser::TemplateParseErrorKind;
+use crate::ui::OutputFormat;
 use crate::ui::Ui;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -90,9 +93,65 @@ pub enum CommandErrorKind {
     Internal,
 }
 
+impl CommandErrorKind {
+    /// Stable name of the error category, as printed by `--error-format json`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Config => "config",
+            Self::Cli => "cli",
+            Self::BrokenPipe => "broken-pipe",
+            Self::Internal => "internal",
+        }
+    }
+
+    /// Process exit code for errors of this category.
+    pub fn exit_code(self) -> u8 {
+        match self {
+            Self::User => 1,
+            Self::Cli => 2,
+            Self::BrokenPipe => BROKEN_PIPE_EXIT_CODE,
+            Self::Config => 4,
+            Self::Internal => 255,
+        }
+    }
+}
+
+/// Stable identifier of a specific error condition.
+///
+/// Unlike the error message, the code doesn't change between versions, so
+/// wrapper scripts and editor integrations can react to it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorCode {
+    AmbiguousRevision,
+    ConflictedBookmark,
+    ImmutableCommit,
+    NoSuchRevision,
+    StaleWorkingCopy,
+    FilesetParse,
+    RevsetParse,
+    TemplateParse,
+}
+
+impl ErrorCode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::AmbiguousRevision => "ambiguous-revision",
+            Self::ConflictedBookmark => "conflicted-bookmark",
+            Self::ImmutableCommit => "immutable-commit",
+            Self::NoSuchRevision => "no-such-revision",
+            Self::StaleWorkingCopy => "stale-working-copy",
+            Self::FilesetParse => "fileset-parse",
+            Self::RevsetParse => "revset-parse",
+            Self::TemplateParse => "template-parse",
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct CommandError {
     pub kind: CommandErrorKind,
+    pub code: Option<ErrorCode>,
     pub error: Arc<dyn error::Error + Send + Sync>,
     pub hints: Vec<ErrorHint>,
 }
@@ -104,6 +163,7 @@ impl CommandError {
     ) -> Self {
         Self {
             kind,
+            code: None,
             error: Arc::from(err.into()),
             hints: vec![],
         }
@@ -117,6 +177,12 @@ impl CommandError {
         Self::new(kind, ErrorWithMessage::new(message, source))
     }
 
+    /// Returns error with the given stable error `code` attached.
+    pub fn with_code(mut self, code: ErrorCode) -> Self {
+        self.code = Some(code);
+        self
+    }
+
     /// Returns error with the given plain-text `hint` attached.
     pub fn hinted(mut self, hint: impl Into<String>) -> Self {
         self.add_hint(hint);
@@ -227,7 +293,7 @@ pub fn internal_error_with_message(
     CommandError::with_message(CommandErrorKind::Internal, message, source)
 }
 
-fn format_similarity_hint<S: AsRef<str>>(candidates: &[S]) -> Option<String> {
+pub(crate) fn format_similarity_hint<S: AsRef<str>>(candidates: &[S]) -> Option<String> {
     match candidates {
         [] => None,
         names => {
@@ -357,10 +423,17 @@ impl From<WorkspaceInitError> for CommandError {
             }
             WorkspaceInitError::SignInit(err) => user_error(err),
             WorkspaceInitError::TransactionCommit(err) => err.into(),
+            WorkspaceInitError::WorkspaceStore(err) => err.into(),
         }
     }
 }
 
+impl From<WorkspaceStoreError> for CommandError {
+    fn from(err: WorkspaceStoreError) -> Self {
+        internal_error_with_message("Failed to access the workspace store", err)
+    }
+}
+
 impl From<OpHeadResolutionError> for CommandError {
     fn from(err: OpHeadResolutionError) -> Self {
         match err {
@@ -405,6 +478,12 @@ impl From<RepoLoaderError> for CommandError {
     }
 }
 
+impl From<IndexReadError> for CommandError {
+    fn from(err: IndexReadError) -> Self {
+        internal_error_with_message("Failed to load the index", err)
+    }
+}
+
 impl From<ResetError> for CommandError {
     fn from(err: ResetError) -> Self {
         internal_error_with_message("Failed to reset the working copy", err)
@@ -632,7 +711,8 @@ impl From<FilesetParseError> for CommandError {
     fn from(err: FilesetParseError) -> Self {
         let hint = fileset_parse_error_hint(&err);
         let mut cmd_err =
-            user_error_with_message(format!("Failed to parse fileset: {}", err.kind()), err);
+            user_error_with_message(format!("Failed to parse fileset: {}", err.kind()), err)
+                .with_code(ErrorCode::FilesetParse);
         cmd_err.extend_hints(hint);
         cmd_err
     }
@@ -654,7 +734,8 @@ impl From<RevsetParseError> for CommandError {
     fn from(err: RevsetParseError) -> Self {
         let hint = revset_parse_error_hint(&err);
         let mut cmd_err =
-            user_error_with_message(format!("Failed to parse revset: {}", err.kind()), err);
+            user_error_with_message(format!("Failed to parse revset: {}", err.kind()), err)
+                .with_code(ErrorCode::RevsetParse);
         cmd_err.extend_hints(hint);
         cmd_err
     }
@@ -663,7 +744,18 @@ impl From<RevsetParseError> for CommandError {
 impl From<RevsetResolutionError> for CommandError {
     fn from(err: RevsetResolutionError) -> Self {
         let hints = revset_resolution_error_hints(&err);
+        let code = match &err {
+            RevsetResolutionError::NoSuchRevision { .. } => Some(ErrorCode::NoSuchRevision),
+            RevsetResolutionError::AmbiguousCommitIdPrefix(_)
+            | RevsetResolutionError::AmbiguousChangeIdPrefix(_)
+            | RevsetResolutionError::DivergentChangeId { .. } => Some(ErrorCode::AmbiguousRevision),
+            RevsetResolutionError::ConflictedRef { kind, .. } if kind.ends_with("bookmark") => {
+                Some(ErrorCode::ConflictedBookmark)
+            }
+            _ => None,
+        };
         let mut cmd_err = user_error(err);
+        cmd_err.code = code;
         cmd_err.extend_hints(hints);
         cmd_err
     }
@@ -682,7 +774,8 @@ impl From<TemplateParseError> for CommandError {
     fn from(err: TemplateParseError) -> Self {
         let hint = template_parse_error_hint(&err);
         let mut cmd_err =
-            user_error_with_message(format!("Failed to parse template: {}", err.kind()), err);
+            user_error_with_message(format!("Failed to parse template: {}", err.kind()), err)
+                .with_code(ErrorCode::TemplateParse);
         cmd_err.extend_hints(hint);
         cmd_err
     }
@@ -896,6 +989,10 @@ fn revset_resolution_error_hints(err: &RevsetResolutionError) -> Vec<String> {
         RevsetResolutionError::NoSuchRevision {
             name: _,
             candidates,
+        }
+        | RevsetResolutionError::WorkspaceMissingWorkingCopy {
+            name: _,
+            candidates,
         } => format_similarity_hint(candidates).into_iter().collect(),
         RevsetResolutionError::DivergentChangeId { symbol, targets } => vec![
             multiple_targets_hint(targets),
@@ -920,7 +1017,6 @@ fn revset_resolution_error_hints(err: &RevsetResolutionError) -> Vec<String> {
             targets,
         } => vec![multiple_targets_hint(targets)],
         RevsetResolutionError::EmptyString
-        | RevsetResolutionError::WorkspaceMissingWorkingCopy { .. }
         | RevsetResolutionError::AmbiguousCommitIdPrefix(_)
         | RevsetResolutionError::AmbiguousChangeIdPrefix(_)
         | RevsetResolutionError::Backend(_)
@@ -967,10 +1063,13 @@ fn try_handle_command_result(ui: &mut Ui, result: Result<(), CommandError>) -> i
     };
     let err = &cmd_err.error;
     let hints = &cmd_err.hints;
+    if ui.error_format() == OutputFormat::Json {
+        return print_json_error(ui, cmd_err);
+    }
     match cmd_err.kind {
         CommandErrorKind::User => {
             print_error(ui, "Error: ", err, hints)?;
-            Ok(1)
+            Ok(cmd_err.kind.exit_code())
         }
         CommandErrorKind::Config => {
             print_error(ui, "Config error: ", err, hints)?;
@@ -979,27 +1078,71 @@ fn try_handle_command_result(ui: &mut Ui, result: Result<(), CommandError>) -> i
                 "For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k \
                  config`."
             )?;
-            Ok(1)
+            Ok(cmd_err.kind.exit_code())
         }
         CommandErrorKind::Cli => {
             if let Some(err) = err.downcast_ref::<clap::Error>() {
                 handle_clap_error(ui, err, hints)
             } else {
                 print_error(ui, "Error: ", err, hints)?;
-                Ok(2)
+                Ok(cmd_err.kind.exit_code())
             }
         }
         CommandErrorKind::BrokenPipe => {
             // A broken pipe is not an error, but a signal to exit gracefully.
-            Ok(BROKEN_PIPE_EXIT_CODE)
+            Ok(cmd_err.kind.exit_code())
         }
         CommandErrorKind::Internal => {
             print_error(ui, "Internal error: ", err, hints)?;
-            Ok(255)
+            Ok(cmd_err.kind.exit_code())
         }
     }
 }
 
+/// Prints the error as a single JSON object to stderr.
+fn print_json_error(ui: &mut Ui, cmd_err: &CommandError) -> io::Result<u8> {
+    if cmd_err.kind == CommandErrorKind::BrokenPipe {
+        return Ok(cmd_err.kind.exit_code());
+    }
+    let (message, first_source) = if let Some(err) = cmd_err.error.downcast_ref::<clap::Error>() {
+        match err.kind() {
+            // Help and version aren't errors.
+            clap::error::ErrorKind::DisplayHelp | clap::error::ErrorKind::DisplayVersion => {
+                return handle_clap_error(ui, err, &cmd_err.hints);
+            }
+            // The first source error is included in the rendered message.
+            _ => (
+                err.render().to_string().trim_end().to_owned(),
+                err.source().and_then(|err| err.source()),
+            ),
+        }
+    } else {
+        (cmd_err.error.to_string(), cmd_err.error.source())
+    };
+    let causes = iter::successors(first_source, |&err| err.source())
+        .map(|err| err.to_string().trim_end().to_owned())
+        .collect_vec();
+    let hints = cmd_err
+        .hints
+        .iter()
+        .map(|hint| match hint {
+            ErrorHint::PlainText(message) => message.clone(),
+            ErrorHint::Formatted(recorded) => String::from_utf8_lossy(recorded.data())
+                .trim_end()
+                .to_owned(),
+        })
+        .collect_vec();
+    let value = serde_json::json!({
+        "category": cmd_err.kind.as_str(),
+        "code": cmd_err.code.map(ErrorCode::as_str),
+        "message": message,
+        "causes": causes,
+        "hints": hints,
+    });
+    writeln!(ui.stderr(), "{value}")?;
+    Ok(cmd_err.kind.exit_code())
+}
+
 fn print_error(
     ui: &Ui,
     heading: &str,
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::io;
 use std::io::Write as _;
 
 use clap_complete::ArgValueCompleter;
@@ -26,6 +27,7 @@ use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::confirm_destructive_command;
 #[cfg(feature = "git")]
 use crate::cli_util::has_tracked_remote_bookmarks;
 use crate::cli_util::print_updated_commits;
@@ -67,6 +69,11 @@ pub(crate) struct AbandonArgs {
     /// Do not modify the content of the children of the abandoned commits
     #[arg(long)]
     restore_descendants: bool,
+    /// Do not ask for confirmation
+    ///
+    /// Confirmation is only asked for if `ui.confirm.abandon` is set.
+    #[arg(long)]
+    yes: bool,
 }
 
 #[instrument(skip_all)]
@@ -107,6 +114,26 @@ pub(crate) fn cmd_abandon(
         return Ok(());
     }
     workspace_command.check_rewritable(&to_abandon)?;
+    confirm_destructive_command(
+        ui,
+        workspace_command.settings(),
+        "abandon",
+        to_abandon.len(),
+        args.yes,
+        |formatter| {
+            writeln!(formatter, "About to abandon {} commits:", to_abandon.len())?;
+            let commits: Vec<_> = to_abandon
+                .iter()
+                .map(|id| workspace_command.repo().store().get_commit(id))
+                .try_collect()
+                .map_err(io::Error::other)?;
+            print_updated_commits(
+                formatter,
+                &workspace_command.commit_summary_template(),
+                &commits,
+            )
+        },
+    )?;
 
     let mut tx = workspace_command.start_transaction();
     let options = RewriteRefsOptions {
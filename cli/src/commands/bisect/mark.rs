@@ -0,0 +1,125 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCompleter;
+use jj_lib::bisect::BISECT_TAG;
+use jj_lib::bisect::BisectSession;
+use jj_lib::bisect::Bisector;
+use jj_lib::bisect::Evaluation;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo as _;
+use jj_lib::revset::RevsetExpression;
+use tracing::instrument;
+
+use super::check_out_next_step;
+use super::find_session;
+use super::resolve_range;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::complete;
+use crate::ui::Ui;
+
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct BisectMarkArgs {
+    /// The revision to mark [default: the revision being evaluated]
+    #[arg(
+        long,
+        short,
+        value_name = "REVSET",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revision: Option<RevisionArg>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_bisect_mark(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BisectMarkArgs,
+    evaluation: Evaluation,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let session = find_session(&workspace_command)?;
+    let commit = if let Some(revision) = &args.revision {
+        workspace_command.resolve_single_rev(ui, revision)?
+    } else if let Some(target) = &session.target {
+        workspace_command.repo().store().get_commit(target)?
+    } else {
+        return Err(user_error_with_hint(
+            "No revision is being evaluated",
+            "Use `-r` to specify the revision to mark.",
+        ));
+    };
+
+    // The range is evaluated as it was when the bisection started, so later
+    // changes to the repo don't affect it.
+    let start_repo = workspace_command
+        .repo()
+        .loader()
+        .load_at(&session.start_operation)?;
+    let input_range = resolve_range(
+        ui,
+        command,
+        &workspace_command,
+        start_repo.as_ref(),
+        &session.range,
+    )?;
+    let in_range = start_repo.index().has_id(commit.id())
+        && input_range
+            .intersection(&RevsetExpression::commit(commit.id().clone()))
+            .evaluate(start_repo.as_ref())?
+            .iter()
+            .next()
+            .is_some();
+    if !in_range {
+        return Err(user_error(format!(
+            "Revision {} is not in the range being bisected: {}",
+            workspace_command.format_commit_summary(&commit),
+            session.range
+        )));
+    }
+
+    let mut bisector = Bisector::new(start_repo.as_ref(), input_range)?;
+    if evaluation != Evaluation::Bad && bisector.bad_commits().contains(commit.id()) {
+        return Err(user_error(format!(
+            "Revision {} is a head of the range being bisected, which is assumed to be bad",
+            workspace_command.format_commit_summary(&commit),
+        )));
+    }
+    for (id, marked_evaluation) in &session.marks {
+        if id != commit.id() {
+            bisector.mark(id.clone(), *marked_evaluation);
+        }
+    }
+    bisector.mark(commit.id().clone(), evaluation);
+
+    let mut tx = workspace_command.start_transaction();
+    tx.set_tag(
+        BISECT_TAG,
+        BisectSession::mark_tag_value(commit.id(), evaluation),
+    );
+    let state = match evaluation {
+        Evaluation::Good => "good",
+        Evaluation::Bad => "bad",
+        Evaluation::Skip => "skipped",
+    };
+    let description = format!(
+        "mark revision {} as {state} for bisection",
+        commit.id().hex()
+    );
+    check_out_next_step(ui, tx, &mut bisector, description)
+}
@@ -12,16 +12,54 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod mark;
+mod reset;
 mod run;
+mod start;
+
+use std::sync::Arc;
+
+use jj_lib::bisect::BISECT_TARGET_TAG;
+use jj_lib::bisect::BisectSession;
+use jj_lib::bisect::BisectionResult;
+use jj_lib::bisect::Bisector;
+use jj_lib::bisect::Evaluation;
+use jj_lib::bisect::NextStep;
+use jj_lib::commit::Commit;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo;
+use jj_lib::revset::ResolvedRevsetExpression;
 
 use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::cli_util::WorkspaceCommandTransaction;
 use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::formatter::Formatter;
+use crate::revset_util::RevsetExpressionEvaluator;
+use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
 
-/// Find a bad revision by bisection.
+/// Find a bad revision by bisection
+///
+/// Use `jj bisect run` to evaluate the revisions with a command, or `jj bisect
+/// start` to evaluate them by hand. In the latter case, the revisions are
+/// marked with `jj bisect good`, `jj bisect bad`, and `jj bisect skip`, and the
+/// marks are recorded in the operation log.
 #[derive(clap::Subcommand, Clone, Debug)]
 pub enum BisectCommand {
+    /// Mark a revision bad and check out the next revision to evaluate
+    Bad(mark::BisectMarkArgs),
+    /// Mark a revision good and check out the next revision to evaluate
+    Good(mark::BisectMarkArgs),
+    Reset(reset::BisectResetArgs),
     Run(run::BisectRunArgs),
+    /// Mark a revision as untestable and check out the next revision to
+    /// evaluate
+    Skip(mark::BisectMarkArgs),
+    Start(start::BisectStartArgs),
 }
 
 pub fn cmd_bisect(
@@ -30,6 +68,115 @@ pub fn cmd_bisect(
     subcommand: &BisectCommand,
 ) -> Result<(), CommandError> {
     match subcommand {
+        BisectCommand::Bad(args) => mark::cmd_bisect_mark(ui, command, args, Evaluation::Bad),
+        BisectCommand::Good(args) => mark::cmd_bisect_mark(ui, command, args, Evaluation::Good),
+        BisectCommand::Reset(args) => reset::cmd_bisect_reset(ui, command, args),
         BisectCommand::Run(args) => run::cmd_bisect_run(ui, command, args),
+        BisectCommand::Skip(args) => mark::cmd_bisect_mark(ui, command, args, Evaluation::Skip),
+        BisectCommand::Start(args) => start::cmd_bisect_start(ui, command, args),
+    }
+}
+
+/// Looks up the bisection session in progress at the current operation.
+fn find_session(workspace_command: &WorkspaceCommandHelper) -> Result<BisectSession, CommandError> {
+    BisectSession::find(workspace_command.repo().operation())?.ok_or_else(|| {
+        user_error_with_hint(
+            "No bisection in progress",
+            "Run `jj bisect start` to start one.",
+        )
+    })
+}
+
+/// Resolves the symbols in the `range` revset against `repo`.
+fn resolve_range(
+    ui: &Ui,
+    command: &CommandHelper,
+    workspace_command: &WorkspaceCommandHelper,
+    repo: &dyn Repo,
+    range: &str,
+) -> Result<Arc<ResolvedRevsetExpression>, CommandError> {
+    let expression = workspace_command
+        .parse_revset(ui, &RevisionArg::from(range.to_owned()))?
+        .expression()
+        .clone();
+    let evaluator = RevsetExpressionEvaluator::new(
+        repo,
+        command.revset_extensions().clone(),
+        workspace_command.id_prefix_context(),
+        expression,
+    );
+    Ok(evaluator.resolve()?)
+}
+
+/// Checks out the next revision to evaluate, or reports the first bad
+/// revisions if bisection is complete, and then finishes the transaction.
+fn check_out_next_step(
+    ui: &Ui,
+    mut tx: WorkspaceCommandTransaction,
+    bisector: &mut Bisector,
+    description: String,
+) -> Result<(), CommandError> {
+    match bisector.next_step()? {
+        NextStep::Evaluate(commit) => {
+            {
+                let mut formatter = ui.stdout_formatter();
+                write!(formatter, "Now evaluating: ")?;
+                tx.commit_summary_template()
+                    .format(&commit, formatter.as_mut())?;
+                writeln!(formatter)?;
+            }
+            tx.set_tag(BISECT_TARGET_TAG, commit.id().hex());
+            tx.check_out(&commit)?;
+            tx.finish(ui, description)?;
+            Ok(())
+        }
+        NextStep::Done(bisection_result) => {
+            tx.set_tag(BISECT_TARGET_TAG, "");
+            let result = {
+                let mut formatter = ui.stdout_formatter();
+                writeln!(formatter, "Search complete.")?;
+                write_bisection_result(
+                    formatter.as_mut(),
+                    &tx.commit_summary_template(),
+                    bisection_result,
+                )
+            };
+            // Nothing but the operation tags changed, but the operation must
+            // be recorded for the mark to be kept.
+            tx.into_inner().commit(description)?;
+            writeln!(
+                ui.hint_default(),
+                "Run `jj bisect reset` to end the bisection."
+            )?;
+            result
+        }
+    }
+}
+
+/// Writes the first bad revisions, or returns an error if they couldn't be
+/// determined.
+fn write_bisection_result(
+    formatter: &mut dyn Formatter,
+    commit_template: &TemplateRenderer<'_, Commit>,
+    bisection_result: BisectionResult,
+) -> Result<(), CommandError> {
+    match bisection_result {
+        BisectionResult::Indeterminate => Err(user_error(
+            "Could not find the first bad revision. Was the input range empty?",
+        )),
+        BisectionResult::Found(first_bad_commits) => {
+            if let [first_bad_commit] = first_bad_commits.as_slice() {
+                write!(formatter, "The first bad revision is: ")?;
+                commit_template.format(first_bad_commit, formatter)?;
+                writeln!(formatter)?;
+            } else {
+                writeln!(formatter, "The first bad revisions are:")?;
+                for first_bad_commit in first_bad_commits {
+                    commit_template.format(&first_bad_commit, formatter)?;
+                    writeln!(formatter)?;
+                }
+            }
+            Ok(())
+        }
     }
 }
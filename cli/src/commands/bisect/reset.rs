@@ -0,0 +1,55 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use jj_lib::bisect::BISECT_TAG;
+use tracing::instrument;
+
+use super::find_session;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::short_operation_hash;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// End the bisection started by `jj bisect start`
+///
+/// The working copy is left as it is.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct BisectResetArgs {}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_bisect_reset(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &BisectResetArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let session = find_session(&workspace_command)?;
+    let mut tx = workspace_command.start_transaction();
+    tx.set_tag(BISECT_TAG, "reset");
+    // Nothing but the operation tags changed, but the operation must be
+    // recorded to end the session.
+    tx.into_inner().commit("end bisection")?;
+
+    let mut formatter = ui.stdout_formatter();
+    writeln!(
+        formatter,
+        "Bisection ended. To discard any revisions created during bisection, run:"
+    )?;
+    writeln!(
+        formatter,
+        "  jj op restore {}",
+        short_operation_hash(&session.start_operation.parent_ids()[0])
+    )?;
+    Ok(())
+}
@@ -13,13 +13,13 @@
 // limitations under the License.
 
 use clap_complete::ArgValueCompleter;
-use jj_lib::bisect::BisectionResult;
 use jj_lib::bisect::Bisector;
 use jj_lib::bisect::Evaluation;
 use jj_lib::commit::Commit;
 use jj_lib::object_id::ObjectId as _;
 use tracing::instrument;
 
+use super::write_bisection_result;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::cli_util::WorkspaceCommandHelper;
@@ -140,29 +140,8 @@ pub(crate) fn cmd_bisect_run(
         short_operation_hash(initial_repo.op_id())
     )?;
 
-    match bisection_result {
-        BisectionResult::Indeterminate => {
-            return Err(user_error(
-                "Could not find the first bad revision. Was the input range empty?",
-            ));
-        }
-        BisectionResult::Found(first_bad_commits) => {
-            let commit_template = workspace_command.commit_summary_template();
-            if let [first_bad_commit] = first_bad_commits.as_slice() {
-                write!(formatter, "The first bad revision is: ")?;
-                commit_template.format(first_bad_commit, formatter.as_mut())?;
-                writeln!(formatter)?;
-            } else {
-                writeln!(formatter, "The first bad revisions are:")?;
-                for first_bad_commit in first_bad_commits {
-                    commit_template.format(&first_bad_commit, formatter.as_mut())?;
-                    writeln!(formatter)?;
-                }
-            }
-        }
-    }
-
-    Ok(())
+    let commit_template = workspace_command.commit_summary_template();
+    write_bisection_result(formatter.as_mut(), &commit_template, bisection_result)
 }
 
 fn evaluate_commit(
@@ -0,0 +1,86 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::bisect::BISECT_RANGE_TAG;
+use jj_lib::bisect::BISECT_TAG;
+use jj_lib::bisect::BisectSession;
+use jj_lib::bisect::Bisector;
+use tracing::instrument;
+
+use super::check_out_next_step;
+use super::resolve_range;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::command_error::user_error_with_hint;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Start bisecting a range of revisions by hand
+///
+/// Checks out the first revision to evaluate. Mark it with `jj bisect good`,
+/// `jj bisect bad`, or `jj bisect skip`, which check out the next revision
+/// until the first bad revision is found. The marks are recorded in the
+/// operation log. Marking a revision again replaces its previous mark. Run `jj
+/// bisect reset` to end the bisection.
+///
+/// It is assumed that if a given revision is bad, then all its descendants
+/// in the input range are also bad.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct BisectStartArgs {
+    /// Range of revisions to bisect
+    ///
+    /// This is typically a range like `v1.0..main`. The heads of the range are
+    /// assumed to be bad. Ancestors of the range that are not also in the range
+    /// are assumed to be good.
+    #[arg(
+        long,
+        short,
+        value_name = "REVSETS",
+        required = true,
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    range: Vec<RevisionArg>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_bisect_start(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BisectStartArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    if BisectSession::find(workspace_command.repo().operation())?.is_some() {
+        return Err(user_error_with_hint(
+            "A bisection is already in progress",
+            "Run `jj bisect reset` to end it first.",
+        ));
+    }
+
+    let range = if let [arg] = args.range.as_slice() {
+        arg.to_string()
+    } else {
+        args.range.iter().map(|arg| format!("({arg})")).join(" | ")
+    };
+    let repo = workspace_command.repo().clone();
+    let input_range = resolve_range(ui, command, &workspace_command, repo.as_ref(), &range)?;
+    let mut bisector = Bisector::new(repo.as_ref(), input_range)?;
+
+    let mut tx = workspace_command.start_transaction();
+    tx.set_tag(BISECT_TAG, "start");
+    tx.set_tag(BISECT_RANGE_TAG, &range);
+    check_out_next_step(ui, tx, &mut bisector, format!("start bisection of {range}"))
+}
@@ -12,15 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io::Write as _;
+
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
 use jj_lib::op_store::RefTarget;
 use jj_lib::str_util::StringPattern;
 
 use super::find_local_bookmarks;
+use super::print_matched_bookmarks;
 use crate::cli_util::CommandHelper;
+use crate::cli_util::confirm_destructive_command;
 use crate::command_error::CommandError;
 use crate::complete;
+use crate::formatter::FormatterExt as _;
 use crate::ui::Ui;
 
 /// Delete an existing bookmark and propagate the deletion to remotes on the
@@ -48,6 +53,11 @@ pub struct BookmarkDeleteArgs {
         add = ArgValueCandidates::new(complete::local_bookmarks),
     )]
     names: Vec<StringPattern>,
+    /// Do not ask for confirmation
+    ///
+    /// Confirmation is only asked for if `ui.confirm.bookmark-delete` is set.
+    #[arg(long)]
+    yes: bool,
 }
 
 pub fn cmd_bookmark_delete(
@@ -58,6 +68,26 @@ pub fn cmd_bookmark_delete(
     let mut workspace_command = command.workspace_helper(ui)?;
     let repo = workspace_command.repo().clone();
     let matched_bookmarks = find_local_bookmarks(repo.view(), &args.names)?;
+    confirm_destructive_command(
+        ui,
+        workspace_command.settings(),
+        "bookmark-delete",
+        matched_bookmarks.len(),
+        args.yes,
+        |formatter| {
+            writeln!(
+                formatter,
+                "About to delete {} bookmarks:",
+                matched_bookmarks.len()
+            )?;
+            for (name, _) in &matched_bookmarks {
+                write!(formatter, "  ")?;
+                write!(formatter.labeled("bookmark"), "{}", name.as_symbol())?;
+                writeln!(formatter)?;
+            }
+            Ok(())
+        },
+    )?;
     let mut tx = workspace_command.start_transaction();
     for (name, _) in &matched_bookmarks {
         tx.repo_mut()
@@ -68,6 +98,11 @@ pub fn cmd_bookmark_delete(
         "Deleted {} bookmarks.",
         matched_bookmarks.len()
     )?;
+    print_matched_bookmarks(
+        ui,
+        args.names.iter().all(StringPattern::is_exact),
+        matched_bookmarks.iter().map(|(name, _)| name.as_symbol()),
+    )?;
     tx.finish(
         ui,
         format!(
@@ -22,6 +22,7 @@ use jj_lib::str_util::StringPattern;
 use jj_lib::view::View;
 
 use super::find_bookmarks_with;
+use super::print_matched_bookmarks;
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::complete;
@@ -93,6 +94,11 @@ pub fn cmd_bookmark_forget(
         "Forgot {} local bookmarks.",
         matched_bookmarks.len()
     )?;
+    print_matched_bookmarks(
+        ui,
+        args.names.iter().all(StringPattern::is_exact),
+        matched_bookmarks.iter().map(|(name, _)| name.as_symbol()),
+    )?;
     if forgotten_remote != 0 {
         writeln!(ui.status(), "Forgot {forgotten_remote} remote bookmarks.")?;
     }
@@ -108,7 +114,7 @@ fn find_forgettable_bookmarks<'a>(
     view: &'a View,
     name_patterns: &[StringPattern],
 ) -> Result<Vec<(&'a RefName, LocalRemoteRefTarget<'a>)>, CommandError> {
-    find_bookmarks_with(name_patterns, |pattern| {
+    find_bookmarks_with(view, name_patterns, |pattern| {
         view.bookmarks()
             .filter(|(name, _)| pattern.is_match(name.as_str()))
             .map(Ok)
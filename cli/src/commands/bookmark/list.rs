@@ -20,6 +20,7 @@ use std::sync::Arc;
 
 use clap::ValueEnum;
 use clap_complete::ArgValueCandidates;
+use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
 use jj_lib::backend;
 use jj_lib::backend::CommitId;
@@ -32,9 +33,11 @@ use jj_lib::str_util::StringPattern;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::command_error::CommandError;
+use crate::command_error::user_error;
 use crate::commit_templater::CommitRef;
 use crate::complete;
 use crate::templater::TemplateRenderer;
+use crate::ui::OutputFormat;
 use crate::ui::Ui;
 
 /// List bookmarks and their targets
@@ -99,7 +102,12 @@ pub struct BookmarkListArgs {
     ///
     /// Note that `-r deleted_bookmark` will not work since `deleted_bookmark`
     /// wouldn't have a local target.
-    #[arg(long, short, value_name = "REVSETS")]
+    #[arg(
+        long,
+        short,
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
     revisions: Option<Vec<RevisionArg>>,
 
     /// Render each bookmark using the given template
@@ -171,6 +179,11 @@ pub fn cmd_bookmark_list(
         None
     };
 
+    if ui.output_format() == OutputFormat::Json && args.template.is_some() {
+        return Err(user_error(
+            "`--output json` cannot be combined with templates",
+        ));
+    }
     let template: TemplateRenderer<Rc<CommitRef>> = {
         let language = workspace_command.commit_template_language();
         let text = match &args.template {
@@ -260,13 +273,16 @@ pub fn cmd_bookmark_list(
     }
     sort(&mut bookmark_list_items, &sort_keys, &commits);
 
-    ui.request_pager();
-    let mut formatter = ui.stdout_formatter();
-    bookmark_list_items
+    let mut commit_refs = bookmark_list_items
         .iter()
-        .flat_map(|item| itertools::chain([&item.primary], &item.tracked))
-        .try_for_each(|commit_ref| template.format(commit_ref, formatter.as_mut()))?;
-    drop(formatter);
+        .flat_map(|item| itertools::chain([&item.primary], &item.tracked));
+    if ui.output_format() == OutputFormat::Json {
+        commit_refs.try_for_each(|commit_ref| ui.write_json(commit_ref))?;
+    } else {
+        ui.request_pager();
+        let mut formatter = ui.stdout_formatter();
+        commit_refs.try_for_each(|commit_ref| template.format(commit_ref, formatter.as_mut()))?;
+    }
 
     #[cfg(feature = "git")]
     if jj_lib::git::get_git_backend(repo.store()).is_ok() {
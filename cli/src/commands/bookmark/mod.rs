@@ -22,8 +22,12 @@ mod set;
 mod track;
 mod untrack;
 
+use std::fmt::Display;
+use std::io::Write as _;
+
 use itertools::Itertools as _;
 use jj_lib::backend::CommitId;
+use jj_lib::dsl_util::collect_similar;
 use jj_lib::op_store::RefTarget;
 use jj_lib::op_store::RemoteRef;
 use jj_lib::ref_name::RefName;
@@ -53,7 +57,9 @@ use self::untrack::cmd_bookmark_untrack;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RemoteBookmarkNamePattern;
 use crate::command_error::CommandError;
+use crate::command_error::format_similarity_hint;
 use crate::command_error::user_error;
+use crate::formatter::FormatterExt as _;
 use crate::ui::Ui;
 
 // Unlike most other aliases, `b` is defined in the config and can be overridden
@@ -108,12 +114,35 @@ fn find_local_bookmarks<'a>(
     view: &'a View,
     name_patterns: &[StringPattern],
 ) -> Result<Vec<(&'a RefName, &'a RefTarget)>, CommandError> {
-    find_bookmarks_with(name_patterns, |pattern| {
+    find_bookmarks_with(view, name_patterns, |pattern| {
         view.local_bookmarks_matching(pattern).map(Ok)
     })
 }
 
+/// Returns an error for the bookmark `name` which doesn't exist, with a hint
+/// listing the `similar_names` if any.
+fn no_such_bookmark_error(
+    kind: &str,
+    name: impl Display,
+    similar_names: &[String],
+) -> CommandError {
+    let mut err = user_error(format!("No such {kind}: {name}"));
+    if let Some(hint) = format_similarity_hint(similar_names) {
+        err.add_hint(hint);
+    }
+    err
+}
+
+/// Returns the local bookmark names similar to `name`.
+fn similar_local_bookmark_names(view: &View, name: &str) -> Vec<String> {
+    let names = view
+        .local_bookmarks()
+        .map(|(name, _)| name.as_symbol().to_string());
+    collect_similar(name, names)
+}
+
 fn find_bookmarks_with<'a, 'b, V, I>(
+    view: &View,
     name_patterns: &'b [StringPattern],
     mut find_matches: impl FnMut(&'b StringPattern) -> I,
 ) -> Result<Vec<(&'a RefName, V)>, CommandError>
@@ -135,7 +164,11 @@ where
             matching_bookmarks.dedup_by_key(|(name, _)| *name);
             Ok(matching_bookmarks)
         }
-        [pattern] if pattern.is_exact() => Err(user_error(format!("No such bookmark: {pattern}"))),
+        [pattern] if pattern.is_exact() => Err(no_such_bookmark_error(
+            "bookmark",
+            pattern,
+            &similar_local_bookmark_names(view, pattern.as_exact().unwrap()),
+        )),
         patterns => Err(user_error(format!(
             "No matching bookmarks for patterns: {}",
             patterns.iter().join(", ")
@@ -165,7 +198,21 @@ fn find_remote_bookmarks<'a>(
             Ok(matching_bookmarks)
         }
         [pattern] if pattern.is_exact() => {
-            Err(user_error(format!("No such remote bookmark: {pattern}")))
+            // Suggest bookmarks of the same remote with similar names.
+            let names = view
+                .all_remote_bookmarks()
+                .filter(|(symbol, _)| pattern.remote.is_match(symbol.remote.as_str()))
+                .map(|(symbol, _)| symbol.name.as_symbol().to_string());
+            let bookmark = pattern.bookmark.as_exact().unwrap();
+            let similar_names = collect_similar(bookmark, names)
+                .into_iter()
+                .map(|name| format!("{name}@{}", pattern.remote))
+                .collect_vec();
+            Err(no_such_bookmark_error(
+                "remote bookmark",
+                pattern,
+                &similar_names,
+            ))
         }
         patterns => Err(user_error(format!(
             "No matching remote bookmarks for patterns: {}",
@@ -174,6 +221,28 @@ fn find_remote_bookmarks<'a>(
     }
 }
 
+/// Lists the bookmark `names` the patterns expanded to, one per line.
+///
+/// Nothing is printed if all patterns matched exactly, since the names would
+/// just repeat the command arguments.
+fn print_matched_bookmarks(
+    ui: &Ui,
+    all_exact: bool,
+    names: impl IntoIterator<Item = impl Display>,
+) -> std::io::Result<()> {
+    if all_exact {
+        return Ok(());
+    }
+    if let Some(mut formatter) = ui.status_formatter() {
+        for name in names {
+            write!(formatter, "  ")?;
+            write!(formatter.labeled("bookmark"), "{name}")?;
+            writeln!(formatter)?;
+        }
+    }
+    Ok(())
+}
+
 fn is_fast_forward(repo: &dyn Repo, old_target: &RefTarget, new_target_id: &CommitId) -> bool {
     if old_target.is_present() {
         // Strictly speaking, "all" old targets should be ancestors, but we allow
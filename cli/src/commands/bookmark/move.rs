@@ -106,7 +106,7 @@ pub fn cmd_bookmark_move(
             Box::new(|_| Ok(true))
         };
         let mut bookmarks = if !args.names.is_empty() {
-            find_bookmarks_with(&args.names, |pattern| {
+            find_bookmarks_with(repo.view(), &args.names, |pattern| {
                 repo.view()
                     .local_bookmarks_matching(pattern)
                     .filter_map(|(name, target)| {
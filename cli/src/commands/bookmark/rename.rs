@@ -16,6 +16,8 @@ use clap_complete::ArgValueCandidates;
 use jj_lib::op_store::RefTarget;
 use jj_lib::ref_name::RefNameBuf;
 
+use super::no_such_bookmark_error;
+use super::similar_local_bookmark_names;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::has_tracked_remote_bookmarks;
 use crate::command_error::CommandError;
@@ -51,10 +53,11 @@ pub fn cmd_bookmark_rename(
     let old_bookmark = &args.old;
     let ref_target = view.get_local_bookmark(old_bookmark).clone();
     if ref_target.is_absent() {
-        return Err(user_error(format!(
-            "No such bookmark: {old_bookmark}",
-            old_bookmark = old_bookmark.as_symbol()
-        )));
+        return Err(no_such_bookmark_error(
+            "bookmark",
+            old_bookmark.as_symbol(),
+            &similar_local_bookmark_names(view, old_bookmark.as_str()),
+        ));
     }
 
     let new_bookmark = &args.new;
@@ -19,6 +19,7 @@ use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
 
 use super::find_remote_bookmarks;
+use super::print_matched_bookmarks;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RemoteBookmarkNamePattern;
 use crate::command_error::CommandError;
@@ -79,6 +80,8 @@ pub fn cmd_bookmark_track(
             "Started tracking {} remote bookmarks.",
             symbols.len()
         )?;
+        let all_exact = args.names.iter().all(|pattern| pattern.is_exact());
+        print_matched_bookmarks(ui, all_exact, &symbols)?;
     }
     tx.finish(
         ui,
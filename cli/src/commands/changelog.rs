@@ -0,0 +1,213 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::trailer::parse_description_trailers;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::formatter::FormatterExt as _;
+use crate::ui::Ui;
+
+/// Generate a changelog from commit descriptions
+///
+/// Commits in the given revisions are grouped into sections according to the
+/// `changelog.sections` config, and rendered as Markdown. A commit belongs to
+/// the first section whose rules it matches. Commits that don't match any
+/// section are listed under `changelog.other-title`, or omitted if that is set
+/// to an empty string.
+///
+/// ```toml
+/// [[changelog.sections]]
+/// title = "New features"
+/// # Match descriptions like "feat: ..." or "feat(cli): ..."
+/// prefixes = ["feat"]
+///
+/// [[changelog.sections]]
+/// title = "Fixed bugs"
+/// prefixes = ["fix"]
+/// # Also match commits with a "Changelog: fix" trailer
+/// trailers = ["Changelog: fix"]
+/// ```
+///
+/// Each commit is rendered using the `templates.changelog_entry` template.
+#[derive(clap::Args, Clone, Debug)]
+pub struct ChangelogArgs {
+    /// Which revisions to include in the changelog
+    #[arg(
+        long, short,
+        required = true,
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revisions: Vec<RevisionArg>,
+
+    /// Render each commit using the given template
+    ///
+    /// All 0-argument methods of the [`Commit` type] are available as keywords
+    /// in the template expression. See [`jj help -k templates`] for more
+    /// information.
+    ///
+    /// [`Commit` type]:
+    ///     https://jj-vcs.github.io/jj/latest/templates/#commit-type
+    ///
+    /// [`jj help -k templates`]:
+    ///     https://jj-vcs.github.io/jj/latest/templates/
+    #[arg(long, short = 'T')]
+    template: Option<String>,
+
+    /// List older commits first in each section
+    #[arg(long)]
+    reversed: bool,
+}
+
+/// Represents an entry in the `changelog.sections` config array.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct SectionConfig {
+    title: String,
+    #[serde(default)]
+    prefixes: Vec<String>,
+    #[serde(default)]
+    trailers: Vec<String>,
+}
+
+impl SectionConfig {
+    fn matches(&self, description: &str) -> bool {
+        let first_line = description.lines().next().unwrap_or_default();
+        if self
+            .prefixes
+            .iter()
+            .any(|prefix| has_conventional_prefix(first_line, prefix))
+        {
+            return true;
+        }
+        let trailers = parse_description_trailers(description);
+        self.trailers.iter().any(|rule| {
+            let (key, value) = match rule.split_once(':') {
+                Some((key, value)) => (key.trim(), Some(value.trim())),
+                None => (rule.trim(), None),
+            };
+            trailers.iter().any(|trailer| {
+                trailer.key.eq_ignore_ascii_case(key)
+                    && value.is_none_or(|value| trailer.value.trim() == value)
+            })
+        })
+    }
+}
+
+/// Returns true if `line` starts with `prefix` in the style of conventional
+/// commits, e.g. `feat: ...`, `feat(scope): ...`, or `feat!: ...`.
+fn has_conventional_prefix(line: &str, prefix: &str) -> bool {
+    let Some(rest) = line.strip_prefix(prefix) else {
+        return false;
+    };
+    let rest = match rest.strip_prefix('(') {
+        Some(rest) => match rest.split_once(')') {
+            Some((_scope, rest)) => rest,
+            None => return false,
+        },
+        None => rest,
+    };
+    let rest = rest.strip_prefix('!').unwrap_or(rest);
+    rest.starts_with(':')
+}
+
+#[instrument(skip_all)]
+pub fn cmd_changelog(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ChangelogArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let settings = workspace_command.settings();
+    let sections: Vec<SectionConfig> = settings.get("changelog.sections")?;
+    let other_title = settings.get_string("changelog.other-title")?;
+    let template = {
+        let text = match &args.template {
+            Some(value) => value.to_owned(),
+            None => settings.get_string("templates.changelog_entry")?,
+        };
+        workspace_command
+            .parse_commit_template(ui, &text)?
+            .labeled(["changelog"])
+    };
+
+    let mut commits: Vec<Commit> = workspace_command
+        .parse_union_revsets(ui, &args.revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    if args.reversed {
+        commits.reverse();
+    }
+
+    let mut grouped: Vec<Vec<&Commit>> = vec![vec![]; sections.len() + 1];
+    for commit in &commits {
+        let index = sections
+            .iter()
+            .position(|section| section.matches(commit.description()))
+            .unwrap_or(sections.len());
+        grouped[index].push(commit);
+    }
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    let titles = sections
+        .iter()
+        .map(|section| section.title.as_str())
+        .chain([other_title.as_str()]);
+    let mut first = true;
+    for (title, commits) in titles.zip(&grouped) {
+        if title.is_empty() || commits.is_empty() {
+            continue;
+        }
+        if !first {
+            writeln!(formatter)?;
+        }
+        first = false;
+        writeln!(
+            formatter.labeled("changelog").labeled("title"),
+            "### {title}"
+        )?;
+        writeln!(formatter)?;
+        for commit in commits {
+            template.format(commit, formatter.as_mut())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_conventional_prefix() {
+        assert!(has_conventional_prefix("feat: add thing", "feat"));
+        assert!(has_conventional_prefix("feat(cli): add thing", "feat"));
+        assert!(has_conventional_prefix("feat!: add thing", "feat"));
+        assert!(has_conventional_prefix("feat(cli)!: add thing", "feat"));
+        assert!(!has_conventional_prefix("feature: add thing", "feat"));
+        assert!(!has_conventional_prefix("feat add thing", "feat"));
+        assert!(!has_conventional_prefix("feat(cli add thing", "feat"));
+        assert!(!has_conventional_prefix("fix: feat: thing", "feat"));
+    }
+}
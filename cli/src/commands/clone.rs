@@ -0,0 +1,41 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::commands::git::GitCloneArgs;
+use crate::commands::git::cmd_git_clone;
+use crate::ui::Ui;
+
+/// Create a new repo by cloning an existing one
+///
+/// Only Git repos can be cloned for now, so this is the same as `jj git
+/// clone`. The source is set up as the `origin` remote, and its default
+/// bookmark is tracked and checked out.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct CloneArgs {
+    #[command(flatten)]
+    git: GitCloneArgs,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_clone(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &CloneArgs,
+) -> Result<(), CommandError> {
+    cmd_git_clone(ui, command, &args.git)
+}
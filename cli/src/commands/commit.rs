@@ -24,7 +24,7 @@ use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
 use crate::complete;
-use crate::description_util::add_trailers;
+use crate::description_util::add_trailers_with_signoff;
 use crate::description_util::description_template;
 use crate::description_util::edit_description;
 use crate::description_util::join_message_paragraphs;
@@ -63,6 +63,11 @@ pub(crate) struct CommitArgs {
     /// The change description to use (don't open editor)
     #[arg(long = "message", short, value_name = "MESSAGE")]
     message_paragraphs: Vec<String>,
+    /// Add a "Signed-off-by" trailer with the committer's name and email
+    ///
+    /// The trailer is added after the ones from `templates.commit_trailers`.
+    #[arg(long)]
+    signoff: bool,
     /// Put these paths in the first commit
     #[arg(
         value_name = "FILESETS",
@@ -151,6 +156,14 @@ new working-copy commit.
             "The given paths do not match any file: {}",
             args.paths.join(" ")
         )?;
+    } else if diff_selector.is_interactive()
+        && tree_id == base_tree.id()
+        && *commit.tree_id() != base_tree.id()
+    {
+        writeln!(
+            ui.warning_default(),
+            "No changes have been selected, so the new revision will be empty"
+        )?;
     }
 
     let mut commit_builder = tx.repo_mut().rewrite_commit(&commit).detach();
@@ -175,11 +188,11 @@ new working-copy commit.
             // can be discarded as soon as it's no longer the working copy. Adding a
             // trailer to an empty description would break that logic.
             commit_builder.set_description(description);
-            description = add_trailers(ui, &tx, &commit_builder)?;
+            description = add_trailers_with_signoff(ui, &tx, &commit_builder, args.signoff)?;
         }
         description
     } else {
-        let description = add_trailers(ui, &tx, &commit_builder)?;
+        let description = add_trailers_with_signoff(ui, &tx, &commit_builder, args.signoff)?;
         commit_builder.set_description(description);
         let temp_commit = commit_builder.write_hidden()?;
         let intro = "";
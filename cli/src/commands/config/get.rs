@@ -17,8 +17,10 @@ use std::io::Write as _;
 use clap_complete::ArgValueCandidates;
 use jj_lib::config::ConfigNamePathBuf;
 use jj_lib::config::ConfigValue;
+use jj_lib::config::StackedConfig;
 use tracing::instrument;
 
+use super::ConfigLevelArgs;
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::complete;
@@ -33,11 +35,17 @@ use crate::ui::Ui;
 /// user.name="Martin von Zweigbergk"
 /// $ jj config get user.name
 /// Martin von Zweigbergk
+///
+/// With `--user` or `--repo`, only the value set at that level is printed. The
+/// command fails if the option isn't set at that level.
 #[derive(clap::Args, Clone, Debug)]
 #[command(verbatim_doc_comment)]
+#[command(mut_group("config_level", |g| g.required(false)))]
 pub struct ConfigGetArgs {
     #[arg(required = true, add = ArgValueCandidates::new(complete::leaf_config_keys))]
     name: ConfigNamePathBuf,
+    #[command(flatten)]
+    level: ConfigLevelArgs,
 }
 
 #[instrument(skip_all)]
@@ -46,7 +54,14 @@ pub fn cmd_config_get(
     command: &CommandHelper,
     args: &ConfigGetArgs,
 ) -> Result<(), CommandError> {
-    let value = command.settings().get_value(&args.name)?;
+    let value = if let Some(source) = args.level.get_source_kind() {
+        let layers = command.settings().config().layers_for(source);
+        let mut config = StackedConfig::empty();
+        config.extend_layers(layers.iter().cloned());
+        config.get_value(&args.name)?
+    } else {
+        command.settings().get_value(&args.name)?
+    };
     let stringified = match value {
         // Remove extra formatting from a string value
         ConfigValue::String(v) => v.into_value(),
@@ -18,6 +18,7 @@ mod list;
 mod path;
 mod set;
 mod unset;
+mod validate;
 
 use std::path::Path;
 
@@ -38,6 +39,8 @@ use self::set::ConfigSetArgs;
 use self::set::cmd_config_set;
 use self::unset::ConfigUnsetArgs;
 use self::unset::cmd_config_unset;
+use self::validate::ConfigValidateArgs;
+use self::validate::cmd_config_validate;
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
@@ -54,6 +57,10 @@ pub(crate) struct ConfigLevelArgs {
     /// Target the repo-level config
     #[arg(long)]
     repo: bool,
+
+    /// Target the workspace-level config
+    #[arg(long)]
+    workspace: bool,
 }
 
 impl ConfigLevelArgs {
@@ -62,6 +69,8 @@ impl ConfigLevelArgs {
             Some(ConfigSource::User)
         } else if self.repo {
             Some(ConfigSource::Repo)
+        } else if self.workspace {
+            Some(ConfigSource::Workspace)
         } else {
             None
         }
@@ -79,6 +88,11 @@ impl ConfigLevelArgs {
                 .repo_config_path()
                 .map(|p| vec![p])
                 .ok_or_else(|| user_error("No repo config path found"))
+        } else if self.workspace {
+            config_env
+                .workspace_config_path()
+                .map(|p| vec![p])
+                .ok_or_else(|| user_error("No workspace config path found"))
         } else {
             panic!("No config_level provided")
         }
@@ -116,6 +130,11 @@ impl ConfigLevelArgs {
                 config_env.repo_config_files(config)?,
                 "No repo config path found to edit",
             )
+        } else if self.workspace {
+            pick_one(
+                config_env.workspace_config_files(config)?,
+                "No workspace config path found to edit",
+            )
         } else {
             panic!("No config_level provided")
         }
@@ -146,6 +165,7 @@ pub(crate) enum ConfigCommand {
     Set(ConfigSetArgs),
     #[command(visible_alias("u"))]
     Unset(ConfigUnsetArgs),
+    Validate(ConfigValidateArgs),
 }
 
 #[instrument(skip_all)]
@@ -161,5 +181,6 @@ pub(crate) fn cmd_config(
         ConfigCommand::Path(args) => cmd_config_path(ui, command, args),
         ConfigCommand::Set(args) => cmd_config_set(ui, command, args),
         ConfigCommand::Unset(args) => cmd_config_unset(ui, command, args),
+        ConfigCommand::Validate(args) => cmd_config_validate(ui, command, args),
     }
 }
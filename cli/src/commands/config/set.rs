@@ -96,7 +96,7 @@ fn check_wc_author(
     new_value: &toml_edit::Value,
     author_change: AuthorChange,
 ) -> io::Result<()> {
-    let helper = match command.workspace_helper(ui) {
+    let helper = match command.workspace_helper_without_index(ui) {
         Ok(helper) => helper,
         Err(_) => return Ok(()), // config set should work even if cwd isn't a jj repo
     };
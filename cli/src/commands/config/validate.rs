@@ -0,0 +1,112 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::io::Write as _;
+
+use itertools::Itertools as _;
+use jj_lib::config::ConfigSource;
+use tracing::instrument;
+
+use super::ConfigLevelArgs;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::config::ConfigValidationError;
+use crate::config::find_config_line;
+use crate::config::validate_config_layer;
+use crate::ui::Ui;
+
+/// Check config files for unknown keys and invalid values
+///
+/// Variables set in the user and repo config files, and by `--config`
+/// arguments are checked against the schema of the known config options. Each
+/// problem is reported along with the file and line where the variable is
+/// defined. Built-in defaults and variables derived from environment
+/// variables are not checked.
+///
+/// Exits with an error if any problem is found.
+#[derive(clap::Args, Clone, Debug)]
+#[command(mut_group("config_level", |g| g.required(false)))]
+pub struct ConfigValidateArgs {
+    #[command(flatten)]
+    pub level: ConfigLevelArgs,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_config_validate(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ConfigValidateArgs,
+) -> Result<(), CommandError> {
+    let source_kind = args.level.get_source_kind();
+    let mut num_errors = 0;
+    let mut formatter = ui.stdout_formatter();
+    for layer in command.raw_config().as_ref().layers() {
+        let is_checked = match source_kind {
+            Some(kind) => layer.source == kind,
+            None => matches!(
+                layer.source,
+                ConfigSource::User
+                    | ConfigSource::Repo
+                    | ConfigSource::Workspace
+                    | ConfigSource::CommandArg
+            ),
+        };
+        if !is_checked {
+            continue;
+        }
+        let errors = validate_config_layer(layer);
+        if errors.is_empty() {
+            continue;
+        }
+        let text = layer
+            .path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok());
+        let lines = errors
+            .iter()
+            .map(|err| {
+                text.as_ref()
+                    .and_then(|text| find_config_line(text, err.name()))
+            })
+            .collect_vec();
+        for (err, line) in errors.iter().zip(&lines).sorted_by_key(|(_, line)| **line) {
+            let location = match (&layer.path, line) {
+                (Some(path), Some(line)) => format!("{}:{line}", path.display()),
+                (Some(path), None) => path.display().to_string(),
+                (None, _) => format!("<{}>", layer.source),
+            };
+            write!(formatter, "{location}: {err}")?;
+            match err {
+                ConfigValidationError::UnknownName { similar_names, .. }
+                    if !similar_names.is_empty() =>
+                {
+                    let similar_names = similar_names.iter().map(|s| format!("`{s}`")).join(", ");
+                    writeln!(formatter, " (did you mean {similar_names}?)")?;
+                }
+                _ => writeln!(formatter)?,
+            }
+        }
+        num_errors += errors.len();
+    }
+    drop(formatter);
+    if num_errors > 0 {
+        return Err(user_error(format!(
+            "Found {num_errors} problem(s) in config"
+        )));
+    }
+    writeln!(ui.status(), "No problems found in config")?;
+    Ok(())
+}
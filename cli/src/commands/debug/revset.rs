@@ -26,6 +26,11 @@ use crate::revset_util;
 use crate::ui::Ui;
 
 /// Evaluate revset to full commit IDs
+///
+/// The expression is printed at each stage: as parsed, with symbols resolved,
+/// optimized, converted for the index backend, and the evaluation plan built
+/// by the index. This can help to understand why a revset is slow or matches
+/// unexpected commits.
 #[derive(clap::Args, Clone, Debug)]
 pub struct DebugRevsetArgs {
     revision: String,
@@ -35,6 +40,11 @@ pub struct DebugRevsetArgs {
     /// Do not rewrite expression to optimized form
     #[arg(long)]
     no_optimize: bool,
+    /// Do not list the commit IDs, only print the evaluation plan
+    ///
+    /// Useful to inspect a revset which takes long to evaluate.
+    #[arg(long, conflicts_with = "no_resolve")]
+    no_commit_ids: bool,
 }
 
 pub fn cmd_debug_revset(
@@ -90,6 +100,9 @@ pub fn cmd_debug_revset(
     writeln!(ui.stdout(), "{revset:#?}")?;
     writeln!(ui.stdout())?;
 
+    if args.no_commit_ids {
+        return Ok(());
+    }
     writeln!(ui.stdout(), "-- Commit IDs:")?;
     for commit_id in revset.iter() {
         writeln!(ui.stdout(), "{}", commit_id?.hex())?;
@@ -43,6 +43,10 @@ use crate::ui::Ui;
 ///
 /// Starts an editor to let you edit the description of changes. The editor
 /// will be $EDITOR, or `nano` if that's not defined (`Notepad` on Windows).
+///
+/// If multiple revisions are specified, their descriptions are edited together
+/// in a single editor. Each description follows a `JJ: describe <commit id>`
+/// line, which must be kept to associate it with its revision.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct DescribeArgs {
     /// The revision(s) whose description to edit (default: @)
@@ -87,6 +91,11 @@ pub(crate) struct DescribeArgs {
     /// allow the message to be edited afterwards.
     #[arg(long)]
     edit: bool,
+    /// Add a "Signed-off-by" trailer with the committer's name and email
+    ///
+    /// The trailer is added after the ones from `templates.commit_trailers`.
+    #[arg(long)]
+    signoff: bool,
     // TODO: Delete in jj 0.40.0+
     /// Reset the author name, email, and timestamp
     ///
@@ -201,7 +210,7 @@ pub(crate) fn cmd_describe(
 
     let use_editor = args.edit || (shared_description.is_none() && !args.no_edit);
 
-    if let Some(trailer_template) = parse_trailers_template(ui, &tx)? {
+    if let Some(trailer_template) = parse_trailers_template(ui, &tx, args.signoff)? {
         for commit_builder in &mut commit_builders {
             // The first trailer would become the first line of the description.
             // Also, a commit with no description is treated in a special way in jujutsu: it
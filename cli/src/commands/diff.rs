@@ -26,11 +26,14 @@ use crate::cli_util::RevisionArg;
 use crate::cli_util::print_unmatched_explicit_paths;
 use crate::cli_util::short_commit_hash;
 use crate::command_error::CommandError;
+use crate::command_error::user_error;
 use crate::command_error::user_error_with_hint;
 use crate::complete;
 use crate::diff_util::DiffFormatArgs;
+use crate::diff_util::collect_diff_summary;
 use crate::diff_util::get_copy_records;
 use crate::diff_util::show_templated;
+use crate::ui::OutputFormat;
 use crate::ui::Ui;
 
 /// Compare file contents between two revisions
@@ -194,6 +197,25 @@ pub(crate) fn cmd_diff(
         }
     }
 
+    if ui.output_format() == OutputFormat::Json {
+        if !args.format.summary || args.template.is_some() {
+            return Err(user_error(
+                "`--output json` is only supported for `jj diff --summary`",
+            ));
+        }
+        let tree_diff = from_tree.diff_stream_with_copies(&to_tree, &matcher, &copy_records);
+        for entry in collect_diff_summary(tree_diff).block_on()? {
+            ui.write_json(&entry)?;
+        }
+        print_unmatched_explicit_paths(
+            ui,
+            &workspace_command,
+            &fileset_expression,
+            [&from_tree, &to_tree],
+        )?;
+        return Ok(());
+    }
+
     // -T disables both short/long rendering formats, but it might be okay to
     // enable long format if explicitly specified (assuming -T is for short or
     // summary output.)
@@ -12,16 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
+use futures::StreamExt as _;
 use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
 use jj_lib::evolution::CommitEvolutionEntry;
 use jj_lib::evolution::walk_predecessors;
 use jj_lib::graph::GraphEdge;
+use jj_lib::graph::GraphEdgeType;
+use jj_lib::graph::GraphNode;
 use jj_lib::graph::TopoGroupedGraphIterator;
 use jj_lib::graph::reverse_graph;
-use jj_lib::matchers::EverythingMatcher;
+use jj_lib::matchers::Matcher;
+use jj_lib::repo::Repo;
+use jj_lib::rewrite::rebase_to_dest_parent;
 use pollster::FutureExt as _;
 use tracing::instrument;
 
@@ -86,6 +94,16 @@ pub(crate) struct EvologArgs {
     /// contaminated by unrelated changes.
     #[arg(long, short = 'p')]
     patch: bool,
+    /// Show only versions modifying the given paths
+    ///
+    /// A version is shown if the content of the paths differs from the
+    /// previous version of the change.
+    #[arg(
+        value_name = "FILESETS",
+        value_hint = clap::ValueHint::AnyPath,
+        add = ArgValueCompleter::new(complete::log_files),
+    )]
+    paths: Vec<String>,
     #[command(flatten)]
     diff_format: DiffFormatArgs,
 }
@@ -103,6 +121,9 @@ pub(crate) fn cmd_evolog(
         .evaluate_to_commit_ids()?
         .try_collect()?;
 
+    let matcher = workspace_command
+        .parse_file_patterns(ui, &args.paths)?
+        .to_matcher();
     let diff_renderer = workspace_command.diff_renderer_for_log(&args.diff_format, args.patch)?;
     let graph_style = GraphStyle::from_settings(workspace_command.settings())?;
     let with_content_format = LogContentFormat::new(ui, workspace_command.settings())?;
@@ -145,6 +166,14 @@ pub(crate) fn cmd_evolog(
             let edges = ids.iter().cloned().map(GraphEdge::direct).collect_vec();
             (entry, edges)
         });
+        let evolution_nodes: Box<dyn Iterator<Item = Result<_, CommandError>>> =
+            if args.paths.is_empty() {
+                Box::new(evolution_nodes.map(|node| Ok(node?)))
+            } else {
+                let nodes: Vec<_> = evolution_nodes.try_collect()?;
+                let nodes = filter_evolution_nodes(repo.as_ref(), nodes, matcher.as_ref())?;
+                Box::new(nodes.into_iter().map(Ok))
+            };
         // TopoGroupedGraphIterator also helps emit squashed commits in reverse
         // chronological order. Predecessors don't need to follow any defined
         // order. However in practice, if there are multiple predecessors, then
@@ -186,7 +215,7 @@ pub(crate) fn cmd_evolog(
                         formatter.as_mut(),
                         &predecessors,
                         &entry.commit,
-                        &EverythingMatcher,
+                        matcher.as_ref(),
                         within_graph.width(),
                     )
                     .block_on()?;
@@ -200,6 +229,18 @@ pub(crate) fn cmd_evolog(
             )?;
         }
     } else {
+        let evolution_entries = evolution_entries
+            .map(|entry| -> Result<_, CommandError> {
+                let entry = entry?;
+                if args.paths.is_empty()
+                    || entry_modifies_paths(repo.as_ref(), &entry, matcher.as_ref())?
+                {
+                    Ok(Some(entry))
+                } else {
+                    Ok(None)
+                }
+            })
+            .filter_map(Result::transpose);
         let evolution_entries = evolution_entries.take(args.limit.unwrap_or(usize::MAX));
         let evolution_entries: Box<dyn Iterator<Item = _>> = if args.reversed {
             let entries: Vec<_> = evolution_entries.try_collect()?;
@@ -220,7 +261,7 @@ pub(crate) fn cmd_evolog(
                         formatter,
                         &predecessors,
                         &entry.commit,
-                        &EverythingMatcher,
+                        matcher.as_ref(),
                         width,
                     )
                     .block_on()?;
@@ -230,3 +271,53 @@ pub(crate) fn cmd_evolog(
 
     Ok(())
 }
+
+/// Returns true if the `entry` commit modifies paths matched by `matcher`
+/// compared to its predecessors.
+fn entry_modifies_paths(
+    repo: &dyn Repo,
+    entry: &CommitEvolutionEntry,
+    matcher: &dyn Matcher,
+) -> Result<bool, CommandError> {
+    let predecessors: Vec<_> = entry.predecessors().try_collect()?;
+    let from_tree = rebase_to_dest_parent(repo, &predecessors, &entry.commit)?;
+    let to_tree = entry.commit.tree()?;
+    let mut diff_stream = from_tree.diff_stream(&to_tree, matcher);
+    Ok(diff_stream.next().block_on().is_some())
+}
+
+/// Removes the nodes which don't modify paths matched by `matcher` from the
+/// evolution graph. Edges to the removed nodes are replaced with indirect
+/// edges to their predecessors.
+fn filter_evolution_nodes(
+    repo: &dyn Repo,
+    nodes: Vec<GraphNode<CommitEvolutionEntry, CommitId>>,
+    matcher: &dyn Matcher,
+) -> Result<Vec<GraphNode<CommitEvolutionEntry, CommitId>>, CommandError> {
+    let mut removed_edges: HashMap<CommitId, Vec<GraphEdge<CommitId>>> = HashMap::new();
+    let mut kept_nodes = vec![];
+    // Visit predecessors first so the edges of removed nodes are resolved.
+    for (entry, edges) in nodes.into_iter().rev() {
+        let edges = edges
+            .into_iter()
+            .flat_map(|edge| match removed_edges.get(&edge.target) {
+                Some(edges) => edges
+                    .iter()
+                    .map(|edge| match edge.edge_type {
+                        GraphEdgeType::Missing => edge.clone(),
+                        _ => GraphEdge::indirect(edge.target.clone()),
+                    })
+                    .collect_vec(),
+                None => vec![edge],
+            })
+            .unique_by(|edge| edge.target.clone())
+            .collect_vec();
+        if entry_modifies_paths(repo, &entry, matcher)? {
+            kept_nodes.push((entry, edges));
+        } else {
+            removed_edges.insert(entry.commit.id().clone(), edges);
+        }
+    }
+    kept_nodes.reverse();
+    Ok(kept_nodes)
+}
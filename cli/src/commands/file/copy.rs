@@ -0,0 +1,209 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCompleter;
+use jj_lib::backend::BackendError;
+use jj_lib::backend::CopyHistory;
+use jj_lib::backend::CopyId;
+use jj_lib::backend::TreeValue;
+use jj_lib::commit::Commit;
+use jj_lib::matchers::PrefixMatcher;
+use jj_lib::merge::Merge;
+use jj_lib::merged_tree::MergedTreeBuilder;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo_path::RepoPath;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::store::Store;
+use pollster::FutureExt as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Copy a file or directory, recording it as a copy
+///
+/// If the backend supports copy tracking, the copy is recorded in the new
+/// files, so later diffs and annotations can follow it without having to guess
+/// from the file contents.
+///
+/// If the destination is an existing directory, the source is copied into it.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct FileCopyArgs {
+    /// The revision to update
+    #[arg(
+        long, short,
+        default_value = "@",
+        value_name = "REVSET",
+        add = ArgValueCompleter::new(complete::revset_expression_mutable),
+    )]
+    revision: RevisionArg,
+    /// The file or directory to copy
+    #[arg(
+        value_hint = clap::ValueHint::AnyPath,
+        add = ArgValueCompleter::new(complete::all_revision_files),
+    )]
+    source: String,
+    /// The path to copy to
+    #[arg(value_hint = clap::ValueHint::AnyPath)]
+    destination: String,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_file_copy(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &FileCopyArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
+    workspace_command.check_rewritable([commit.id()])?;
+    let source = workspace_command.parse_file_path(&args.source)?;
+    let destination = workspace_command.parse_file_path(&args.destination)?;
+    copy_paths(
+        ui,
+        workspace_command,
+        &commit,
+        &source,
+        &destination,
+        CopyMode::Copy,
+    )
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum CopyMode {
+    Copy,
+    Move,
+}
+
+/// Copies or moves `source` to `destination` in the tree of `commit`, and
+/// rewrites the commit with the new tree.
+pub(crate) fn copy_paths(
+    ui: &mut Ui,
+    mut workspace_command: WorkspaceCommandHelper,
+    commit: &Commit,
+    source: &RepoPath,
+    destination: &RepoPath,
+    mode: CopyMode,
+) -> Result<(), CommandError> {
+    let format_path = |path: &RepoPath| workspace_command.format_file_path(path);
+    let tree = commit.tree()?;
+    let store = tree.store().clone();
+    if source.is_root() {
+        return Err(user_error("The source cannot be the root directory"));
+    }
+    if tree.path_value(source)?.is_absent() {
+        return Err(user_error(format!("No such path: {}", format_path(source))));
+    }
+    // Like cp(1) and mv(1), copy into the destination if it's a directory.
+    let destination_value = tree.path_value(destination)?;
+    let destination = if let Some(Some(TreeValue::Tree(_))) = destination_value.as_resolved() {
+        let (_, name) = source.split().unwrap();
+        destination.join(name)
+    } else {
+        destination.to_owned()
+    };
+    if destination.starts_with(source) {
+        return Err(user_error(format!(
+            "Destination is inside the source: {}",
+            format_path(&destination)
+        )));
+    }
+    if tree.path_value(&destination)?.is_present() {
+        return Err(user_error(format!(
+            "Destination already exists: {}",
+            format_path(&destination)
+        )));
+    }
+
+    let mut tree_builder = MergedTreeBuilder::new(commit.tree_id().clone());
+    let matcher = PrefixMatcher::new([source]);
+    for (path, value) in tree.entries_matching(&matcher) {
+        let mut value = value?;
+        let relative_path = path.strip_prefix(source).unwrap();
+        let new_path = relative_path
+            .components()
+            .fold(destination.clone(), |path, name| path.join(name));
+        for value in value.iter_mut().flatten() {
+            if let TreeValue::File { copy_id, .. } = value {
+                *copy_id = record_copy(&store, copy_id, &path, &new_path)?;
+            }
+        }
+        tree_builder.set_or_remove(new_path, value);
+        if mode == CopyMode::Move {
+            tree_builder.set_or_remove(path, Merge::absent());
+        }
+    }
+
+    let mut tx = workspace_command.start_transaction();
+    let new_tree_id = tree_builder.write_tree(&store)?;
+    tx.repo_mut()
+        .rewrite_commit(commit)
+        .set_tree_id(new_tree_id)
+        .write()?;
+    let verb = match mode {
+        CopyMode::Copy => "copy",
+        CopyMode::Move => "move",
+    };
+    tx.finish(
+        ui,
+        format!(
+            "{verb} {} to {} in commit {}",
+            source.as_internal_file_string(),
+            destination.as_internal_file_string(),
+            commit.id().hex()
+        ),
+    )
+}
+
+/// Records that the file at `new_path` is a copy of the file at `old_path`,
+/// and returns the copy id for the new file.
+///
+/// If the backend doesn't support copy tracking, the old copy id is kept, and
+/// it's up to the backend to detect the copy (e.g. by comparing contents).
+fn record_copy(
+    store: &Store,
+    copy_id: &CopyId,
+    old_path: &RepoPath,
+    new_path: &RepoPathBuf,
+) -> Result<CopyId, CommandError> {
+    let result = (|| {
+        let parent = if *copy_id == CopyId::placeholder() {
+            // The source was never recorded, so start its history here.
+            let history = CopyHistory {
+                current_path: old_path.to_owned(),
+                parents: vec![],
+                salt: vec![],
+            };
+            store.backend().write_copy(&history).block_on()?
+        } else {
+            copy_id.clone()
+        };
+        let history = CopyHistory {
+            current_path: new_path.clone(),
+            parents: vec![parent],
+            salt: vec![],
+        };
+        store.backend().write_copy(&history).block_on()
+    })();
+    match result {
+        Ok(copy_id) => Ok(copy_id),
+        Err(BackendError::Unsupported(_)) => Ok(copy_id.clone()),
+        Err(err) => Err(err.into()),
+    }
+}
@@ -14,7 +14,9 @@
 
 mod annotate;
 mod chmod;
+mod copy;
 mod list;
+mod r#move;
 mod show;
 mod track;
 mod untrack;
@@ -28,7 +30,11 @@ use crate::ui::Ui;
 pub enum FileCommand {
     Annotate(annotate::FileAnnotateArgs),
     Chmod(chmod::FileChmodArgs),
+    #[command(visible_alias = "cp")]
+    Copy(copy::FileCopyArgs),
     List(list::FileListArgs),
+    #[command(visible_alias = "mv")]
+    Move(r#move::FileMoveArgs),
     Show(show::FileShowArgs),
     Track(track::FileTrackArgs),
     Untrack(untrack::FileUntrackArgs),
@@ -42,7 +48,9 @@ pub fn cmd_file(
     match subcommand {
         FileCommand::Annotate(args) => annotate::cmd_file_annotate(ui, command, args),
         FileCommand::Chmod(args) => chmod::cmd_file_chmod(ui, command, args),
+        FileCommand::Copy(args) => copy::cmd_file_copy(ui, command, args),
         FileCommand::List(args) => list::cmd_file_list(ui, command, args),
+        FileCommand::Move(args) => r#move::cmd_file_move(ui, command, args),
         FileCommand::Show(args) => show::cmd_file_show(ui, command, args),
         FileCommand::Track(args) => track::cmd_file_track(ui, command, args),
         FileCommand::Untrack(args) => untrack::cmd_file_untrack(ui, command, args),
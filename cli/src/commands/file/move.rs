@@ -0,0 +1,73 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCompleter;
+use tracing::instrument;
+
+use super::copy::CopyMode;
+use super::copy::copy_paths;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Move or rename a file or directory, recording it as a rename
+///
+/// If the backend supports copy tracking, the rename is recorded in the moved
+/// files, so later diffs and annotations can follow it without having to guess
+/// from the file contents.
+///
+/// If the destination is an existing directory, the source is moved into it.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct FileMoveArgs {
+    /// The revision to update
+    #[arg(
+        long, short,
+        default_value = "@",
+        value_name = "REVSET",
+        add = ArgValueCompleter::new(complete::revset_expression_mutable),
+    )]
+    revision: RevisionArg,
+    /// The file or directory to move
+    #[arg(
+        value_hint = clap::ValueHint::AnyPath,
+        add = ArgValueCompleter::new(complete::all_revision_files),
+    )]
+    source: String,
+    /// The path to move to
+    #[arg(value_hint = clap::ValueHint::AnyPath)]
+    destination: String,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_file_move(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &FileMoveArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
+    workspace_command.check_rewritable([commit.id()])?;
+    let source = workspace_command.parse_file_path(&args.source)?;
+    let destination = workspace_command.parse_file_path(&args.destination)?;
+    copy_paths(
+        ui,
+        workspace_command,
+        &commit,
+        &source,
+        &destination,
+        CopyMode::Move,
+    )
+}
@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fs;
 use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
 
 use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
@@ -21,6 +24,7 @@ use jj_lib::conflicts::ConflictMaterializeOptions;
 use jj_lib::conflicts::MaterializedTreeValue;
 use jj_lib::conflicts::materialize_merge_result;
 use jj_lib::conflicts::materialize_tree_value;
+use jj_lib::file_util::IoResultExt as _;
 use jj_lib::file_util::copy_async_to_sync;
 use jj_lib::fileset::FilePattern;
 use jj_lib::fileset::FilesetExpression;
@@ -35,6 +39,7 @@ use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::print_unmatched_explicit_paths;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
 use crate::commit_templater::TreeEntry;
 use crate::complete;
 use crate::templater::TemplateRenderer;
@@ -71,6 +76,19 @@ pub(crate) struct FileShowArgs {
     #[arg(long, short = 'T')]
     template: Option<String>,
 
+    /// Write the files to this directory instead of printing them
+    ///
+    /// The files are written to the same relative paths as in the repo,
+    /// replacing existing files.
+    #[arg(
+        long,
+        short,
+        value_name = "DIR",
+        value_hint = clap::ValueHint::DirPath,
+        conflicts_with = "template"
+    )]
+    output_dir: Option<PathBuf>,
+
     /// Paths to print
     #[arg(
         required = true,
@@ -104,6 +122,25 @@ pub(crate) fn cmd_file_show(
             .labeled(["file_show"])
     };
 
+    if let Some(output_dir) = &args.output_dir {
+        let matcher = fileset_expression.to_matcher();
+        let num_written = write_tree_entries_to_dir(
+            ui,
+            &workspace_command,
+            &command.cwd().join(output_dir),
+            tree.entries_matching(matcher.as_ref())
+                .map(|(path, value)| Ok((path, value?)))
+                .map_ok(|(path, value)| TreeEntry { path, value }),
+        )?;
+        print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, [&tree])?;
+        writeln!(
+            ui.status(),
+            "Wrote {num_written} files to {}",
+            output_dir.display()
+        )?;
+        return Ok(());
+    }
+
     // Try fast path for single file entry
     if let Some(path) = get_single_path(&fileset_expression) {
         let value = tree.path_value(path)?;
@@ -195,3 +232,72 @@ fn write_tree_entries(
     }
     Ok(())
 }
+
+/// Writes the files of the `entries` to the same relative paths under
+/// `output_dir`. Returns the number of files written.
+fn write_tree_entries_to_dir(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    output_dir: &Path,
+    entries: impl IntoIterator<Item = BackendResult<TreeEntry>>,
+) -> Result<usize, CommandError> {
+    let repo = workspace_command.repo();
+    let mut num_written = 0;
+    for entry in entries {
+        let entry = entry?;
+        let materialized =
+            materialize_tree_value(repo.store(), &entry.path, entry.value).block_on()?;
+        let (contents, executable) = match materialized {
+            MaterializedTreeValue::Absent => panic!("absent values should be excluded"),
+            MaterializedTreeValue::AccessDenied(err) => {
+                let ui_path = workspace_command.format_file_path(&entry.path);
+                writeln!(
+                    ui.warning_default(),
+                    "Path '{ui_path}' exists but access is denied: {err}"
+                )?;
+                continue;
+            }
+            MaterializedTreeValue::File(mut file) => {
+                (file.read_all(&entry.path).block_on()?, file.executable)
+            }
+            MaterializedTreeValue::FileConflict(file) => {
+                let options = ConflictMaterializeOptions {
+                    marker_style: workspace_command.env().conflict_marker_style(),
+                    marker_len: None,
+                    merge: repo.store().merge_options().clone(),
+                };
+                let mut contents = vec![];
+                materialize_merge_result(&file.contents, &mut contents, &options)?;
+                (contents, file.executable.unwrap_or(false))
+            }
+            MaterializedTreeValue::OtherConflict { id } => (id.describe().into_bytes(), false),
+            MaterializedTreeValue::Symlink { .. } | MaterializedTreeValue::GitSubmodule(_) => {
+                let ui_path = workspace_command.format_file_path(&entry.path);
+                writeln!(
+                    ui.warning_default(),
+                    "Path '{ui_path}' exists but is not a file"
+                )?;
+                continue;
+            }
+            MaterializedTreeValue::Tree(_) => panic!("entries should not contain trees"),
+        };
+        let path = entry
+            .path
+            .to_fs_path(output_dir)
+            .map_err(|err| user_error_with_message("Invalid path in the revision", err))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context(parent)?;
+        }
+        fs::write(&path, contents).context(&path)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt as _;
+            let mode = if executable { 0o755 } else { 0o644 };
+            fs::set_permissions(&path, fs::Permissions::from_mode(mode)).context(&path)?;
+        }
+        #[cfg(not(unix))]
+        let _ = executable;
+        num_written += 1;
+    }
+    Ok(num_written)
+}
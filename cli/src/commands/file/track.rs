@@ -25,6 +25,7 @@ use tracing::instrument;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::print_untracked_files;
 use crate::command_error::CommandError;
+use crate::operation_notify::notify_operation_completed;
 use crate::ui::Ui;
 
 /// Start tracking specified paths in the working copy
@@ -65,6 +66,12 @@ pub(crate) fn cmd_file_track(
     }
     let repo = tx.commit("track paths")?;
     locked_ws.finish(repo.op_id().clone())?;
+    notify_operation_completed(
+        workspace_command.settings(),
+        workspace_command.repo_path(),
+        workspace_command.repo(),
+        &repo,
+    );
     print_track_snapshot_stats(
         ui,
         auto_stats,
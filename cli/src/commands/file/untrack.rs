@@ -26,6 +26,7 @@ use crate::cli_util::print_snapshot_stats;
 use crate::command_error::CommandError;
 use crate::command_error::user_error_with_hint;
 use crate::complete;
+use crate::operation_notify::notify_operation_completed;
 use crate::ui::Ui;
 
 /// Stop tracking specified paths in the working copy
@@ -111,6 +112,12 @@ Make sure they're ignored, then try again.",
     }
     let repo = tx.commit("untrack paths")?;
     locked_ws.finish(repo.op_id().clone())?;
+    notify_operation_completed(
+        workspace_command.settings(),
+        workspace_command.repo_path(),
+        workspace_command.repo(),
+        &repo,
+    );
     print_snapshot_stats(ui, &stats, workspace_command.env().path_converter())?;
     Ok(())
 }
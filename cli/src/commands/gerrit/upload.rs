@@ -18,6 +18,8 @@ use std::io::Write as _;
 use std::sync::Arc;
 
 use bstr::BStr;
+use clap_complete::ArgValueCandidates;
+use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
 use jj_lib::backend::BackendError;
 use jj_lib::backend::CommitId;
@@ -40,6 +42,7 @@ use crate::command_error::internal_error;
 use crate::command_error::user_error;
 use crate::command_error::user_error_with_hint;
 use crate::command_error::user_error_with_message;
+use crate::complete;
 use crate::git_util::with_remote_git_callbacks;
 use crate::ui::Ui;
 
@@ -68,7 +71,11 @@ pub struct UploadArgs {
     /// commit at the head of a stack, all ancestors are pushed too. This means
     /// that `jj gerrit upload -r foo` is equivalent to `jj gerrit upload -r
     /// 'mutable()::foo`.
-    #[arg(long, short = 'r')]
+    #[arg(
+        long,
+        short = 'r',
+        add = ArgValueCompleter::new(complete::revset_expression_mutable),
+    )]
     revisions: Vec<RevisionArg>,
 
     /// The location where your changes are intended to land
@@ -82,7 +89,7 @@ pub struct UploadArgs {
     ///
     /// Can be configured with the `gerrit.default-remote` repository option as
     /// well. This is typically a full SSH URL for your Gerrit instance.
-    #[arg(long)]
+    #[arg(long, add = ArgValueCandidates::new(complete::git_remotes))]
     remote: Option<String>,
 
     /// Do not actually push the changes to Gerrit
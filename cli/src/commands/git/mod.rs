@@ -34,8 +34,8 @@ use jj_lib::ref_name::RemoteNameBuf;
 use jj_lib::ref_name::RemoteRefSymbol;
 use jj_lib::store::Store;
 
-use self::clone::GitCloneArgs;
-use self::clone::cmd_git_clone;
+pub(crate) use self::clone::GitCloneArgs;
+pub(crate) use self::clone::cmd_git_clone;
 use self::export::GitExportArgs;
 use self::export::cmd_git_export;
 use self::fetch::GitFetchArgs;
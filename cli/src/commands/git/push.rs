@@ -18,6 +18,7 @@ use std::fmt;
 use std::io;
 use std::io::Write as _;
 use std::iter;
+use std::sync::Arc;
 
 use clap::ArgGroup;
 use clap_complete::ArgValueCandidates;
@@ -28,9 +29,11 @@ use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
 use jj_lib::commit::CommitIteratorExt as _;
 use jj_lib::config::ConfigGetResultExt as _;
+use jj_lib::copies::CopyRecords;
 use jj_lib::git;
 use jj_lib::git::GitBranchPushTargets;
 use jj_lib::git::GitPushStats;
+use jj_lib::matchers::EverythingMatcher;
 use jj_lib::op_store::RefTarget;
 use jj_lib::ref_name::RefName;
 use jj_lib::ref_name::RefNameBuf;
@@ -43,18 +46,23 @@ use jj_lib::refs::LocalAndRemoteRef;
 use jj_lib::refs::classify_bookmark_push_action;
 use jj_lib::repo::Repo;
 use jj_lib::revset::RevsetExpression;
+use jj_lib::revset::UserRevsetExpression;
+use jj_lib::rewrite::merge_commit_trees;
 use jj_lib::settings::UserSettings;
 use jj_lib::signing::SignBehavior;
 use jj_lib::str_util::StringPattern;
 use jj_lib::view::View;
+use pollster::FutureExt as _;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::WorkspaceCommandTransaction;
 use crate::cli_util::has_tracked_remote_bookmarks;
+use crate::cli_util::print_updated_commits;
 use crate::cli_util::short_commit_hash;
 use crate::command_error::CommandError;
+use crate::command_error::ErrorCode;
 use crate::command_error::cli_error;
 use crate::command_error::cli_error_with_message;
 use crate::command_error::user_error;
@@ -62,9 +70,14 @@ use crate::command_error::user_error_with_hint;
 use crate::command_error::user_error_with_message;
 use crate::commands::git::get_single_remote;
 use crate::complete;
+use crate::diff_util::DiffFormat;
+use crate::diff_util::get_copy_records;
 use crate::formatter::Formatter;
 use crate::formatter::FormatterExt as _;
 use crate::git_util::with_remote_git_callbacks;
+use crate::hooks::HookKind;
+use crate::hooks::PrePushInput;
+use crate::hooks::PushUpdate;
 use crate::revset_util::parse_bookmark_name;
 use crate::ui::Ui;
 
@@ -190,8 +203,15 @@ pub struct GitPushArgs {
     )]
     named: Vec<String>,
     /// Only display what will change on the remote
+    ///
+    /// Lists the bookmarks that would be updated and the commits that would be
+    /// pushed.
     #[arg(long)]
     dry_run: bool,
+    /// With `--dry-run`, also show a histogram of the changes in the commits
+    /// that would be pushed
+    #[arg(long, requires = "dry_run")]
+    stat: bool,
 }
 
 fn make_bookmark_term(bookmark_names: &[impl fmt::Display]) -> String {
@@ -394,15 +414,22 @@ pub fn cmd_git_push(
         return Ok(());
     }
 
-    let sign_behavior = if tx.settings().get_bool("git.sign-on-push")? {
-        Some(SignBehavior::Own)
-    } else {
-        None
-    };
+    // --no-sign opts out of signing the pushed commits.
+    let sign_behavior =
+        if tx.settings().get_bool("git.sign-on-push")? && !command.global_args().no_sign {
+            Some(SignBehavior::Own)
+        } else {
+            None
+        };
     let commits_to_sign =
         validate_commits_ready_to_push(ui, &bookmark_updates, remote, &tx, args, sign_behavior)?;
-    if !args.dry_run
-        && !commits_to_sign.is_empty()
+    if args.dry_run && !commits_to_sign.is_empty() {
+        writeln!(
+            ui.status(),
+            "Would update signatures of {} commits",
+            commits_to_sign.len()
+        )?;
+    } else if !commits_to_sign.is_empty()
         && let Some(sign_behavior) = sign_behavior
     {
         let num_updated_signatures = commits_to_sign.len();
@@ -433,10 +460,28 @@ pub fn cmd_git_push(
     }
 
     if args.dry_run {
+        print_dry_run_details(ui, &bookmark_updates, remote, &tx, args)?;
         writeln!(ui.status(), "Dry-run requested, not pushing.")?;
         return Ok(());
     }
 
+    let input = PrePushInput {
+        remote: remote.as_str().to_owned(),
+        updates: bookmark_updates
+            .iter()
+            .map(|(name, update)| PushUpdate {
+                bookmark: name.as_str().to_owned(),
+                old_target: update.old_target.clone(),
+                new_target: update.new_target.clone(),
+            })
+            .collect(),
+    };
+    tx.base_workspace_helper()
+        .run_hook(ui, HookKind::PrePush, &input)?;
+    // The remote can't be restored if the hook aborted the operation after
+    // pushing, so check the signed commits now.
+    tx.run_pre_commit_rewrite_hook(ui, &tx_description)?;
+
     let targets = GitBranchPushTargets {
         branch_updates: bookmark_updates,
     };
@@ -494,6 +539,97 @@ fn process_push_stats(push_stats: &GitPushStats) -> Result<(), CommandError> {
     }
 }
 
+/// Returns the commits that would become public by pushing `bookmark_updates`.
+fn commits_to_push_expression(
+    bookmark_updates: &[(RefNameBuf, BookmarkPushUpdate)],
+    remote: &RemoteName,
+    workspace_helper: &WorkspaceCommandHelper,
+) -> Arc<UserRevsetExpression> {
+    let new_heads = bookmark_updates
+        .iter()
+        .filter_map(|(_, update)| update.new_target.clone())
+        .collect_vec();
+    let old_heads = workspace_helper
+        .repo()
+        .view()
+        .remote_bookmarks(remote)
+        .flat_map(|(_, old_head)| old_head.target.added_ids())
+        .cloned()
+        .collect_vec();
+    RevsetExpression::commits(old_heads)
+        .union(workspace_helper.env().immutable_heads_expression())
+        .range(&RevsetExpression::commits(new_heads))
+}
+
+/// Prints the commits that would be pushed, and optionally their diff stat.
+fn print_dry_run_details(
+    ui: &Ui,
+    bookmark_updates: &[(RefNameBuf, BookmarkPushUpdate)],
+    remote: &RemoteName,
+    tx: &WorkspaceCommandTransaction,
+    args: &GitPushArgs,
+) -> Result<(), CommandError> {
+    let Some(mut formatter) = ui.status_formatter() else {
+        return Ok(());
+    };
+    let workspace_helper = tx.base_workspace_helper();
+    let commits_to_push = commits_to_push_expression(bookmark_updates, remote, workspace_helper);
+    let commits: Vec<Commit> = workspace_helper
+        .attach_revset_evaluator(commits_to_push.clone())
+        .evaluate_to_commits()?
+        .try_collect()?;
+    if commits.is_empty() {
+        return Ok(());
+    }
+    writeln!(
+        formatter,
+        "Commits to push to {remote}:",
+        remote = remote.as_symbol()
+    )?;
+    print_updated_commits(
+        formatter.as_mut(),
+        &workspace_helper.commit_summary_template(),
+        &commits,
+    )?;
+
+    if args.stat {
+        let repo = workspace_helper.repo();
+        let heads: Vec<Commit> = workspace_helper
+            .attach_revset_evaluator(commits_to_push.heads())
+            .evaluate_to_commits()?
+            .try_collect()?;
+        let roots: Vec<Commit> = workspace_helper
+            .attach_revset_evaluator(commits_to_push.roots())
+            .evaluate_to_commits()?
+            .try_collect()?;
+        let parents: IndexSet<Commit> = roots.iter().flat_map(|c| c.parents()).try_collect()?;
+        let parents = parents.into_iter().collect_vec();
+        let from_tree = merge_commit_trees(repo.as_ref(), &parents).block_on()?;
+        let to_tree = merge_commit_trees(repo.as_ref(), &heads).block_on()?;
+        let mut copy_records = CopyRecords::default();
+        for parent in &parents {
+            for head in &heads {
+                let records =
+                    get_copy_records(repo.store(), parent.id(), head.id(), &EverythingMatcher)?;
+                copy_records.add_records(records)?;
+            }
+        }
+        let stat_format = DiffFormat::Stat(Box::default());
+        workspace_helper
+            .diff_renderer(vec![stat_format])
+            .show_diff(
+                ui,
+                formatter.as_mut(),
+                [&from_tree, &to_tree],
+                &EverythingMatcher,
+                &copy_records,
+                ui.term_width(),
+            )
+            .block_on()?;
+    }
+    Ok(())
+}
+
 /// Validates that the commits that will be pushed are ready (have authorship
 /// information, are not conflicted, etc.).
 ///
@@ -507,21 +643,7 @@ fn validate_commits_ready_to_push(
     sign_behavior: Option<SignBehavior>,
 ) -> Result<Vec<Commit>, CommandError> {
     let workspace_helper = tx.base_workspace_helper();
-    let repo = workspace_helper.repo();
-
-    let new_heads = bookmark_updates
-        .iter()
-        .filter_map(|(_, update)| update.new_target.clone())
-        .collect_vec();
-    let old_heads = repo
-        .view()
-        .remote_bookmarks(remote)
-        .flat_map(|(_, old_head)| old_head.target.added_ids())
-        .cloned()
-        .collect_vec();
-    let commits_to_push = RevsetExpression::commits(old_heads)
-        .union(workspace_helper.env().immutable_heads_expression())
-        .range(&RevsetExpression::commits(new_heads));
+    let commits_to_push = commits_to_push_expression(bookmark_updates, remote, workspace_helper);
 
     let settings = workspace_helper.settings();
     let private_revset_str = RevisionArg::from(settings.get_string("git.private-commits")?);
@@ -733,6 +855,7 @@ fn get_default_push_remote(
 struct RejectedBookmarkUpdateReason {
     message: String,
     hint: Option<String>,
+    code: Option<ErrorCode>,
 }
 
 impl RejectedBookmarkUpdateReason {
@@ -747,8 +870,13 @@ impl RejectedBookmarkUpdateReason {
 
 impl From<RejectedBookmarkUpdateReason> for CommandError {
     fn from(reason: RejectedBookmarkUpdateReason) -> Self {
-        let RejectedBookmarkUpdateReason { message, hint } = reason;
+        let RejectedBookmarkUpdateReason {
+            message,
+            hint,
+            code,
+        } = reason;
         let mut cmd_err = user_error(message);
+        cmd_err.code = code;
         cmd_err.extend_hints(hint);
         cmd_err
     }
@@ -772,16 +900,19 @@ fn classify_bookmark_update(
                 "Run `jj bookmark list` to inspect, and use `jj bookmark set` to fix it up."
                     .to_owned(),
             ),
+            code: Some(ErrorCode::ConflictedBookmark),
         }),
         BookmarkPushAction::RemoteConflicted => Err(RejectedBookmarkUpdateReason {
             message: format!("Bookmark {remote_symbol} is conflicted"),
             hint: Some("Run `jj git fetch` to update the conflicted remote bookmark.".to_owned()),
+            code: Some(ErrorCode::ConflictedBookmark),
         }),
         BookmarkPushAction::RemoteUntracked => Err(RejectedBookmarkUpdateReason {
             message: format!("Non-tracking remote bookmark {remote_symbol} exists"),
             hint: Some(format!(
                 "Run `jj bookmark track {remote_symbol}` to import the remote bookmark."
             )),
+            code: None,
         }),
         BookmarkPushAction::Update(update) if update.old_target.is_none() && !allow_new => {
             Err(RejectedBookmarkUpdateReason {
@@ -791,6 +922,7 @@ fn classify_bookmark_update(
                      push to."
                         .to_owned(),
                 ),
+                code: None,
             })
         }
         BookmarkPushAction::Update(update) if update.new_target.is_none() && !allow_delete => {
@@ -804,6 +936,7 @@ fn classify_bookmark_update(
                      this warning."
                         .to_owned(),
                 ),
+                code: None,
             })
         }
         BookmarkPushAction::Update(update) => Ok(Some(update)),
@@ -0,0 +1,141 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use bstr::ByteSlice as _;
+use clap_complete::ArgValueCompleter;
+use futures::StreamExt as _;
+use futures::stream;
+use itertools::Itertools as _;
+use jj_lib::backend::TreeValue;
+use jj_lib::conflicts::MaterializedTreeValue;
+use jj_lib::conflicts::materialize_tree_value;
+use jj_lib::repo::Repo as _;
+use jj_lib::str_util::StringPattern;
+use pollster::FutureExt as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::short_change_hash;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::formatter::FormatterExt as _;
+use crate::text_util::parse_substring_pattern;
+use crate::ui::Ui;
+
+/// Search for a pattern in the contents of files
+///
+/// Prints the lines which match the pattern in the files of the given
+/// revisions. Each line is prefixed by the change id of the revision, the path
+/// of the file, and the line number, separated by `:`.
+///
+/// Conflicted files and binary files are not searched.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct GrepArgs {
+    /// The pattern to search for
+    ///
+    /// By default, lines containing the pattern as a substring match. Other
+    /// kinds of [string patterns] can be selected with a prefix, such as
+    /// `regex:`.
+    ///
+    /// [string patterns]:
+    ///     https://jj-vcs.github.io/jj/latest/revsets/#string-patterns
+    #[arg(value_parser = parse_substring_pattern)]
+    pattern: StringPattern,
+    /// The revisions to search in
+    #[arg(
+        long,
+        short,
+        default_value = "@",
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revisions: Vec<RevisionArg>,
+    /// Only search in these paths
+    #[arg(
+        value_name = "FILESETS",
+        value_hint = clap::ValueHint::AnyPath,
+        add = ArgValueCompleter::new(complete::all_revision_files),
+    )]
+    paths: Vec<String>,
+    /// Only print the change ids and paths of the files which match
+    #[arg(long, short = 'l')]
+    files_with_matches: bool,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_grep(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &GrepArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let commits: Vec<_> = workspace_command
+        .parse_union_revsets(ui, &args.revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    let matcher = workspace_command
+        .parse_file_patterns(ui, &args.paths)?
+        .to_matcher();
+    let store = workspace_command.repo().store();
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for commit in &commits {
+        let change_hash = short_change_hash(commit.change_id());
+        let tree = commit.tree()?;
+        let files = tree
+            .entries_matching(matcher.as_ref())
+            .filter(|(_, value)| {
+                value.as_ref().map_or(true, |value| {
+                    matches!(value.as_resolved(), Some(Some(TreeValue::File { .. })))
+                })
+            });
+        // Read the files concurrently, but search them in order
+        let mut contents = stream::iter(files)
+            .map(async |(path, value)| -> Result<_, CommandError> {
+                let materialized = materialize_tree_value(store, &path, value?).await?;
+                let MaterializedTreeValue::File(mut file) = materialized else {
+                    return Ok((path, vec![]));
+                };
+                let content = file.read_all(&path).await?;
+                Ok((path, content))
+            })
+            .buffered(store.concurrency().max(1));
+        while let Some(result) = contents.next().block_on() {
+            let (path, content) = result?;
+            // Skip binary files, detected the same way as in diffs
+            if content[..content.len().min(8000)].contains(&b'\0') {
+                continue;
+            }
+            let ui_path = workspace_command.format_file_path(&path);
+            for (line_number, line) in content.lines().enumerate() {
+                if !args.pattern.is_match_bytes(line) {
+                    continue;
+                }
+                write!(formatter.labeled("change_id"), "{change_hash}")?;
+                if args.files_with_matches {
+                    writeln!(formatter, ":{ui_path}")?;
+                    break;
+                }
+                write!(formatter, ":{ui_path}:{}:", line_number + 1)?;
+                formatter.write_all(line)?;
+                writeln!(formatter)?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -12,34 +12,60 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::iter;
+use std::ops::Range;
+use std::sync::Arc;
+
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
+use futures::future::try_join_all;
 use itertools::Itertools as _;
+use jj_lib::annotate::FileAnnotator;
+use jj_lib::annotate::LineRangeChange;
+use jj_lib::annotate::find_line_range_changes;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
+use jj_lib::fileset::FilesetExpression;
 use jj_lib::graph::GraphEdge;
 use jj_lib::graph::GraphEdgeType;
 use jj_lib::graph::TopoGroupedGraphIterator;
 use jj_lib::graph::reverse_graph;
+use jj_lib::matchers::FilesMatcher;
 use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::revset::RevsetEvaluationError;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::RevsetFilterPredicate;
-use jj_lib::revset::RevsetIteratorExt as _;
+use jj_lib::revset::UserRevsetExpression;
+use jj_lib::store::Store;
+use jj_lib::str_util::StringPattern;
 use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::LogContentFormat;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::format_template;
 use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
 use crate::complete;
+use crate::diff_util;
 use crate::diff_util::DiffFormatArgs;
+use crate::diff_util::UnifiedDiffOptions;
+use crate::diff_util::get_copy_records;
+use crate::formatter::Formatter;
 use crate::formatter::FormatterExt as _;
 use crate::graphlog::GraphStyle;
 use crate::graphlog::get_graphlog;
+use crate::revset_util::RevsetExpressionEvaluator;
 use crate::templater::TemplateRenderer;
+use crate::text_util::parse_substring_pattern;
+use crate::ui::OutputFormat;
 use crate::ui::Ui;
 
 /// Show revision history
@@ -84,6 +110,50 @@ pub(crate) struct LogArgs {
         add = ArgValueCompleter::new(complete::log_files),
     )]
     paths: Vec<String>,
+    /// Follow the history of the given file across renames and copies
+    ///
+    /// A single file path must be given. Revisions which changed the file are
+    /// shown along with the revisions which changed the file it was renamed
+    /// or copied from. Renames are found based on the copies recorded by the
+    /// backend, which for the Git backend means that they are detected by
+    /// similarity of the file contents.
+    #[arg(long, requires = "paths")]
+    follow: bool,
+    /// Show only revisions whose author name or email matches the pattern
+    ///
+    /// This is a shorthand for `-r 'author(PATTERN)'`. If given multiple
+    /// times, revisions matching any of the patterns are shown. By default,
+    /// the pattern matches a substring.
+    #[arg(long, value_name = "PATTERN", value_parser = parse_substring_pattern)]
+    author: Vec<StringPattern>,
+    /// Show only revisions whose description matches the pattern
+    ///
+    /// This is a shorthand for `-r 'description(PATTERN)'`. If given multiple
+    /// times, revisions matching any of the patterns are shown. By default,
+    /// the pattern matches a substring.
+    #[arg(long, value_name = "PATTERN", value_parser = parse_substring_pattern)]
+    grep: Vec<StringPattern>,
+    /// Show only revisions committed after the given date
+    ///
+    /// This is a shorthand for `-r 'committer_date(after:DATE)'`. See [date
+    /// pattern] for the supported formats.
+    ///
+    /// [date pattern]:
+    ///     https://jj-vcs.github.io/jj/latest/revsets/#date-patterns
+    #[arg(long, value_name = "DATE")]
+    since: Option<String>,
+    /// Show only revisions committed before the given date
+    ///
+    /// This is a shorthand for `-r 'committer_date(before:DATE)'`.
+    #[arg(long, value_name = "DATE")]
+    until: Option<String>,
+    /// Follow only the first parent of merge commits
+    ///
+    /// The revisions are limited to `first_ancestors(heads(REVSETS))`, so
+    /// that only the mainline history of the shown revisions is included, and
+    /// the graph doesn't show edges to the other parents.
+    #[arg(long)]
+    first_parent: bool,
     /// Limit number of revisions to show
     ///
     /// Applied after revisions are filtered and reordered topologically, but
@@ -116,10 +186,120 @@ pub(crate) struct LogArgs {
     /// Show patch
     #[arg(long, short = 'p')]
     patch: bool,
+    /// Show revisions changing the given range of lines in a file
+    ///
+    /// The range is given as `<START>,<END>:<FILE>` or
+    /// `<START>,+<COUNT>:<FILE>`, with 1-based line numbers of the file in the
+    /// working-copy commit. The lines are followed through the history like
+    /// in `jj file annotate`, and each revision changing them is shown with
+    /// the hunks of its diff touching them. Use `--revisions` to limit the
+    /// revisions to search. Renamed files are followed to their sources.
+    #[arg(
+        long,
+        short = 'L',
+        value_name = "RANGE",
+        value_parser = parse_line_range,
+        conflicts_with_all = ["paths", "patch", "short-format", "long-format", "tool"],
+    )]
+    line_range: Option<LineRangeArg>,
     #[command(flatten)]
     diff_format: DiffFormatArgs,
 }
 
+/// Range of lines in a file specified by `--line-range`.
+#[derive(Clone, Debug)]
+pub(crate) struct LineRangeArg {
+    /// 0-based range of lines.
+    lines: Range<usize>,
+    path: String,
+}
+
+fn parse_line_range(value: &str) -> Result<LineRangeArg, String> {
+    let (range, path) = value
+        .split_once(':')
+        .ok_or("Expected <START>,<END>:<FILE>")?;
+    let (start, end) = range
+        .split_once(',')
+        .ok_or("Expected <START>,<END>:<FILE>")?;
+    let parse_number = |s: &str| {
+        s.parse::<usize>()
+            .map_err(|err| format!("Invalid line number {s:?}: {err}"))
+    };
+    let start = parse_number(start)?;
+    if start == 0 {
+        return Err("Line numbers start at 1".to_owned());
+    }
+    let end = match end.strip_prefix('+') {
+        Some(count) => start + parse_number(count)?.saturating_sub(1),
+        None => parse_number(end)?,
+    };
+    if end < start {
+        return Err(format!("End line {end} is before start line {start}"));
+    }
+    if path.is_empty() {
+        return Err("Expected <START>,<END>:<FILE>".to_owned());
+    }
+    Ok(LineRangeArg {
+        lines: start - 1..end,
+        path: path.to_owned(),
+    })
+}
+
+fn has_filter_args(args: &LogArgs) -> bool {
+    !args.author.is_empty() || !args.grep.is_empty() || args.since.is_some() || args.until.is_some()
+}
+
+/// Builds the expression for the `--author`, `--grep`, `--since`, and
+/// `--until` arguments.
+fn filter_args_expression(
+    workspace_command: &WorkspaceCommandHelper,
+    args: &LogArgs,
+) -> Result<Option<Arc<UserRevsetExpression>>, CommandError> {
+    let mut filters = vec![];
+    if !args.author.is_empty() {
+        let expressions = args
+            .author
+            .iter()
+            .flat_map(|pattern| {
+                [
+                    RevsetFilterPredicate::AuthorName(pattern.clone()),
+                    RevsetFilterPredicate::AuthorEmail(pattern.clone()),
+                ]
+            })
+            .map(RevsetExpression::filter)
+            .collect_vec();
+        filters.push(RevsetExpression::union_all(&expressions));
+    }
+    if !args.grep.is_empty() {
+        let expressions = args
+            .grep
+            .iter()
+            .map(|pattern| {
+                RevsetExpression::filter(RevsetFilterPredicate::Description(pattern.clone()))
+            })
+            .collect_vec();
+        filters.push(RevsetExpression::union_all(&expressions));
+    }
+    let revset_context = workspace_command.env().revset_parse_context();
+    let date_context = &revset_context.date_pattern_context;
+    for (arg_name, kind, value) in [
+        ("--since", "after", &args.since),
+        ("--until", "before", &args.until),
+    ] {
+        if let Some(value) = value {
+            let pattern = date_context.parse_relative(value, kind).map_err(|err| {
+                user_error_with_message(format!("Invalid date for {arg_name}: {value}"), err)
+            })?;
+            filters.push(RevsetExpression::filter(
+                RevsetFilterPredicate::CommitterDate(pattern),
+            ));
+        }
+    }
+    Ok(filters
+        .into_iter()
+        .reduce(|acc, filter| acc.intersection(&filter)))
+}
+
 #[instrument(skip_all)]
 pub(crate) fn cmd_log(
     ui: &mut Ui,
@@ -129,19 +309,44 @@ pub(crate) fn cmd_log(
     let workspace_command = command.workspace_helper(ui)?;
     let settings = workspace_command.settings();
 
-    let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
+    let mut fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
+    let line_range_changes = args
+        .line_range
+        .as_ref()
+        .map(|line_range| find_line_range_changes_for_log(ui, &workspace_command, args, line_range))
+        .transpose()?;
     let revset_expression = {
         // only use default revset if neither revset nor path are specified
-        let mut expression = if args.revisions.is_empty() && args.paths.is_empty() {
+        let mut expression = if let Some(changes) = &line_range_changes {
+            let commit_ids = changes.keys().cloned().collect_vec();
+            workspace_command.attach_revset_evaluator(RevsetExpression::commits(commit_ids))
+        } else if args.revisions.is_empty() && args.paths.is_empty() && !has_filter_args(args) {
             let revset_string = settings.get_string("revsets.log")?;
             workspace_command.parse_revset(ui, &RevisionArg::from(revset_string))?
         } else if !args.revisions.is_empty() {
             workspace_command.parse_union_revsets(ui, &args.revisions)?
         } else {
-            // a path was specified so we use all() and add path filter later
+            // a path or filter was specified so we use all() and add filters later
             workspace_command.attach_revset_evaluator(RevsetExpression::all())
         };
-        if !args.paths.is_empty() {
+        if let Some(filter) = filter_args_expression(&workspace_command, args)? {
+            expression.intersect_with(&filter);
+        }
+        if args.first_parent {
+            let first_ancestors = expression.expression().heads().first_ancestors();
+            expression.intersect_with(&first_ancestors);
+        }
+        if args.follow {
+            let (commit_ids, paths) = find_file_history(&workspace_command, &expression, args)?;
+            expression.intersect_with(&RevsetExpression::commits(commit_ids));
+            // Show the diffs of all the paths the file had.
+            fileset_expression = FilesetExpression::union_all(
+                paths
+                    .into_iter()
+                    .map(FilesetExpression::file_path)
+                    .collect(),
+            );
+        } else if !args.paths.is_empty() {
             // Beware that args.paths = ["root:."] is not identical to []. The
             // former will filter out empty commits.
             let predicate = RevsetFilterPredicate::File(fileset_expression.clone());
@@ -159,6 +364,22 @@ pub(crate) fn cmd_log(
     let store = repo.store();
     let diff_renderer = workspace_command.diff_renderer_for_log(&args.diff_format, args.patch)?;
     let graph_style = GraphStyle::from_settings(settings)?;
+    let json_output = ui.output_format() == OutputFormat::Json;
+    if json_output
+        && (args.template.is_some() || diff_renderer.is_some() || line_range_changes.is_some())
+    {
+        return Err(user_error(
+            "`--output json` cannot be combined with templates or diffs",
+        ));
+    }
+
+    let line_range_options = if line_range_changes.is_some() {
+        let mut options = UnifiedDiffOptions::from_settings(settings)?;
+        options.merge_args(&args.diff_format);
+        Some(options)
+    } else {
+        None
+    };
 
     let use_elided_nodes = settings.get_bool("ui.log-synthetic-elided-nodes")?;
     let with_content_format = LogContentFormat::new(ui, settings)?;
@@ -184,7 +405,8 @@ pub(crate) fn cmd_log(
         let mut formatter = ui.stdout_formatter();
         let formatter = formatter.as_mut();
 
-        if !args.no_graph {
+        // Structured output is a flat list of commits.
+        if !args.no_graph && !json_output {
             let mut raw_output = formatter.raw()?;
             let mut graph = get_graphlog(graph_style, raw_output.as_mut());
             let iter: Box<dyn Iterator<Item = _>> = {
@@ -208,8 +430,12 @@ pub(crate) fn cmd_log(
                     Box::new(forward_iter)
                 }
             };
-            for node in iter {
-                let (commit_id, edges) = node?;
+            for item in load_commits_in_batches(store, iter, |(id, _)| id) {
+                let ((commit_id, mut edges), commit) = item?;
+                if args.first_parent {
+                    // The first edge leads to the first parent.
+                    edges.truncate(1);
+                }
 
                 // The graph is keyed by (CommitId, is_synthetic)
                 let mut graphlog_edges = vec![];
@@ -241,7 +467,6 @@ pub(crate) fn cmd_log(
                 }
                 let mut buffer = vec![];
                 let key = (commit_id, false);
-                let commit = store.get_commit(&key.0)?;
                 let within_graph =
                     with_content_format.sub_width(graph.width(&key, &graphlog_edges));
                 within_graph.write(ui.new_formatter(&mut buffer).as_mut(), |formatter| {
@@ -262,6 +487,16 @@ pub(crate) fn cmd_log(
                         )
                         .block_on()?;
                 }
+                if let (Some(changes), Some(options)) = (&line_range_changes, &line_range_options) {
+                    let mut formatter = ui.new_formatter(&mut buffer);
+                    show_line_range_diff(
+                        formatter.as_mut(),
+                        &workspace_command,
+                        options,
+                        &commit,
+                        &changes[commit.id()],
+                    )?;
+                }
 
                 let node_symbol = format_template(ui, &Some(commit), &node_template);
                 graph.add_node(
@@ -299,8 +534,12 @@ pub(crate) fn cmd_log(
                     Box::new(forward_iter)
                 }
             };
-            for commit_or_error in iter.commits(store) {
-                let commit = commit_or_error?;
+            for item in load_commits_in_batches(store, iter, |id| id) {
+                let (_, commit) = item?;
+                if json_output {
+                    ui.write_json(&commit)?;
+                    continue;
+                }
                 with_content_format
                     .write(formatter, |formatter| template.format(&commit, formatter))?;
                 if let Some(renderer) = &diff_renderer {
@@ -309,6 +548,15 @@ pub(crate) fn cmd_log(
                         .show_patch(ui, formatter, &commit, matcher.as_ref(), width)
                         .block_on()?;
                 }
+                if let (Some(changes), Some(options)) = (&line_range_changes, &line_range_options) {
+                    show_line_range_diff(
+                        formatter,
+                        &workspace_command,
+                        options,
+                        &commit,
+                        &changes[commit.id()],
+                    )?;
+                }
             }
         }
     }
@@ -339,3 +587,211 @@ pub(crate) fn cmd_log(
 
     Ok(())
 }
+
+/// Number of commits loaded before the first one is rendered.
+const INITIAL_BATCH_SIZE: usize = 16;
+/// Maximum number of commits loaded at once.
+const MAX_BATCH_SIZE: usize = 256;
+
+/// Loads the commits of the `items` in batches, so that the commit objects of
+/// a batch can be fetched concurrently while the output is streamed.
+///
+/// The first batch is small so the first screen of output is shown quickly.
+/// Subsequent batches grow up to `MAX_BATCH_SIZE`. The input iterator is
+/// consumed lazily, so only the commits that are rendered get loaded.
+fn load_commits_in_batches<T, E>(
+    store: &Arc<Store>,
+    items: impl Iterator<Item = Result<T, E>>,
+    as_id: impl Fn(&T) -> &CommitId,
+) -> impl Iterator<Item = Result<(T, Commit), CommandError>>
+where
+    CommandError: From<E>,
+{
+    let store = store.clone();
+    let mut items = items.fuse();
+    let mut batch_size = INITIAL_BATCH_SIZE;
+    let mut loaded = VecDeque::new();
+    iter::from_fn(move || {
+        if loaded.is_empty() {
+            let batch: Vec<T> = match items.by_ref().take(batch_size).try_collect() {
+                Ok(batch) => batch,
+                Err(err) => return Some(Err(err.into())),
+            };
+            batch_size = (batch_size * 2).min(MAX_BATCH_SIZE);
+            let commits =
+                try_join_all(batch.iter().map(|item| store.get_commit_async(as_id(item))))
+                    .block_on();
+            match commits {
+                Ok(commits) => {
+                    // The parents are likely to be rendered next, and their
+                    // trees are needed to diff the loaded commits.
+                    let loaded_ids: HashSet<_> = commits.iter().map(|c| c.id()).collect();
+                    let parent_ids = commits
+                        .iter()
+                        .flat_map(|commit| commit.parent_ids())
+                        .filter(|id| !loaded_ids.contains(id))
+                        .unique()
+                        .cloned()
+                        .collect();
+                    store.prefetch_commits(parent_ids);
+                    loaded.extend(batch.into_iter().zip(commits));
+                }
+                Err(err) => return Some(Err(err.into())),
+            }
+        }
+        loaded.pop_front().map(Ok)
+    })
+}
+
+/// Finds the revisions changing the lines of `--line-range`, and the ranges of
+/// the lines in these revisions.
+fn find_line_range_changes_for_log(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    args: &LogArgs,
+    line_range: &LineRangeArg,
+) -> Result<HashMap<CommitId, LineRangeChange>, CommandError> {
+    let repo = workspace_command.repo();
+    let wc_commit_id = workspace_command
+        .get_wc_commit_id()
+        .ok_or_else(|| user_error("This command requires a working copy"))?;
+    let starting_commit = repo.store().get_commit(wc_commit_id)?;
+    let file_path = workspace_command.parse_file_path(&line_range.path)?;
+    let ui_path = workspace_command.format_file_path(&file_path);
+    let file_value = starting_commit.tree()?.path_value(&file_path)?;
+    if file_value.is_absent() {
+        return Err(user_error(format!("No such path: {ui_path}")));
+    }
+    if file_value.is_tree() {
+        return Err(user_error(format!(
+            "Path exists but is not a regular file: {ui_path}"
+        )));
+    }
+    let num_lines = FileAnnotator::from_commit(&starting_commit, &file_path)?
+        .to_annotation()
+        .lines()
+        .count();
+    if line_range.lines.end > num_lines {
+        return Err(user_error(format!(
+            "Line range {},{} is out of bounds: {ui_path} has {num_lines} lines",
+            line_range.lines.start + 1,
+            line_range.lines.end
+        )));
+    }
+
+    let domain = if args.revisions.is_empty() {
+        RevsetExpression::all()
+    } else {
+        workspace_command
+            .parse_union_revsets(ui, &args.revisions)?
+            .resolve()?
+    };
+    let changes = find_line_range_changes(
+        repo.as_ref(),
+        &starting_commit,
+        &file_path,
+        line_range.lines.clone(),
+        &domain,
+    )?;
+    let changes = changes
+        .into_iter()
+        .map(|change| (change.commit_id.clone(), change))
+        .collect();
+    Ok(changes)
+}
+
+fn show_line_range_diff(
+    formatter: &mut dyn Formatter,
+    workspace_command: &WorkspaceCommandHelper,
+    options: &UnifiedDiffOptions,
+    commit: &Commit,
+    change: &LineRangeChange,
+) -> Result<(), CommandError> {
+    let repo = workspace_command.repo();
+    let left_tree = commit.parent_tree(repo.as_ref())?;
+    let right_tree = commit.tree()?;
+    diff_util::show_line_range_diff(
+        formatter,
+        repo.store(),
+        [&left_tree, &right_tree],
+        [&change.parent_path, &change.path],
+        change.line_range.clone(),
+        options,
+        workspace_command.env().conflict_marker_style(),
+    )
+    .block_on()?;
+    Ok(())
+}
+
+/// Finds the revisions changing the file given to `--follow`, and all the paths
+/// the file had in these revisions.
+///
+/// The file is followed from the heads of the `domain` through their
+/// ancestors, switching to the source path whenever the file was renamed or
+/// copied.
+fn find_file_history(
+    workspace_command: &WorkspaceCommandHelper,
+    domain: &RevsetExpressionEvaluator,
+    args: &LogArgs,
+) -> Result<(Vec<CommitId>, Vec<RepoPathBuf>), CommandError> {
+    let [path] = args.paths.as_slice() else {
+        return Err(user_error("--follow requires a single file path"));
+    };
+    let file_path = workspace_command.parse_file_path(path)?;
+    let repo = workspace_command.repo();
+    let store = repo.store();
+    let heads = domain.resolve()?.heads();
+
+    let mut pending: HashMap<CommitId, HashSet<RepoPathBuf>> = heads
+        .clone()
+        .evaluate(repo.as_ref())?
+        .iter()
+        .map_ok(|commit_id| (commit_id, HashSet::from([file_path.clone()])))
+        .try_collect()?;
+    let mut all_paths = vec![file_path.clone()];
+    let mut commit_ids = vec![];
+    for commit_id in heads.ancestors().evaluate(repo.as_ref())?.iter() {
+        let commit_id = commit_id?;
+        let Some(paths) = pending.remove(&commit_id) else {
+            continue;
+        };
+        let commit = store.get_commit(&commit_id)?;
+        let tree = commit.tree()?;
+        let parent_tree = commit.parent_tree(repo.as_ref())?;
+        let mut changed = false;
+        for path in &paths {
+            changed |= tree.path_value(path)? != parent_tree.path_value(path)?;
+        }
+        for parent_id in commit.parent_ids() {
+            let mut parent_paths = paths.clone();
+            if changed {
+                let matcher = FilesMatcher::new(&paths);
+                for record in get_copy_records(store, parent_id, &commit_id, &matcher)? {
+                    let record = record?;
+                    parent_paths.remove(&record.target);
+                    if !all_paths.contains(&record.source) {
+                        all_paths.push(record.source.clone());
+                    }
+                    parent_paths.insert(record.source);
+                }
+                // Stop following paths which were added by this commit.
+                for path in parent_paths.clone() {
+                    if parent_tree.path_value(&path)?.is_absent() {
+                        parent_paths.remove(&path);
+                    }
+                }
+            }
+            if parent_paths.is_empty() {
+                continue;
+            }
+            pending
+                .entry(parent_id.clone())
+                .or_default()
+                .extend(parent_paths);
+        }
+        if changed {
+            commit_ids.push(commit_id);
+        }
+    }
+    Ok((commit_ids, all_paths))
+}
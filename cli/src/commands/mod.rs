@@ -19,6 +19,9 @@ mod backout;
 mod bench;
 mod bisect;
 mod bookmark;
+mod changelog;
+#[cfg(feature = "git")]
+mod clone;
 mod commit;
 mod config;
 mod debug;
@@ -34,6 +37,7 @@ mod fix;
 mod gerrit;
 #[cfg(feature = "git")]
 mod git;
+mod grep;
 mod help;
 mod interdiff;
 mod log;
@@ -58,9 +62,11 @@ mod split;
 mod squash;
 mod status;
 mod tag;
+mod test;
 mod undo;
 mod unsign;
 mod util;
+mod verify;
 mod version;
 mod workspace;
 
@@ -77,7 +83,9 @@ use tracing::instrument;
 use crate::cli_util::Args;
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
+use crate::command_error::cli_error;
 use crate::complete;
+use crate::ui::OutputFormat;
 use crate::ui::Ui;
 
 const STYLES: Styles = Styles::styled()
@@ -103,6 +111,9 @@ enum Command {
     Bisect(bisect::BisectCommand),
     #[command(subcommand)]
     Bookmark(bookmark::BookmarkCommand),
+    Changelog(changelog::ChangelogArgs),
+    #[cfg(feature = "git")]
+    Clone(clone::CloneArgs),
     Commit(commit::CommitArgs),
     #[command(subcommand)]
     Config(config::ConfigCommand),
@@ -124,6 +135,7 @@ enum Command {
     #[cfg(feature = "git")]
     #[command(subcommand)]
     Git(git::GitCommand),
+    Grep(grep::GrepArgs),
     Help(help::HelpArgs),
     Interdiff(interdiff::InterdiffArgs),
     Log(log::LogArgs),
@@ -154,10 +166,12 @@ enum Command {
     Status(status::StatusArgs),
     #[command(subcommand)]
     Tag(tag::TagCommand),
+    Test(test::TestArgs),
     Undo(undo::UndoArgs),
     Unsign(unsign::UnsignArgs),
     #[command(subcommand)]
     Util(util::UtilCommand),
+    Verify(verify::VerifyArgs),
     Version(version::VersionArgs),
     #[command(subcommand)]
     Workspace(workspace::WorkspaceCommand),
@@ -170,6 +184,9 @@ pub fn default_app() -> clap::Command {
 #[instrument(skip_all)]
 pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), CommandError> {
     let subcommand = Command::from_arg_matches(command_helper.matches()).unwrap();
+    if ui.output_format() == OutputFormat::Json && !supports_json_output(&subcommand) {
+        return Err(cli_error("This command doesn't support `--output json`"));
+    }
     match &subcommand {
         Command::Abandon(args) => abandon::cmd_abandon(ui, command_helper, args),
         Command::Absorb(args) => absorb::cmd_absorb(ui, command_helper, args),
@@ -178,6 +195,9 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         Command::Bench(args) => bench::cmd_bench(ui, command_helper, args),
         Command::Bisect(args) => bisect::cmd_bisect(ui, command_helper, args),
         Command::Bookmark(args) => bookmark::cmd_bookmark(ui, command_helper, args),
+        Command::Changelog(args) => changelog::cmd_changelog(ui, command_helper, args),
+        #[cfg(feature = "git")]
+        Command::Clone(args) => clone::cmd_clone(ui, command_helper, args),
         Command::Commit(args) => commit::cmd_commit(ui, command_helper, args),
         Command::Config(args) => config::cmd_config(ui, command_helper, args),
         Command::Debug(args) => debug::cmd_debug(ui, command_helper, args),
@@ -193,6 +213,7 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         Command::Gerrit(sub_args) => gerrit::cmd_gerrit(ui, command_helper, sub_args),
         #[cfg(feature = "git")]
         Command::Git(args) => git::cmd_git(ui, command_helper, args),
+        Command::Grep(args) => grep::cmd_grep(ui, command_helper, args),
         Command::Help(args) => help::cmd_help(ui, command_helper, args),
         Command::Interdiff(args) => interdiff::cmd_interdiff(ui, command_helper, args),
         Command::Log(args) => log::cmd_log(ui, command_helper, args),
@@ -219,14 +240,31 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         Command::Squash(args) => squash::cmd_squash(ui, command_helper, args),
         Command::Status(args) => status::cmd_status(ui, command_helper, args),
         Command::Tag(args) => tag::cmd_tag(ui, command_helper, args),
+        Command::Test(args) => test::cmd_test(ui, command_helper, args),
         Command::Undo(args) => undo::cmd_undo(ui, command_helper, args),
         Command::Unsign(args) => unsign::cmd_unsign(ui, command_helper, args),
         Command::Util(args) => util::cmd_util(ui, command_helper, args),
+        Command::Verify(args) => verify::cmd_verify(ui, command_helper, args),
         Command::Version(args) => version::cmd_version(ui, command_helper, args),
         Command::Workspace(args) => workspace::cmd_workspace(ui, command_helper, args),
     }
 }
 
+/// Returns true if the command implements `--output json`.
+///
+/// The schema of each supported command is documented in `docs/json-output.md`.
+fn supports_json_output(subcommand: &Command) -> bool {
+    matches!(
+        subcommand,
+        Command::Bookmark(bookmark::BookmarkCommand::List(_))
+            | Command::Diff(_)
+            | Command::Log(_)
+            | Command::Operation(operation::OperationCommand::Log(_))
+            | Command::Status(_)
+            | Command::Workspace(workspace::WorkspaceCommand::List(_))
+    )
+}
+
 /// Wraps deprecated command of `old_name` which has been renamed to `new_name`.
 pub(crate) fn renamed_cmd<Args>(
     old_name: &'static str,
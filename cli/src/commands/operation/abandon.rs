@@ -18,14 +18,18 @@ use std::slice;
 
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
+use jj_lib::object_id::ObjectId as _;
 use jj_lib::op_walk;
 
 use crate::cli_util::CommandHelper;
+use crate::cli_util::confirm_destructive_command;
 use crate::cli_util::short_operation_hash;
 use crate::command_error::CommandError;
 use crate::command_error::cli_error;
 use crate::command_error::user_error;
 use crate::complete;
+use crate::operation_notify::OperationNotification;
+use crate::operation_notify::send_operation_notification;
 use crate::ui::Ui;
 
 /// Abandon operation history
@@ -46,6 +50,11 @@ pub struct OperationAbandonArgs {
     /// The operation or operation range to abandon
     #[arg(add = ArgValueCandidates::new(complete::operations))]
     operation: String,
+    /// Do not ask for confirmation
+    ///
+    /// Confirmation is only asked for if `ui.confirm.operation-abandon` is set.
+    #[arg(long)]
+    yes: bool,
 }
 
 pub fn cmd_op_abandon(
@@ -121,6 +130,22 @@ pub fn cmd_op_abandon(
         writeln!(ui.status(), "Nothing changed.")?;
         return Ok(());
     }
+    // The reparented operations aren't visible until the op heads are updated,
+    // so nothing has to be undone if the user declines.
+    confirm_destructive_command(
+        ui,
+        command.settings(),
+        "operation-abandon",
+        stats.unreachable_count,
+        args.yes,
+        |formatter| {
+            writeln!(
+                formatter,
+                "About to abandon {} operations and reparent {} descendant operations.",
+                stats.unreachable_count, stats.rewritten_count,
+            )
+        },
+    )?;
     writeln!(
         ui.status(),
         "Abandoned {} operations and reparented {} descendant operations.",
@@ -129,6 +154,13 @@ pub fn cmd_op_abandon(
     )?;
     for (old, new_id) in reparented_head_ops().filter(|&(old, new_id)| old.id() != new_id) {
         op_heads_store.update_op_heads(slice::from_ref(old.id()), new_id)?;
+        // Reparenting doesn't change the view, so no working-copy commits
+        // were changed.
+        let notification = OperationNotification {
+            operation_id: new_id.hex(),
+            workspaces: vec![],
+        };
+        send_operation_notification(command.settings(), workspace.repo_path(), &notification);
     }
     // Remap the operation id of the current workspace. If there were any
     // divergent operations, user will need to re-abandon their ancestors.
@@ -29,6 +29,7 @@ use crate::cli_util::LogContentFormat;
 use crate::cli_util::WorkspaceCommandEnvironment;
 use crate::cli_util::format_template;
 use crate::command_error::CommandError;
+use crate::command_error::user_error;
 use crate::complete;
 use crate::diff_util::DiffFormatArgs;
 use crate::diff_util::DiffRenderer;
@@ -38,6 +39,7 @@ use crate::graphlog::GraphStyle;
 use crate::graphlog::get_graphlog;
 use crate::operation_templater::OperationTemplateLanguage;
 use crate::templater::TemplateRenderer;
+use crate::ui::OutputFormat;
 use crate::ui::Ui;
 
 /// Show the operation log
@@ -196,13 +198,21 @@ fn do_op_log(
         None
     };
 
+    let json_output = ui.output_format() == OutputFormat::Json;
+    if json_output && (args.template.is_some() || maybe_show_op_diff.is_some()) {
+        return Err(user_error(
+            "`--output json` cannot be combined with templates or diffs",
+        ));
+    }
+
     ui.request_pager();
     let mut formatter = ui.stdout_formatter();
     let formatter = formatter.as_mut();
     let iter =
         op_walk::walk_ancestors(slice::from_ref(current_op)).take(args.limit.unwrap_or(usize::MAX));
 
-    if !args.no_graph {
+    // Structured output is a flat list of operations.
+    if !args.no_graph && !json_output {
         let mut raw_output = formatter.raw()?;
         let mut graph = get_graphlog(graph_style, raw_output.as_mut());
         let iter = iter.map(|op| -> Result<_, OpStoreError> {
@@ -246,6 +256,10 @@ fn do_op_log(
         };
         for op in iter {
             let op = op?;
+            if json_output {
+                ui.write_json(&op)?;
+                continue;
+            }
             with_content_format.write(formatter, |formatter| template.format(&op, formatter))?;
             if let Some(show) = &maybe_show_op_diff {
                 show(ui, formatter, &op, &with_content_format)?;
@@ -106,6 +106,12 @@ pub(crate) struct RestoreArgs {
     /// Preserve the content (not the diff) when rebasing descendants
     #[arg(long)]
     restore_descendants: bool,
+    /// Abandon the destination revision if it becomes empty
+    ///
+    /// The destination is abandoned only if it has no changes compared to its
+    /// parent(s) after restoring. Its description and other metadata are lost.
+    #[arg(long)]
+    abandon_if_empty: bool,
 }
 
 #[instrument(skip_all)]
@@ -168,10 +174,16 @@ pub(crate) fn cmd_restore(
         writeln!(ui.status(), "Nothing changed.")?;
     } else {
         let mut tx = workspace_command.start_transaction();
-        tx.repo_mut()
-            .rewrite_commit(&to_commit)
-            .set_tree_id(new_tree_id)
-            .write()?;
+        let abandon =
+            args.abandon_if_empty && new_tree_id == to_commit.parent_tree(tx.repo())?.id();
+        if abandon {
+            tx.repo_mut().record_abandoned_commit(&to_commit);
+        } else {
+            tx.repo_mut()
+                .rewrite_commit(&to_commit)
+                .set_tree_id(new_tree_id)
+                .write()?;
+        }
         // rebase_descendants early; otherwise the new commit would always have
         // a conflicted change id at this point.
         let (num_rebased, extra_msg) = if args.restore_descendants {
@@ -182,13 +194,19 @@ pub(crate) fn cmd_restore(
         } else {
             (tx.repo_mut().rebase_descendants()?, "")
         };
-        if let Some(mut formatter) = ui.status_formatter()
-            && num_rebased > 0
-        {
-            writeln!(
-                formatter,
-                "Rebased {num_rebased} descendant commits{extra_msg}"
-            )?;
+        if let Some(mut formatter) = ui.status_formatter() {
+            if abandon {
+                write!(formatter, "Abandoned emptied commit ")?;
+                tx.base_workspace_helper()
+                    .write_commit_summary(formatter.as_mut(), &to_commit)?;
+                writeln!(formatter)?;
+            }
+            if num_rebased > 0 {
+                writeln!(
+                    formatter,
+                    "Rebased {num_rebased} descendant commits{extra_msg}"
+                )?;
+            }
         }
         tx.finish(ui, format!("restore into commit {}", to_commit.id().hex()))?;
     }
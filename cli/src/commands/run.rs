@@ -14,12 +14,14 @@
 
 //! This file contains the internal implementation of `run`.
 
+use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
+use crate::complete;
 use crate::ui::Ui;
 
 /// (**Stub**, does not work yet) Run a command across a set of revisions.
@@ -40,7 +42,13 @@ pub struct RunArgs {
     /// The command to run across all selected revisions.
     shell_command: String,
     /// The revisions to change.
-    #[arg(long, short, default_value = "@", value_name = "REVSETS")]
+    #[arg(
+        long,
+        short,
+        default_value = "@",
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_mutable),
+    )]
     revisions: Vec<RevisionArg>,
     /// A no-op option to match the interface of `git rebase -x`.
     #[arg(short = 'x', hide = true)]
@@ -14,6 +14,8 @@
 
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
 use jj_lib::matchers::EverythingMatcher;
 use pollster::FutureExt as _;
 use tracing::instrument;
@@ -21,22 +23,26 @@ use tracing::instrument;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::command_error::CommandError;
+use crate::command_error::user_error;
 use crate::complete;
 use crate::diff_util::DiffFormatArgs;
 use crate::ui::Ui;
 
-/// Show commit description and changes in a revision
+/// Show commit description and changes in revisions
+///
+/// If the revset contains multiple revisions, they are shown one after another
+/// in the same order as `jj log`.
 #[derive(clap::Args, Clone, Debug)]
 #[command(mut_arg("ignore_all_space", |a| a.short('w')))]
 #[command(mut_arg("ignore_space_change", |a| a.short('b')))]
 pub(crate) struct ShowArgs {
-    /// Show changes in this revision, compared to its parent(s)
+    /// Show changes in these revisions, compared to their parent(s)
     #[arg(
         default_value = "@",
-        value_name = "REVSET",
+        value_name = "REVSETS",
         add = ArgValueCompleter::new(complete::revset_expression_all),
     )]
-    revision: RevisionArg,
+    revisions: Vec<RevisionArg>,
     /// Ignored (but lets you pass `-r` for consistency with other commands)
     #[arg(short = 'r', hide = true)]
     unused_revision: bool,
@@ -66,7 +72,13 @@ pub(crate) fn cmd_show(
     args: &ShowArgs,
 ) -> Result<(), CommandError> {
     let workspace_command = command.workspace_helper(ui)?;
-    let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
+    let commits: Vec<Commit> = workspace_command
+        .parse_union_revsets(ui, &args.revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    if commits.is_empty() {
+        return Err(user_error("Revset didn't resolve to any revisions"));
+    }
     let template_string = match &args.template {
         Some(value) => value.clone(),
         None => workspace_command.settings().get_string("templates.show")?,
@@ -78,11 +90,16 @@ pub(crate) fn cmd_show(
     ui.request_pager();
     let mut formatter = ui.stdout_formatter();
     let formatter = formatter.as_mut();
-    template.format(&commit, formatter)?;
-    if !args.no_patch {
-        diff_renderer
-            .show_patch(ui, formatter, &commit, &EverythingMatcher, ui.term_width())
-            .block_on()?;
+    for (i, commit) in commits.iter().enumerate() {
+        if i > 0 {
+            writeln!(formatter)?;
+        }
+        template.format(commit, formatter)?;
+        if !args.no_patch {
+            diff_renderer
+                .show_patch(ui, formatter, commit, &EverythingMatcher, ui.term_width())
+                .block_on()?;
+        }
     }
     Ok(())
 }
@@ -13,31 +13,64 @@
 // limitations under the License.
 
 use std::fmt::Write as _;
+use std::io;
+use std::io::IsTerminal as _;
 use std::path::Path;
 
 use itertools::Itertools as _;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPath;
 use jj_lib::repo_path::RepoPathBuf;
 use tracing::instrument;
 
+use super::picker::SparseTree;
+use super::picker::read_dir_entries;
+use super::picker::run_sparse_picker;
 use super::update_sparse_patterns_with;
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::command_error::internal_error;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
 use crate::command_error::user_error_with_message;
 use crate::description_util::TextEditor;
 use crate::ui::Ui;
 
 /// Start an editor to update the patterns that are present in the working copy
 #[derive(clap::Args, Clone, Debug)]
-pub struct SparseEditArgs {}
+pub struct SparseEditArgs {
+    /// Select the directories to include from a tree view instead of editing
+    /// the patterns as text
+    ///
+    /// The tree of the working-copy commit is shown with a checkbox for each
+    /// directory and file. Directories are read when they are expanded, and the
+    /// sizes of the files in them are shown. Selecting a directory includes all
+    /// of its contents.
+    #[arg(long, short)]
+    interactive: bool,
+}
 
 #[instrument(skip_all)]
 pub fn cmd_sparse_edit(
     ui: &mut Ui,
     command: &CommandHelper,
-    _args: &SparseEditArgs,
+    args: &SparseEditArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
+    if args.interactive {
+        let wc_commit_id = workspace_command
+            .get_wc_commit_id()
+            .ok_or_else(|| user_error("This command requires a working copy"))?;
+        let tree = workspace_command
+            .repo()
+            .store()
+            .get_commit(wc_commit_id)?
+            .tree()?;
+        return update_sparse_patterns_with(ui, &mut workspace_command, |_ui, old_patterns| {
+            select_sparse(&tree, old_patterns)
+        });
+    }
     let editor = workspace_command.text_editor()?;
     update_sparse_patterns_with(ui, &mut workspace_command, |_ui, old_patterns| {
         let mut new_patterns = edit_sparse(&editor, old_patterns)?;
@@ -80,3 +113,19 @@ fn edit_sparse(
         })
         .try_collect()
 }
+
+fn select_sparse(
+    tree: &MergedTree,
+    sparse: &[RepoPathBuf],
+) -> Result<Vec<RepoPathBuf>, CommandError> {
+    if !io::stdin().is_terminal() || !io::stderr().is_terminal() {
+        return Err(user_error_with_hint(
+            "Cannot select sparse patterns since the output is not connected to a terminal",
+            "Run `jj sparse edit` without `--interactive` to edit the patterns in an editor.",
+        ));
+    }
+    let mut sparse_tree = SparseTree::new(sparse);
+    let mut read_dir = |dir: &RepoPath| read_dir_entries(tree, dir);
+    run_sparse_picker(&mut sparse_tree, &mut read_dir)?
+        .ok_or_else(|| user_error("Canceled selecting sparse patterns"))
+}
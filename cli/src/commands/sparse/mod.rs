@@ -14,6 +14,7 @@
 
 mod edit;
 mod list;
+mod picker;
 mod reset;
 mod set;
 
@@ -0,0 +1,495 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Expandable tree of the working-copy commit for `jj sparse edit -i`.
+//!
+//! Directories are read from the tree only when they are expanded, so the
+//! picker opens quickly even in large repos.
+
+use std::io;
+use std::io::Write as _;
+
+use crossterm::cursor;
+use crossterm::event;
+use crossterm::event::Event;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use crossterm::queue;
+use crossterm::style::Attribute;
+use crossterm::style::SetAttribute;
+use crossterm::terminal;
+use jj_lib::backend::TreeValue;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::repo_path::RepoPath;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::repo_path::RepoPathComponentBuf;
+use pollster::FutureExt as _;
+
+use crate::command_error::CommandError;
+use crate::revision_picker::TerminalGuard;
+use crate::text_util;
+use crate::text_util::format_byte_size;
+
+/// Entry of a directory, as shown in the picker.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DirEntry {
+    pub name: RepoPathComponentBuf,
+    pub is_dir: bool,
+    /// Size of the file, if it's a regular file.
+    pub size: Option<u64>,
+}
+
+/// Reads the entries of the directory at `dir` in `tree`.
+///
+/// Only the sizes of the files directly in `dir` are looked up.
+pub fn read_dir_entries(tree: &MergedTree, dir: &RepoPath) -> Result<Vec<DirEntry>, CommandError> {
+    let Some(dir_tree) = tree.sub_tree_recursive(dir).block_on()? else {
+        return Ok(vec![]);
+    };
+    let store = tree.store();
+    let mut entries = vec![];
+    for name in dir_tree.names() {
+        let value = dir_tree.value(name);
+        let (is_dir, size) = if value.is_tree() {
+            (true, None)
+        } else if let Ok(Some(TreeValue::File { id, .. })) = value.into_resolved() {
+            let size = store.file_size(&dir.join(name), id).block_on()?;
+            (false, Some(size))
+        } else {
+            (false, None)
+        };
+        entries.push(DirEntry {
+            name: name.to_owned(),
+            is_dir,
+            size,
+        });
+    }
+    Ok(entries)
+}
+
+#[derive(Debug)]
+struct Node {
+    path: RepoPathBuf,
+    entry: DirEntry,
+    depth: usize,
+    parent: Option<usize>,
+    checked: bool,
+    expanded: bool,
+    /// Indices of the child nodes, or `None` if the directory hasn't been read
+    /// yet.
+    children: Option<Vec<usize>>,
+}
+
+/// Selection state of the tree.
+///
+/// A checked node includes everything below it. Patterns below nodes which
+/// haven't been read yet are kept as they are.
+#[derive(Debug)]
+pub struct SparseTree {
+    nodes: Vec<Node>,
+    /// Initial patterns which haven't been replaced by toggling a node.
+    patterns: Vec<RepoPathBuf>,
+}
+
+impl SparseTree {
+    /// Creates a tree with only the root directory, which has to be expanded
+    /// to read its entries.
+    pub fn new(patterns: &[RepoPathBuf]) -> Self {
+        let root = Node {
+            path: RepoPathBuf::root(),
+            entry: DirEntry {
+                name: RepoPathComponentBuf::new(".").unwrap(),
+                is_dir: true,
+                size: None,
+            },
+            depth: 0,
+            parent: None,
+            checked: patterns.iter().any(|pattern| pattern.is_root()),
+            expanded: false,
+            children: None,
+        };
+        Self {
+            nodes: vec![root],
+            patterns: patterns.to_vec(),
+        }
+    }
+
+    /// Indices of the nodes to show, in display order.
+    pub fn visible_nodes(&self) -> Vec<usize> {
+        let mut visible = vec![];
+        let mut to_visit = vec![0];
+        while let Some(index) = to_visit.pop() {
+            visible.push(index);
+            let node = &self.nodes[index];
+            if let (true, Some(children)) = (node.expanded, &node.children) {
+                to_visit.extend(children.iter().rev());
+            }
+        }
+        visible
+    }
+
+    /// Shows the entries of the directory at `index`, reading them with
+    /// `read_dir` if needed.
+    pub fn expand(
+        &mut self,
+        index: usize,
+        read_dir: &mut dyn FnMut(&RepoPath) -> Result<Vec<DirEntry>, CommandError>,
+    ) -> Result<(), CommandError> {
+        if !self.nodes[index].entry.is_dir {
+            return Ok(());
+        }
+        if self.nodes[index].children.is_none() {
+            let mut entries = read_dir(&self.nodes[index].path)?;
+            // Directories first, like most file managers.
+            entries.sort_by(|a, b| (!a.is_dir, &a.name).cmp(&(!b.is_dir, &b.name)));
+            let mut children = vec![];
+            for entry in entries {
+                let path = self.nodes[index].path.join(&entry.name);
+                let checked = self.nodes[index].checked
+                    || self
+                        .patterns
+                        .iter()
+                        .any(|pattern| path.starts_with(pattern));
+                children.push(self.nodes.len());
+                self.nodes.push(Node {
+                    path,
+                    entry,
+                    depth: self.nodes[index].depth + 1,
+                    parent: Some(index),
+                    checked,
+                    expanded: false,
+                    children: None,
+                });
+            }
+            self.nodes[index].children = Some(children);
+        }
+        self.nodes[index].expanded = true;
+        Ok(())
+    }
+
+    /// Hides the entries of the directory at `index`.
+    pub fn collapse(&mut self, index: usize) {
+        self.nodes[index].expanded = false;
+    }
+
+    pub fn is_expanded(&self, index: usize) -> bool {
+        self.nodes[index].expanded
+    }
+
+    pub fn parent(&self, index: usize) -> Option<usize> {
+        self.nodes[index].parent
+    }
+
+    /// Checks or unchecks the node at `index` and everything below it.
+    pub fn toggle(&mut self, index: usize) {
+        let checked = !self.nodes[index].checked;
+        let path = self.nodes[index].path.clone();
+        self.patterns.retain(|pattern| !pattern.starts_with(&path));
+        let mut to_visit = vec![index];
+        while let Some(index) = to_visit.pop() {
+            let node = &mut self.nodes[index];
+            node.checked = checked;
+            to_visit.extend(node.children.iter().flatten());
+        }
+        // Update the ancestors: unchecking a node unchecks its ancestors, but
+        // keeps its siblings checked. A directory whose entries are all
+        // checked is checked, too.
+        let mut parent = self.nodes[index].parent;
+        while let Some(index) = parent {
+            let all_checked = self.nodes[index]
+                .children
+                .iter()
+                .flatten()
+                .all(|&child| self.nodes[child].checked);
+            self.nodes[index].checked = all_checked;
+            parent = self.nodes[index].parent;
+        }
+    }
+
+    /// Returns true if some but not all paths below the node at `index` are
+    /// selected.
+    fn is_partially_checked(&self, index: usize) -> bool {
+        let node = &self.nodes[index];
+        if node.checked {
+            return false;
+        }
+        let has_pattern_below = self
+            .patterns
+            .iter()
+            .any(|pattern| pattern.starts_with(&node.path));
+        has_pattern_below
+            || node
+                .children
+                .iter()
+                .flatten()
+                .any(|&child| self.nodes[child].checked || self.is_partially_checked(child))
+    }
+
+    /// Returns the selected patterns, without patterns covered by other
+    /// patterns.
+    pub fn to_patterns(&self) -> Vec<RepoPathBuf> {
+        let mut patterns = vec![];
+        let mut to_visit = vec![0];
+        while let Some(index) = to_visit.pop() {
+            let node = &self.nodes[index];
+            if node.checked {
+                patterns.push(node.path.clone());
+            } else if let Some(children) = &node.children {
+                to_visit.extend(children);
+            }
+        }
+        // Keep the initial patterns which weren't replaced, and which aren't
+        // covered by a checked node.
+        for pattern in &self.patterns {
+            if !patterns.iter().any(|path| pattern.starts_with(path)) {
+                patterns.push(pattern.clone());
+            }
+        }
+        patterns.sort_unstable();
+        patterns.dedup();
+        patterns
+    }
+
+    fn format_row(&self, index: usize) -> String {
+        let node = &self.nodes[index];
+        let checkbox = if node.checked {
+            "[x]"
+        } else if self.is_partially_checked(index) {
+            "[~]"
+        } else {
+            "[ ]"
+        };
+        let marker = match (node.entry.is_dir, node.expanded) {
+            (true, true) => "v ",
+            (true, false) => "> ",
+            (false, _) => "  ",
+        };
+        let suffix = if node.entry.is_dir { "/" } else { "" };
+        let size = node
+            .entry
+            .size
+            .map(|size| format!("  {}", format_byte_size(size)))
+            .unwrap_or_default();
+        format!(
+            "{:indent$}{marker}{checkbox} {}{suffix}{size}",
+            "",
+            node.entry.name.as_internal_str(),
+            indent = node.depth * 2
+        )
+    }
+}
+
+/// Lets the user select the paths to include from the tree. Returns `None` if
+/// the user canceled.
+pub fn run_sparse_picker(
+    tree: &mut SparseTree,
+    read_dir: &mut dyn FnMut(&RepoPath) -> Result<Vec<DirEntry>, CommandError>,
+) -> Result<Option<Vec<RepoPathBuf>>, CommandError> {
+    let _guard = TerminalGuard::new()?;
+    tree.expand(0, read_dir)?;
+    let mut selected = 0;
+    loop {
+        let visible = tree.visible_nodes();
+        selected = selected.min(visible.len() - 1);
+        draw(tree, &visible, selected)?;
+
+        let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press | KeyEventKind::Repeat,
+            ..
+        }) = event::read()?
+        else {
+            continue;
+        };
+        let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+        let index = visible[selected];
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => return Ok(None),
+            KeyCode::Char('c') if ctrl => return Ok(None),
+            KeyCode::Enter => return Ok(Some(tree.to_patterns())),
+            KeyCode::Up | KeyCode::Char('k') => selected = selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => selected += 1,
+            KeyCode::Char(' ') => tree.toggle(index),
+            KeyCode::Right | KeyCode::Char('l') => tree.expand(index, read_dir)?,
+            KeyCode::Left | KeyCode::Char('h') => {
+                if tree.is_expanded(index) {
+                    tree.collapse(index);
+                } else if let Some(parent) = tree.parent(index) {
+                    selected = visible.iter().position(|&i| i == parent).unwrap();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn draw(tree: &SparseTree, visible: &[usize], selected: usize) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    let width = usize::from(width);
+    let mut stderr = io::stderr().lock();
+    queue!(
+        stderr,
+        cursor::MoveTo(0, 0),
+        terminal::Clear(terminal::ClearType::All)
+    )?;
+    let header = "space: toggle, right/left: expand/collapse, enter: confirm, esc: cancel";
+    write!(stderr, "{}", text_util::elide_end(header, "...", width).0)?;
+    // Scroll so that the selected row is visible.
+    let num_rows = usize::from(height).saturating_sub(1).max(1);
+    let first_row = selected.saturating_sub(num_rows - 1);
+    for (row, &index) in visible.iter().enumerate().skip(first_row).take(num_rows) {
+        queue!(stderr, cursor::MoveTo(0, (row - first_row + 1) as u16))?;
+        let text = tree.format_row(index);
+        let (text, _) = text_util::elide_end(&text, "...", width);
+        if row == selected {
+            queue!(stderr, SetAttribute(Attribute::Reverse))?;
+            write!(stderr, "{text}")?;
+            queue!(stderr, SetAttribute(Attribute::Reset))?;
+        } else {
+            write!(stderr, "{text}")?;
+        }
+    }
+    stderr.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use itertools::Itertools as _;
+
+    use super::*;
+
+    fn repo_path_buf(value: &str) -> RepoPathBuf {
+        RepoPathBuf::from_internal_string(value).unwrap()
+    }
+
+    /// Reads entries from a list of file paths, and records the directories
+    /// read.
+    fn fake_read_dir<'a>(
+        files: &'a [&'a str],
+        read: &'a mut Vec<String>,
+    ) -> impl FnMut(&RepoPath) -> Result<Vec<DirEntry>, CommandError> + 'a {
+        move |dir| {
+            read.push(dir.as_internal_file_string().to_owned());
+            let mut entries = BTreeMap::new();
+            for file in files {
+                let path = repo_path_buf(file);
+                let Some(rest) = path.strip_prefix(dir) else {
+                    continue;
+                };
+                let mut components = rest.components();
+                let name = components.next().unwrap().to_owned();
+                let is_dir = components.next().is_some();
+                entries.insert(
+                    name.clone(),
+                    DirEntry {
+                        name,
+                        is_dir,
+                        size: (!is_dir).then_some(1024),
+                    },
+                );
+            }
+            Ok(entries.into_values().collect())
+        }
+    }
+
+    fn render(tree: &SparseTree) -> String {
+        tree.visible_nodes()
+            .into_iter()
+            .map(|index| tree.format_row(index) + "\n")
+            .join("")
+    }
+
+    fn find(tree: &SparseTree, path: &str) -> usize {
+        let path = repo_path_buf(path);
+        tree.nodes
+            .iter()
+            .position(|node| node.path == path)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_sparse_tree_reads_expanded_dirs_only() {
+        let files = ["a/b/file", "a/file", "c/file", "file"];
+        let mut read = vec![];
+        let mut read_dir = fake_read_dir(&files, &mut read);
+        let mut tree = SparseTree::new(&[repo_path_buf("a/b"), repo_path_buf("c/missing")]);
+        tree.expand(0, &mut read_dir).unwrap();
+        insta::assert_snapshot!(render(&tree), @r"
+        v [~] ./
+          > [~] a/
+          > [~] c/
+            [ ] file  1.0 KiB
+        ");
+
+        tree.expand(find(&tree, "a"), &mut read_dir).unwrap();
+        insta::assert_snapshot!(render(&tree), @r"
+        v [~] ./
+          v [~] a/
+            > [x] b/
+              [ ] file  1.0 KiB
+          > [~] c/
+            [ ] file  1.0 KiB
+        ");
+
+        // Collapsing and expanding again doesn't read the directory again
+        tree.collapse(find(&tree, "a"));
+        tree.expand(find(&tree, "a"), &mut read_dir).unwrap();
+        drop(read_dir);
+        assert_eq!(read, ["", "a"]);
+
+        // Patterns below unread directories are kept
+        assert_eq!(
+            tree.to_patterns(),
+            [repo_path_buf("a/b"), repo_path_buf("c/missing")]
+        );
+    }
+
+    #[test]
+    fn test_sparse_tree_toggle() {
+        let files = ["a/b/file", "a/file", "c/file", "file"];
+        let mut read = vec![];
+        let mut read_dir = fake_read_dir(&files, &mut read);
+        let mut tree = SparseTree::new(&[repo_path_buf("a/b"), repo_path_buf("c/missing")]);
+        tree.expand(0, &mut read_dir).unwrap();
+        tree.expand(find(&tree, "a"), &mut read_dir).unwrap();
+
+        // Checking all entries of a directory checks the directory
+        tree.toggle(find(&tree, "a/file"));
+        assert_eq!(
+            tree.to_patterns(),
+            [repo_path_buf("a"), repo_path_buf("c/missing")]
+        );
+
+        // Toggling a directory replaces the patterns below it
+        tree.toggle(find(&tree, "c"));
+        assert_eq!(tree.to_patterns(), [repo_path_buf("a"), repo_path_buf("c")]);
+        tree.toggle(find(&tree, "c"));
+        assert_eq!(tree.to_patterns(), [repo_path_buf("a")]);
+
+        // Unchecking an entry keeps its siblings checked
+        tree.toggle(find(&tree, "a/b"));
+        assert_eq!(tree.to_patterns(), [repo_path_buf("a/file")]);
+
+        // Checking the root selects everything
+        tree.toggle(0);
+        assert_eq!(tree.to_patterns(), [RepoPathBuf::root()]);
+        tree.toggle(0);
+        assert_eq!(tree.to_patterns(), [] as [RepoPathBuf; 0]);
+    }
+}
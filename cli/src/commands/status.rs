@@ -12,24 +12,38 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::borrow::Cow;
+
+use clap_complete::ArgValueCompleter;
+use indoc::writedoc;
 use itertools::Itertools as _;
+use jj_lib::commit::Commit;
 use jj_lib::copies::CopyRecords;
+use jj_lib::matchers::Matcher;
 use jj_lib::merged_tree::MergedTree;
+use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo as _;
 use jj_lib::repo_path::RepoPath;
 use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::RevsetFilterPredicate;
+use jj_lib::working_copy::SnapshotStats;
 use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::print_conflicted_paths;
 use crate::cli_util::print_snapshot_stats;
 use crate::command_error::CommandError;
+use crate::complete;
 use crate::diff_util::DiffFormat;
+use crate::diff_util::DiffSummaryEntry;
+use crate::diff_util::collect_diff_summary;
 use crate::diff_util::get_copy_records;
+use crate::formatter::Formatter;
 use crate::formatter::FormatterExt as _;
+use crate::ui::OutputFormat;
 use crate::ui::Ui;
 
 /// Show high-level repo status [default alias: st]
@@ -43,12 +57,19 @@ use crate::ui::Ui;
 ///
 /// * [Conflicted bookmarks]
 ///
+/// Which of these sections are shown, and in which order, can be configured
+/// with `status.sections`.
+///
 /// [Conflicted bookmarks]:
 ///     https://jj-vcs.github.io/jj/latest/bookmarks/#conflicts
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct StatusArgs {
     /// Restrict the status display to these paths
-    #[arg(value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
+    #[arg(
+        value_name = "FILESETS",
+        value_hint = clap::ValueHint::AnyPath,
+        add = ArgValueCompleter::new(complete::modified_files),
+    )]
     paths: Vec<String>,
 }
 
@@ -72,20 +93,36 @@ pub(crate) fn cmd_status(
     let matcher = workspace_command
         .parse_file_patterns(ui, &args.paths)?
         .to_matcher();
+    if ui.output_format() == OutputFormat::Json {
+        let status = StatusJson::new(
+            &workspace_command,
+            maybe_wc_commit.as_ref(),
+            &snapshot_stats,
+            matcher.as_ref(),
+        )?;
+        ui.write_json(&status)?;
+        return Ok(());
+    }
+    let sections: Vec<StatusSection> = workspace_command.settings().get("status.sections")?;
     ui.request_pager();
     let mut formatter = ui.stdout_formatter();
     let formatter = formatter.as_mut();
 
-    if let Some(wc_commit) = &maybe_wc_commit {
-        let parent_tree = wc_commit.parent_tree(repo.as_ref())?;
-        let tree = wc_commit.tree()?;
-
-        let wc_has_changes = tree.id() != parent_tree.id();
-        let wc_has_untracked = !snapshot_stats.untracked_paths.is_empty();
-        if !wc_has_changes && !wc_has_untracked {
-            writeln!(formatter, "The working copy has no changes.")?;
-        } else {
-            if wc_has_changes {
+    let Some(wc_commit) = &maybe_wc_commit else {
+        writeln!(formatter, "No working copy")?;
+        if sections.contains(&StatusSection::BookmarkConflicts) {
+            print_conflicted_bookmarks(formatter, repo.as_ref())?;
+        }
+        return Ok(());
+    };
+    let parent_tree = wc_commit.parent_tree(repo.as_ref())?;
+    let tree = wc_commit.tree()?;
+    let wc_has_changes = tree.id() != parent_tree.id();
+    let wc_has_untracked =
+        !snapshot_stats.untracked_paths.is_empty() && sections.contains(&StatusSection::Untracked);
+    for section in &sections {
+        match section {
+            StatusSection::Changes if wc_has_changes => {
                 writeln!(formatter, "Working copy changes:")?;
                 let mut copy_records = CopyRecords::default();
                 for parent in wc_commit.parent_ids() {
@@ -105,12 +142,15 @@ pub(crate) fn cmd_status(
                     )
                     .block_on()?;
             }
-
-            if wc_has_untracked {
+            StatusSection::Changes if !wc_has_untracked => {
+                writeln!(formatter, "The working copy has no changes.")?;
+            }
+            StatusSection::Changes => {}
+            StatusSection::Untracked if wc_has_untracked => {
                 writeln!(formatter, "Untracked paths:")?;
                 visit_collapsed_untracked_files(
                     snapshot_stats.untracked_paths.keys(),
-                    tree,
+                    tree.clone(),
                     |path, is_dir| {
                         let ui_path = workspace_command.path_converter().format_file_path(path);
                         writeln!(
@@ -127,61 +167,135 @@ pub(crate) fn cmd_status(
                 )
                 .block_on()?;
             }
+            StatusSection::Untracked => {}
+            StatusSection::Commits => {
+                let template = workspace_command.commit_summary_template();
+                write!(formatter, "Working copy  (@) : ")?;
+                template.format(wc_commit, formatter)?;
+                writeln!(formatter)?;
+                for parent in wc_commit.parents() {
+                    let parent = parent?;
+                    //                "Working copy  (@) : "
+                    write!(formatter, "Parent commit (@-): ")?;
+                    template.format(&parent, formatter)?;
+                    writeln!(formatter)?;
+                }
+            }
+            StatusSection::Conflicts => {
+                print_conflicts(formatter, &workspace_command, wc_commit)?;
+            }
+            StatusSection::BookmarkConflicts => {
+                print_conflicted_bookmarks(formatter, repo.as_ref())?;
+            }
         }
+    }
+
+    Ok(())
+}
+
+/// Section of the `jj status` output, as listed in `status.sections`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum StatusSection {
+    /// Summary of the changes in the working-copy commit
+    Changes,
+    /// Untracked paths in the working copy
+    Untracked,
+    /// The working-copy commit and its parents
+    Commits,
+    /// Conflicts in the working-copy commit, and how to resolve them
+    Conflicts,
+    /// Conflicted local and remote bookmarks
+    BookmarkConflicts,
+}
 
-        let template = workspace_command.commit_summary_template();
-        write!(formatter, "Working copy  (@) : ")?;
-        template.format(wc_commit, formatter)?;
-        writeln!(formatter)?;
+fn print_conflicts(
+    formatter: &mut dyn Formatter,
+    workspace_command: &WorkspaceCommandHelper,
+    wc_commit: &Commit,
+) -> Result<(), CommandError> {
+    let repo = workspace_command.repo();
+    if !wc_commit.has_conflict()? {
         for parent in wc_commit.parents() {
             let parent = parent?;
-            //                "Working copy  (@) : "
-            write!(formatter, "Parent commit (@-): ")?;
-            template.format(&parent, formatter)?;
-            writeln!(formatter)?;
+            if parent.has_conflict()? {
+                writeln!(
+                    formatter.labeled("hint").with_heading("Hint: "),
+                    "Conflict in parent commit has been resolved in working copy"
+                )?;
+                break;
+            }
         }
+        return Ok(());
+    }
 
-        if wc_commit.has_conflict()? {
-            // TODO: Conflicts should also be filtered by the `matcher`. See the related
-            // TODO on `MergedTree::conflicts()`.
-            let conflicts = wc_commit.tree()?.conflicts().collect_vec();
-            writeln!(
-                formatter.labeled("warning").with_heading("Warning: "),
-                "There are unresolved conflicts at these paths:"
-            )?;
-            print_conflicted_paths(conflicts, formatter, &workspace_command)?;
-
-            let wc_revset = RevsetExpression::commit(wc_commit.id().clone());
-
-            // Ancestors with conflicts, excluding the current working copy commit.
-            let ancestors_conflicts: Vec<_> = workspace_command
-                .attach_revset_evaluator(
-                    wc_revset
-                        .parents()
-                        .ancestors()
-                        .filtered(RevsetFilterPredicate::HasConflict)
-                        .minus(&workspace_command.env().immutable_expression()),
-                )
-                .evaluate_to_commit_ids()?
-                .try_collect()?;
+    // TODO: Conflicts should also be filtered by the `matcher`. See the related
+    // TODO on `MergedTree::conflicts()`.
+    let conflicts = wc_commit.tree()?.conflicts().collect_vec();
+    let first_conflicted_path = conflicts.first().map(|(path, _)| path.clone());
+    writeln!(
+        formatter.labeled("warning").with_heading("Warning: "),
+        "There are unresolved conflicts at these paths:"
+    )?;
+    print_conflicted_paths(conflicts, formatter, workspace_command)?;
 
-            workspace_command.report_repo_conflicts(formatter, repo, ancestors_conflicts)?;
-        } else {
-            for parent in wc_commit.parents() {
-                let parent = parent?;
-                if parent.has_conflict()? {
-                    writeln!(
-                        formatter.labeled("hint").with_heading("Hint: "),
-                        "Conflict in parent commit has been resolved in working copy"
-                    )?;
-                    break;
-                }
-            }
+    let wc_revset = RevsetExpression::commit(wc_commit.id().clone());
+
+    // Ancestors with conflicts, excluding the current working copy commit.
+    let ancestors_conflicts: Vec<_> = workspace_command
+        .attach_revset_evaluator(
+            wc_revset
+                .parents()
+                .ancestors()
+                .filtered(RevsetFilterPredicate::HasConflict)
+                .minus(&workspace_command.env().immutable_expression()),
+        )
+        .evaluate_to_commit_ids()?
+        .try_collect()?;
+
+    if ancestors_conflicts.is_empty() {
+        // The conflicts were created in the working-copy commit (e.g. by
+        // merging), so they can be resolved in place.
+        if let Some(path) = first_conflicted_path
+            && workspace_command
+                .settings()
+                .get_bool("hints.resolving-conflicts")?
+        {
+            let path = workspace_command.format_file_path(&path);
+            writedoc!(
+                formatter.labeled("hint").with_heading("Hint: "),
+                "
+                To resolve the conflicts, use a merge tool, e.g.:
+                  jj resolve {path}
+                or edit the conflict markers in the files directly.
+                Once the conflicts are resolved, you can inspect the result with `jj diff`.
+                ",
+                path = quote_path_arg(&path),
+            )?;
         }
     } else {
-        writeln!(formatter, "No working copy")?;
+        workspace_command.report_repo_conflicts(formatter, repo, ancestors_conflicts)?;
     }
+    Ok(())
+}
 
+/// Quotes `path` for the shell if it contains characters other than the
+/// usual ones.
+fn quote_path_arg(path: &str) -> Cow<'_, str> {
+    if path
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == std::path::MAIN_SEPARATOR || "+,-./:=@_".contains(c))
+    {
+        Cow::Borrowed(path)
+    } else {
+        Cow::Owned(format!("'{}'", path.replace('\'', r"'\''")))
+    }
+}
+
+fn print_conflicted_bookmarks(
+    formatter: &mut dyn Formatter,
+    repo: &ReadonlyRepo,
+) -> Result<(), CommandError> {
     let conflicted_local_bookmarks = repo
         .view()
         .local_bookmarks()
@@ -229,6 +343,69 @@ pub(crate) fn cmd_status(
     Ok(())
 }
 
+/// Output of `jj status --output json`.
+#[derive(Debug, serde::Serialize)]
+struct StatusJson {
+    working_copy: Option<Commit>,
+    parents: Vec<Commit>,
+    changes: Vec<DiffSummaryEntry>,
+    untracked: Vec<String>,
+    conflicts: Vec<String>,
+    conflicted_bookmarks: Vec<String>,
+    conflicted_remote_bookmarks: Vec<String>,
+}
+
+impl StatusJson {
+    fn new(
+        workspace_command: &WorkspaceCommandHelper,
+        maybe_wc_commit: Option<&Commit>,
+        snapshot_stats: &SnapshotStats,
+        matcher: &dyn Matcher,
+    ) -> Result<Self, CommandError> {
+        let repo = workspace_command.repo();
+        let mut status = Self {
+            working_copy: maybe_wc_commit.cloned(),
+            parents: vec![],
+            changes: vec![],
+            untracked: snapshot_stats
+                .untracked_paths
+                .keys()
+                .map(|path| path.as_internal_file_string().to_owned())
+                .collect(),
+            conflicts: vec![],
+            conflicted_bookmarks: repo
+                .view()
+                .local_bookmarks()
+                .filter(|(_, target)| target.has_conflict())
+                .map(|(name, _)| name.as_str().to_owned())
+                .collect(),
+            conflicted_remote_bookmarks: repo
+                .view()
+                .all_remote_bookmarks()
+                .filter(|(_, remote_ref)| remote_ref.target.has_conflict())
+                .map(|(symbol, _)| symbol.to_string())
+                .collect(),
+        };
+        if let Some(wc_commit) = maybe_wc_commit {
+            status.parents = wc_commit.parents().try_collect()?;
+            let parent_tree = wc_commit.parent_tree(repo.as_ref())?;
+            let tree = wc_commit.tree()?;
+            let mut copy_records = CopyRecords::default();
+            for parent in wc_commit.parent_ids() {
+                let records = get_copy_records(repo.store(), parent, wc_commit.id(), matcher)?;
+                copy_records.add_records(records)?;
+            }
+            let tree_diff = parent_tree.diff_stream_with_copies(&tree, matcher, &copy_records);
+            status.changes = collect_diff_summary(tree_diff).block_on()?;
+            status.conflicts = tree
+                .conflicts()
+                .map(|(path, _)| path.as_internal_file_string().to_owned())
+                .collect();
+        }
+        Ok(status)
+    }
+}
+
 async fn visit_collapsed_untracked_files(
     untracked_paths: impl IntoIterator<Item = impl AsRef<RepoPath>>,
     tree: MergedTree,
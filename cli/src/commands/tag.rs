@@ -41,7 +41,7 @@ pub struct TagListArgs {
     ///
     /// [wildcard pattern]:
     ///     https://jj-vcs.github.io/jj/latest/revsets/#string-patterns
-    #[arg(value_parser = StringPattern::parse)]
+    #[arg(value_parser = StringPattern::parse, add = ArgValueCandidates::new(complete::tags))]
     pub names: Vec<StringPattern>,
     /// Render each tag using the given template
     ///
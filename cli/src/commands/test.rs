@@ -0,0 +1,202 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+use std::process::Stdio;
+
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::config::ConfigGetResultExt as _;
+use jj_lib::fsmonitor::FsmonitorSettings;
+use jj_lib::local_working_copy::EolConversionMode;
+use jj_lib::local_working_copy::TreeState;
+use jj_lib::local_working_copy::TreeStateSettings;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::internal_error_with_message;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::command_error::user_error_with_message;
+use crate::complete;
+use crate::config::CommandNameAndArgs;
+use crate::formatter::FormatterExt as _;
+use crate::merge_tools::new_utf8_temp_dir;
+use crate::test_results::TestResult;
+use crate::test_results::TestResultStore;
+use crate::ui::Ui;
+
+/// Run a test command on revisions and remember the results
+///
+/// Each revision is checked out into a temporary directory, where the test
+/// command is run. A revision passes if the command exits with status 0.
+/// Results are recorded in the repository, keyed by the command and the
+/// contents of the revision, so revisions that have already been tested are
+/// skipped. Since the key is the content, rewriting a revision without
+/// changing its files (e.g. with `jj describe`) keeps its result.
+///
+/// The results of `test.command` can be displayed in `jj log` with the
+/// `test_result` template keyword.
+///
+/// The revision's commit ID is available to the command in the
+/// `$JJ_TEST_TARGET` environment variable.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct TestArgs {
+    /// The revisions to test
+    #[arg(
+        long,
+        short,
+        default_value = "@",
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revisions: Vec<RevisionArg>,
+    /// The command to run [default: `test.command` config]
+    #[arg(long, value_name = "COMMAND")]
+    command: Option<CommandNameAndArgs>,
+    /// Run the command even on revisions that have already been tested
+    #[arg(long)]
+    rerun: bool,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_test(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &TestArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let test_command = match &args.command {
+        Some(test_command) => test_command.clone(),
+        None => workspace_command
+            .settings()
+            .get::<CommandNameAndArgs>("test.command")
+            .optional()?
+            .ok_or_else(|| {
+                user_error_with_hint(
+                    "No test command given",
+                    "Pass it with `--command`, or set `test.command` in the config.",
+                )
+            })?,
+    };
+    let store = TestResultStore::new(workspace_command.repo_path(), &test_command);
+
+    let mut commits: Vec<Commit> = workspace_command
+        .parse_union_revsets(ui, &args.revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    // Test older revisions first, as their results are usually more
+    // interesting when looking for a breakage.
+    commits.reverse();
+
+    let commit_template = workspace_command.commit_summary_template();
+    let mut num_failed = 0;
+    for commit in &commits {
+        let cached = if args.rerun {
+            None
+        } else {
+            store.get(commit.tree_id())?
+        };
+        let result = match cached {
+            Some(result) => result,
+            None => {
+                let result = run_test(&workspace_command, &test_command, commit)?;
+                store.set(commit.tree_id(), result)?;
+                result
+            }
+        };
+        if result == TestResult::Failed {
+            num_failed += 1;
+        }
+        let mut formatter = ui.stdout_formatter();
+        let message = match result {
+            TestResult::Passed => "Passed",
+            TestResult::Failed => "Failed",
+        };
+        write!(
+            formatter.labeled("test_result").labeled(result.as_str()),
+            "{message}"
+        )?;
+        if cached.is_some() {
+            write!(formatter, " (cached)")?;
+        }
+        write!(formatter, ": ")?;
+        commit_template.format(commit, formatter.as_mut())?;
+        writeln!(formatter)?;
+    }
+
+    if num_failed > 0 {
+        return Err(user_error(format!(
+            "{num_failed} of {} revisions failed the test",
+            commits.len()
+        )));
+    }
+    Ok(())
+}
+
+fn run_test(
+    workspace_command: &WorkspaceCommandHelper,
+    test_command: &CommandNameAndArgs,
+    commit: &Commit,
+) -> Result<TestResult, CommandError> {
+    let temp_dir = new_utf8_temp_dir("jj-test-")?;
+    let wc_path = temp_dir.path().join("workspace");
+    let state_dir = temp_dir.path().join("state");
+    std::fs::create_dir(&wc_path)?;
+    std::fs::create_dir(&state_dir)?;
+    let tree_state_settings = TreeStateSettings {
+        conflict_marker_style: workspace_command
+            .settings()
+            .get("ui.conflict-marker-style")?,
+        eol_conversion_mode: EolConversionMode::None,
+        fsmonitor_settings: FsmonitorSettings::None,
+        skip_reserved_names: workspace_command
+            .settings()
+            .get_bool("working-copy.skip-reserved-names")?,
+    };
+    let mut tree_state = TreeState::init(
+        workspace_command.repo().store().clone(),
+        wc_path.clone(),
+        state_dir,
+        &tree_state_settings,
+    )
+    .map_err(|err| internal_error_with_message("Failed to set up test directory", err))?;
+    tree_state
+        .check_out(&commit.tree()?)
+        .map_err(|err| internal_error_with_message("Failed to check out revision", err))?;
+
+    let jj_executable_path = std::env::current_exe().map_err(|err| {
+        internal_error_with_message("Could not get path for the jj executable", err)
+    })?;
+    let mut cmd = test_command.to_command();
+    tracing::info!(?cmd, "running test command");
+    let status = cmd
+        .current_dir(&wc_path)
+        .env("JJ_EXECUTABLE_PATH", jj_executable_path)
+        .env("JJ_TEST_TARGET", commit.id().hex())
+        .stdin(Stdio::null())
+        .status()
+        .map_err(|err| user_error_with_message("Failed to run test command", err))?;
+    Ok(if status.success() {
+        TestResult::Passed
+    } else {
+        TestResult::Failed
+    })
+}
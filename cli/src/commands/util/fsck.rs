@@ -0,0 +1,455 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::io::Write as _;
+
+use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::default_index::DefaultIndexStore;
+use jj_lib::op_walk;
+use jj_lib::ref_name::RefName;
+use jj_lib::repo::ReadonlyRepo;
+use jj_lib::repo::Repo as _;
+use jj_lib::workspace::Workspace;
+use pollster::FutureExt as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::print_snapshot_stats;
+use crate::cli_util::short_commit_hash;
+use crate::cli_util::short_operation_hash;
+use crate::command_error::CommandError;
+use crate::command_error::internal_error;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::formatter::FormatterExt as _;
+use crate::ui::Ui;
+
+/// Check the repository for corruption and inconsistencies
+///
+/// Cross-checks the operation log, the view, the commit store, the commit
+/// index, and the state of the working copy, and reports dangling references
+/// and other problems it finds.
+///
+/// With `--repair`, problems that can be fixed without losing data are
+/// repaired: the commit index is rebuilt, and a stale working copy is updated
+/// (after snapshotting any local changes).
+#[derive(clap::Args, Clone, Debug)]
+pub struct UtilFsckArgs {
+    /// Repair problems that can be fixed safely
+    #[arg(long)]
+    repair: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Repair {
+    Reindex,
+    UpdateStale,
+}
+
+#[derive(Debug)]
+struct Problem {
+    severity: Severity,
+    message: String,
+    repair: Option<Repair>,
+}
+
+#[derive(Debug, Default)]
+struct Problems(Vec<Problem>);
+
+impl Problems {
+    fn error(&mut self, message: impl Into<String>, repair: Option<Repair>) {
+        self.push(Severity::Error, message.into(), repair);
+    }
+
+    fn warning(&mut self, message: impl Into<String>, repair: Option<Repair>) {
+        self.push(Severity::Warning, message.into(), repair);
+    }
+
+    fn push(&mut self, severity: Severity, message: String, repair: Option<Repair>) {
+        self.0.push(Problem {
+            severity,
+            message,
+            repair,
+        });
+    }
+
+    fn needs_repair(&self, repair: Repair) -> bool {
+        self.0.iter().any(|problem| problem.repair == Some(repair))
+    }
+}
+
+#[instrument(skip_all)]
+pub fn cmd_util_fsck(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &UtilFsckArgs,
+) -> Result<(), CommandError> {
+    if !command.is_at_head_operation() {
+        return Err(user_error(
+            "Cannot check the repository at a non-head operation",
+        ));
+    }
+    // Load the workspace without the repo so that a broken view or index
+    // doesn't prevent this command from running.
+    let workspace = command.load_workspace()?;
+    let mut problems = Problems::default();
+    if let Some(repo) = check_operations(&workspace, &mut problems)? {
+        check_commits(&repo, &mut problems)?;
+        check_working_copy(&workspace, &repo, &mut problems)?;
+    }
+
+    {
+        let mut formatter = ui.stdout_formatter();
+        for problem in &problems.0 {
+            let (label, heading) = match problem.severity {
+                Severity::Warning => ("warning", "Warning: "),
+                Severity::Error => ("error", "Error: "),
+            };
+            writeln!(
+                formatter.labeled(label).with_heading(heading),
+                "{}",
+                problem.message
+            )?;
+        }
+    }
+
+    let num_errors = problems
+        .0
+        .iter()
+        .filter(|problem| problem.severity == Severity::Error)
+        .count();
+    let num_warnings = problems.0.len() - num_errors;
+    if problems.0.is_empty() {
+        writeln!(ui.status(), "No problems found.")?;
+        return Ok(());
+    }
+    writeln!(
+        ui.status(),
+        "Found {num_errors} errors and {num_warnings} warnings."
+    )?;
+
+    let repairable = problems
+        .0
+        .iter()
+        .filter(|problem| problem.repair.is_some())
+        .count();
+    if !args.repair {
+        if num_errors > 0 {
+            return Err(if repairable > 0 {
+                user_error_with_hint(
+                    "The repository has problems",
+                    "Run `jj util fsck --repair` to repair the problems that can be fixed safely.",
+                )
+            } else {
+                user_error("The repository has problems")
+            });
+        }
+        return Ok(());
+    }
+
+    if problems.needs_repair(Repair::Reindex) {
+        reindex(ui, command, &workspace)?;
+    }
+    if problems.needs_repair(Repair::UpdateStale) {
+        let (workspace_command, stats) = command.recover_stale_working_copy(ui)?;
+        print_snapshot_stats(ui, &stats, workspace_command.env().path_converter())?;
+    }
+    let num_unrepaired_errors = problems
+        .0
+        .iter()
+        .filter(|problem| problem.severity == Severity::Error && problem.repair.is_none())
+        .count();
+    if num_unrepaired_errors > 0 {
+        return Err(user_error(format!(
+            "{num_unrepaired_errors} errors could not be repaired automatically"
+        )));
+    }
+    Ok(())
+}
+
+/// Checks that the operation heads and their ancestors are readable, and loads
+/// the repo at the current operation.
+fn check_operations(
+    workspace: &Workspace,
+    problems: &mut Problems,
+) -> Result<Option<std::sync::Arc<ReadonlyRepo>>, CommandError> {
+    let repo_loader = workspace.repo_loader();
+    let op_head_ids = repo_loader
+        .op_heads_store()
+        .get_op_heads()
+        .map_err(internal_error)?;
+    let mut op_heads = vec![];
+    for id in &op_head_ids {
+        match repo_loader.load_operation(id) {
+            Ok(op) => op_heads.push(op),
+            Err(err) => problems.error(
+                format!(
+                    "Operation head {} is unreadable: {err}",
+                    short_operation_hash(id)
+                ),
+                None,
+            ),
+        }
+    }
+    if op_heads.len() > 1 {
+        problems.warning(
+            format!(
+                "There are {} operation heads, which will be merged by the next command",
+                op_heads.len()
+            ),
+            None,
+        );
+    }
+
+    let mut head_views_readable = true;
+    for op in op_walk::walk_ancestors(&op_heads) {
+        let op = match op {
+            Ok(op) => op,
+            Err(err) => {
+                problems.error(format!("Operation log is broken: {err}"), None);
+                break;
+            }
+        };
+        if let Err(err) = op.view() {
+            problems.error(
+                format!(
+                    "View of operation {} is unreadable: {err}",
+                    short_operation_hash(op.id())
+                ),
+                None,
+            );
+            head_views_readable &= !op_heads.contains(&op);
+        }
+    }
+
+    let [op] = &op_heads[..] else {
+        return Ok(None);
+    };
+    if !head_views_readable {
+        return Ok(None);
+    }
+    match repo_loader.load_at(op) {
+        Ok(repo) => Ok(Some(repo)),
+        Err(err) => {
+            problems.error(
+                format!("Failed to load the repository: {err}"),
+                Some(Repair::Reindex),
+            );
+            Ok(None)
+        }
+    }
+}
+
+/// Checks that commits referenced by the view and their ancestors exist in the
+/// store and in the index.
+fn check_commits(repo: &ReadonlyRepo, problems: &mut Problems) -> Result<(), CommandError> {
+    let view = repo.view();
+    let mut refs: Vec<(String, &CommitId)> = vec![];
+    for (name, target) in view.local_bookmarks() {
+        refs.extend(
+            target
+                .added_ids()
+                .map(|id| (ref_label("bookmark", name), id)),
+        );
+    }
+    for (symbol, remote_ref) in view.all_remote_bookmarks() {
+        refs.extend(
+            remote_ref
+                .target
+                .added_ids()
+                .map(|id| (format!("remote bookmark {symbol}"), id)),
+        );
+    }
+    for (name, target) in view.tags() {
+        refs.extend(target.added_ids().map(|id| (ref_label("tag", name), id)));
+    }
+    for (name, target) in view.git_refs() {
+        refs.extend(
+            target
+                .added_ids()
+                .map(|id| (format!("git ref {}", name.as_str()), id)),
+        );
+    }
+    refs.extend(
+        view.git_head()
+            .added_ids()
+            .map(|id| ("git HEAD".to_owned(), id)),
+    );
+    for (name, id) in view.wc_commit_ids() {
+        refs.push((
+            format!("working copy of workspace {}", name.as_symbol()),
+            id,
+        ));
+    }
+    for id in view.heads().iter().sorted() {
+        refs.push(("visible heads".to_owned(), id));
+    }
+
+    let store = repo.store();
+    let index = repo.index();
+    let mut visited: HashSet<CommitId> = HashSet::new();
+    let mut to_visit: Vec<CommitId> = vec![];
+    for (label, id) in refs {
+        if !index.has_id(id) {
+            problems.error(
+                format!(
+                    "Commit {} referenced by {label} is not indexed",
+                    short_commit_hash(id)
+                ),
+                Some(Repair::Reindex),
+            );
+        }
+        if store.get_commit(id).is_err() {
+            problems.error(
+                format!(
+                    "Commit {} referenced by {label} is missing from the store",
+                    short_commit_hash(id)
+                ),
+                None,
+            );
+        } else if visited.insert(id.clone()) {
+            to_visit.push(id.clone());
+        }
+    }
+
+    while let Some(id) = to_visit.pop() {
+        let commit = match store.get_commit(&id) {
+            Ok(commit) => commit,
+            Err(err) => {
+                problems.error(
+                    format!("Commit {} is unreadable: {err}", short_commit_hash(&id)),
+                    None,
+                );
+                continue;
+            }
+        };
+        if let Err(err) = commit.tree() {
+            problems.error(
+                format!(
+                    "Tree of commit {} is unreadable: {err}",
+                    short_commit_hash(&id)
+                ),
+                None,
+            );
+        }
+        for parent_id in commit.parent_ids() {
+            if !index.has_id(parent_id) {
+                problems.error(
+                    format!(
+                        "Parent {} of commit {} is not indexed",
+                        short_commit_hash(parent_id),
+                        short_commit_hash(&id)
+                    ),
+                    Some(Repair::Reindex),
+                );
+            }
+            if visited.insert(parent_id.clone()) {
+                to_visit.push(parent_id.clone());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the working copy state is consistent with the repo.
+fn check_working_copy(
+    workspace: &Workspace,
+    repo: &ReadonlyRepo,
+    problems: &mut Problems,
+) -> Result<(), CommandError> {
+    let working_copy = workspace.working_copy();
+    let workspace_name = workspace.workspace_name();
+    if let Err(err) = repo.loader().load_operation(working_copy.operation_id()) {
+        problems.error(
+            format!(
+                "The working copy refers to an unreadable operation {}: {err}",
+                short_operation_hash(working_copy.operation_id())
+            ),
+            Some(Repair::UpdateStale),
+        );
+        return Ok(());
+    }
+    let tree_id = match working_copy.tree_id() {
+        Ok(tree_id) => tree_id.clone(),
+        Err(err) => {
+            problems.error(format!("The working copy state is unreadable: {err}"), None);
+            return Ok(());
+        }
+    };
+    if let Err(err) = repo.store().get_root_tree(&tree_id) {
+        problems.error(
+            format!("The tree of the working copy is unreadable: {err}"),
+            None,
+        );
+    }
+    let Some(wc_commit_id) = repo.view().get_wc_commit_id(workspace_name) else {
+        problems.warning(
+            format!(
+                "Workspace {} doesn't have a working-copy commit in the repo",
+                workspace_name.as_symbol()
+            ),
+            None,
+        );
+        return Ok(());
+    };
+    let Ok(wc_commit) = repo.store().get_commit(wc_commit_id) else {
+        // Already reported by check_commits().
+        return Ok(());
+    };
+    if *wc_commit.tree_id() != tree_id && working_copy.operation_id() != repo.op_id() {
+        problems.warning(
+            format!(
+                "The working copy is stale (not updated since operation {})",
+                short_operation_hash(working_copy.operation_id())
+            ),
+            Some(Repair::UpdateStale),
+        );
+    }
+    Ok(())
+}
+
+fn reindex(ui: &Ui, command: &CommandHelper, workspace: &Workspace) -> Result<(), CommandError> {
+    let repo_loader = workspace.repo_loader();
+    let op = command.resolve_operation(ui, repo_loader)?;
+    let index_store = repo_loader.index_store();
+    let Some(default_index_store) = index_store.downcast_ref::<DefaultIndexStore>() else {
+        return Err(user_error(format!(
+            "Cannot reindex indexes of type '{}'",
+            index_store.name()
+        )));
+    };
+    default_index_store.reinit().map_err(internal_error)?;
+    let default_index = default_index_store
+        .build_index_at_operation(&op, repo_loader.store())
+        .block_on()
+        .map_err(internal_error)?;
+    writeln!(
+        ui.status(),
+        "Rebuilt the commit index with {} commits.",
+        default_index.num_commits()
+    )?;
+    Ok(())
+}
+
+fn ref_label(kind: &str, name: &RefName) -> String {
+    format!("{kind} {}", name.as_symbol())
+}
@@ -12,12 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::slice;
 use std::time::Duration;
 use std::time::SystemTime;
 
-use jj_lib::repo::Repo as _;
-
+use crate::auto_gc::AutoGcConfig;
+use crate::auto_gc::DEFAULT_EXPIRY;
+use crate::auto_gc::gc_repo;
+use crate::auto_gc::needs_gc;
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
@@ -27,8 +28,15 @@ use crate::ui::Ui;
 ///
 /// To garbage-collect old operations and the commits/objects referenced by
 /// them, run `jj op abandon ..<some old operation>` before `jj util gc`.
+///
+/// Garbage collection also runs automatically after commands that modify the
+/// repo once the thresholds in the `gc` config section are exceeded, unless
+/// `gc.auto` is disabled.
 #[derive(clap::Args, Clone, Debug)]
 pub struct UtilGcArgs {
+    /// Only run garbage collection if the `gc.auto-*` thresholds are exceeded
+    #[arg(long)]
+    auto: bool,
     /// Time threshold
     ///
     /// By default, only obsolete objects and operations older than 2 weeks are
@@ -52,15 +60,20 @@ pub fn cmd_util_gc(
         ));
     }
     let keep_newer = match args.expire.as_deref() {
-        None => SystemTime::now() - Duration::from_secs(14 * 86400),
+        None => SystemTime::now() - DEFAULT_EXPIRY,
         Some("now") => SystemTime::now() - Duration::ZERO,
         _ => return Err(user_error("--expire only accepts 'now'")),
     };
     let workspace_command = command.workspace_helper(ui)?;
 
     let repo = workspace_command.repo();
-    repo.op_store()
-        .gc(slice::from_ref(repo.op_id()), keep_newer)?;
-    repo.store().gc(repo.index(), keep_newer)?;
-    Ok(())
+    let repo_path = workspace_command.repo_path();
+    if args.auto {
+        let config = AutoGcConfig::from_settings(workspace_command.settings())?;
+        if !needs_gc(&config, repo, repo_path)? {
+            writeln!(ui.status(), "Nothing to garbage collect")?;
+            return Ok(());
+        }
+    }
+    gc_repo(repo, repo_path, keep_newer)
 }
@@ -15,9 +15,12 @@
 mod completion;
 mod config_schema;
 mod exec;
+mod fsck;
 mod gc;
 mod install_man_pages;
 mod markdown_help;
+mod stats;
+mod unlock;
 
 use clap::Subcommand;
 use tracing::instrument;
@@ -28,12 +31,18 @@ use self::config_schema::UtilConfigSchemaArgs;
 use self::config_schema::cmd_util_config_schema;
 use self::exec::UtilExecArgs;
 use self::exec::cmd_util_exec;
+use self::fsck::UtilFsckArgs;
+use self::fsck::cmd_util_fsck;
 use self::gc::UtilGcArgs;
 use self::gc::cmd_util_gc;
 use self::install_man_pages::UtilInstallManPagesArgs;
 use self::install_man_pages::cmd_util_install_man_pages;
 use self::markdown_help::UtilMarkdownHelp;
 use self::markdown_help::cmd_util_markdown_help;
+use self::stats::UtilStatsArgs;
+use self::stats::cmd_util_stats;
+use self::unlock::UtilUnlockArgs;
+use self::unlock::cmd_util_unlock;
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::ui::Ui;
@@ -44,9 +53,12 @@ pub(crate) enum UtilCommand {
     Completion(UtilCompletionArgs),
     ConfigSchema(UtilConfigSchemaArgs),
     Exec(UtilExecArgs),
+    Fsck(UtilFsckArgs),
     Gc(UtilGcArgs),
     InstallManPages(UtilInstallManPagesArgs),
     MarkdownHelp(UtilMarkdownHelp),
+    Stats(UtilStatsArgs),
+    Unlock(UtilUnlockArgs),
 }
 
 #[instrument(skip_all)]
@@ -59,8 +71,11 @@ pub(crate) fn cmd_util(
         UtilCommand::Completion(args) => cmd_util_completion(ui, command, args),
         UtilCommand::ConfigSchema(args) => cmd_util_config_schema(ui, command, args),
         UtilCommand::Exec(args) => cmd_util_exec(ui, command, args),
+        UtilCommand::Fsck(args) => cmd_util_fsck(ui, command, args),
         UtilCommand::Gc(args) => cmd_util_gc(ui, command, args),
         UtilCommand::InstallManPages(args) => cmd_util_install_man_pages(ui, command, args),
         UtilCommand::MarkdownHelp(args) => cmd_util_markdown_help(ui, command, args),
+        UtilCommand::Stats(args) => cmd_util_stats(ui, command, args),
+        UtilCommand::Unlock(args) => cmd_util_unlock(ui, command, args),
     }
 }
@@ -0,0 +1,222 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::backend::TreeValue;
+use jj_lib::commit::Commit;
+use jj_lib::default_index::DefaultReadonlyIndex;
+use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPathBuf;
+use pollster::FutureExt as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::text_util::format_byte_size;
+use crate::ui::Ui;
+
+/// Show statistics about the repository
+///
+/// Reports commit counts per author and per month, the number of changes, the
+/// largest files, disk usage of the repository storage, and the state of the
+/// commit index. This can be useful for repository maintenance and for
+/// planning migrations.
+#[derive(clap::Args, Clone, Debug)]
+pub struct UtilStatsArgs {
+    /// Which revisions to compute statistics over
+    #[arg(
+        long, short,
+        default_value = "~root()",
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revisions: Vec<RevisionArg>,
+
+    /// Maximum number of authors and files to list
+    #[arg(long, short = 'n', default_value_t = 10)]
+    limit: usize,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_util_stats(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &UtilStatsArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo();
+    let expression = workspace_command.parse_union_revsets(ui, &args.revisions)?;
+    let commits: Vec<Commit> = expression.evaluate_to_commits()?.try_collect()?;
+
+    let mut num_merges = 0;
+    let mut change_ids = HashSet::new();
+    let mut authors: HashMap<String, usize> = HashMap::new();
+    let mut months: BTreeMap<String, usize> = BTreeMap::new();
+    for commit in &commits {
+        if commit.parent_ids().len() > 1 {
+            num_merges += 1;
+        }
+        change_ids.insert(commit.change_id().clone());
+        let author = commit.author();
+        *authors
+            .entry(format!("{} <{}>", author.name, author.email))
+            .or_default() += 1;
+        let month = match author.timestamp.to_datetime() {
+            Ok(datetime) => datetime.format("%Y-%m").to_string(),
+            Err(_) => "(invalid)".to_owned(),
+        };
+        *months.entry(month).or_default() += 1;
+    }
+
+    let mut formatter = ui.stdout_formatter();
+    let formatter = formatter.as_mut();
+    writeln!(formatter, "=== Commits ===")?;
+    writeln!(formatter, "Number of commits: {}", commits.len())?;
+    writeln!(formatter, "Number of merges: {num_merges}")?;
+    writeln!(formatter, "Number of changes: {}", change_ids.len())?;
+    writeln!(formatter, "Commits per author:")?;
+    for (author, count) in authors
+        .iter()
+        .sorted_by(|(a1, c1), (a2, c2)| c2.cmp(c1).then(a1.cmp(a2)))
+        .take(args.limit)
+    {
+        writeln!(formatter, "  {count:>6} {author}")?;
+    }
+    writeln!(formatter, "Commits per month:")?;
+    for (month, count) in &months {
+        writeln!(formatter, "  {month}: {count}")?;
+    }
+
+    // Files are compared across the heads of the revset, so that a file which
+    // was shrunk in a later commit isn't reported by its old size.
+    let heads: Vec<Commit> = workspace_command
+        .attach_revset_evaluator(expression.expression().heads())
+        .evaluate_to_commits()?
+        .try_collect()?;
+    let mut file_sizes: HashMap<RepoPathBuf, u64> = HashMap::new();
+    for commit in &heads {
+        let tree = commit.tree()?;
+        for (path, value) in tree.entries() {
+            let Ok(Some(TreeValue::File { id, .. })) = value?.into_resolved() else {
+                continue;
+            };
+            let size = repo.store().file_size(&path, &id).block_on()?;
+            let max_size = file_sizes.entry(path).or_default();
+            *max_size = (*max_size).max(size);
+        }
+    }
+    writeln!(formatter, "=== Largest files ===")?;
+    for (path, size) in file_sizes
+        .iter()
+        .sorted_by(|(p1, s1), (p2, s2)| s2.cmp(s1).then(p1.cmp(p2)))
+        .take(args.limit)
+    {
+        writeln!(
+            formatter,
+            "  {:>9} {}",
+            format_byte_size(*size),
+            workspace_command.format_file_path(path)
+        )?;
+    }
+
+    writeln!(formatter, "=== Storage ===")?;
+    writeln!(formatter, "Backend: {}", repo.store().backend().name())?;
+    // Object storage is laid out differently by each backend, so report the
+    // disk usage of each directory (and its immediate subdirectories, which
+    // usually correspond to object types) instead.
+    for (depth, name, size) in dir_sizes(workspace_command.repo_path(), 2)? {
+        writeln!(
+            formatter,
+            "{:indent$}{name}: {:>9}",
+            "",
+            format_byte_size(size),
+            indent = depth * 2
+        )?;
+    }
+
+    writeln!(formatter, "=== Index ===")?;
+    if let Some(index) = repo.readonly_index().downcast_ref::<DefaultReadonlyIndex>() {
+        let stats = index.stats();
+        writeln!(formatter, "Indexed commits: {}", stats.num_commits)?;
+        writeln!(formatter, "Number of heads: {}", stats.num_heads)?;
+        writeln!(
+            formatter,
+            "Commit index levels: {}",
+            stats.commit_levels.len()
+        )?;
+        let num_changed_path_commits = stats
+            .changed_path_commits_range
+            .as_ref()
+            .map_or(0, |range| range.len());
+        writeln!(
+            formatter,
+            "Commits with changed-path index: {num_changed_path_commits}"
+        )?;
+    } else {
+        writeln!(formatter, "No statistics available for this index type")?;
+    }
+    Ok(())
+}
+
+/// Lists directories under `path` up to `max_depth` levels deep, along with
+/// their total sizes in bytes.
+fn dir_sizes(path: &Path, max_depth: usize) -> io::Result<Vec<(usize, String, u64)>> {
+    let mut entries = vec![];
+    for entry in fs::read_dir(path)?.sorted_by_key(|entry| {
+        entry
+            .as_ref()
+            .map(|entry| entry.file_name())
+            .unwrap_or_default()
+    }) {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let size = dir_size(&entry.path())?;
+        entries.push((1, name, size));
+        if max_depth > 1 {
+            entries.extend(
+                dir_sizes(&entry.path(), max_depth - 1)?
+                    .into_iter()
+                    .map(|(depth, name, size)| (depth + 1, name, size)),
+            );
+        }
+    }
+    Ok(entries)
+}
+
+fn dir_size(path: &Path) -> io::Result<u64> {
+    let mut size = 0;
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            size += dir_size(&entry.path())?;
+        } else if file_type.is_file() {
+            size += entry.metadata()?.len();
+        }
+    }
+    Ok(size)
+}
@@ -0,0 +1,128 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use jj_lib::file_util;
+use jj_lib::file_util::IoResultExt as _;
+use jj_lib::lock;
+use jj_lib::lock::LockOwner;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Remove lock files left behind by crashed processes
+///
+/// Lock files record the host and process that own them. A lock owned by a
+/// process on this host that no longer exists is removed. Locks owned by
+/// processes on other hosts, which can happen if the repo is on a network
+/// filesystem, are only removed with `--force`.
+///
+/// The command doesn't take any locks itself, so it can be run while another
+/// command is stuck waiting for a lock.
+#[derive(clap::Args, Clone, Debug)]
+pub struct UtilUnlockArgs {
+    /// Also remove locks whose owner can't be checked from this host
+    ///
+    /// Make sure the owner process isn't running before using this option.
+    /// Locks owned by running processes on this host are never removed.
+    #[arg(long)]
+    force: bool,
+}
+
+pub fn cmd_util_unlock(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &UtilUnlockArgs,
+) -> Result<(), CommandError> {
+    let loader = command.workspace_loader()?;
+    let repo_path = loader.repo_path();
+    let lock_paths = [
+        repo_path.join("op_heads").join("lock"),
+        repo_path.join("store").join("extra").join("lock"),
+        repo_path.join("workspace_store").join("lock"),
+        loader
+            .workspace_root()
+            .join(".jj")
+            .join("working_copy")
+            .join("working_copy.lock"),
+    ];
+    let mut num_found = 0;
+    for path in &lock_paths {
+        if !path.exists() {
+            continue;
+        }
+        num_found += 1;
+        let display_path = file_util::relative_path(command.cwd(), path);
+        let display_path = display_path.display();
+        let owner = LockOwner::read(path).context(path)?;
+        let remove = match &owner {
+            Some(owner) => {
+                let LockOwner { hostname, pid, .. } = owner;
+                match owner.is_stale() {
+                    Some(true) => {
+                        writeln!(
+                            ui.status(),
+                            "Lock {display_path} is held by process {pid} on {hostname}, which no \
+                             longer exists"
+                        )?;
+                        true
+                    }
+                    Some(false) => {
+                        writeln!(
+                            ui.status(),
+                            "Lock {display_path} is held by running process {pid} on {hostname}"
+                        )?;
+                        false
+                    }
+                    None => {
+                        writeln!(
+                            ui.status(),
+                            "Lock {display_path} is held by process {pid} on {hostname}, which \
+                             can't be checked from this host"
+                        )?;
+                        args.force
+                    }
+                }
+            }
+            None => {
+                writeln!(ui.status(), "Lock {display_path} has no owner information")?;
+                args.force
+            }
+        };
+        if remove {
+            if lock::break_lock(path, owner.as_ref()).context(path)? {
+                writeln!(ui.status(), "Removed lock {display_path}")?;
+            } else {
+                writeln!(
+                    ui.status(),
+                    "Lock {display_path} was taken over by another process"
+                )?;
+            }
+        } else if owner
+            .as_ref()
+            .is_none_or(|owner| owner.is_stale().is_none())
+        {
+            writeln!(
+                ui.hint_default(),
+                "Use `--force` to remove it if the owner process isn't running."
+            )?;
+        }
+    }
+    if num_found == 0 {
+        writeln!(ui.status(), "No locks found")?;
+    }
+    Ok(())
+}
@@ -0,0 +1,121 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::signing::SigStatus;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::complete;
+use crate::formatter::FormatterExt as _;
+use crate::ui::Ui;
+
+/// Verify the cryptographic signatures of revisions
+///
+/// Each revision must have a good signature, as checked by the configured
+/// [commit signing] backends. If `signing.trusted-keys` is set, the signature
+/// must also be made by one of the listed keys. Exits with an error if any
+/// revision doesn't pass, which makes it suitable for enforcing signed history
+/// in CI.
+///
+/// [commit signing]:
+///     https://jj-vcs.github.io/jj/latest/config/#commit-signing
+#[derive(clap::Args, Clone, Debug)]
+pub struct VerifyArgs {
+    /// The revisions to verify
+    #[arg(
+        long, short,
+        default_value = "@",
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revisions: Vec<RevisionArg>,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_verify(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &VerifyArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let trusted_keys: Vec<String> = workspace_command.settings().get("signing.trusted-keys")?;
+    let commits: Vec<Commit> = workspace_command
+        .parse_union_revsets(ui, &args.revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+
+    let commit_template = workspace_command.commit_summary_template();
+    let mut num_failed = 0;
+    let mut formatter = ui.stdout_formatter();
+    for commit in &commits {
+        // The label is one of the "signature status" labels also used by the
+        // signature template type.
+        let (label, status, key) = match commit.verification() {
+            Ok(None) => ("bad", "Unsigned", None),
+            Ok(Some(verification)) => match verification.status {
+                SigStatus::Good
+                    if trusted_keys.is_empty()
+                        || verification
+                            .key
+                            .as_ref()
+                            .is_some_and(|key| trusted_keys.contains(key)) =>
+                {
+                    ("good", "Good", verification.key)
+                }
+                SigStatus::Good => ("unknown", "Untrusted", verification.key),
+                SigStatus::Unknown => ("unknown", "Unknown", verification.key),
+                SigStatus::Bad => ("bad", "Bad", verification.key),
+            },
+            Err(err) => {
+                tracing::info!(?err, "failed to verify signature");
+                ("invalid", "Invalid", None)
+            }
+        };
+        if label != "good" {
+            num_failed += 1;
+        }
+        write!(
+            formatter
+                .labeled("signature")
+                .labeled("status")
+                .labeled(label),
+            "{status}"
+        )?;
+        write!(formatter, ": ")?;
+        commit_template.format(commit, formatter.as_mut())?;
+        if let Some(key) = key {
+            write!(formatter, " (key: ")?;
+            write!(formatter.labeled("signature").labeled("key"), "{key}")?;
+            write!(formatter, ")")?;
+        }
+        writeln!(formatter)?;
+    }
+    drop(formatter);
+
+    if num_failed > 0 {
+        return Err(user_error(format!(
+            "{num_failed} of {} revisions failed verification",
+            commits.len()
+        )));
+    }
+    Ok(())
+}
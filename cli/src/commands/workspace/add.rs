@@ -12,14 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::convert::Infallible;
 use std::fs;
 
+use clap_complete::ArgValueCandidates;
+use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
 use jj_lib::commit::CommitIteratorExt as _;
+use jj_lib::config::ConfigGetResultExt as _;
 use jj_lib::file_util;
 use jj_lib::file_util::IoResultExt as _;
 use jj_lib::ref_name::WorkspaceNameBuf;
 use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::rewrite::merge_commit_trees;
 use jj_lib::workspace::Workspace;
 use pollster::FutureExt as _;
@@ -30,10 +35,13 @@ use crate::cli_util::RevisionArg;
 use crate::command_error::CommandError;
 use crate::command_error::internal_error_with_message;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::command_error::user_error_with_message;
+use crate::complete;
 use crate::ui::Ui;
 
 /// How to handle sparse patterns when creating a new workspace.
-#[derive(clap::ValueEnum, Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 enum SparseInheritance {
     /// Copy all sparse patterns from the current workspace.
     Copy,
@@ -41,12 +49,27 @@ enum SparseInheritance {
     Full,
     /// Clear all files from the workspace (it will be empty).
     Empty,
+    /// Use the patterns of the named preset in `sparse.presets`.
+    Preset(String),
+}
+
+impl SparseInheritance {
+    fn parse(s: &str) -> Result<Self, Infallible> {
+        Ok(match s {
+            "copy" => Self::Copy,
+            "full" => Self::Full,
+            "empty" => Self::Empty,
+            name => Self::Preset(name.to_owned()),
+        })
+    }
 }
 
 /// Add a workspace
 ///
 /// By default, the new workspace inherits the sparse patterns of the current
-/// workspace. You can override this with the `--sparse-patterns` option.
+/// workspace. You can override this with the `--sparse-patterns` and
+/// `--include` options, so only the files you need are written to the new
+/// workspace.
 #[derive(clap::Args, Clone, Debug)]
 pub struct WorkspaceAddArgs {
     /// Where to create the new workspace
@@ -70,11 +93,36 @@ pub struct WorkspaceAddArgs {
     /// the new working-copy commit will be created with all these revisions as
     /// parents, i.e. the working-copy commit will exist as if you had run `jj
     /// new r1 r2 r3 ...`.
-    #[arg(long, short, value_name = "REVSETS")]
+    #[arg(
+        long,
+        short,
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
     revision: Vec<RevisionArg>,
-    /// How to handle sparse patterns when creating a new workspace.
-    #[arg(long, value_enum, default_value_t = SparseInheritance::Copy)]
-    sparse_patterns: SparseInheritance,
+    /// How to handle sparse patterns when creating a new workspace
+    ///
+    /// `copy` copies the patterns of the current workspace, `full` includes
+    /// all files, and `empty` includes no files. Any other value is the name
+    /// of a preset defined in the `sparse.presets` config table.
+    ///
+    /// Defaults to `empty` if `--include` is given, and `copy` otherwise.
+    #[arg(
+        long,
+        value_name = "copy|full|empty|PRESET",
+        value_parser = SparseInheritance::parse,
+        add = ArgValueCandidates::new(complete::sparse_presets),
+    )]
+    sparse_patterns: Option<SparseInheritance>,
+    /// Paths to add to the sparse patterns of the new workspace (can be
+    /// repeated)
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = clap::ValueHint::AnyPath,
+        value_parser = |s: &str| RepoPathBuf::from_relative_path(s),
+    )]
+    include: Vec<RepoPathBuf>,
 }
 
 #[instrument(skip_all)]
@@ -84,6 +132,59 @@ pub fn cmd_workspace_add(
     args: &WorkspaceAddArgs,
 ) -> Result<(), CommandError> {
     let old_workspace_command = command.workspace_helper(ui)?;
+    let sparse_inheritance = match &args.sparse_patterns {
+        Some(sparse_inheritance) => sparse_inheritance,
+        None if args.include.is_empty() => &SparseInheritance::Copy,
+        None => &SparseInheritance::Empty,
+    };
+    let sparsity = match sparse_inheritance {
+        SparseInheritance::Full => {
+            if !args.include.is_empty() {
+                return Err(user_error(
+                    "--include cannot be used with --sparse-patterns=full",
+                ));
+            }
+            None
+        }
+        SparseInheritance::Empty => Some(vec![]),
+        SparseInheritance::Copy => {
+            let sparse_patterns = old_workspace_command
+                .working_copy()
+                .sparse_patterns()?
+                .to_vec();
+            Some(sparse_patterns)
+        }
+        SparseInheritance::Preset(name) => {
+            let Some(paths) = command
+                .settings()
+                .get::<Vec<String>>(["sparse", "presets", name])
+                .optional()?
+            else {
+                return Err(user_error_with_hint(
+                    format!("Sparse patterns preset `{name}` doesn't exist"),
+                    "Use `copy`, `full`, `empty`, or a preset defined in `sparse.presets`.",
+                ));
+            };
+            let sparse_patterns: Vec<_> = paths
+                .iter()
+                .map(RepoPathBuf::from_relative_path)
+                .try_collect()
+                .map_err(|err| {
+                    user_error_with_message(
+                        format!("Invalid path in sparse patterns preset `{name}`"),
+                        err,
+                    )
+                })?;
+            Some(sparse_patterns)
+        }
+    };
+    let sparsity = sparsity.map(|mut sparse_patterns| {
+        sparse_patterns.extend(args.include.iter().cloned());
+        sparse_patterns.sort_unstable();
+        sparse_patterns.dedup();
+        sparse_patterns
+    });
+
     let destination_path = command.cwd().join(&args.destination);
     if destination_path.exists() {
         return Err(user_error("Workspace already exists"));
@@ -136,18 +237,6 @@ pub fn cmd_workspace_add(
 
     let mut new_workspace_command = command.for_workable_repo(ui, new_workspace, repo)?;
 
-    let sparsity = match args.sparse_patterns {
-        SparseInheritance::Full => None,
-        SparseInheritance::Empty => Some(vec![]),
-        SparseInheritance::Copy => {
-            let sparse_patterns = old_workspace_command
-                .working_copy()
-                .sparse_patterns()?
-                .to_vec();
-            Some(sparse_patterns)
-        }
-    };
-
     if let Some(sparse_patterns) = sparsity {
         let (mut locked_ws, _wc_commit) = new_workspace_command.start_working_copy_mutation()?;
         locked_ws
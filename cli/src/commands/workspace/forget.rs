@@ -12,27 +12,57 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashSet;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
+use jj_lib::file_util;
+use jj_lib::file_util::IoResultExt as _;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::ref_name::WorkspaceName;
 use jj_lib::ref_name::WorkspaceNameBuf;
+use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::workspace::LockedWorkspace;
+use jj_lib::workspace::Workspace;
+use jj_lib::workspace_store::SimpleWorkspaceStore;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::cli_util::confirm_destructive_command;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
 use crate::complete;
 use crate::ui::Ui;
 
 /// Stop tracking a workspace's working-copy commit in the repo
 ///
-/// The workspace will not be touched on disk. It can be deleted from disk
-/// before or after running this command.
+/// The workspace will not be touched on disk unless `--delete-working-copy`
+/// is given. It can be deleted from disk before or after running this command.
 #[derive(clap::Args, Clone, Debug)]
 pub struct WorkspaceForgetArgs {
     /// Names of the workspaces to forget. By default, forgets only the current
     /// workspace.
     #[arg(add = ArgValueCandidates::new(complete::workspaces))]
     workspaces: Vec<WorkspaceNameBuf>,
+    /// Also delete the workspace directories from disk
+    ///
+    /// This removes the working-copy files and the `.jj` directory of each
+    /// workspace. The command fails without deleting anything if a workspace
+    /// has changes that haven't been snapshotted, or if the workspace contains
+    /// the repository itself. Untracked and ignored files are listed, and
+    /// confirmation is asked for before deleting them if
+    /// `ui.confirm.workspace-delete` is set.
+    #[arg(long)]
+    delete_working_copy: bool,
+    /// Do not ask for confirmation
+    #[arg(long, requires = "delete_working_copy")]
+    yes: bool,
 }
 
 #[instrument(skip_all)]
@@ -60,6 +90,60 @@ pub fn cmd_workspace_forget(
         }
     }
 
+    let mut workspaces_to_delete: Vec<Workspace> = if args.delete_working_copy {
+        wss.iter()
+            .map(|ws| load_deletable_workspace(command, &workspace_command, ws))
+            .try_collect()?
+    } else {
+        vec![]
+    };
+    // Hold the working-copy locks until the directories are deleted so that
+    // other commands can't write to them in the meantime.
+    let mut locked_wss = vec![];
+    let mut untracked_files = vec![];
+    for workspace in &mut workspaces_to_delete {
+        let root = workspace.workspace_root().to_owned();
+        let name = workspace.workspace_name().to_owned();
+        let mut locked_ws = workspace.start_working_copy_mutation()?;
+        check_snapshotted(ui, &workspace_command, &name, &root, &mut locked_ws)?;
+        let tree = workspace_command
+            .repo()
+            .store()
+            .get_root_tree(locked_ws.locked_wc().old_tree_id())?;
+        untracked_files.extend(
+            find_untracked_files(&root, &tree)?
+                .into_iter()
+                .map(|path| root.join(path)),
+        );
+        locked_wss.push((root, locked_ws));
+    }
+    confirm_destructive_command(
+        ui,
+        workspace_command.settings(),
+        "workspace-delete",
+        untracked_files.len(),
+        args.yes,
+        |formatter| {
+            writeln!(
+                formatter,
+                "About to delete {} untracked or ignored files:",
+                untracked_files.len()
+            )?;
+            for path in untracked_files.iter().take(MAX_LISTED_FILES) {
+                let path = file_util::relative_path(command.cwd(), path);
+                writeln!(formatter, "  {}", path.display())?;
+            }
+            if untracked_files.len() > MAX_LISTED_FILES {
+                writeln!(
+                    formatter,
+                    "  ...and {} more",
+                    untracked_files.len() - MAX_LISTED_FILES
+                )?;
+            }
+            Ok(())
+        },
+    )?;
+
     // bundle every workspace forget into a single transaction, so that e.g.
     // undo correctly restores all of them at once.
     let mut tx = workspace_command.start_transaction();
@@ -75,5 +159,126 @@ pub fn cmd_workspace_forget(
     };
 
     tx.finish(ui, description)?;
+
+    if args.delete_working_copy {
+        let workspace_store = SimpleWorkspaceStore::load(workspace_command.repo_path());
+        workspace_store.forget(&wss.iter().map(|ws| ws.as_ref()).collect_vec())?;
+        for (root, locked_ws) in locked_wss {
+            std::fs::remove_dir_all(&root).context(&root)?;
+            drop(locked_ws);
+            writeln!(
+                ui.status(),
+                "Deleted workspace directory {}",
+                root.display()
+            )?;
+        }
+    }
     Ok(())
 }
+
+/// Maximum number of untracked files listed before deleting a workspace.
+const MAX_LISTED_FILES: usize = 10;
+
+/// Loads the workspace `name` if its directory can be deleted without deleting
+/// the repo.
+fn load_deletable_workspace(
+    command: &CommandHelper,
+    workspace_command: &WorkspaceCommandHelper,
+    name: &WorkspaceName,
+) -> Result<Workspace, CommandError> {
+    let root = if name == workspace_command.workspace_name() {
+        workspace_command.workspace_root().to_owned()
+    } else {
+        SimpleWorkspaceStore::load(workspace_command.repo_path())
+            .get_workspace_path(name)?
+            .ok_or_else(|| {
+                user_error(format!(
+                    "Cannot delete workspace {}: its location is unknown",
+                    name.as_symbol()
+                ))
+            })?
+    };
+    let workspace = command.load_workspace_at(&root, workspace_command.settings())?;
+    // The directory may have been reused by another workspace.
+    if workspace.workspace_name() != name {
+        return Err(user_error(format!(
+            "Cannot delete workspace {}: {} belongs to workspace {}",
+            name.as_symbol(),
+            root.display(),
+            workspace.workspace_name().as_symbol()
+        )));
+    }
+    if contains_repo(&root, workspace.repo_path()) {
+        return Err(user_error(format!(
+            "Cannot delete workspace {}: it contains the repository",
+            name.as_symbol()
+        )));
+    }
+    Ok(workspace)
+}
+
+/// Fails if the locked working copy has changes that haven't been snapshotted.
+fn check_snapshotted(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    name: &WorkspaceName,
+    root: &Path,
+    locked_ws: &mut LockedWorkspace,
+) -> Result<(), CommandError> {
+    // Snapshot without committing the result to check for pending changes.
+    let auto_tracking_matcher = workspace_command.auto_tracking_matcher(ui)?;
+    let options =
+        workspace_command.snapshot_options_with_start_tracking_matcher(&auto_tracking_matcher)?;
+    let (new_tree_id, _stats) = locked_ws.locked_wc().snapshot(&options)?;
+    if new_tree_id != *locked_ws.locked_wc().old_tree_id() {
+        return Err(user_error_with_hint(
+            format!(
+                "Cannot delete workspace {}: it has changes that haven't been snapshotted",
+                name.as_symbol()
+            ),
+            format!(
+                "Run `jj status` in {} to snapshot them first.",
+                root.display()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the files in the workspace directory which aren't tracked in
+/// `tree`, such as ignored files. The paths are relative to `root`.
+fn find_untracked_files(root: &Path, tree: &MergedTree) -> Result<Vec<PathBuf>, CommandError> {
+    let tracked_paths: HashSet<RepoPathBuf> = tree.entries().map(|(path, _)| path).collect();
+    let mut untracked_files = vec![];
+    let mut dirs = vec![PathBuf::new()];
+    while let Some(dir) = dirs.pop() {
+        let disk_dir = root.join(&dir);
+        for entry in disk_dir.read_dir().context(&disk_dir)? {
+            let entry = entry.context(&disk_dir)?;
+            let path = dir.join(entry.file_name());
+            if path == Path::new(".jj") {
+                continue;
+            }
+            if entry.file_type().context(entry.path())?.is_dir() {
+                dirs.push(path);
+            } else if !RepoPathBuf::from_relative_path(&path)
+                .is_ok_and(|repo_path| tracked_paths.contains(&repo_path))
+            {
+                untracked_files.push(path);
+            }
+        }
+    }
+    untracked_files.sort();
+    Ok(untracked_files)
+}
+
+fn contains_repo(workspace_root: &Path, repo_path: &Path) -> bool {
+    match (
+        dunce::canonicalize(workspace_root),
+        dunce::canonicalize(repo_path),
+    ) {
+        (Ok(workspace_root), Ok(repo_path)) => repo_path.starts_with(workspace_root),
+        // Be conservative if the paths can't be resolved.
+        _ => true,
+    }
+}
@@ -12,16 +12,30 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::path::Path;
+use std::path::PathBuf;
+
+use jj_lib::backend::MergedTreeId;
+use jj_lib::commit::Commit;
+use jj_lib::ref_name::WorkspaceName;
 use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::workspace_store::SimpleWorkspaceStore;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::command_error::CommandError;
+use crate::command_error::user_error;
 use crate::commit_templater::WorkspaceRef;
 use crate::templater::TemplateRenderer;
+use crate::ui::OutputFormat;
 use crate::ui::Ui;
 
 /// List workspaces
+///
+/// With `--output json`, each workspace is printed as a JSON object including
+/// its root path, working-copy commit, and sparse patterns.
 #[derive(clap::Args, Clone, Debug)]
 pub struct WorkspaceListArgs {
     /// Render each workspace using the given template
@@ -47,6 +61,27 @@ pub fn cmd_workspace_list(
 ) -> Result<(), CommandError> {
     let workspace_command = command.workspace_helper(ui)?;
 
+    if ui.output_format() == OutputFormat::Json {
+        if args.template.is_some() {
+            return Err(user_error(
+                "`--output json` cannot be combined with templates",
+            ));
+        }
+        let repo = workspace_command.repo();
+        let workspace_store = SimpleWorkspaceStore::load(workspace_command.repo_path());
+        for (name, wc_commit_id) in repo.view().wc_commit_ids() {
+            let commit = repo.store().get_commit(wc_commit_id)?;
+            let root = if name == workspace_command.workspace_name() {
+                Some(workspace_command.workspace_root().to_owned())
+            } else {
+                workspace_store.get_workspace_path(name)?
+            };
+            let entry = WorkspaceListJson::new(command, &workspace_command, name, commit, root)?;
+            ui.write_json(&entry)?;
+        }
+        return Ok(());
+    }
+
     let template: TemplateRenderer<WorkspaceRef> = {
         let language = workspace_command.commit_template_language();
 
@@ -74,3 +109,75 @@ pub fn cmd_workspace_list(
 
     Ok(())
 }
+
+/// Output of `jj workspace list --output json`.
+#[derive(Debug, serde::Serialize)]
+struct WorkspaceListJson {
+    name: String,
+    current: bool,
+    root: Option<String>,
+    working_copy: Commit,
+    stale: Option<bool>,
+    sparse_patterns: Option<Vec<String>>,
+}
+
+impl WorkspaceListJson {
+    fn new(
+        command: &CommandHelper,
+        workspace_command: &WorkspaceCommandHelper,
+        name: &WorkspaceName,
+        commit: Commit,
+        root: Option<PathBuf>,
+    ) -> Result<Self, CommandError> {
+        let current = name == workspace_command.workspace_name();
+        // The working-copy state is only available if the workspace directory
+        // still exists.
+        let state = if current {
+            let wc = workspace_command.working_copy();
+            Some((wc.tree_id()?.clone(), wc.sparse_patterns()?.to_vec()))
+        } else {
+            root.as_deref()
+                .and_then(|root| load_working_copy_state(command, workspace_command, name, root))
+        };
+        let (stale, sparse_patterns) = match state {
+            Some((tree_id, patterns)) => (
+                Some(tree_id != *commit.tree_id()),
+                Some(
+                    patterns
+                        .iter()
+                        .map(|path| path.as_internal_file_string().to_owned())
+                        .collect(),
+                ),
+            ),
+            None => (None, None),
+        };
+        Ok(Self {
+            name: name.as_str().to_owned(),
+            current,
+            root: root.map(|root| root.to_string_lossy().into_owned()),
+            working_copy: commit,
+            stale,
+            sparse_patterns,
+        })
+    }
+}
+
+fn load_working_copy_state(
+    command: &CommandHelper,
+    workspace_command: &WorkspaceCommandHelper,
+    name: &WorkspaceName,
+    root: &Path,
+) -> Option<(MergedTreeId, Vec<RepoPathBuf>)> {
+    let workspace = command
+        .load_workspace_at(root, workspace_command.settings())
+        .ok()?;
+    // The directory may have been reused by another workspace.
+    if workspace.workspace_name() != name {
+        return None;
+    }
+    let wc = workspace.working_copy();
+    Some((
+        wc.tree_id().ok()?.clone(),
+        wc.sparse_patterns().ok()?.to_vec(),
+    ))
+}
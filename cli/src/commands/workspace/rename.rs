@@ -13,11 +13,13 @@
 // limitations under the License.
 
 use jj_lib::ref_name::WorkspaceNameBuf;
+use jj_lib::workspace_store::SimpleWorkspaceStore;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
+use crate::operation_notify::notify_operation_completed;
 use crate::ui::Ui;
 
 /// Renames the current workspace
@@ -71,6 +73,21 @@ pub fn cmd_workspace_rename(
         new = new_name.as_symbol()
     ))?;
     locked_ws.finish(repo.op_id().clone())?;
+    notify_operation_completed(
+        workspace_command.settings(),
+        workspace_command.repo_path(),
+        workspace_command.repo(),
+        &repo,
+    );
+    let workspace_store = SimpleWorkspaceStore::load(workspace_command.repo_path());
+    workspace_store.forget(&[&old_name])?;
+    workspace_store.add(new_name, workspace_command.workspace_root())?;
+    writeln!(
+        ui.status(),
+        "Renamed workspace {old} to {new}",
+        old = old_name.as_symbol(),
+        new = new_name.as_symbol()
+    )?;
 
     Ok(())
 }
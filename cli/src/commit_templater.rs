@@ -113,6 +113,7 @@ use crate::templater::Template;
 use crate::templater::TemplateFormatter;
 use crate::templater::TemplatePropertyError;
 use crate::templater::TemplatePropertyExt as _;
+use crate::test_results::TestResultStore;
 
 pub trait CommitTemplateLanguageExtension {
     fn build_fn_table<'repo>(&self) -> CommitTemplateBuildFnTable<'repo>;
@@ -120,6 +121,76 @@ pub trait CommitTemplateLanguageExtension {
     fn build_cache_extensions(&self, extensions: &mut ExtensionsMap);
 }
 
+/// Function computing the value of a custom commit keyword.
+pub type CommitKeywordFn = dyn Fn(&Commit) -> Result<String, TemplatePropertyError> + Send + Sync;
+
+/// Extension adding string keywords to the commit type, such as metadata
+/// fetched from a code review service.
+///
+/// The keywords can be used as methods of commit objects, e.g.
+/// `commit.review_url()`, or as `review_url` in `jj log -T`.
+#[derive(Clone, Default)]
+pub struct CommitKeywordsExtension {
+    keywords: HashMap<&'static str, Arc<CommitKeywordFn>>,
+}
+
+impl CommitKeywordsExtension {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keywords.is_empty()
+    }
+
+    /// Registers keyword of the `name` which is evaluated by `func`.
+    ///
+    /// Panics if a keyword of the same name has already been registered.
+    pub fn add_keyword(
+        &mut self,
+        name: &'static str,
+        func: impl Fn(&Commit) -> Result<String, TemplatePropertyError> + Send + Sync + 'static,
+    ) {
+        let old = self.keywords.insert(name, Arc::new(func));
+        assert!(
+            old.is_none(),
+            "Conflict registering commit keyword '{name}'"
+        );
+    }
+}
+
+impl CommitTemplateLanguageExtension for CommitKeywordsExtension {
+    fn build_fn_table<'repo>(&self) -> CommitTemplateBuildFnTable<'repo> {
+        let mut table = CommitTemplateBuildFnTable::empty();
+        for &name in self.keywords.keys() {
+            table
+                .commit_methods
+                .insert(name, build_commit_keyword_method);
+        }
+        table
+    }
+
+    fn build_cache_extensions(&self, extensions: &mut ExtensionsMap) {
+        extensions.insert(self.clone());
+    }
+}
+
+fn build_commit_keyword_method<'repo>(
+    language: &CommitTemplateLanguage<'repo>,
+    _diagnostics: &mut TemplateDiagnostics,
+    _build_ctx: &BuildContext<CommitTemplatePropertyKind<'repo>>,
+    self_property: BoxedTemplateProperty<'repo, Commit>,
+    function: &FunctionCallNode,
+) -> TemplateParseResult<CommitTemplatePropertyKind<'repo>> {
+    function.expect_no_arguments()?;
+    let extension = language
+        .cache_extension::<CommitKeywordsExtension>()
+        .unwrap();
+    let func = extension.keywords[function.name].clone();
+    let out_property = self_property.and_then(move |commit| func(&commit));
+    Ok(out_property.into_dyn_wrapped())
+}
+
 /// Template environment for `jj log` and `jj evolog`.
 pub struct CommitTemplateLanguage<'repo> {
     repo: &'repo dyn Repo,
@@ -134,6 +205,7 @@ pub struct CommitTemplateLanguage<'repo> {
     id_prefix_context: &'repo IdPrefixContext,
     immutable_expression: Arc<UserRevsetExpression>,
     conflict_marker_style: ConflictMarkerStyle,
+    test_results: Option<&'repo TestResultStore>,
     build_fn_table: CommitTemplateBuildFnTable<'repo>,
     keyword_cache: CommitKeywordCache<'repo>,
     cache_extensions: ExtensionsMap,
@@ -151,6 +223,7 @@ impl<'repo> CommitTemplateLanguage<'repo> {
         id_prefix_context: &'repo IdPrefixContext,
         immutable_expression: Arc<UserRevsetExpression>,
         conflict_marker_style: ConflictMarkerStyle,
+        test_results: Option<&'repo TestResultStore>,
         extensions: &[impl AsRef<dyn CommitTemplateLanguageExtension>],
     ) -> Self {
         let mut build_fn_table = CommitTemplateBuildFnTable::builtin();
@@ -171,6 +244,7 @@ impl<'repo> CommitTemplateLanguage<'repo> {
             id_prefix_context,
             immutable_expression,
             conflict_marker_style,
+            test_results,
             build_fn_table,
             keyword_cache: CommitKeywordCache::default(),
             cache_extensions,
@@ -1187,6 +1261,21 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "test_result",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let test_results = language.test_results;
+            let out_property = self_property.and_then(move |commit| {
+                let Some(test_results) = test_results else {
+                    return Ok(String::new());
+                };
+                let result = test_results.get(commit.tree_id())?;
+                Ok(result.map_or("", |result| result.as_str()).to_owned())
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "conflict",
         |_language, _diagnostics, _build_ctx, self_property, function| {
@@ -2246,8 +2335,11 @@ fn builtin_tree_diff_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, T
                 })
                 .transpose()?;
             let path_converter = language.path_converter;
-            // No user configuration exists for diff stat.
-            let options = diff_util::DiffStatOptions::default();
+            let options =
+                diff_util::DiffStatOptions::from_settings(language.settings()).map_err(|err| {
+                    let message = "Failed to load diff settings";
+                    TemplateParseError::expression(message, function.name_span).with_source(err)
+                })?;
             let conflict_marker_style = language.conflict_marker_style;
             // TODO: cache and reuse stats within the current evaluation?
             let out_property = (self_property, width_property).and_then(move |(diff, width)| {
@@ -2685,6 +2777,7 @@ mod tests {
         template_aliases_map: TemplateAliasesMap,
         immutable_expression: Arc<UserRevsetExpression>,
         extra_functions: HashMap<&'static str, BuildFunctionFn>,
+        extensions: Vec<Box<dyn CommitTemplateLanguageExtension>>,
     }
 
     impl CommitTemplateTestEnv {
@@ -2708,6 +2801,7 @@ mod tests {
                 template_aliases_map: TemplateAliasesMap::new(),
                 immutable_expression: RevsetExpression::none(),
                 extra_functions: HashMap::new(),
+                extensions: vec![],
             }
         }
 
@@ -2742,7 +2836,8 @@ mod tests {
                 &self.id_prefix_context,
                 self.immutable_expression.clone(),
                 ConflictMarkerStyle::Diff,
-                &[] as &[Box<dyn CommitTemplateLanguageExtension>],
+                None,
+                &self.extensions,
             );
             // Not using .extend() to infer lifetime of f
             for (&name, &f) in &self.extra_functions {
@@ -2942,4 +3037,27 @@ mod tests {
         insta::assert_snapshot!(
             env.render_ok("json(self)", &id), @r#"{"prefix":"012","rest":"3abcdef"}"#);
     }
+
+    #[test]
+    fn test_commit_keywords_extension() {
+        let mut env = CommitTemplateTestEnv::init();
+        let mut keywords = CommitKeywordsExtension::new();
+        keywords.add_keyword("review_url", |commit| {
+            Ok(format!("https://review.example.com/{}", commit.id().hex()))
+        });
+        keywords.add_keyword("presubmit_status", |_commit| {
+            Err(TemplatePropertyError("service unavailable".into()))
+        });
+        env.extensions.push(Box::new(keywords));
+
+        let commit = env.test_workspace.repo.store().root_commit();
+        insta::assert_snapshot!(
+            env.render_ok("review_url", &commit),
+            @"https://review.example.com/0000000000000000000000000000000000000000");
+        insta::assert_snapshot!(
+            env.render_ok("self.review_url().len()", &commit), @"67");
+        insta::assert_snapshot!(
+            env.render_ok("presubmit_status", &commit), @"<Error: service unavailable>");
+        assert!(env.parse::<Commit>("review_url(1)").is_err());
+    }
 }
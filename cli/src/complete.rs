@@ -171,6 +171,28 @@ pub fn bookmarks() -> Vec<CompletionCandidate> {
     })
 }
 
+pub fn tags() -> Vec<CompletionCandidate> {
+    with_jj(|jj, _| {
+        let output = jj
+            .build()
+            .arg("tag")
+            .arg("list")
+            .arg("--config")
+            .arg(BOOKMARK_HELP_TEMPLATE)
+            .arg("--template")
+            .arg(r#"name ++ bookmark_help() ++ "\n""#)
+            .output()
+            .map_err(user_error)?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        Ok(stdout
+            .lines()
+            .map(split_help_text)
+            .map(|(name, help)| CompletionCandidate::new(name).help(help))
+            .collect())
+    })
+}
+
 pub fn git_remotes() -> Vec<CompletionCandidate> {
     with_jj(|jj, _| {
         let output = jj
@@ -224,8 +246,9 @@ fn revisions(match_prefix: &str, revset_filter: Option<&str>) -> Vec<CompletionC
         const LOCAL_BOOKMARK: usize = 0;
         const TAG: usize = 1;
         const CHANGE_ID: usize = 2;
-        const REMOTE_BOOKMARK: usize = 3;
-        const REVSET_ALIAS: usize = 4;
+        const WORKSPACE: usize = 3;
+        const REMOTE_BOOKMARK: usize = 4;
+        const REVSET_ALIAS: usize = 5;
 
         let mut candidates = Vec::new();
 
@@ -326,6 +349,36 @@ fn revisions(match_prefix: &str, revset_filter: Option<&str>) -> Vec<CompletionC
                 }),
         );
 
+        // working-copy commits of workspaces
+
+        let revisions = match revset_filter {
+            Some(revs) => format!("working_copies() & ({revs})"),
+            None => "working_copies()".to_owned(),
+        };
+        let output = jj
+            .build()
+            .arg("log")
+            .arg("--no-graph")
+            .arg("--revisions")
+            .arg(revisions)
+            .arg("--template")
+            .arg(r#"working_copies.map(|w| w.name() ++ "@ " ++ if(description, description.first_line(), "(no description set)") ++ "\n").join("")"#)
+            .output()
+            .map_err(user_error)?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        candidates.extend(
+            stdout
+                .lines()
+                .map(split_help_text)
+                .filter(|(symbol, _)| symbol.starts_with(match_prefix))
+                .map(|(symbol, desc)| {
+                    CompletionCandidate::new(symbol)
+                        .help(desc)
+                        .display_order(Some(WORKSPACE))
+                }),
+        );
+
         // revset aliases
 
         let revset_aliases = load_revset_aliases(&Ui::null(), settings.config())?;
@@ -471,6 +524,20 @@ fn merge_tools_filtered_by(
     })
 }
 
+pub fn sparse_presets() -> Vec<CompletionCandidate> {
+    with_jj(|_, settings| {
+        Ok(["copy", "full", "empty"]
+            .into_iter()
+            .map(CompletionCandidate::new)
+            .chain(
+                settings
+                    .table_keys("sparse.presets")
+                    .map(CompletionCandidate::new),
+            )
+            .collect())
+    })
+}
+
 pub fn merge_editors() -> Vec<CompletionCandidate> {
     with_jj(|_, settings| {
         Ok([":builtin", ":ours", ":theirs"]
@@ -940,7 +1007,9 @@ fn get_jj_command() -> Result<(JjBuilder, UserSettings), CommandError> {
     let _ = config_env.reload_user_config(&mut raw_config);
     if let Ok(loader) = &maybe_cwd_workspace_loader {
         config_env.reset_repo_path(loader.repo_path());
+        config_env.reset_workspace_path(loader.workspace_root());
         let _ = config_env.reload_repo_config(&mut raw_config);
+        let _ = config_env.reload_workspace_config(&mut raw_config);
     }
     let mut config = config_env.resolve_config(&raw_config)?;
     // skip 2 because of the clap_complete prelude: jj -- jj <actual args...>
@@ -958,7 +1027,9 @@ fn get_jj_command() -> Result<(JjBuilder, UserSettings), CommandError> {
         // Try to update repo-specific config on a best-effort basis.
         if let Ok(loader) = DefaultWorkspaceLoaderFactory.create(&cwd.join(&repository)) {
             config_env.reset_repo_path(loader.repo_path());
+            config_env.reset_workspace_path(loader.workspace_root());
             let _ = config_env.reload_repo_config(&mut raw_config);
+            let _ = config_env.reload_workspace_config(&mut raw_config);
             if let Ok(new_config) = config_env.resolve_config(&raw_config) {
                 config = new_config;
             }
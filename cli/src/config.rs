@@ -18,9 +18,12 @@ use std::collections::HashMap;
 use std::env;
 use std::env::split_paths;
 use std::fmt;
+use std::iter;
+use std::ops::Range;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::Command;
+use std::sync::Arc;
 use std::sync::LazyLock;
 
 use etcetera::BaseStrategy as _;
@@ -194,6 +197,247 @@ pub fn resolved_config_values(
     config_vals
 }
 
+/// Problem found in config variables by [`validate_config_layer()`].
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ConfigValidationError {
+    /// The name isn't defined in the schema.
+    #[error("Unknown config key {name}")]
+    UnknownName {
+        name: ConfigNamePathBuf,
+        similar_names: Vec<String>,
+    },
+    /// The value doesn't match the type or constraints defined in the schema.
+    #[error("Invalid type or value for {name}: expected {expected}")]
+    InvalidValue {
+        name: ConfigNamePathBuf,
+        expected: String,
+    },
+}
+
+impl ConfigValidationError {
+    /// Name of the config variable or table.
+    pub fn name(&self) -> &ConfigNamePathBuf {
+        match self {
+            Self::UnknownName { name, .. } | Self::InvalidValue { name, .. } => name,
+        }
+    }
+}
+
+/// Checks variables in the `layer` against the [`CONFIG_SCHEMA`].
+pub fn validate_config_layer(layer: &ConfigLayer) -> Vec<ConfigValidationError> {
+    let schema: serde_json::Value = serde_json::from_str(CONFIG_SCHEMA).unwrap();
+    let table = layer.data.as_table().clone().into_inline_table();
+    let value = serde_json::to_value(to_serializable_value(table.into()))
+        .expect("config value should be serializable");
+    let mut errors = vec![];
+    validate_schema_value(
+        &schema,
+        &schema,
+        &ConfigNamePathBuf::root(),
+        &value,
+        &mut errors,
+    );
+    errors
+}
+
+fn resolve_schema_ref<'a>(
+    root: &'a serde_json::Value,
+    mut schema: &'a serde_json::Value,
+) -> &'a serde_json::Value {
+    while let Some(reference) = schema.get("$ref").and_then(|r| r.as_str()) {
+        let pointer = reference
+            .strip_prefix('#')
+            .expect("reference should be local");
+        schema = root.pointer(pointer).expect("reference should be valid");
+    }
+    schema
+}
+
+fn schema_alternatives(schema: &serde_json::Value) -> Option<&Vec<serde_json::Value>> {
+    schema
+        .get("oneOf")
+        .or_else(|| schema.get("anyOf"))
+        .and_then(|alternatives| alternatives.as_array())
+}
+
+fn validate_schema_value(
+    root: &serde_json::Value,
+    schema: &serde_json::Value,
+    name: &ConfigNamePathBuf,
+    value: &serde_json::Value,
+    errors: &mut Vec<ConfigValidationError>,
+) {
+    let schema = resolve_schema_ref(root, schema);
+    if let Some(alternatives) = schema_alternatives(schema) {
+        // Report unknown names of the first alternative matching the value
+        for alternative in alternatives {
+            let mut alternative_errors = vec![];
+            validate_schema_value(root, alternative, name, value, &mut alternative_errors);
+            if !alternative_errors
+                .iter()
+                .any(|err| matches!(err, ConfigValidationError::InvalidValue { .. }))
+            {
+                errors.extend(alternative_errors);
+                return;
+            }
+        }
+        errors.push(ConfigValidationError::InvalidValue {
+            name: name.clone(),
+            expected: describe_schema(root, schema),
+        });
+        return;
+    }
+    if !matches_schema_constraints(schema, value) {
+        errors.push(ConfigValidationError::InvalidValue {
+            name: name.clone(),
+            expected: describe_schema(root, schema),
+        });
+        return;
+    }
+    match value {
+        serde_json::Value::Object(table) => {
+            let properties = schema.get("properties").and_then(|p| p.as_object());
+            let additional_properties = schema.get("additionalProperties");
+            for (key, sub_value) in table {
+                let mut sub_name = name.clone();
+                sub_name.push(key.as_str());
+                if let Some(sub_schema) = properties.and_then(|p| p.get(key)) {
+                    validate_schema_value(root, sub_schema, &sub_name, sub_value, errors);
+                    continue;
+                }
+                match additional_properties {
+                    Some(serde_json::Value::Bool(true)) => {}
+                    None if properties.is_none() => {}
+                    Some(serde_json::Value::Bool(false)) | None => {
+                        let known_keys = properties.into_iter().flat_map(|p| p.keys());
+                        let similar_names = dsl_util::collect_similar(key, known_keys)
+                            .into_iter()
+                            .map(|similar_key| {
+                                let mut similar_name = name.clone();
+                                similar_name.push(similar_key);
+                                similar_name.to_string()
+                            })
+                            .collect();
+                        errors.push(ConfigValidationError::UnknownName {
+                            name: sub_name,
+                            similar_names,
+                        });
+                    }
+                    Some(sub_schema) => {
+                        validate_schema_value(root, sub_schema, &sub_name, sub_value, errors);
+                    }
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for item in items {
+                    validate_schema_value(root, item_schema, name, item, errors);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn matches_schema_constraints(schema: &serde_json::Value, value: &serde_json::Value) -> bool {
+    let matches_type = |ty: &serde_json::Value| match ty.as_str() {
+        Some("string") => value.is_string(),
+        Some("integer") => value.is_i64() || value.is_u64(),
+        Some("number") => value.is_number(),
+        Some("boolean") => value.is_boolean(),
+        Some("array") => value.is_array(),
+        Some("object") => value.is_object(),
+        _ => false,
+    };
+    let type_ok = match schema.get("type") {
+        Some(serde_json::Value::Array(types)) => types.iter().any(matches_type),
+        Some(ty) => matches_type(ty),
+        None => true,
+    };
+    let enum_ok = schema
+        .get("enum")
+        .and_then(|values| values.as_array())
+        .is_none_or(|values| values.contains(value));
+    let minimum_ok = schema
+        .get("minimum")
+        .and_then(|min| min.as_f64())
+        .is_none_or(|min| value.as_f64().is_none_or(|n| n >= min));
+    let pattern_ok = schema
+        .get("pattern")
+        .and_then(|pattern| pattern.as_str())
+        .is_none_or(|pattern| {
+            let regex = Regex::new(pattern).expect("schema pattern should be valid");
+            value.as_str().is_none_or(|s| regex.is_match(s))
+        });
+    type_ok && enum_ok && minimum_ok && pattern_ok
+}
+
+fn describe_schema(root: &serde_json::Value, schema: &serde_json::Value) -> String {
+    let schema = resolve_schema_ref(root, schema);
+    if let Some(alternatives) = schema_alternatives(schema) {
+        return alternatives
+            .iter()
+            .map(|alternative| describe_schema(root, alternative))
+            .dedup()
+            .join(" or ");
+    }
+    if let Some(values) = schema.get("enum").and_then(|values| values.as_array()) {
+        return format!("one of {}", values.iter().join(", "));
+    }
+    let describe_type = |ty: &serde_json::Value| match ty.as_str() {
+        Some("object") => "table".to_owned(),
+        Some("array") => match schema.get("items") {
+            Some(items) => format!("array of {}", describe_schema(root, items)),
+            None => "array".to_owned(),
+        },
+        Some("string") => match schema.get("pattern") {
+            Some(pattern) => format!("string matching {pattern}"),
+            None => "string".to_owned(),
+        },
+        Some(ty) => ty.to_owned(),
+        None => "value".to_owned(),
+    };
+    match schema.get("type") {
+        Some(serde_json::Value::Array(types)) => types.iter().map(describe_type).join(" or "),
+        Some(ty) => describe_type(ty),
+        None => "value".to_owned(),
+    }
+}
+
+/// Looks up the line number (starting from 1) where the config variable or
+/// table of the given `name` is defined in the TOML `text`.
+///
+/// Tables in arrays are searched in order, so the line of the first matching
+/// table is returned.
+pub fn find_config_line(text: &str, name: &ConfigNamePathBuf) -> Option<usize> {
+    let doc = toml_edit::Document::parse(text).ok()?;
+    let keys = name.components().cloned().collect_vec();
+    let span = find_key_span(doc.as_table(), &keys)?;
+    Some(text[..span.start].matches('\n').count() + 1)
+}
+
+fn find_key_span(
+    table: &dyn toml_edit::TableLike,
+    keys: &[toml_edit::Key],
+) -> Option<Range<usize>> {
+    let (first, rest) = keys.split_first()?;
+    let (key, item) = table.get_key_value(first.get())?;
+    if rest.is_empty() {
+        return key.span();
+    }
+    match item {
+        toml_edit::Item::ArrayOfTables(tables) => {
+            tables.iter().find_map(|table| find_key_span(table, rest))
+        }
+        toml_edit::Item::Value(ConfigValue::Array(array)) => array
+            .iter()
+            .filter_map(|value| value.as_inline_table())
+            .find_map(|table| find_key_span(table, rest)),
+        _ => find_key_span(item.as_table_like()?, rest),
+    }
+}
+
 /// Newtype for unprocessed (or unresolved) [`StackedConfig`].
 ///
 /// This doesn't provide any strict guarantee about the underlying config
@@ -373,8 +617,10 @@ impl UnresolvedConfigEnv {
 pub struct ConfigEnv {
     home_dir: Option<PathBuf>,
     repo_path: Option<PathBuf>,
+    workspace_path: Option<PathBuf>,
     user_config_paths: Vec<ConfigPath>,
     repo_config_path: Option<ConfigPath>,
+    workspace_config_path: Option<ConfigPath>,
     command: Option<String>,
 }
 
@@ -418,8 +664,10 @@ impl ConfigEnv {
         Self {
             home_dir,
             repo_path: None,
+            workspace_path: None,
             user_config_paths: env.resolve(ui),
             repo_config_path: None,
+            workspace_config_path: None,
             command: None,
         }
     }
@@ -493,6 +741,14 @@ impl ConfigEnv {
         self.repo_config_path = Some(ConfigPath::new(path.join("config.toml")));
     }
 
+    /// Sets the workspace root directory, which is used to resolve
+    /// `--when.workspaces` conditions. The workspace-specific config file is
+    /// stored in `.jj` under this directory.
+    pub fn reset_workspace_path(&mut self, path: &Path) {
+        self.workspace_path = Some(path.to_owned());
+        self.workspace_config_path = Some(ConfigPath::new(path.join(".jj").join("config.toml")));
+    }
+
     /// Returns a path to the repo-specific config file.
     pub fn repo_config_path(&self) -> Option<&Path> {
         self.repo_config_path.as_ref().map(|p| p.as_path())
@@ -538,16 +794,244 @@ impl ConfigEnv {
         Ok(())
     }
 
-    /// Resolves conditional scopes within the current environment. Returns new
-    /// resolved config.
+    /// Returns a path to the workspace-specific config file.
+    pub fn workspace_config_path(&self) -> Option<&Path> {
+        self.workspace_config_path.as_ref().map(|p| p.as_path())
+    }
+
+    /// Returns a path to the existing workspace-specific config file.
+    fn existing_workspace_config_path(&self) -> Option<&Path> {
+        match self.workspace_config_path {
+            Some(ref path) if path.exists() => Some(path.as_path()),
+            _ => None,
+        }
+    }
+
+    /// Returns workspace configuration files for modification. Instantiates
+    /// one if `config` has no workspace configuration layers.
+    ///
+    /// If the workspace path is unknown, this function returns an empty `Vec`.
+    pub fn workspace_config_files(
+        &self,
+        config: &RawConfig,
+    ) -> Result<Vec<ConfigFile>, ConfigLoadError> {
+        config_files_for(config, ConfigSource::Workspace, || {
+            self.new_workspace_config_file()
+        })
+    }
+
+    fn new_workspace_config_file(&self) -> Result<Option<ConfigFile>, ConfigLoadError> {
+        self.workspace_config_path()
+            .map(|path| ConfigFile::load_or_empty(ConfigSource::Workspace, path))
+            .transpose()
+    }
+
+    /// Loads workspace-specific config file into the given `config`. The old
+    /// workspace-config layer will be replaced if any.
+    #[instrument]
+    pub fn reload_workspace_config(&self, config: &mut RawConfig) -> Result<(), ConfigLoadError> {
+        config.as_mut().remove_layers(ConfigSource::Workspace);
+        if let Some(path) = self.existing_workspace_config_path() {
+            config.as_mut().load_file(ConfigSource::Workspace, path)?;
+        }
+        Ok(())
+    }
+
+    /// Resolves conditional scopes within the current environment, and
+    /// expands environment variables and `~` in path and command values.
+    /// Returns new resolved config.
     pub fn resolve_config(&self, config: &RawConfig) -> Result<StackedConfig, ConfigGetError> {
         let context = ConfigResolutionContext {
             home_dir: self.home_dir.as_deref(),
             repo_path: self.repo_path.as_deref(),
+            workspace_path: self.workspace_path.as_deref(),
             command: self.command.as_deref(),
         };
-        jj_lib::config::resolve(config.as_ref(), &context)
+        let mut config = jj_lib::config::resolve(config.as_ref(), &context)?;
+        expand_config_values(&mut config, self.home_dir.as_deref(), |name| {
+            env::var(name).ok()
+        });
+        Ok(config)
+    }
+}
+
+/// Config keys whose values are paths or commands, in which environment
+/// variables and `~` are expanded. `*` matches any key. Values nested in
+/// arrays and tables under these keys are expanded as well.
+///
+/// Remote URLs aren't listed since they are stored in the Git config, not in
+/// the jj config.
+const EXPANDED_CONFIG_KEYS: &[&str] = &[
+    "auth.helper",
+    "fix.tools.*.command",
+    "git.executable-path",
+    "hooks",
+    "merge-tools.*.program",
+    "notify.operation-socket",
+    "plugins",
+    "signing.backends.*.program",
+    "signing.backends.ssh.allowed-signers",
+    "signing.backends.ssh.default-key-command",
+    "signing.key",
+    "test.command",
+    "ui.diff-editor",
+    "ui.diff-formatter",
+    "ui.editor",
+    "ui.merge-editor",
+    "ui.pager",
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ExpandScope {
+    /// The value is under one of the expanded keys.
+    Expand,
+    /// The value is a table which may contain expanded keys.
+    Descend,
+    /// The value is not expanded.
+    Skip,
+}
+
+fn expand_scope(name: &ConfigNamePathBuf) -> ExpandScope {
+    let mut scope = ExpandScope::Skip;
+    for pattern in EXPANDED_CONFIG_KEYS {
+        let pattern = pattern.split('.').collect_vec();
+        let matches = iter::zip(&pattern, name.components())
+            .all(|(expected, key)| *expected == "*" || *expected == key.get());
+        if !matches {
+            continue;
+        }
+        if name.components().len() >= pattern.len() {
+            return ExpandScope::Expand;
+        }
+        scope = ExpandScope::Descend;
+    }
+    scope
+}
+
+/// Expands `${NAME}` environment variables and a leading `~` in string values
+/// of the non-default config layers, if the values are paths or commands.
+///
+/// `$${` is unescaped to `${` without expanding the variable. Variables which
+/// aren't set are left unexpanded.
+fn expand_config_values(
+    config: &mut StackedConfig,
+    home_dir: Option<&Path>,
+    get_env: impl Fn(&str) -> Option<String>,
+) {
+    for layer in config.layers_mut() {
+        if layer.source == ConfigSource::Default {
+            continue;
+        }
+        let layer = Arc::make_mut(layer);
+        expand_table_values(
+            layer.data.as_table_mut(),
+            &ConfigNamePathBuf::root(),
+            home_dir,
+            &get_env,
+        );
+    }
+}
+
+fn expand_table_values(
+    table: &mut dyn toml_edit::TableLike,
+    table_name: &ConfigNamePathBuf,
+    home_dir: Option<&Path>,
+    get_env: &impl Fn(&str) -> Option<String>,
+) {
+    for (key, item) in table.iter_mut() {
+        let mut name = table_name.clone();
+        name.push(key.get());
+        match (expand_scope(&name), item) {
+            (ExpandScope::Skip, _) | (_, toml_edit::Item::None) => {}
+            (ExpandScope::Expand, toml_edit::Item::Value(value)) => {
+                expand_value(value, home_dir, get_env);
+            }
+            (ExpandScope::Descend, toml_edit::Item::Value(ConfigValue::InlineTable(table))) => {
+                expand_table_values(table, &name, home_dir, get_env);
+            }
+            (ExpandScope::Descend, toml_edit::Item::Value(_)) => {}
+            (_, toml_edit::Item::Table(table)) => {
+                expand_table_values(table, &name, home_dir, get_env);
+            }
+            (_, toml_edit::Item::ArrayOfTables(tables)) => {
+                for table in tables.iter_mut() {
+                    expand_table_values(table, &name, home_dir, get_env);
+                }
+            }
+        }
+    }
+}
+
+fn expand_value(
+    value: &mut ConfigValue,
+    home_dir: Option<&Path>,
+    get_env: &impl Fn(&str) -> Option<String>,
+) {
+    match value {
+        ConfigValue::String(formatted) => {
+            if let Some(expanded) = expand_string(formatted.value(), home_dir, get_env) {
+                let decor = formatted.decor().clone();
+                *formatted = toml_edit::Formatted::new(expanded);
+                *formatted.decor_mut() = decor;
+            }
+        }
+        ConfigValue::Array(array) => {
+            for value in array.iter_mut() {
+                expand_value(value, home_dir, get_env);
+            }
+        }
+        ConfigValue::InlineTable(table) => {
+            for (_, value) in table.iter_mut() {
+                expand_value(value, home_dir, get_env);
+            }
+        }
+        ConfigValue::Integer(_)
+        | ConfigValue::Float(_)
+        | ConfigValue::Boolean(_)
+        | ConfigValue::Datetime(_) => {}
+    }
+}
+
+/// Returns the expanded `text`, or `None` if there's nothing to expand.
+fn expand_string(
+    text: &str,
+    home_dir: Option<&Path>,
+    get_env: &impl Fn(&str) -> Option<String>,
+) -> Option<String> {
+    if !text.contains("${") && text != "~" && !text.starts_with("~/") {
+        return None;
+    }
+    let mut expanded = String::new();
+    let mut rest = text;
+    if let Some(tail) = rest.strip_prefix('~')
+        && (tail.is_empty() || tail.starts_with('/'))
+        && let Some(home_dir) = home_dir.and_then(|dir| dir.to_str())
+    {
+        expanded.push_str(home_dir);
+        rest = tail;
+    }
+    while let Some(pos) = rest.find("${") {
+        if let Some(literal) = rest[..pos].strip_suffix('$') {
+            expanded.push_str(literal);
+            expanded.push_str("${");
+            rest = &rest[pos + 2..];
+            continue;
+        }
+        let Some((var_name, tail)) = rest[pos + 2..].split_once('}') else {
+            break;
+        };
+        let var_end = rest.len() - tail.len();
+        match get_env(var_name) {
+            Some(var_value) => {
+                expanded.push_str(&rest[..pos]);
+                expanded.push_str(&var_value);
+            }
+            None => expanded.push_str(&rest[..var_end]),
+        }
+        rest = tail;
     }
+    expanded.push_str(rest);
+    (expanded != text).then_some(expanded)
 }
 
 fn config_files_for(
@@ -575,7 +1059,7 @@ fn config_files_for(
 /// 2. Base environment variables
 /// 3. [User configs](https://jj-vcs.github.io/jj/latest/config/)
 /// 4. Repo config `.jj/repo/config.toml`
-/// 5. TODO: Workspace config `.jj/config.toml`
+/// 5. Workspace config `.jj/config.toml`
 /// 6. Override environment variables
 /// 7. Command-line arguments `--config` and `--config-file`
 ///
@@ -951,6 +1435,7 @@ mod tests {
     use std::env::join_paths;
     use std::fmt::Write as _;
 
+    use assert_matches::assert_matches;
     use indoc::indoc;
     use maplit::hashmap;
     use test_case::test_case;
@@ -1106,6 +1591,143 @@ mod tests {
         assert_eq!(args, ["-nw"].as_ref());
     }
 
+    #[test]
+    fn test_expand_string() {
+        let get_env = |name: &str| (name == "FOO").then(|| "foo".to_owned());
+        let expand = |text| expand_string(text, Some(Path::new("/home/dir")), &get_env);
+        assert_eq!(expand("plain"), None);
+        assert_eq!(expand("~").as_deref(), Some("/home/dir"));
+        assert_eq!(expand("~/a").as_deref(), Some("/home/dir/a"));
+        assert_eq!(expand("~a"), None);
+        assert_eq!(expand("a/~/b"), None);
+        assert_eq!(expand("${FOO}").as_deref(), Some("foo"));
+        assert_eq!(expand("~/${FOO}/b").as_deref(), Some("/home/dir/foo/b"));
+        assert_eq!(expand("$FOO $${FOO}").as_deref(), Some("$FOO ${FOO}"));
+        assert_eq!(expand("$$${FOO}").as_deref(), Some("$${FOO}"));
+        // Unset variables and incomplete references are kept as is
+        assert_eq!(expand("${BAR}"), None);
+        assert_eq!(expand("${BAR}/${FOO}").as_deref(), Some("${BAR}/foo"));
+        assert_eq!(expand("${FOO}/${FOO").as_deref(), Some("foo/${FOO"));
+        assert_eq!(expand_string("~/${BAR}", None, &get_env), None);
+    }
+
+    #[test]
+    fn test_expand_scope() {
+        let scope = |name: &str| expand_scope(&name.parse().unwrap());
+        assert_eq!(scope("ui"), ExpandScope::Descend);
+        assert_eq!(scope("ui.editor"), ExpandScope::Expand);
+        assert_eq!(scope("ui.pager.command"), ExpandScope::Expand);
+        assert_eq!(scope("ui.color"), ExpandScope::Skip);
+        assert_eq!(scope("merge-tools.foo"), ExpandScope::Descend);
+        assert_eq!(scope("merge-tools.foo.program"), ExpandScope::Expand);
+        assert_eq!(scope("merge-tools.foo.merge-args"), ExpandScope::Skip);
+        assert_eq!(scope("aliases.sh"), ExpandScope::Skip);
+        assert_eq!(scope("revset-aliases"), ExpandScope::Skip);
+    }
+
+    #[test]
+    fn test_expand_config_values() {
+        let get_env = |name: &str| (name == "FOO").then(|| "foo".to_owned());
+        let mut config = StackedConfig::empty();
+        config
+            .add_layer(ConfigLayer::parse(ConfigSource::Default, "ui.editor = '${FOO}'").unwrap());
+        config.add_layer(
+            ConfigLayer::parse(
+                ConfigSource::User,
+                indoc! {"
+                    aliases.sh = ['util', 'exec', '--', 'sh', '-c', 'echo ${FOO} ${1}']
+                    ui.editor = '${FOO}'  # comment
+                    ui.pager = { command = ['~/pager', '${BAR}'], env = { A = '${FOO}' } }
+                    ui.color = '~'
+                    [merge-tools.foo]
+                    program = '~/bin/foo'
+                    merge-args = ['${FOO}']
+                    [[fix.tools.bar.command]]
+                    a = '~'
+                "},
+            )
+            .unwrap(),
+        );
+        expand_config_values(&mut config, Some(Path::new("/home")), get_env);
+        insta::assert_snapshot!(config.layers()[0].data, @"ui.editor = '${FOO}'");
+        insta::assert_snapshot!(config.layers()[1].data, @r#"
+        aliases.sh = ['util', 'exec', '--', 'sh', '-c', 'echo ${FOO} ${1}']
+        ui.editor = "foo"  # comment
+        ui.pager = { command = ["/home/pager", '${BAR}'], env = { A = "foo" } }
+        ui.color = '~'
+        [merge-tools.foo]
+        program = "/home/bin/foo"
+        merge-args = ['${FOO}']
+        [[fix.tools.bar.command]]
+        a = "/home"
+        "#);
+    }
+
+    #[test]
+    fn test_validate_config_layer() {
+        let validate = |text: &str| {
+            let layer = ConfigLayer::parse(ConfigSource::User, text).unwrap();
+            validate_config_layer(&layer)
+                .iter()
+                .map(|err| err.to_string())
+                .join("\n")
+        };
+        insta::assert_snapshot!(validate(""), @"");
+        insta::assert_snapshot!(validate(indoc! {"
+            ui.color = 'never'
+            ui.editor = ['vim', '-f']
+            colors.error = { fg = 'red', bold = true }
+            revset-aliases.'mine()' = 'author(me)'
+            [[--scope]]
+            --when.commands = ['log']
+            ui.pager = 'less'
+        "}), @"");
+
+        insta::assert_snapshot!(validate(indoc! {"
+            ui.colour = 'never'
+            ui.color = 'sometimes'
+            ui.editor = 1
+            colors.error = { fgg = 'red' }
+            [[--scope]]
+            ui.log-word-wrap = 'yes'
+        "}), @r#"
+        Invalid type or value for --scope.ui.log-word-wrap: expected boolean
+        Unknown config key colors.error.fgg
+        Invalid type or value for ui.color: expected one of "always", "never", "debug", "auto"
+        Unknown config key ui.colour
+        Invalid type or value for ui.editor: expected string or array of string or table
+        "#);
+
+        let layer = ConfigLayer::parse(ConfigSource::User, "ui.colour = 'never'").unwrap();
+        let errors = validate_config_layer(&layer);
+        assert_matches!(
+            &errors[..],
+            [ConfigValidationError::UnknownName { similar_names, .. }]
+                if similar_names == &["ui.color"]
+        );
+    }
+
+    #[test]
+    fn test_find_config_line() {
+        let text = indoc! {"
+            ui.color = 'never'
+            [colors]
+            error = { fg = 'red' }
+            [[--scope]]
+            ui.pager = 'less'
+            [[--scope]]
+            ui.editor = 'vim'
+        "};
+        let find = |name: &str| find_config_line(text, &name.parse().unwrap());
+        assert_eq!(find("ui.color"), Some(1));
+        assert_eq!(find("colors"), Some(2));
+        assert_eq!(find("colors.error.fg"), Some(3));
+        assert_eq!(find("--scope.ui.pager"), Some(5));
+        assert_eq!(find("--scope.ui.editor"), Some(7));
+        assert_eq!(find("ui.diff-editor"), None);
+        assert_eq!(find_config_line("invalid", &"ui".parse().unwrap()), None);
+    }
+
     #[test]
     fn test_resolved_config_values_empty() {
         let config = StackedConfig::empty();
@@ -1812,8 +2434,10 @@ mod tests {
         ConfigEnv {
             home_dir,
             repo_path: None,
+            workspace_path: None,
             user_config_paths: env.resolve(&Ui::null()),
             repo_config_path: None,
+            workspace_config_path: None,
             command: None,
         }
     }
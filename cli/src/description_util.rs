@@ -336,7 +336,7 @@ pub fn combine_messages_for_editing(
         combined.push_str(commit.description());
     }
 
-    if let Some(template) = parse_trailers_template(ui, tx)? {
+    if let Some(template) = parse_trailers_template(ui, tx, false)? {
         // show the user only trailers that were not in one of the squashed commits
         let old_trailers: Vec<_> = sources
             .iter()
@@ -378,12 +378,25 @@ pub fn join_message_paragraphs(paragraphs: &[String]) -> String {
 
 /// Parse the commit trailers template from the configuration
 ///
-/// Returns None if the commit trailers template is empty.
+/// If `signoff` is true, a "Signed-off-by" trailer is added after the
+/// configured trailers. Returns None if the resulting template is empty.
 pub fn parse_trailers_template<'a>(
     ui: &Ui,
     tx: &'a WorkspaceCommandTransaction,
+    signoff: bool,
 ) -> Result<Option<TemplateRenderer<'a, Commit>>, CommandError> {
-    let trailer_template = tx.settings().get_string("templates.commit_trailers")?;
+    let mut trailer_template = tx.settings().get_string("templates.commit_trailers")?;
+    if signoff {
+        trailer_template = if trailer_template.is_empty() {
+            "format_signed_off_by_trailer(self)".to_owned()
+        } else {
+            // The configured trailers may or may not end with a newline.
+            format!(
+                "separate(\"\\n\", stringify({trailer_template}\n).trim_end(), \
+                 format_signed_off_by_trailer(self))"
+            )
+        };
+    }
     if trailer_template.is_empty() {
         Ok(None)
     } else {
@@ -431,7 +444,18 @@ pub fn add_trailers(
     tx: &WorkspaceCommandTransaction,
     commit_builder: &DetachedCommitBuilder,
 ) -> Result<String, CommandError> {
-    if let Some(renderer) = parse_trailers_template(ui, tx)? {
+    add_trailers_with_signoff(ui, tx, commit_builder, false)
+}
+
+/// Like [`add_trailers()`], but also adds a "Signed-off-by" trailer if
+/// `signoff` is true.
+pub fn add_trailers_with_signoff(
+    ui: &Ui,
+    tx: &WorkspaceCommandTransaction,
+    commit_builder: &DetachedCommitBuilder,
+    signoff: bool,
+) -> Result<String, CommandError> {
+    if let Some(renderer) = parse_trailers_template(ui, tx, signoff)? {
         let commit = commit_builder.write_hidden()?;
         add_trailers_with_template(&renderer, &commit)
     } else {
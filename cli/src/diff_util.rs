@@ -24,6 +24,7 @@ use std::path::PathBuf;
 
 use bstr::BStr;
 use bstr::BString;
+use bstr::ByteSlice as _;
 use clap_complete::ArgValueCandidates;
 use futures::StreamExt as _;
 use futures::TryStreamExt as _;
@@ -39,10 +40,13 @@ use jj_lib::commit::Commit;
 use jj_lib::config::ConfigGetError;
 use jj_lib::conflicts::ConflictMarkerStyle;
 use jj_lib::conflicts::ConflictMaterializeOptions;
+use jj_lib::conflicts::MaterializedFileConflictValue;
 use jj_lib::conflicts::MaterializedFileValue;
 use jj_lib::conflicts::MaterializedTreeDiffEntry;
 use jj_lib::conflicts::MaterializedTreeValue;
+use jj_lib::conflicts::materialize_file_conflict_to_bytes;
 use jj_lib::conflicts::materialize_merge_result_to_bytes;
+use jj_lib::conflicts::materialize_tree_value;
 use jj_lib::conflicts::materialized_diff_stream;
 use jj_lib::copies::CopiesTreeDiffEntry;
 use jj_lib::copies::CopiesTreeDiffEntryPath;
@@ -60,7 +64,11 @@ use jj_lib::files::ConflictDiffHunk;
 use jj_lib::files::DiffLineHunkSide;
 use jj_lib::files::DiffLineIterator;
 use jj_lib::files::DiffLineNumber;
+use jj_lib::fileset;
+use jj_lib::fileset::FilesetDiagnostics;
+use jj_lib::fileset::FilesetExpression;
 use jj_lib::matchers::Matcher;
+use jj_lib::matchers::NothingMatcher;
 use jj_lib::merge::Diff;
 use jj_lib::merge::Merge;
 use jj_lib::merge::MergeBuilder;
@@ -83,6 +91,7 @@ use crate::command_error::CommandError;
 use crate::command_error::cli_error;
 use crate::commit_templater;
 use crate::config::CommandNameAndArgs;
+use crate::formatter::FormatRecorder;
 use crate::formatter::Formatter;
 use crate::formatter::FormatterExt as _;
 use crate::merge_tools;
@@ -92,6 +101,7 @@ use crate::merge_tools::ExternalMergeTool;
 use crate::merge_tools::generate_diff;
 use crate::merge_tools::invoke_external_diff;
 use crate::merge_tools::new_utf8_temp_dir;
+use crate::syntax_highlight::SyntaxHighlights;
 use crate::templater::TemplateRenderer;
 use crate::text_util;
 use crate::ui::Ui;
@@ -99,7 +109,7 @@ use crate::ui::Ui;
 #[derive(clap::Args, Clone, Debug)]
 #[command(next_help_heading = "Diff Formatting Options")]
 #[command(group(clap::ArgGroup::new("short-format").args(&["summary", "stat", "types", "name_only"])))]
-#[command(group(clap::ArgGroup::new("long-format").args(&["git", "color_words"])))]
+#[command(group(clap::ArgGroup::new("long-format").args(&["git", "color_words", "side_by_side"])))]
 pub struct DiffFormatArgs {
     /// For each path, show only whether it was modified, added, or deleted
     #[arg(long, short)]
@@ -128,8 +138,17 @@ pub struct DiffFormatArgs {
     /// Show a word-level diff with changes indicated only by color
     #[arg(long)]
     pub color_words: bool,
+    /// Show a two-column diff with the old and new contents side by side
+    ///
+    /// The columns are sized to fit the terminal width.
+    #[arg(long)]
+    pub side_by_side: bool,
     /// Generate diff by external command
     ///
+    /// The command can be the name of a tool configured in the
+    /// `merge-tools` table, which overrides `ui.diff-formatter` for this
+    /// invocation only.
+    ///
     /// A builtin format can also be specified as `:<name>`. For example,
     /// `--tool=:git` is equivalent to `--git`.
     #[arg(
@@ -159,6 +178,7 @@ pub enum DiffFormat {
     NameOnly,
     Git(Box<UnifiedDiffOptions>),
     ColorWords(Box<ColorWordsDiffOptions>),
+    SideBySide(Box<UnifiedDiffOptions>),
     Tool(Box<ExternalMergeTool>),
 }
 
@@ -170,6 +190,7 @@ enum BuiltinFormatKind {
     NameOnly,
     Git,
     ColorWords,
+    SideBySide,
 }
 
 impl BuiltinFormatKind {
@@ -183,6 +204,7 @@ impl BuiltinFormatKind {
         Self::NameOnly,
         Self::Git,
         Self::ColorWords,
+        Self::SideBySide,
     ];
 
     fn from_name(name: &str) -> Result<Self, String> {
@@ -193,6 +215,7 @@ impl BuiltinFormatKind {
             "name-only" => Ok(Self::NameOnly),
             "git" => Ok(Self::Git),
             "color-words" => Ok(Self::ColorWords),
+            "side-by-side" => Ok(Self::SideBySide),
             _ => Err(format!("Invalid builtin diff format: {name}")),
         }
     }
@@ -216,6 +239,8 @@ impl BuiltinFormatKind {
             Some(Self::Git)
         } else if args.color_words {
             Some(Self::ColorWords)
+        } else if args.side_by_side {
+            Some(Self::SideBySide)
         } else {
             None
         }
@@ -224,7 +249,7 @@ impl BuiltinFormatKind {
     fn is_short(self) -> bool {
         match self {
             Self::Summary | Self::Stat | Self::Types | Self::NameOnly => true,
-            Self::Git | Self::ColorWords => false,
+            Self::Git | Self::ColorWords | Self::SideBySide => false,
         }
     }
 
@@ -236,6 +261,7 @@ impl BuiltinFormatKind {
             Self::NameOnly => "name-only",
             Self::Git => "git",
             Self::ColorWords => "color-words",
+            Self::SideBySide => "side-by-side",
         }
     }
 
@@ -247,7 +273,7 @@ impl BuiltinFormatKind {
         match self {
             Self::Summary => Ok(DiffFormat::Summary),
             Self::Stat => {
-                let mut options = DiffStatOptions::default();
+                let mut options = DiffStatOptions::from_settings(settings)?;
                 options.merge_args(args);
                 Ok(DiffFormat::Stat(Box::new(options)))
             }
@@ -263,6 +289,12 @@ impl BuiltinFormatKind {
                 options.merge_args(args);
                 Ok(DiffFormat::ColorWords(Box::new(options)))
             }
+            Self::SideBySide => {
+                let mut options = UnifiedDiffOptions::from_settings(settings)?;
+                options.context = settings.get("diff.side-by-side.context")?;
+                options.merge_args(args);
+                Ok(DiffFormat::SideBySide(Box::new(options)))
+            }
         }
     }
 }
@@ -503,6 +535,20 @@ impl<'a> DiffRenderer<'a> {
                     )
                     .await?;
                 }
+                DiffFormat::SideBySide(options) => {
+                    let tree_diff =
+                        from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
+                    show_side_by_side_diff(
+                        formatter,
+                        store,
+                        tree_diff,
+                        path_converter,
+                        options,
+                        self.conflict_marker_style,
+                        width,
+                    )
+                    .await?;
+                }
                 DiffFormat::Tool(tool) => {
                     match tool.diff_invocation_mode {
                         DiffToolMode::FileByFile => {
@@ -544,6 +590,7 @@ impl<'a> DiffRenderer<'a> {
         &self,
         formatter: &mut dyn Formatter,
         [from_description, to_description]: [&Merge<&str>; 2],
+        width: usize,
     ) -> Result<(), DiffRenderError> {
         if from_description == to_description {
             return Ok(());
@@ -581,6 +628,20 @@ impl<'a> DiffRenderer<'a> {
                         &materialize_options,
                     )?;
                 }
+                DiffFormat::SideBySide(options) => {
+                    writeln!(formatter.labeled("header"), "Modified commit description:")?;
+                    let contents = materialize_diff_texts(
+                        [from_description, to_description],
+                        &materialize_options,
+                    );
+                    show_side_by_side_diff_hunks(
+                        formatter,
+                        contents.each_ref().map(AsRef::as_ref),
+                        [DUMMY_PATH, DUMMY_PATH],
+                        options,
+                        width,
+                    )?;
+                }
                 DiffFormat::Tool(_) => {
                     // TODO: materialize commit description as file?
                 }
@@ -617,7 +678,11 @@ impl<'a> DiffRenderer<'a> {
         let from_tree = rebase_to_dest_parent(self.repo, from_commits, to_commit)?;
         let to_tree = to_commit.tree_async().await?;
         let copy_records = CopyRecords::default(); // TODO
-        self.show_diff_commit_descriptions(*formatter, [&from_description, &to_description])?;
+        self.show_diff_commit_descriptions(
+            *formatter,
+            [&from_description, &to_description],
+            width,
+        )?;
         self.show_diff_trees(
             ui,
             *formatter,
@@ -680,14 +745,48 @@ pub enum ConflictDiffMethod {
     Pair,
 }
 
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct LineDiffOptions {
     /// How equivalence of lines is tested.
     pub compare_mode: LineCompareMode,
+    /// Files whose contents are never compared by line, but are always
+    /// treated as binary.
+    pub binary_files: FilesetExpression,
     // TODO: add --ignore-blank-lines, etc. which aren't mutually exclusive.
 }
 
+impl Default for LineDiffOptions {
+    fn default() -> Self {
+        Self {
+            compare_mode: LineCompareMode::default(),
+            binary_files: FilesetExpression::none(),
+        }
+    }
+}
+
 impl LineDiffOptions {
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        let name = "diff.binary-files";
+        let text = settings.get_string(name)?;
+        let binary_files = fileset::parse(
+            &mut FilesetDiagnostics::new(),
+            &text,
+            &RepoPathUiConverter::Fs {
+                cwd: "".into(),
+                base: "".into(),
+            },
+        )
+        .map_err(|err| ConfigGetError::Type {
+            name: name.to_owned(),
+            error: err.into(),
+            source_path: None,
+        })?;
+        Ok(Self {
+            compare_mode: LineCompareMode::default(),
+            binary_files,
+        })
+    }
+
     fn merge_args(&mut self, args: &DiffFormatArgs) {
         self.compare_mode = if args.ignore_all_space {
             LineCompareMode::IgnoreAllSpace
@@ -759,7 +858,7 @@ impl ColorWordsDiffOptions {
         Ok(Self {
             conflict: settings.get("diff.color-words.conflict")?,
             context: settings.get("diff.color-words.context")?,
-            line_diff: LineDiffOptions::default(),
+            line_diff: LineDiffOptions::from_settings(settings)?,
             max_inline_alternation,
         })
     }
@@ -1228,6 +1327,7 @@ impl FileContent<Merge<BString>> {
 fn file_content_for_diff<T>(
     path: &RepoPath,
     file: &mut MaterializedFileValue,
+    binary_matcher: &dyn Matcher,
     map_resolved: impl FnOnce(BString) -> T,
 ) -> BackendResult<FileContent<T>> {
     // If this is a binary file, don't show the full contents.
@@ -1240,36 +1340,59 @@ fn file_content_for_diff<T>(
     let contents = BString::new(file.read_all(path).block_on()?);
     let start = &contents[..PEEK_SIZE.min(contents.len())];
     Ok(FileContent {
-        is_binary: start.contains(&b'\0'),
+        is_binary: binary_matcher.matches(path) || start.contains(&b'\0'),
         contents: map_resolved(contents),
     })
 }
 
 fn diff_content(
+    store: &Store,
     path: &RepoPath,
     value: MaterializedTreeValue,
+    binary_matcher: &dyn Matcher,
     materialize_options: &ConflictMaterializeOptions,
 ) -> BackendResult<FileContent<BString>> {
     diff_content_with(
         path,
         value,
+        binary_matcher,
         |content| content,
-        |contents| materialize_merge_result_to_bytes(&contents, materialize_options),
+        |file| materialize_file_conflict_to_bytes(store, &file, materialize_options),
     )
 }
 
+/// Like [`diff_content()`], but keeps the conflict terms if they are to be
+/// diffed pairwise.
 fn diff_content_as_merge(
+    store: &Store,
     path: &RepoPath,
     value: MaterializedTreeValue,
+    binary_matcher: &dyn Matcher,
+    conflict_method: ConflictDiffMethod,
+    materialize_options: &ConflictMaterializeOptions,
 ) -> BackendResult<FileContent<Merge<BString>>> {
-    diff_content_with(path, value, Merge::resolved, |contents| contents)
+    diff_content_with(
+        path,
+        value,
+        binary_matcher,
+        Merge::resolved,
+        |file| match conflict_method {
+            ConflictDiffMethod::Materialize => Merge::resolved(materialize_file_conflict_to_bytes(
+                store,
+                &file,
+                materialize_options,
+            )),
+            ConflictDiffMethod::Pair => file.contents,
+        },
+    )
 }
 
 fn diff_content_with<T>(
     path: &RepoPath,
     value: MaterializedTreeValue,
+    binary_matcher: &dyn Matcher,
     map_resolved: impl FnOnce(BString) -> T,
-    map_conflict: impl FnOnce(Merge<BString>) -> T,
+    map_conflict: impl FnOnce(MaterializedFileConflictValue) -> T,
 ) -> BackendResult<FileContent<T>> {
     match value {
         MaterializedTreeValue::Absent => Ok(FileContent {
@@ -1281,7 +1404,7 @@ fn diff_content_with<T>(
             contents: map_resolved(format!("Access denied: {err}").into()),
         }),
         MaterializedTreeValue::File(mut file) => {
-            file_content_for_diff(path, &mut file, map_resolved)
+            file_content_for_diff(path, &mut file, binary_matcher, map_resolved)
         }
         MaterializedTreeValue::Symlink { id: _, target } => Ok(FileContent {
             // Unix file paths can't contain null bytes.
@@ -1294,8 +1417,8 @@ fn diff_content_with<T>(
         }),
         // TODO: are we sure this is never binary?
         MaterializedTreeValue::FileConflict(file) => Ok(FileContent {
-            is_binary: false,
-            contents: map_conflict(file.contents),
+            is_binary: binary_matcher.matches(path),
+            contents: map_conflict(file),
         }),
         MaterializedTreeValue::OtherConflict { id } => Ok(FileContent {
             is_binary: false,
@@ -1307,6 +1430,53 @@ fn diff_content_with<T>(
     }
 }
 
+/// Returns a description of how a path present on both sides was modified,
+/// e.g. "Modified regular file".
+fn describe_modified_value(
+    left_value: &MaterializedTreeValue,
+    right_value: &MaterializedTreeValue,
+) -> String {
+    match (left_value, right_value) {
+        (MaterializedTreeValue::File(left), MaterializedTreeValue::File(right)) => {
+            if left.executable && right.executable {
+                "Modified executable file".to_string()
+            } else if left.executable {
+                "Executable file became non-executable at".to_string()
+            } else if right.executable {
+                "Non-executable file became executable at".to_string()
+            } else {
+                "Modified regular file".to_string()
+            }
+        }
+        (
+            MaterializedTreeValue::FileConflict(_) | MaterializedTreeValue::OtherConflict { .. },
+            MaterializedTreeValue::FileConflict(_) | MaterializedTreeValue::OtherConflict { .. },
+        ) => "Modified conflict in".to_string(),
+        (
+            MaterializedTreeValue::FileConflict(_) | MaterializedTreeValue::OtherConflict { .. },
+            _,
+        ) => "Resolved conflict in".to_string(),
+        (
+            _,
+            MaterializedTreeValue::FileConflict(_) | MaterializedTreeValue::OtherConflict { .. },
+        ) => "Created conflict in".to_string(),
+        (MaterializedTreeValue::Symlink { .. }, MaterializedTreeValue::Symlink { .. }) => {
+            "Symlink target changed at".to_string()
+        }
+        (_, _) => {
+            let left_type = basic_diff_file_type(left_value);
+            let right_type = basic_diff_file_type(right_value);
+            let (first, rest) = left_type.split_at(1);
+            format!(
+                "{}{} became {} at",
+                first.to_ascii_uppercase(),
+                rest,
+                right_type
+            )
+        }
+    }
+}
+
 fn basic_diff_file_type(value: &MaterializedTreeValue) -> &'static str {
     match value {
         MaterializedTreeValue::Absent => {
@@ -1342,6 +1512,7 @@ pub async fn show_color_words_diff(
         marker_len: None,
         merge: store.merge_options().clone(),
     };
+    let binary_matcher = options.line_diff.binary_files.to_matcher();
     let empty_content = || Merge::resolved(BString::default());
     let mut diff_stream = materialized_diff_stream(store, tree_diff);
     while let Some(MaterializedTreeDiffEntry { path, values }) = diff_stream.next().await {
@@ -1376,7 +1547,14 @@ pub async fn show_color_words_diff(
                 formatter.labeled("header"),
                 "Added {description} {right_ui_path}:"
             )?;
-            let right_content = diff_content_as_merge(right_path, right_value)?;
+            let right_content = diff_content_as_merge(
+                store,
+                right_path,
+                right_value,
+                binary_matcher.as_ref(),
+                options.conflict,
+                &materialize_options,
+            )?;
             if right_content.is_empty() {
                 writeln!(formatter.labeled("empty"), "    (empty)")?;
             } else if right_content.is_binary {
@@ -1390,51 +1568,23 @@ pub async fn show_color_words_diff(
                 )?;
             }
         } else if right_value.is_present() {
-            let description = match (&left_value, &right_value) {
-                (MaterializedTreeValue::File(left), MaterializedTreeValue::File(right)) => {
-                    if left.executable && right.executable {
-                        "Modified executable file".to_string()
-                    } else if left.executable {
-                        "Executable file became non-executable at".to_string()
-                    } else if right.executable {
-                        "Non-executable file became executable at".to_string()
-                    } else {
-                        "Modified regular file".to_string()
-                    }
-                }
-                (
-                    MaterializedTreeValue::FileConflict(_)
-                    | MaterializedTreeValue::OtherConflict { .. },
-                    MaterializedTreeValue::FileConflict(_)
-                    | MaterializedTreeValue::OtherConflict { .. },
-                ) => "Modified conflict in".to_string(),
-                (
-                    MaterializedTreeValue::FileConflict(_)
-                    | MaterializedTreeValue::OtherConflict { .. },
-                    _,
-                ) => "Resolved conflict in".to_string(),
-                (
-                    _,
-                    MaterializedTreeValue::FileConflict(_)
-                    | MaterializedTreeValue::OtherConflict { .. },
-                ) => "Created conflict in".to_string(),
-                (MaterializedTreeValue::Symlink { .. }, MaterializedTreeValue::Symlink { .. }) => {
-                    "Symlink target changed at".to_string()
-                }
-                (_, _) => {
-                    let left_type = basic_diff_file_type(&left_value);
-                    let right_type = basic_diff_file_type(&right_value);
-                    let (first, rest) = left_type.split_at(1);
-                    format!(
-                        "{}{} became {} at",
-                        first.to_ascii_uppercase(),
-                        rest,
-                        right_type
-                    )
-                }
-            };
-            let left_content = diff_content_as_merge(left_path, left_value)?;
-            let right_content = diff_content_as_merge(right_path, right_value)?;
+            let description = describe_modified_value(&left_value, &right_value);
+            let left_content = diff_content_as_merge(
+                store,
+                left_path,
+                left_value,
+                binary_matcher.as_ref(),
+                options.conflict,
+                &materialize_options,
+            )?;
+            let right_content = diff_content_as_merge(
+                store,
+                right_path,
+                right_value,
+                binary_matcher.as_ref(),
+                options.conflict,
+                &materialize_options,
+            )?;
             if left_path == right_path {
                 writeln!(
                     formatter.labeled("header"),
@@ -1462,7 +1612,14 @@ pub async fn show_color_words_diff(
                 formatter.labeled("header"),
                 "Removed {description} {right_ui_path}:"
             )?;
-            let left_content = diff_content_as_merge(left_path, left_value)?;
+            let left_content = diff_content_as_merge(
+                store,
+                left_path,
+                left_value,
+                binary_matcher.as_ref(),
+                options.conflict,
+                &materialize_options,
+            )?;
             if left_content.is_empty() {
                 writeln!(formatter.labeled("empty"), "    (empty)")?;
             } else if left_content.is_binary {
@@ -1502,7 +1659,7 @@ pub async fn show_file_by_file_diff(
      -> Result<PathBuf, DiffRenderError> {
         let fs_path = path.to_fs_path(wc_dir)?;
         std::fs::create_dir_all(fs_path.parent().unwrap())?;
-        let content = diff_content(path, value, &materialize_options)?;
+        let content = diff_content(store, path, value, &NothingMatcher, &materialize_options)?;
         std::fs::write(&fs_path, content.contents)?;
         Ok(fs_path)
     };
@@ -1570,8 +1727,10 @@ struct GitDiffPart {
 }
 
 fn git_diff_part(
+    store: &Store,
     path: &RepoPath,
     value: MaterializedTreeValue,
+    binary_matcher: &dyn Matcher,
     materialize_options: &ConflictMaterializeOptions,
 ) -> Result<GitDiffPart, DiffRenderError> {
     const DUMMY_HASH: &str = "0000000000";
@@ -1598,7 +1757,7 @@ fn git_diff_part(
         MaterializedTreeValue::File(mut file) => {
             mode = if file.executable { "100755" } else { "100644" };
             hash = file.id.hex();
-            content = file_content_for_diff(path, &mut file, |content| content)?;
+            content = file_content_for_diff(path, &mut file, binary_matcher, |content| content)?;
         }
         MaterializedTreeValue::Symlink { id, target } => {
             mode = "120000";
@@ -1625,8 +1784,9 @@ fn git_diff_part(
             };
             hash = DUMMY_HASH.to_owned();
             content = FileContent {
-                is_binary: false, // TODO: are we sure this is never binary?
-                contents: materialize_merge_result_to_bytes(&file.contents, materialize_options),
+                // TODO: are we sure this is never binary?
+                is_binary: binary_matcher.matches(path),
+                contents: materialize_file_conflict_to_bytes(store, &file, materialize_options),
             };
         }
         MaterializedTreeValue::OtherConflict { id } => {
@@ -1653,6 +1813,10 @@ fn git_diff_part(
 pub struct UnifiedDiffOptions {
     /// Number of context lines to show.
     pub context: usize,
+    /// Whether to highlight the changed words within modified lines.
+    pub highlight_words: bool,
+    /// Whether to highlight the syntax of known file types.
+    pub syntax_highlight: bool,
     /// How lines are tokenized and compared.
     pub line_diff: LineDiffOptions,
 }
@@ -1661,11 +1825,13 @@ impl UnifiedDiffOptions {
     pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
         Ok(Self {
             context: settings.get("diff.git.context")?,
-            line_diff: LineDiffOptions::default(),
+            highlight_words: settings.get("diff.git.highlight-words")?,
+            syntax_highlight: settings.get("diff.syntax-highlight")?,
+            line_diff: LineDiffOptions::from_settings(settings)?,
         })
     }
 
-    fn merge_args(&mut self, args: &DiffFormatArgs) {
+    pub fn merge_args(&mut self, args: &DiffFormatArgs) {
         if let Some(context) = args.context {
             self.context = context;
         }
@@ -1765,12 +1931,23 @@ fn unified_diff_hunks<'content>(
                 // The next hunk should be of DiffHunk::Different type if any.
                 current_hunk.extend_context_lines(before_lines.into_iter().rev());
             }
-            DiffHunkKind::Different => {
+            DiffHunkKind::Different if options.highlight_words => {
                 let [left_lines, right_lines] =
                     unzip_diff_hunks_to_lines(ContentDiff::by_word(hunk.contents).hunks());
                 current_hunk.extend_removed_lines(left_lines);
                 current_hunk.extend_added_lines(right_lines);
             }
+            DiffHunkKind::Different => {
+                let [left, right] = hunk.contents[..].try_into().unwrap();
+                let to_lines = |content: &'content BStr| {
+                    content
+                        .split_inclusive(|b| *b == b'\n')
+                        .map(|line| vec![(DiffTokenType::Matching, line)])
+                        .collect_vec()
+                };
+                current_hunk.extend_removed_lines(to_lines(left));
+                current_hunk.extend_added_lines(to_lines(right));
+            }
         }
     }
     if !current_hunk.lines.is_empty() {
@@ -1834,10 +2011,9 @@ where
     [left_lines, right_lines]
 }
 
-fn show_unified_diff_hunks(
+fn show_unified_diff_hunk_header(
     formatter: &mut dyn Formatter,
-    contents: [&BStr; 2],
-    options: &UnifiedDiffOptions,
+    hunk: &UnifiedDiffHunk,
 ) -> io::Result<()> {
     // "If the chunk size is 0, the first number is one lower than one would
     // expect." - https://www.artima.com/weblogs/viewpost.jsp?thread=164293
@@ -1854,32 +2030,93 @@ fn show_unified_diff_hunks(
         }
     }
 
+    writeln!(
+        formatter.labeled("hunk_header"),
+        "@@ -{},{} +{},{} @@",
+        to_line_number(hunk.left_line_range.clone()),
+        hunk.left_line_range.len(),
+        to_line_number(hunk.right_line_range.clone()),
+        hunk.right_line_range.len()
+    )
+}
+
+/// Returns the syntax highlights of the `contents` if enabled and the file
+/// types are known.
+fn syntax_highlights<'content>(
+    contents: [&'content BStr; 2],
+    paths: [&str; 2],
+    options: &UnifiedDiffOptions,
+) -> Vec<(&'content BStr, SyntaxHighlights)> {
+    if !options.syntax_highlight {
+        return vec![];
+    }
+    iter::zip(contents, paths)
+        .filter_map(|(content, path)| Some((content, SyntaxHighlights::new(path, content)?)))
+        .collect()
+}
+
+fn show_unified_diff_hunks(
+    formatter: &mut dyn Formatter,
+    contents: [&BStr; 2],
+    paths: [&str; 2],
+    options: &UnifiedDiffOptions,
+) -> io::Result<()> {
+    let highlights = syntax_highlights(contents, paths, options);
     for hunk in unified_diff_hunks(contents, options) {
-        writeln!(
-            formatter.labeled("hunk_header"),
-            "@@ -{},{} +{},{} @@",
-            to_line_number(hunk.left_line_range.clone()),
-            hunk.left_line_range.len(),
-            to_line_number(hunk.right_line_range.clone()),
-            hunk.right_line_range.len()
-        )?;
-        for (line_type, tokens) in &hunk.lines {
-            let (label, sigil) = match line_type {
-                DiffLineType::Context => ("context", " "),
-                DiffLineType::Removed => ("removed", "-"),
-                DiffLineType::Added => ("added", "+"),
-            };
-            write!(formatter.labeled(label), "{sigil}")?;
+        show_unified_diff_hunk(formatter, &hunk, &highlights)?;
+    }
+    Ok(())
+}
+
+fn show_unified_diff_hunk(
+    formatter: &mut dyn Formatter,
+    hunk: &UnifiedDiffHunk,
+    highlights: &[(&BStr, SyntaxHighlights)],
+) -> io::Result<()> {
+    show_unified_diff_hunk_header(formatter, hunk)?;
+    for (line_type, tokens) in &hunk.lines {
+        let (label, sigil) = match line_type {
+            DiffLineType::Context => ("context", " "),
+            DiffLineType::Removed => ("removed", "-"),
+            DiffLineType::Added => ("added", "+"),
+        };
+        write!(formatter.labeled(label), "{sigil}")?;
+        if highlights.is_empty() {
             show_diff_line_tokens(*formatter.labeled(label), tokens)?;
-            let (_, content) = tokens.last().expect("hunk line must not be empty");
-            if !content.ends_with(b"\n") {
-                write!(formatter, "\n\\ No newline at end of file\n")?;
-            }
+        } else {
+            show_highlighted_diff_line_tokens(*formatter.labeled(label), tokens, highlights)?;
+        }
+        let (_, content) = tokens.last().expect("hunk line must not be empty");
+        if !content.ends_with(b"\n") {
+            write!(formatter, "\n\\ No newline at end of file\n")?;
         }
     }
     Ok(())
 }
 
+/// Returns true if the `hunk` adds lines within the `line_range` of the right
+/// content, or removes lines from inside of it.
+fn unified_diff_hunk_changes_lines(hunk: &UnifiedDiffHunk, line_range: &Range<usize>) -> bool {
+    let mut right_line = hunk.right_line_range.start;
+    for (line_type, _) in &hunk.lines {
+        match line_type {
+            DiffLineType::Context => right_line += 1,
+            DiffLineType::Removed => {
+                if line_range.start < right_line && right_line < line_range.end {
+                    return true;
+                }
+            }
+            DiffLineType::Added => {
+                if line_range.contains(&right_line) {
+                    return true;
+                }
+                right_line += 1;
+            }
+        }
+    }
+    false
+}
+
 fn show_diff_line_tokens(
     formatter: &mut dyn Formatter,
     tokens: &[(DiffTokenType, &[u8])],
@@ -1893,6 +2130,42 @@ fn show_diff_line_tokens(
     Ok(())
 }
 
+/// Like [`show_diff_line_tokens()`], but also highlights the syntax of the
+/// tokens. `highlights` are the syntax highlights of the contents the tokens
+/// are sliced from.
+fn show_highlighted_diff_line_tokens(
+    formatter: &mut dyn Formatter,
+    tokens: &[(DiffTokenType, &[u8])],
+    highlights: &[(&BStr, SyntaxHighlights)],
+) -> io::Result<()> {
+    let write_data = |formatter: &mut dyn Formatter, data: &[u8]| {
+        // Find the content which the token was sliced from.
+        let data_range = data.as_ptr_range();
+        let found = highlights.iter().find_map(|(content, highlights)| {
+            let content_range = content.as_ptr_range();
+            (content_range.start <= data_range.start && data_range.end <= content_range.end).then(
+                || {
+                    (
+                        data_range.start as usize - content_range.start as usize,
+                        highlights,
+                    )
+                },
+            )
+        });
+        match found {
+            Some((offset, highlights)) => highlights.write(formatter, offset, data),
+            None => formatter.write_all(data),
+        }
+    };
+    for (token_type, data) in tokens {
+        match token_type {
+            DiffTokenType::Matching => write_data(formatter, data)?,
+            DiffTokenType::Different => write_data(*formatter.labeled("token"), data)?,
+        }
+    }
+    Ok(())
+}
+
 pub async fn show_git_diff(
     formatter: &mut dyn Formatter,
     store: &Store,
@@ -1905,6 +2178,7 @@ pub async fn show_git_diff(
         marker_len: None,
         merge: store.merge_options().clone(),
     };
+    let binary_matcher = options.line_diff.binary_files.to_matcher();
     let mut diff_stream = materialized_diff_stream(store, tree_diff);
     while let Some(MaterializedTreeDiffEntry { path, values }) = diff_stream.next().await {
         let left_path = path.source();
@@ -1913,8 +2187,20 @@ pub async fn show_git_diff(
         let right_path_string = right_path.as_internal_file_string();
         let (left_value, right_value) = values?;
 
-        let left_part = git_diff_part(left_path, left_value, &materialize_options)?;
-        let right_part = git_diff_part(right_path, right_value, &materialize_options)?;
+        let left_part = git_diff_part(
+            store,
+            left_path,
+            left_value,
+            binary_matcher.as_ref(),
+            &materialize_options,
+        )?;
+        let right_part = git_diff_part(
+            store,
+            right_path,
+            right_value,
+            binary_matcher.as_ref(),
+            &materialize_options,
+        )?;
 
         {
             let mut formatter = formatter.labeled("file_header");
@@ -1981,6 +2267,7 @@ pub async fn show_git_diff(
             show_unified_diff_hunks(
                 formatter,
                 [&left_part.content.contents, &right_part.content.contents].map(BStr::new),
+                [left_path_string, right_path_string],
                 options,
             )?;
         }
@@ -1988,6 +2275,94 @@ pub async fn show_git_diff(
     Ok(())
 }
 
+/// Shows the hunks of the diff of the file at `path` which change the lines in
+/// `line_range` of the right tree, in Git format.
+pub async fn show_line_range_diff(
+    formatter: &mut dyn Formatter,
+    store: &Store,
+    [left_tree, right_tree]: [&MergedTree; 2],
+    [left_path, right_path]: [&RepoPath; 2],
+    line_range: Range<usize>,
+    options: &UnifiedDiffOptions,
+    marker_style: ConflictMarkerStyle,
+) -> Result<(), DiffRenderError> {
+    let materialize_options = ConflictMaterializeOptions {
+        marker_style,
+        marker_len: None,
+        merge: store.merge_options().clone(),
+    };
+    let binary_matcher = options.line_diff.binary_files.to_matcher();
+    let left_path_string = left_path.as_internal_file_string();
+    let right_path_string = right_path.as_internal_file_string();
+    let left_value = materialize_tree_value(
+        store,
+        left_path,
+        left_tree.path_value_async(left_path).await?,
+    );
+    let right_value = materialize_tree_value(
+        store,
+        right_path,
+        right_tree.path_value_async(right_path).await?,
+    );
+    let left_part = git_diff_part(
+        store,
+        left_path,
+        left_value.await?,
+        binary_matcher.as_ref(),
+        &materialize_options,
+    )?;
+    let right_part = git_diff_part(
+        store,
+        right_path,
+        right_value.await?,
+        binary_matcher.as_ref(),
+        &materialize_options,
+    )?;
+    if left_part.content.contents == right_part.content.contents {
+        return Ok(());
+    }
+
+    let left_path = match left_part.mode {
+        Some(_) => format!("a/{left_path_string}"),
+        None => "/dev/null".to_owned(),
+    };
+    let right_path = match right_part.mode {
+        Some(_) => format!("b/{right_path_string}"),
+        None => "/dev/null".to_owned(),
+    };
+    if left_part.content.is_binary || right_part.content.is_binary {
+        writeln!(
+            formatter,
+            "Binary files {left_path} and {right_path} differ"
+        )?;
+        return Ok(());
+    }
+    writeln!(formatter.labeled("file_header"), "--- {left_path}")?;
+    writeln!(formatter.labeled("file_header"), "+++ {right_path}")?;
+    let contents = [&left_part.content.contents, &right_part.content.contents].map(BStr::new);
+    let highlights = syntax_highlights(contents, [left_path_string, right_path_string], options);
+    for hunk in unified_diff_hunks(contents, options) {
+        if unified_diff_hunk_changes_lines(&hunk, &line_range) {
+            show_unified_diff_hunk(formatter, &hunk, &highlights)?;
+        }
+    }
+    Ok(())
+}
+
+/// Materializes conflicts in non-binary contents.
+fn materialize_diff_texts<'a, T: AsRef<[u8]>>(
+    contents: [&'a Merge<T>; 2],
+    materialize_options: &ConflictMaterializeOptions,
+) -> [Cow<'a, BStr>; 2] {
+    contents.map(|content| match content.as_resolved() {
+        Some(text) => Cow::Borrowed(BStr::new(text)),
+        None => Cow::Owned(materialize_merge_result_to_bytes(
+            content,
+            materialize_options,
+        )),
+    })
+}
+
 /// Generates diff of non-binary contents in Git format.
 fn show_git_diff_texts<T: AsRef<[u8]>>(
     formatter: &mut dyn Formatter,
@@ -2002,14 +2377,266 @@ fn show_git_diff_texts<T: AsRef<[u8]>>(
         writeln!(formatter, "--- {left_path}")?;
         writeln!(formatter, "+++ {right_path}")?;
     }
-    let [left, right] = contents.map(|content| match content.as_resolved() {
-        Some(text) => Cow::Borrowed(BStr::new(text)),
-        None => Cow::Owned(materialize_merge_result_to_bytes(
-            content,
-            materialize_options,
-        )),
-    });
-    show_unified_diff_hunks(formatter, [left.as_ref(), right.as_ref()], options)
+    let [left, right] = materialize_diff_texts(contents, materialize_options);
+    show_unified_diff_hunks(
+        formatter,
+        [left.as_ref(), right.as_ref()],
+        [left_path, right_path],
+        options,
+    )
+}
+
+pub async fn show_side_by_side_diff(
+    formatter: &mut dyn Formatter,
+    store: &Store,
+    tree_diff: BoxStream<'_, CopiesTreeDiffEntry>,
+    path_converter: &RepoPathUiConverter,
+    options: &UnifiedDiffOptions,
+    marker_style: ConflictMarkerStyle,
+    width: usize,
+) -> Result<(), DiffRenderError> {
+    let materialize_options = ConflictMaterializeOptions {
+        marker_style,
+        marker_len: None,
+        merge: store.merge_options().clone(),
+    };
+    let binary_matcher = options.line_diff.binary_files.to_matcher();
+    let mut diff_stream = materialized_diff_stream(store, tree_diff);
+    while let Some(MaterializedTreeDiffEntry { path, values }) = diff_stream.next().await {
+        let left_path = path.source();
+        let right_path = path.target();
+        let left_ui_path = path_converter.format_file_path(left_path);
+        let right_ui_path = path_converter.format_file_path(right_path);
+        let (left_value, right_value) = values?;
+
+        match (&left_value, &right_value) {
+            (MaterializedTreeValue::AccessDenied(source), _) => {
+                write!(
+                    formatter.labeled("access-denied"),
+                    "Access denied to {left_ui_path}:"
+                )?;
+                writeln!(formatter, " {source}")?;
+                continue;
+            }
+            (_, MaterializedTreeValue::AccessDenied(source)) => {
+                write!(
+                    formatter.labeled("access-denied"),
+                    "Access denied to {right_ui_path}:"
+                )?;
+                writeln!(formatter, " {source}")?;
+                continue;
+            }
+            _ => {}
+        }
+        let is_added_or_removed = left_value.is_absent() || right_value.is_absent();
+        if left_value.is_absent() {
+            let description = basic_diff_file_type(&right_value);
+            writeln!(
+                formatter.labeled("header"),
+                "Added {description} {right_ui_path}:"
+            )?;
+        } else if right_value.is_absent() {
+            let description = basic_diff_file_type(&left_value);
+            writeln!(
+                formatter.labeled("header"),
+                "Removed {description} {right_ui_path}:"
+            )?;
+        } else {
+            let description = describe_modified_value(&left_value, &right_value);
+            if left_path == right_path {
+                writeln!(
+                    formatter.labeled("header"),
+                    "{description} {right_ui_path}:"
+                )?;
+            } else {
+                writeln!(
+                    formatter.labeled("header"),
+                    "{description} {right_ui_path} ({left_ui_path} => {right_ui_path}):"
+                )?;
+            }
+        }
+
+        let left_content = diff_content(
+            store,
+            left_path,
+            left_value,
+            binary_matcher.as_ref(),
+            &materialize_options,
+        )?;
+        let right_content = diff_content(
+            store,
+            right_path,
+            right_value,
+            binary_matcher.as_ref(),
+            &materialize_options,
+        )?;
+        if left_content.is_binary || right_content.is_binary {
+            writeln!(formatter.labeled("binary"), "    (binary)")?;
+        } else if left_content.contents != right_content.contents {
+            show_side_by_side_diff_hunks(
+                formatter,
+                [&left_content.contents, &right_content.contents].map(BStr::new),
+                [left_path, right_path].map(RepoPath::as_internal_file_string),
+                options,
+                width,
+            )?;
+        } else if is_added_or_removed {
+            writeln!(formatter.labeled("empty"), "    (empty)")?;
+        }
+    }
+    Ok(())
+}
+
+/// Renders the hunks of the unified diff in two columns, with the left
+/// contents on the left and the right contents on the right.
+fn show_side_by_side_diff_hunks(
+    formatter: &mut dyn Formatter,
+    contents: [&BStr; 2],
+    paths: [&str; 2],
+    options: &UnifiedDiffOptions,
+    width: usize,
+) -> io::Result<()> {
+    const SEPARATOR: &str = " │ ";
+    let highlights = syntax_highlights(contents, paths, options);
+    let hunks = unified_diff_hunks(contents, options);
+    let max_line_number = hunks
+        .iter()
+        .map(|hunk| max(hunk.left_line_range.end, hunk.right_line_range.end))
+        .max()
+        .unwrap_or(0);
+    let number_width = max(max_line_number.to_string().len(), 4);
+    // Each column consists of the line number, a space, and the line text.
+    let column_width = width.saturating_sub(SEPARATOR.width()) / 2;
+    let text_width = column_width.saturating_sub(number_width + 1).max(1);
+    let layout = SideBySideLayout {
+        number_width,
+        text_width,
+        separator: SEPARATOR,
+    };
+
+    for hunk in &hunks {
+        show_unified_diff_hunk_header(formatter, hunk)?;
+        let mut left_line_number = hunk.left_line_range.start + 1;
+        let mut right_line_number = hunk.right_line_range.start + 1;
+        let chunks = hunk
+            .lines
+            .iter()
+            .chunk_by(|(line_type, _)| *line_type == DiffLineType::Context);
+        for (is_context, lines) in &chunks {
+            if is_context {
+                for (_, tokens) in lines {
+                    let left = ("context", left_line_number, tokens.as_slice());
+                    let right = ("context", right_line_number, tokens.as_slice());
+                    layout.show_row(formatter, [Some(left), Some(right)], &highlights)?;
+                    left_line_number += 1;
+                    right_line_number += 1;
+                }
+                continue;
+            }
+            // Modified lines are listed as removed lines followed by added
+            // lines. Show them next to each other.
+            let (removed, added): (Vec<_>, Vec<_>) = lines
+                .map(|(line_type, tokens)| (*line_type, tokens.as_slice()))
+                .partition(|(line_type, _)| *line_type == DiffLineType::Removed);
+            for pair in removed.iter().zip_longest(&added) {
+                let (left, right) = pair
+                    .map_any(
+                        |(_, tokens)| {
+                            left_line_number += 1;
+                            ("removed", left_line_number - 1, *tokens)
+                        },
+                        |(_, tokens)| {
+                            right_line_number += 1;
+                            ("added", right_line_number - 1, *tokens)
+                        },
+                    )
+                    .left_and_right();
+                layout.show_row(formatter, [left, right], &highlights)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Label, line number, and tokens of a line in side-by-side diff.
+type SideBySideLine<'a> = (&'a str, usize, &'a [(DiffTokenType, &'a [u8])]);
+
+/// Column sizes of side-by-side diff.
+struct SideBySideLayout {
+    number_width: usize,
+    text_width: usize,
+    separator: &'static str,
+}
+
+impl SideBySideLayout {
+    /// Shows a row with the left and right lines.
+    fn show_row(
+        &self,
+        formatter: &mut dyn Formatter,
+        [left, right]: [Option<SideBySideLine>; 2],
+        highlights: &[(&BStr, SyntaxHighlights)],
+    ) -> io::Result<()> {
+        let number_width = self.number_width;
+        if let Some((label, line_number, tokens)) = left {
+            write!(
+                formatter.labeled(label).labeled("line_number"),
+                "{line_number:>number_width$}"
+            )?;
+            write!(formatter, " ")?;
+            let written_width =
+                self.show_line_text(*formatter.labeled(label), tokens, highlights)?;
+            write!(
+                formatter,
+                "{:pad$}",
+                "",
+                pad = self.text_width - written_width
+            )?;
+        } else {
+            write!(
+                formatter,
+                "{:pad$}",
+                "",
+                pad = number_width + 1 + self.text_width
+            )?;
+        }
+        if let Some((label, line_number, tokens)) = right {
+            write!(formatter, "{}", self.separator)?;
+            write!(
+                formatter.labeled(label).labeled("line_number"),
+                "{line_number:>number_width$}"
+            )?;
+            write!(formatter, " ")?;
+            self.show_line_text(*formatter.labeled(label), tokens, highlights)?;
+        } else {
+            write!(formatter, "{}", self.separator.trim_end())?;
+        }
+        writeln!(formatter)
+    }
+
+    /// Shows the line text truncated to the column width, and returns the
+    /// width of the shown text.
+    fn show_line_text(
+        &self,
+        formatter: &mut dyn Formatter,
+        tokens: &[(DiffTokenType, &[u8])],
+        highlights: &[(&BStr, SyntaxHighlights)],
+    ) -> io::Result<usize> {
+        let mut recorder = FormatRecorder::new();
+        if highlights.is_empty() {
+            show_diff_line_tokens(&mut recorder, tokens)?;
+        } else {
+            show_highlighted_diff_line_tokens(&mut recorder, tokens, highlights)?;
+        }
+        // Tabs and the line terminator would break the alignment of the
+        // columns.
+        let mut text = FormatRecorder::new();
+        recorder.replay_with(&mut text, |formatter, range| {
+            let data = recorder.data()[range].trim_end_with(|c| c == '\n' || c == '\r');
+            formatter.write_all(&data.replace("\t", "    "))
+        })?;
+        let ellipsis = FormatRecorder::with_data("…");
+        text_util::write_truncated_end(formatter, &text, &ellipsis, self.text_width)
+    }
 }
 
 #[instrument(skip_all)]
@@ -2031,6 +2658,39 @@ pub async fn show_diff_summary(
     Ok(())
 }
 
+/// Entry of `--summary` output in `--output json` mode.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct DiffSummaryEntry {
+    /// One of `modified`, `added`, `removed`, `copied`, or `renamed`.
+    pub status: &'static str,
+    /// Repo-relative path of the file after the change.
+    pub path: String,
+    /// Repo-relative path of the copy or rename source.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// Collects the entries of `tree_diff` in the structured form of
+/// [`show_diff_summary()`].
+pub async fn collect_diff_summary(
+    mut tree_diff: BoxStream<'_, CopiesTreeDiffEntry>,
+) -> Result<Vec<DiffSummaryEntry>, DiffRenderError> {
+    let mut entries = vec![];
+    while let Some(CopiesTreeDiffEntry { path, values }) = tree_diff.next().await {
+        let values = values?;
+        let (status, _) = diff_status_label_and_char(&path, &values);
+        let source = path
+            .copy_operation()
+            .map(|_| path.source().as_internal_file_string().to_owned());
+        entries.push(DiffSummaryEntry {
+            status,
+            path: path.target().as_internal_file_string().to_owned(),
+            source,
+        });
+    }
+    Ok(entries)
+}
+
 pub fn diff_status_label_and_char(
     path: &CopiesTreeDiffEntryPath,
     values: &Diff<MergedTreeValue>,
@@ -2057,6 +2717,12 @@ pub struct DiffStatOptions {
 }
 
 impl DiffStatOptions {
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        Ok(Self {
+            line_diff: LineDiffOptions::from_settings(settings)?,
+        })
+    }
+
     fn merge_args(&mut self, args: &DiffFormatArgs) {
         self.line_diff.merge_args(args);
     }
@@ -2080,11 +2746,24 @@ impl DiffStats {
             marker_len: None,
             merge: store.merge_options().clone(),
         };
+        let binary_matcher = options.line_diff.binary_files.to_matcher();
         let entries = materialized_diff_stream(store, tree_diff)
             .map(|MaterializedTreeDiffEntry { path, values }| {
                 let (left, right) = values?;
-                let left_content = diff_content(path.source(), left, &materialize_options)?;
-                let right_content = diff_content(path.target(), right, &materialize_options)?;
+                let left_content = diff_content(
+                    store,
+                    path.source(),
+                    left,
+                    binary_matcher.as_ref(),
+                    &materialize_options,
+                )?;
+                let right_content = diff_content(
+                    store,
+                    path.target(),
+                    right,
+                    binary_matcher.as_ref(),
+                    &materialize_options,
+                )?;
                 let stat = get_diff_stat_entry(path, [&left_content, &right_content], options);
                 BackendResult::Ok(stat)
             })
@@ -31,6 +31,8 @@ use crossterm::style::SetBackgroundColor;
 use crossterm::style::SetForegroundColor;
 use itertools::Itertools as _;
 use jj_lib::config::ConfigGetError;
+use jj_lib::config::ConfigGetResultExt as _;
+use jj_lib::config::ConfigSource;
 use jj_lib::config::StackedConfig;
 use serde::de::Deserialize as _;
 use serde::de::Error as _;
@@ -445,14 +447,44 @@ impl<W: Write> ColorFormatter<W> {
 }
 
 fn rules_from_config(config: &StackedConfig) -> Result<Rules, ConfigGetError> {
+    // The colors of the theme override the built-in colors, and are in turn
+    // overridden by the colors configured by the user.
+    let (default_layers, user_layers): (Vec<_>, Vec<_>) = config
+        .layers()
+        .iter()
+        .cloned()
+        .partition(|layer| layer.source == ConfigSource::Default);
+    let mut default_config = StackedConfig::empty();
+    default_config.extend_layers(default_layers);
+    let mut user_config = StackedConfig::empty();
+    user_config.extend_layers(user_layers);
+
+    let mut rules = rules_from_table(&default_config, &["colors"])?;
+    if let Some(theme) = config.get::<String>("ui.theme").optional()? {
+        if config.get_table(["themes", &theme]).is_err() {
+            return Err(ConfigGetError::Type {
+                name: "ui.theme".to_owned(),
+                error: format!("No such theme: {theme}").into(),
+                source_path: None,
+            });
+        }
+        rules.extend(rules_from_table(config, &["themes", &theme])?);
+    }
+    rules.extend(rules_from_table(&user_config, &["colors"])?);
+    Ok(rules)
+}
+
+/// Parses the table of styles at `table_path`, e.g. `colors`.
+fn rules_from_table(config: &StackedConfig, table_path: &[&str]) -> Result<Rules, ConfigGetError> {
     config
-        .table_keys("colors")
+        .table_keys(table_path)
         .map(|key| {
             let labels = key
                 .split_whitespace()
                 .map(ToString::to_string)
                 .collect_vec();
-            let style = config.get_value_with(["colors", key], |value| {
+            let name = table_path.iter().copied().chain([key]).collect_vec();
+            let style = config.get_value_with(name.as_slice(), |value| {
                 if value.is_str() {
                     Ok(Style {
                         fg: Some(deserialize_color(value.into_deserializer())?),
@@ -949,6 +981,51 @@ mod tests {
             to_snapshot_string(output), @" before [38;5;2m inside [39m after [EOF]");
     }
 
+    #[test]
+    fn test_color_formatter_theme() {
+        // Theme colors override the default colors, but not the user colors.
+        let mut config = StackedConfig::empty();
+        config.add_layer(
+            ConfigLayer::parse(
+                ConfigSource::Default,
+                indoc! {"
+                    colors.a = 'red'
+                    colors.b = 'red'
+                    themes.default = {}
+                "},
+            )
+            .unwrap(),
+        );
+        config.add_layer(
+            ConfigLayer::parse(
+                ConfigSource::User,
+                indoc! {"
+                    ui.theme = 'custom'
+                    themes.custom.a = 'green'
+                    themes.custom.b = 'green'
+                    colors.b = 'blue'
+                "},
+            )
+            .unwrap(),
+        );
+        let mut output: Vec<u8> = vec![];
+        let mut formatter = ColorFormatter::for_config(&mut output, &config, false).unwrap();
+        for label in ["a", "b"] {
+            formatter.push_label(label);
+            write!(formatter, " {label} ").unwrap();
+            formatter.pop_label();
+        }
+        drop(formatter);
+        insta::assert_snapshot!(
+            to_snapshot_string(output), @"[38;5;2m a [38;5;4m b [39m[EOF]");
+
+        let config = config_from_string("ui.theme = 'unknown'");
+        let err = ColorFormatter::for_config(vec![], &config, false)
+            .map(|_| ())
+            .unwrap_err();
+        insta::assert_snapshot!(err, @"Invalid type or value for ui.theme");
+    }
+
     #[test]
     fn test_color_formatter_attributes() {
         // Test that each attribute of the style can be set and that they can be
@@ -21,7 +21,11 @@ use jj_lib::graph::GraphEdge;
 use jj_lib::graph::GraphEdgeType;
 use jj_lib::settings::UserSettings;
 use renderdag::Ancestor;
+use renderdag::GraphRow;
 use renderdag::GraphRowRenderer;
+use renderdag::LinkLine;
+use renderdag::NodeLine;
+use renderdag::PadLine;
 use renderdag::Renderer;
 
 pub trait GraphLog<K: Clone + Eq + Hash> {
@@ -101,6 +105,7 @@ pub enum GraphStyle {
     AsciiLarge,
     Curved,
     Square,
+    Compact,
 }
 
 impl GraphStyle {
@@ -121,5 +126,196 @@ pub fn get_graphlog<'a, K: Clone + Eq + Hash + 'a>(
         GraphStyle::Square => {
             SaplingGraphLog::create(builder.build_box_drawing().with_square_glyphs(), formatter)
         }
+        GraphStyle::Compact => {
+            SaplingGraphLog::create(CompactRenderer::new(GraphRowRenderer::new()), formatter)
+        }
+    }
+}
+
+/// Renders the graph with curved box drawing characters, using a single
+/// character per column instead of two.
+///
+/// Unlike the other renderers, there's no space between the columns, so the
+/// graph takes about half as much horizontal space.
+struct CompactRenderer<N, R> {
+    inner: R,
+    /// Pad line to be printed before the next row, after a terminated column.
+    extra_pad_line: Option<String>,
+    _phantom: std::marker::PhantomData<N>,
+}
+
+impl<N, R> CompactRenderer<N, R> {
+    fn new(inner: R) -> Self {
+        Self {
+            inner,
+            extra_pad_line: None,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+fn compact_pad_glyph(pad: PadLine) -> char {
+    match pad {
+        PadLine::Parent => '│',
+        PadLine::Ancestor => '╷',
+        PadLine::Blank => ' ',
+    }
+}
+
+/// Returns the glyph of a link column. This follows the logic of the box
+/// drawing renderer of `renderdag`.
+fn compact_link_glyph(cur: LinkLine, merge: bool) -> char {
+    if cur.intersects(LinkLine::HORIZONTAL) {
+        if cur.intersects(LinkLine::CHILD)
+            || (cur.intersects(LinkLine::ANY_FORK) && cur.intersects(LinkLine::ANY_MERGE))
+            || (cur.intersects(LinkLine::ANY_FORK)
+                && cur.intersects(LinkLine::VERT_PARENT)
+                && !merge)
+        {
+            '┼'
+        } else if cur.intersects(LinkLine::ANY_FORK) {
+            '┬'
+        } else if cur.intersects(LinkLine::ANY_MERGE) {
+            '┴'
+        } else {
+            '─'
+        }
+    } else if cur.intersects(LinkLine::VERT_PARENT) && !merge {
+        let left = cur.intersects(LinkLine::LEFT_MERGE | LinkLine::LEFT_FORK);
+        let right = cur.intersects(LinkLine::RIGHT_MERGE | LinkLine::RIGHT_FORK);
+        match (left, right) {
+            (true, true) => '┼',
+            (true, false) => '┤',
+            (false, true) => '├',
+            (false, false) => '│',
+        }
+    } else if cur.intersects(LinkLine::VERTICAL)
+        && !cur.intersects(LinkLine::LEFT_FORK | LinkLine::RIGHT_FORK)
+    {
+        let left = cur.intersects(LinkLine::LEFT_MERGE);
+        let right = cur.intersects(LinkLine::RIGHT_MERGE);
+        match (left, right) {
+            (true, true) => '┼',
+            (true, false) => '┤',
+            (false, true) => '├',
+            (false, false) if cur.intersects(LinkLine::VERT_ANCESTOR) => '╷',
+            (false, false) => '│',
+        }
+    } else if cur.intersects(LinkLine::LEFT_FORK)
+        && cur.intersects(LinkLine::LEFT_MERGE | LinkLine::CHILD)
+    {
+        '┤'
+    } else if cur.intersects(LinkLine::RIGHT_FORK)
+        && cur.intersects(LinkLine::RIGHT_MERGE | LinkLine::CHILD)
+    {
+        '├'
+    } else if cur.intersects(LinkLine::LEFT_MERGE) && cur.intersects(LinkLine::RIGHT_MERGE) {
+        '┴'
+    } else if cur.intersects(LinkLine::LEFT_FORK) && cur.intersects(LinkLine::RIGHT_FORK) {
+        '┬'
+    } else if cur.intersects(LinkLine::LEFT_FORK) {
+        '╮'
+    } else if cur.intersects(LinkLine::LEFT_MERGE) {
+        '╯'
+    } else if cur.intersects(LinkLine::RIGHT_FORK) {
+        '╭'
+    } else if cur.intersects(LinkLine::RIGHT_MERGE) {
+        '╰'
+    } else {
+        ' '
+    }
+}
+
+impl<N, R> Renderer<N> for CompactRenderer<N, R>
+where
+    N: Clone + Eq,
+    R: Renderer<N, Output = GraphRow<N>>,
+{
+    type Output = String;
+
+    fn width(&self, node: Option<&N>, parents: Option<&Vec<Ancestor<N>>>) -> u64 {
+        self.inner.width(node, parents).saturating_add(1)
+    }
+
+    fn reserve(&mut self, node: N) {
+        self.inner.reserve(node);
+    }
+
+    fn next_row(
+        &mut self,
+        node: N,
+        parents: Vec<Ancestor<N>>,
+        glyph: String,
+        message: String,
+    ) -> String {
+        let row = self.inner.next_row(node, parents, glyph, message);
+        let mut out = String::new();
+        let mut message_lines = row.message.lines();
+        let mut push_line = |mut graph: String, message: Option<&str>| {
+            if let Some(message) = message {
+                graph.push(' ');
+                graph.push_str(message);
+            }
+            out.push_str(graph.trim_end());
+            out.push('\n');
+        };
+
+        if let Some(extra_pad_line) = self.extra_pad_line.take() {
+            push_line(extra_pad_line, None);
+        }
+
+        let node_line = row
+            .node_line
+            .iter()
+            .map(|entry| match entry {
+                NodeLine::Node => row.glyph.as_str(),
+                NodeLine::Parent => "│",
+                NodeLine::Ancestor => "╷",
+                NodeLine::Blank => " ",
+            })
+            .collect();
+        push_line(node_line, message_lines.next());
+
+        if let Some(link_line) = &row.link_line {
+            let link_line = link_line
+                .iter()
+                .map(|cur| compact_link_glyph(*cur, row.merge))
+                .collect();
+            push_line(link_line, message_lines.next());
+        }
+
+        let base_pad_line: String = row
+            .pad_lines
+            .iter()
+            .copied()
+            .map(compact_pad_glyph)
+            .collect();
+        let mut need_extra_pad_line = false;
+        if let Some(term_line) = &row.term_line {
+            for term_glyph in ['│', '~'] {
+                let line = term_line
+                    .iter()
+                    .zip(&row.pad_lines)
+                    .map(|(term, pad)| {
+                        if *term {
+                            term_glyph
+                        } else {
+                            compact_pad_glyph(*pad)
+                        }
+                    })
+                    .collect();
+                push_line(line, message_lines.next());
+            }
+            need_extra_pad_line = true;
+        }
+
+        for message in message_lines {
+            push_line(base_pad_line.clone(), Some(message));
+            need_extra_pad_line = false;
+        }
+        if need_extra_pad_line {
+            self.extra_pad_line = Some(base_pad_line);
+        }
+        out
     }
 }
@@ -0,0 +1,247 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! User-configured programs run at certain points of command execution.
+//!
+//! Hooks are configured as `hooks.<name>`. The program receives a JSON object
+//! describing the change in its stdin. If a `pre-*` hook exits with non-zero
+//! status, the operation is aborted.
+
+use std::io::Write as _;
+use std::path::Path;
+use std::process::Stdio;
+
+use jj_lib::backend::ChangeId;
+use jj_lib::backend::CommitId;
+use jj_lib::backend::Signature;
+use jj_lib::commit::Commit;
+use jj_lib::config::ConfigGetResultExt as _;
+use jj_lib::settings::UserSettings;
+use serde::Serialize;
+
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::config::CommandNameAndArgs;
+use crate::ui::Ui;
+
+/// Point of command execution where a hook is run.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HookKind {
+    /// Before committing a transaction that creates or rewrites commits.
+    PreCommitRewrite,
+    /// Before pushing bookmarks to a remote.
+    PrePush,
+    /// After committing a transaction.
+    PostOperation,
+    /// After updating the working copy to another commit.
+    PostCheckout,
+}
+
+impl HookKind {
+    /// Config name of the hook.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::PreCommitRewrite => "pre-commit-rewrite",
+            Self::PrePush => "pre-push",
+            Self::PostOperation => "post-operation",
+            Self::PostCheckout => "post-checkout",
+        }
+    }
+
+    /// Whether the hook can abort the operation.
+    pub fn can_veto(self) -> bool {
+        match self {
+            Self::PreCommitRewrite | Self::PrePush => true,
+            Self::PostOperation | Self::PostCheckout => false,
+        }
+    }
+}
+
+/// Returns true if the hook of the `kind` is configured.
+///
+/// Use this to avoid computing the hook input if it wouldn't be used.
+pub fn is_hook_enabled(settings: &UserSettings, kind: HookKind) -> Result<bool, CommandError> {
+    Ok(get_hook_command(settings, kind)?.is_some())
+}
+
+fn get_hook_command(
+    settings: &UserSettings,
+    kind: HookKind,
+) -> Result<Option<CommandNameAndArgs>, CommandError> {
+    let command = settings
+        .get::<CommandNameAndArgs>(["hooks", kind.name()])
+        .optional()?;
+    Ok(command)
+}
+
+/// Runs the hook of the `kind` if configured, passing `input` serialized as
+/// JSON object to its stdin.
+///
+/// The output of the hook is printed to stderr. If the hook can veto the
+/// operation and exits with non-zero status, an error is returned. Failures of
+/// the other hooks are reported as warnings.
+pub fn run_hook(
+    ui: &Ui,
+    settings: &UserSettings,
+    workspace_root: &Path,
+    repo_path: &Path,
+    kind: HookKind,
+    input: &impl Serialize,
+) -> Result<(), CommandError> {
+    let Some(hook_command) = get_hook_command(settings, kind)? else {
+        return Ok(());
+    };
+    let input = serde_json::to_vec(&HookInput {
+        hook: kind.name(),
+        data: input,
+    })
+    .unwrap();
+    let mut command = hook_command.to_command();
+    command
+        .current_dir(workspace_root)
+        .env("JJ_HOOK", kind.name())
+        .env("JJ_WORKSPACE_ROOT", workspace_root)
+        .env("JJ_REPO_PATH", repo_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Ok(jj_path) = std::env::current_exe() {
+        command.env("JJ_EXECUTABLE_PATH", jj_path);
+    }
+    tracing::info!(?command, "running hook");
+    let mut child = command.spawn().map_err(|err| {
+        user_error_with_message(
+            format!(
+                "Failed to run {name} hook `{hook_command}`",
+                name = kind.name()
+            ),
+            err,
+        )
+    })?;
+    let mut stdin = child.stdin.take().unwrap();
+    let output = std::thread::scope(|s| {
+        s.spawn(move || {
+            // The hook may exit without reading the input.
+            stdin.write_all(&input).ok();
+        });
+        child.wait_with_output()
+    })
+    .map_err(|err| {
+        user_error_with_message(
+            format!(
+                "Failed to wait for {name} hook `{hook_command}`",
+                name = kind.name()
+            ),
+            err,
+        )
+    })?;
+    tracing::info!(?output.status, "hook exited");
+    let mut stderr = ui.stderr();
+    stderr.write_all(&output.stdout)?;
+    stderr.write_all(&output.stderr)?;
+    drop(stderr);
+    if output.status.success() {
+        Ok(())
+    } else if kind.can_veto() {
+        Err(user_error(format!(
+            "The {name} hook rejected the operation ({status})",
+            name = kind.name(),
+            status = output.status
+        )))
+    } else {
+        writeln!(
+            ui.warning_default(),
+            "The {name} hook failed ({status})",
+            name = kind.name(),
+            status = output.status
+        )?;
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct HookInput<'a, T> {
+    hook: &'a str,
+    #[serde(flatten)]
+    data: T,
+}
+
+/// Commit described in hook input.
+#[derive(Clone, Debug, Serialize)]
+pub struct HookCommit {
+    pub commit_id: CommitId,
+    pub change_id: ChangeId,
+    pub parent_ids: Vec<CommitId>,
+    pub predecessor_ids: Vec<CommitId>,
+    pub description: String,
+    pub author: Signature,
+    pub committer: Signature,
+}
+
+impl HookCommit {
+    pub fn new(commit: &Commit, predecessor_ids: Vec<CommitId>) -> Self {
+        Self {
+            commit_id: commit.id().clone(),
+            change_id: commit.change_id().clone(),
+            parent_ids: commit.parent_ids().to_vec(),
+            predecessor_ids,
+            description: commit.description().to_owned(),
+            author: commit.author().clone(),
+            committer: commit.committer().clone(),
+        }
+    }
+}
+
+/// Input of the `pre-commit-rewrite` hook.
+#[derive(Clone, Debug, Serialize)]
+pub struct PreCommitRewriteInput {
+    /// Description of the pending operation.
+    pub operation: String,
+    /// Commits that become visible.
+    pub new_commits: Vec<HookCommit>,
+    /// Commits that become hidden, including predecessors of rewritten
+    /// commits.
+    pub hidden_commit_ids: Vec<CommitId>,
+}
+
+/// Input of the `pre-push` hook.
+#[derive(Clone, Debug, Serialize)]
+pub struct PrePushInput {
+    pub remote: String,
+    pub updates: Vec<PushUpdate>,
+}
+
+/// Bookmark update described in the `pre-push` hook input.
+#[derive(Clone, Debug, Serialize)]
+pub struct PushUpdate {
+    pub bookmark: String,
+    pub old_target: Option<CommitId>,
+    pub new_target: Option<CommitId>,
+}
+
+/// Input of the `post-operation` hook.
+#[derive(Clone, Debug, Serialize)]
+pub struct PostOperationInput {
+    pub operation_id: String,
+    pub description: String,
+}
+
+/// Input of the `post-checkout` hook.
+#[derive(Clone, Debug, Serialize)]
+pub struct PostCheckoutInput {
+    pub workspace: String,
+    pub old_commit_id: Option<CommitId>,
+    pub new_commit_id: CommitId,
+}
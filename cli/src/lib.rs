@@ -14,6 +14,7 @@
 
 #![deny(unused_must_use)]
 
+pub mod auto_gc;
 pub mod cleanup_guard;
 pub mod cli_util;
 pub mod command_error;
@@ -39,14 +40,19 @@ pub mod git_util {
     }
 }
 pub mod graphlog;
+pub mod hooks;
 pub mod merge_tools;
 pub mod movement_util;
+pub mod operation_notify;
 pub mod operation_templater;
 mod progress;
+pub mod revision_picker;
 pub mod revset_util;
+pub mod syntax_highlight;
 pub mod template_builder;
 pub mod template_parser;
 pub mod templater;
+pub mod test_results;
 pub mod text_util;
 pub mod time_util;
 pub mod ui;
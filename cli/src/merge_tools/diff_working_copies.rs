@@ -153,6 +153,7 @@ pub(crate) fn check_out_trees(
             conflict_marker_style,
             eol_conversion_mode: EolConversionMode::None,
             fsmonitor_settings: FsmonitorSettings::None,
+            skip_reserved_names: true,
         };
         let mut state = TreeState::init(store.clone(), wc_path, state_dir, &tree_state_settings)?;
         state.set_sparse_patterns(changed_files.clone())?;
@@ -283,6 +284,7 @@ diff editing in mind and be a little inaccurate.
             progress: None,
             start_tracking_matcher: &EverythingMatcher,
             max_new_file_size: u64::MAX,
+            max_new_file_size_overrides: vec![],
         })?;
         Ok(output_tree_state.current_tree_id().clone())
     }
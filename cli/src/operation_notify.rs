@@ -0,0 +1,144 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Notifications of completed operations for editors and GUIs.
+//!
+//! Tools showing the state of the repo can watch the notification file, or
+//! listen on the configured socket, instead of polling `jj op log`.
+
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use itertools::Itertools as _;
+use jj_lib::config::ConfigGetResultExt as _;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::ReadonlyRepo;
+use jj_lib::settings::UserSettings;
+use serde::Serialize;
+
+/// Name of the file in the repo directory which is rewritten whenever an
+/// operation completes, if `notify.operation-file` is enabled.
+pub const OPERATION_FILE_NAME: &str = "last_operation.json";
+
+/// Message describing a completed operation.
+#[derive(Clone, Debug, Serialize)]
+pub struct OperationNotification {
+    pub operation_id: String,
+    /// Workspaces whose working-copy commit was changed by the operation.
+    pub workspaces: Vec<String>,
+}
+
+impl OperationNotification {
+    /// Describes the operation of `new_repo` which was created on top of
+    /// `old_repo`.
+    pub fn new(old_repo: &ReadonlyRepo, new_repo: &ReadonlyRepo) -> Self {
+        let old_wc_commit_ids = old_repo.view().wc_commit_ids();
+        let new_wc_commit_ids = new_repo.view().wc_commit_ids();
+        let workspaces = old_wc_commit_ids
+            .keys()
+            .chain(new_wc_commit_ids.keys())
+            .filter(|name| old_wc_commit_ids.get(*name) != new_wc_commit_ids.get(*name))
+            .map(|name| name.as_str().to_owned())
+            .sorted()
+            .dedup()
+            .collect();
+        Self {
+            operation_id: new_repo.op_id().hex(),
+            workspaces,
+        }
+    }
+}
+
+/// Notifies the configured listeners that the operation of `new_repo`
+/// completed.
+///
+/// Failures are logged and otherwise ignored since the operation has already
+/// been committed.
+pub fn notify_operation_completed(
+    settings: &UserSettings,
+    repo_path: &Path,
+    old_repo: &ReadonlyRepo,
+    new_repo: &ReadonlyRepo,
+) {
+    if old_repo.op_id() == new_repo.op_id() {
+        return;
+    }
+    let notification = OperationNotification::new(old_repo, new_repo);
+    send_operation_notification(settings, repo_path, &notification);
+}
+
+/// Sends the `notification` to the configured listeners.
+///
+/// Failures are logged and otherwise ignored.
+pub fn send_operation_notification(
+    settings: &UserSettings,
+    repo_path: &Path,
+    notification: &OperationNotification,
+) {
+    let write_file = settings
+        .get_bool("notify.operation-file")
+        .unwrap_or_else(|err| {
+            tracing::warn!(?err, "invalid notify.operation-file");
+            false
+        });
+    let socket_path = settings
+        .get::<PathBuf>("notify.operation-socket")
+        .optional()
+        .unwrap_or_else(|err| {
+            tracing::warn!(?err, "invalid notify.operation-socket");
+            None
+        });
+    if !write_file && socket_path.is_none() {
+        return;
+    }
+
+    let mut message = serde_json::to_vec(notification).unwrap();
+    message.push(b'\n');
+    if write_file && let Err(err) = write_operation_file(repo_path, &message) {
+        tracing::warn!(?err, "failed to write operation notification file");
+    }
+    if let Some(path) = &socket_path
+        && let Err(err) = send_to_socket(path, &message)
+    {
+        tracing::warn!(?err, ?path, "failed to send operation notification");
+    }
+}
+
+fn write_operation_file(repo_path: &Path, message: &[u8]) -> io::Result<()> {
+    // Replace the file atomically so readers never see partial content.
+    let mut temp_file = tempfile::NamedTempFile::new_in(repo_path)?;
+    temp_file.write_all(message)?;
+    temp_file.persist(repo_path.join(OPERATION_FILE_NAME))?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn send_to_socket(path: &Path, message: &[u8]) -> io::Result<()> {
+    use std::os::unix::net::UnixStream;
+    use std::time::Duration;
+
+    let mut stream = UnixStream::connect(path)?;
+    // Don't block the command if the listener doesn't read the message.
+    stream.set_write_timeout(Some(Duration::from_secs(1)))?;
+    stream.write_all(message)
+}
+
+#[cfg(not(unix))]
+fn send_to_socket(path: &Path, message: &[u8]) -> io::Result<()> {
+    // Named pipes such as `\\.\pipe\name` can be opened as files.
+    let mut pipe = std::fs::OpenOptions::new().write(true).open(path)?;
+    pipe.write_all(message)
+}
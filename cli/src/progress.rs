@@ -1,5 +1,8 @@
 use std::path::Path;
 use std::sync::Mutex;
+use std::sync::mpsc;
+use std::thread;
+use std::thread::JoinHandle;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -64,3 +67,80 @@ pub fn snapshot_progress(ui: &Ui) -> Option<impl Fn(&RepoPath) + use<>> {
         _ = state.output.flush();
     })
 }
+
+/// Returns a callback that displays `message` with the number of items
+/// processed so far, for operations whose total isn't known in advance.
+pub fn count_progress(ui: &Ui, message: &'static str) -> Option<impl FnMut(usize) + use<>> {
+    let mut output = ui.progress_output()?;
+    let mut guard = None;
+    // Don't clutter the output during fast operations.
+    let mut next_display_time = Instant::now() + INITIAL_DELAY;
+    Some(move |count: usize| {
+        let now = Instant::now();
+        if now < next_display_time {
+            return;
+        }
+        next_display_time = now + Duration::from_secs(1) / UPDATE_HZ;
+        guard.get_or_insert_with(|| {
+            output.output_guard(format!("\r{}", Clear(ClearType::CurrentLine)))
+        });
+        _ = write!(
+            output,
+            "\r{}{message}: {count}",
+            Clear(ClearType::CurrentLine),
+        );
+        _ = output.flush();
+    })
+}
+
+/// Spinner displayed while an operation of unknown length is running.
+///
+/// The spinner is drawn by a background thread, and is removed when this is
+/// dropped.
+pub struct Spinner {
+    stop_sender: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for Spinner {
+    fn drop(&mut self) {
+        // Disconnecting the channel stops the thread.
+        drop(self.stop_sender.take());
+        if let Some(thread) = self.thread.take() {
+            _ = thread.join();
+        }
+    }
+}
+
+/// Starts displaying a spinner with `message` until the returned value is
+/// dropped.
+pub fn spinner(ui: &Ui, message: impl Into<String>) -> Option<Spinner> {
+    const FRAMES: [char; 4] = ['|', '/', '-', '\\'];
+    const FRAME_INTERVAL: Duration = Duration::from_millis(100);
+
+    let mut output = ui.progress_output()?;
+    let message = message.into();
+    let (stop_sender, stop_receiver) = mpsc::channel();
+    let thread = thread::spawn(move || {
+        // Don't clutter the output during fast operations.
+        if stop_receiver.recv_timeout(INITIAL_DELAY) != Err(mpsc::RecvTimeoutError::Timeout) {
+            return;
+        }
+        let _guard = output.output_guard(format!("\r{}", Clear(ClearType::CurrentLine)));
+        for frame in FRAMES.iter().cycle() {
+            _ = write!(
+                output,
+                "\r{}{message} {frame}",
+                Clear(ClearType::CurrentLine),
+            );
+            _ = output.flush();
+            if stop_receiver.recv_timeout(FRAME_INTERVAL) != Err(mpsc::RecvTimeoutError::Timeout) {
+                break;
+            }
+        }
+    });
+    Some(Spinner {
+        stop_sender: Some(stop_sender),
+        thread: Some(thread),
+    })
+}
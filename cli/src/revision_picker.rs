@@ -0,0 +1,240 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Interactive picker used when a revision argument is `?`.
+
+use std::io;
+use std::io::IsTerminal as _;
+use std::io::Write as _;
+
+use crossterm::cursor;
+use crossterm::event;
+use crossterm::event::Event;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use crossterm::queue;
+use crossterm::style::Attribute;
+use crossterm::style::SetAttribute;
+use crossterm::terminal;
+use itertools::Itertools as _;
+
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::text_util;
+use crate::ui::Ui;
+
+/// Revision argument which opens the picker instead of being parsed as a
+/// revset.
+pub const PICK_REVISION_ARG: &str = "?";
+
+/// Lets the user choose one of the `labels`, and returns its index.
+///
+/// If stdin and stderr are terminals, a full-screen picker is shown, where the
+/// labels can be filtered by typing a fuzzy search query. Otherwise, the user
+/// is prompted for the number of the label.
+pub fn pick(ui: &Ui, labels: &[String]) -> Result<usize, CommandError> {
+    if labels.is_empty() {
+        return Err(user_error("There are no revisions to pick from"));
+    }
+    let picked = if io::stdin().is_terminal() && io::stderr().is_terminal() {
+        run_terminal_picker(labels)?
+    } else if Ui::can_prompt() {
+        prompt_for_index(ui, labels)?
+    } else {
+        return Err(user_error_with_hint(
+            "Cannot pick a revision since the output is not connected to a terminal",
+            "Specify the revision instead of `?`.",
+        ));
+    };
+    picked.ok_or_else(|| user_error("No revision was picked"))
+}
+
+fn prompt_for_index(ui: &Ui, labels: &[String]) -> io::Result<Option<usize>> {
+    let mut stderr = ui.stderr();
+    for (i, label) in labels.iter().enumerate() {
+        writeln!(stderr, "{}: {label}", i + 1)?;
+    }
+    writeln!(stderr, "q: quit the prompt")?;
+    drop(stderr);
+    let choices = (1..=labels.len())
+        .map(|i| i.to_string())
+        .chain(["q".to_owned()])
+        .collect_vec();
+    let index = ui.prompt_choice("Enter the number of the revision", &choices, None)?;
+    Ok((index < labels.len()).then_some(index))
+}
+
+/// Switches the terminal to the alternate screen in raw mode, and restores the
+/// terminal state when dropped.
+pub(crate) struct TerminalGuard;
+
+impl TerminalGuard {
+    pub(crate) fn new() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let guard = Self;
+        crossterm::execute!(io::stderr(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        _ = crossterm::execute!(io::stderr(), cursor::Show, terminal::LeaveAlternateScreen);
+        _ = terminal::disable_raw_mode();
+    }
+}
+
+fn run_terminal_picker(labels: &[String]) -> io::Result<Option<usize>> {
+    let _guard = TerminalGuard::new()?;
+    let mut query = String::new();
+    let mut selected = 0;
+    loop {
+        let matches = filter_labels(&query, labels);
+        selected = selected.min(matches.len().saturating_sub(1));
+        draw(&query, labels, &matches, selected)?;
+
+        let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press | KeyEventKind::Repeat,
+            ..
+        }) = event::read()?
+        else {
+            continue;
+        };
+        let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+        match code {
+            KeyCode::Esc => return Ok(None),
+            KeyCode::Char('c') if ctrl => return Ok(None),
+            KeyCode::Enter => return Ok(matches.get(selected).copied()),
+            KeyCode::Up => selected = selected.saturating_sub(1),
+            KeyCode::Char('p') if ctrl => selected = selected.saturating_sub(1),
+            KeyCode::Down => selected += 1,
+            KeyCode::Char('n') if ctrl => selected += 1,
+            KeyCode::Backspace => {
+                query.pop();
+            }
+            KeyCode::Char('u') if ctrl => query.clear(),
+            KeyCode::Char(c) if !ctrl => {
+                query.push(c);
+                selected = 0;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn draw(query: &str, labels: &[String], matches: &[usize], selected: usize) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    let width = usize::from(width);
+    let mut stderr = io::stderr().lock();
+    queue!(
+        stderr,
+        cursor::MoveTo(0, 0),
+        terminal::Clear(terminal::ClearType::All)
+    )?;
+    let header = format!("{}/{} > {query}", matches.len(), labels.len());
+    write!(
+        stderr,
+        "{}",
+        text_util::elide_start(&header, "...", width).0
+    )?;
+    // Scroll so that the selected label is visible.
+    let num_rows = usize::from(height).saturating_sub(1).max(1);
+    let first_row = selected.saturating_sub(num_rows - 1);
+    for (row, &index) in matches.iter().enumerate().skip(first_row).take(num_rows) {
+        queue!(stderr, cursor::MoveTo(0, (row - first_row + 1) as u16))?;
+        let (label, _) = text_util::elide_end(&labels[index], "...", width);
+        if row == selected {
+            queue!(stderr, SetAttribute(Attribute::Reverse))?;
+            write!(stderr, "{label}")?;
+            queue!(stderr, SetAttribute(Attribute::Reset))?;
+        } else {
+            write!(stderr, "{label}")?;
+        }
+    }
+    stderr.flush()
+}
+
+/// Returns the indices of the `labels` matching the fuzzy `query`, best match
+/// first. Labels that match equally well are kept in their original order.
+fn filter_labels(query: &str, labels: &[String]) -> Vec<usize> {
+    labels
+        .iter()
+        .enumerate()
+        .filter_map(|(index, label)| Some((fuzzy_match(query, label)?, index)))
+        .sorted()
+        .map(|(_, index)| index)
+        .collect()
+}
+
+/// Matches the characters of `query` in order against `text`, ignoring case.
+///
+/// Returns `None` if `text` doesn't match. Otherwise returns a score, which is
+/// lower for better matches: matches with fewer gaps between the matched
+/// characters, then matches starting earlier, are preferred.
+fn fuzzy_match(query: &str, text: &str) -> Option<(usize, usize)> {
+    let mut query_chars = query.chars().flat_map(char::to_lowercase).peekable();
+    let mut num_gaps = 0;
+    let mut start = None;
+    let mut last_matched = None;
+    for (pos, c) in text.chars().flat_map(char::to_lowercase).enumerate() {
+        let Some(&query_char) = query_chars.peek() else {
+            break;
+        };
+        if c != query_char {
+            continue;
+        }
+        query_chars.next();
+        start.get_or_insert(pos);
+        if last_matched.is_some_and(|last| last + 1 != pos) {
+            num_gaps += 1;
+        }
+        last_matched = Some(pos);
+    }
+    query_chars
+        .peek()
+        .is_none()
+        .then(|| (num_gaps, start.unwrap_or(0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert_eq!(fuzzy_match("", "anything"), Some((0, 0)));
+        assert_eq!(fuzzy_match("abc", "abc"), Some((0, 0)));
+        assert_eq!(fuzzy_match("abc", "xxabcxx"), Some((0, 2)));
+        assert_eq!(fuzzy_match("ABC", "xabc"), Some((0, 1)));
+        assert_eq!(fuzzy_match("abc", "a-b-c"), Some((2, 0)));
+        assert_eq!(fuzzy_match("abc", "ab-c"), Some((1, 0)));
+        assert_eq!(fuzzy_match("abc", "acb"), None);
+        assert_eq!(fuzzy_match("abc", "ab"), None);
+    }
+
+    #[test]
+    fn test_filter_labels() {
+        let labels = ["fix the bug", "add feature", "fix typo", "bump"].map(str::to_owned);
+        assert_eq!(filter_labels("", &labels), [0, 1, 2, 3]);
+        assert_eq!(filter_labels("fix", &labels), [0, 2]);
+        // Contiguous matches come first
+        assert_eq!(filter_labels("bu", &labels), [3, 0]);
+        assert_eq!(filter_labels("xyz", &labels), [] as [usize; 0]);
+    }
+}
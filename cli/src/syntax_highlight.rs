@@ -0,0 +1,237 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Syntax highlighting of file contents in diffs.
+//!
+//! Instead of using a color theme, the syntax elements are mapped to labels
+//! (e.g. `syntax keyword`), so they can be styled like the rest of the output.
+
+use std::io;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use syntect::parsing::ParseState;
+use syntect::parsing::Scope;
+use syntect::parsing::ScopeStack;
+use syntect::parsing::ScopeStackOp;
+use syntect::parsing::SyntaxReference;
+use syntect::parsing::SyntaxSet;
+
+use crate::formatter::Formatter;
+use crate::formatter::FormatterExt as _;
+
+/// Files larger than this aren't highlighted, as parsing is relatively slow.
+const MAX_CONTENT_SIZE: usize = 1024 * 1024;
+
+static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+
+/// Scope prefixes and their labels. The first matching entry wins, so more
+/// specific scopes must come first.
+static SCOPE_LABELS: LazyLock<Vec<(Scope, &'static str)>> = LazyLock::new(|| {
+    [
+        ("comment", "comment"),
+        ("string", "string"),
+        ("constant.character.escape", "string"),
+        ("constant", "constant"),
+        ("entity.name.type", "type"),
+        ("support.type", "type"),
+        ("entity.name.function", "function"),
+        ("support.function", "function"),
+        ("keyword.operator", "operator"),
+        ("keyword", "keyword"),
+        ("storage", "keyword"),
+    ]
+    .into_iter()
+    .map(|(scope, label)| (Scope::new(scope).unwrap(), label))
+    .collect()
+});
+
+/// Labeled byte ranges of a file content.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SyntaxHighlights {
+    /// Sorted, non-overlapping ranges and their labels.
+    spans: Vec<(Range<usize>, &'static str)>,
+}
+
+impl SyntaxHighlights {
+    /// Highlights `content` based on the file name or extension of `path`.
+    ///
+    /// Returns `None` if the file type isn't known, or if the content can't be
+    /// highlighted (e.g. because it isn't valid UTF-8).
+    pub fn new(path: &str, content: &[u8]) -> Option<Self> {
+        if content.len() > MAX_CONTENT_SIZE {
+            return None;
+        }
+        let syntax = find_syntax(path)?;
+        let text = str::from_utf8(content).ok()?;
+        let mut spans: Vec<(Range<usize>, &'static str)> = vec![];
+        let mut state = ParseState::new(syntax);
+        let mut stack = ScopeStack::new();
+        let mut line_start = 0;
+        for line in text.split_inclusive('\n') {
+            let ops = state.parse_line(line, &SYNTAX_SET).ok()?;
+            let mut pos = 0;
+            // Newlines aren't labeled, so the label of e.g. a line comment
+            // doesn't extend past the end of the line.
+            let text_len = line.trim_end_matches(['\r', '\n']).len();
+            let end_op = (line.len(), ScopeStackOp::Noop);
+            for (op_pos, op) in ops.iter().chain([&end_op]) {
+                let span_end = (*op_pos).min(text_len);
+                if span_end > pos
+                    && let Some(label) = label_for_scopes(&stack)
+                {
+                    let range = line_start + pos..line_start + span_end;
+                    match spans.last_mut() {
+                        Some((last, last_label))
+                            if last.end == range.start && *last_label == label =>
+                        {
+                            last.end = range.end;
+                        }
+                        _ => spans.push((range, label)),
+                    }
+                }
+                pos = *op_pos;
+                stack.apply(op).ok()?;
+            }
+            line_start += line.len();
+        }
+        Some(Self { spans })
+    }
+
+    /// Writes `data`, which starts at `offset` in the highlighted content, with
+    /// the labels of the syntax elements it contains.
+    pub fn write(
+        &self,
+        formatter: &mut dyn Formatter,
+        offset: usize,
+        data: &[u8],
+    ) -> io::Result<()> {
+        let end = offset + data.len();
+        let first = self.spans.partition_point(|(range, _)| range.end <= offset);
+        let mut pos = offset;
+        for (range, label) in &self.spans[first..] {
+            if range.start >= end {
+                break;
+            }
+            let start = range.start.max(pos);
+            formatter.write_all(&data[pos - offset..start - offset])?;
+            let span_end = range.end.min(end);
+            formatter
+                .labeled("syntax")
+                .labeled(label)
+                .write_all(&data[start - offset..span_end - offset])?;
+            pos = span_end;
+        }
+        formatter.write_all(&data[pos - offset..])
+    }
+}
+
+fn find_syntax(path: &str) -> Option<&'static SyntaxReference> {
+    let path = Path::new(path);
+    // Syntaxes list both extensions and file names like "Makefile".
+    let file_name = path.file_name()?.to_str()?;
+    SYNTAX_SET.find_syntax_by_extension(file_name).or_else(|| {
+        let extension = path.extension()?.to_str()?;
+        SYNTAX_SET.find_syntax_by_extension(extension)
+    })
+}
+
+fn label_for_scopes(stack: &ScopeStack) -> Option<&'static str> {
+    stack.as_slice().iter().rev().find_map(|scope| {
+        SCOPE_LABELS
+            .iter()
+            .find(|(prefix, _)| prefix.is_prefix_of(*scope))
+            .map(|(_, label)| *label)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use jj_lib::config::StackedConfig;
+
+    use super::*;
+    use crate::formatter::ColorFormatter;
+
+    fn labeled_spans(path: &str, content: &str) -> Vec<(&'static str, String)> {
+        let highlights = SyntaxHighlights::new(path, content.as_bytes()).unwrap();
+        highlights
+            .spans
+            .iter()
+            .map(|(range, label)| (*label, content[range.clone()].to_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn test_highlight_rust() {
+        let spans = labeled_spans(
+            "src/lib.rs",
+            "// hello\nfn main() { let x: Option<i32> = None; }\n",
+        );
+        insta::assert_debug_snapshot!(spans, @r#"
+        [
+            (
+                "comment",
+                "// hello",
+            ),
+            (
+                "keyword",
+                "fn",
+            ),
+            (
+                "function",
+                "main",
+            ),
+            (
+                "keyword",
+                "let",
+            ),
+            (
+                "keyword",
+                "i32",
+            ),
+            (
+                "operator",
+                "=",
+            ),
+            (
+                "type",
+                "None",
+            ),
+        ]
+        "#);
+    }
+
+    #[test]
+    fn test_unknown_file_type() {
+        assert_eq!(SyntaxHighlights::new("file.unknown-ext", b"text"), None);
+        assert_eq!(SyntaxHighlights::new("file", b"text"), None);
+        // Invalid UTF-8
+        assert_eq!(SyntaxHighlights::new("file.rs", b"\xff"), None);
+    }
+
+    #[test]
+    fn test_write_partial_content() {
+        let content = "let x = 1;\n";
+        let highlights = SyntaxHighlights::new("file.rs", content.as_bytes()).unwrap();
+        let mut output: Vec<u8> = vec![];
+        let mut formatter =
+            ColorFormatter::for_config(&mut output, &StackedConfig::empty(), true).unwrap();
+        highlights
+            .write(&mut formatter, 2, &content.as_bytes()[2..])
+            .unwrap();
+        drop(formatter);
+        insta::assert_snapshot!(String::from_utf8(output).unwrap(), @"<<syntax keyword::t>> x <<syntax operator::=>> <<syntax constant::1>>;");
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cache of `jj test` results.
+
+use std::fs;
+use std::io;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+
+use itertools::Itertools as _;
+use jj_lib::backend::MergedTreeId;
+use jj_lib::content_hash::blake2b_hash;
+use jj_lib::file_util::IoResultExt as _;
+use jj_lib::file_util::PathError;
+use jj_lib::file_util::persist_temp_file;
+use jj_lib::hex_util;
+
+use crate::config::CommandNameAndArgs;
+
+/// Outcome of running a test command on a tree.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TestResult {
+    Passed,
+    Failed,
+}
+
+impl TestResult {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Passed => "passed",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// Stores the results of a single test command, keyed by tree id.
+///
+/// Since the result only depends on the contents of the tree, commits that
+/// were rewritten without changing their contents (e.g. by `jj describe`)
+/// don't have to be tested again.
+#[derive(Clone, Debug)]
+pub struct TestResultStore {
+    dir: PathBuf,
+}
+
+impl TestResultStore {
+    /// Creates store for the results of `command` in the repo at `repo_path`.
+    pub fn new(repo_path: &Path, command: &CommandNameAndArgs) -> Self {
+        // Equivalent commands should share results no matter how they were
+        // spelled in the config, so hash the parsed command and environment.
+        let (name, args) = command.split_name_and_args();
+        let mut key = match command {
+            CommandNameAndArgs::Structured { env, .. } => env
+                .iter()
+                .map(|(name, value)| format!("{name}={value}"))
+                .sorted()
+                .collect_vec(),
+            _ => vec![],
+        };
+        key.push(name.into_owned());
+        key.extend(args.iter().cloned());
+        let command_hash = hex_util::encode_hex(&blake2b_hash(&key)[..16]);
+        Self {
+            dir: repo_path.join("test_results").join(command_hash),
+        }
+    }
+
+    /// Looks up the result of testing the given tree.
+    pub fn get(&self, tree_id: &MergedTreeId) -> Result<Option<TestResult>, PathError> {
+        let path = self.result_path(tree_id);
+        match fs::read_to_string(&path) {
+            Ok(content) => match content.trim() {
+                "passed" => Ok(Some(TestResult::Passed)),
+                "failed" => Ok(Some(TestResult::Failed)),
+                // Treat unknown content as untested so the result is updated.
+                _ => Ok(None),
+            },
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err).context(path),
+        }
+    }
+
+    /// Records the result of testing the given tree.
+    pub fn set(&self, tree_id: &MergedTreeId, result: TestResult) -> Result<(), PathError> {
+        fs::create_dir_all(&self.dir).context(&self.dir)?;
+        let mut temp_file = tempfile::NamedTempFile::new_in(&self.dir).context(&self.dir)?;
+        io::Write::write_all(temp_file.as_file_mut(), result.as_str().as_bytes())
+            .context(temp_file.path())?;
+        let path = self.result_path(tree_id);
+        // Unlike content-addressed files, an existing result must be replaced.
+        persist_temp_file(temp_file, &path).context(&path)?;
+        Ok(())
+    }
+
+    fn result_path(&self, tree_id: &MergedTreeId) -> PathBuf {
+        let tree_hash = hex_util::encode_hex(&blake2b_hash(&tree_id.to_merge()));
+        self.dir.join(tree_hash)
+    }
+}
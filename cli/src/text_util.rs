@@ -17,6 +17,9 @@ use std::cmp;
 use std::io;
 
 use bstr::ByteSlice as _;
+use jj_lib::fmt_util::binary_prefix;
+use jj_lib::str_util::StringPattern;
+use jj_lib::str_util::StringPatternParseError;
 use unicode_width::UnicodeWidthChar as _;
 use unicode_width::UnicodeWidthStr as _;
 
@@ -547,6 +550,29 @@ pub fn parse_author(author: &str) -> Result<(String, String), &'static str> {
     Ok((captures["name"].to_string(), captures["email"].to_string()))
 }
 
+/// Parses a string pattern which matches a substring unless another kind is
+/// specified, like the patterns of revset functions.
+pub fn parse_substring_pattern(src: &str) -> Result<StringPattern, StringPatternParseError> {
+    let Some((kind, pattern)) = src.split_once(':') else {
+        return Ok(StringPattern::substring(src));
+    };
+    match StringPattern::from_str_kind(pattern, kind) {
+        // The text before ":" is part of the pattern, e.g. "fix: bug"
+        Err(StringPatternParseError::InvalidKind(_)) => Ok(StringPattern::substring(src)),
+        result => result,
+    }
+}
+
+/// Formats a size in bytes with a binary prefix, e.g. "1.5 KiB".
+pub fn format_byte_size(size: u64) -> String {
+    let (scaled, prefix) = binary_prefix(size as f32);
+    if prefix.is_empty() {
+        format!("{size} B")
+    } else {
+        format!("{scaled:.1} {prefix}B")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::io::Write as _;
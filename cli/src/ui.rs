@@ -211,6 +211,8 @@ impl Write for UiStderr<'_> {
 
 pub struct Ui {
     quiet: bool,
+    output_format: OutputFormat,
+    error_format: OutputFormat,
     pager: PagerConfig,
     progress_indicator: bool,
     formatter_factory: FormatterFactory,
@@ -226,6 +228,16 @@ pub enum ColorChoice {
     Auto,
 }
 
+/// Format of the primary output of a command.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text, possibly colored and paginated
+    #[default]
+    Human,
+    /// One JSON value per line
+    Json,
+}
+
 impl fmt::Display for ColorChoice {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
@@ -320,7 +332,9 @@ impl StreampagerConfig {
 enum PagerConfig {
     Disabled,
     Builtin(StreampagerConfig),
-    External(CommandNameAndArgs),
+    /// External pager, and the config of the builtin pager to fall back to
+    /// if the external one can't be found.
+    External(CommandNameAndArgs, StreampagerConfig),
 }
 
 impl PagerConfig {
@@ -332,7 +346,7 @@ impl PagerConfig {
         if args.as_str() == Some(BUILTIN_PAGER_NAME) {
             Ok(Self::Builtin(config.get("ui.streampager")?))
         } else {
-            Ok(Self::External(args))
+            Ok(Self::External(args, config.get("ui.streampager")?))
         }
     }
 }
@@ -341,6 +355,8 @@ impl Ui {
     pub fn null() -> Self {
         Self {
             quiet: true,
+            output_format: OutputFormat::Human,
+            error_format: OutputFormat::Human,
             pager: PagerConfig::Disabled,
             progress_indicator: false,
             formatter_factory: FormatterFactory::plain_text(),
@@ -352,6 +368,8 @@ impl Ui {
         let formatter_factory = prepare_formatter_factory(config, &io::stdout())?;
         Ok(Self {
             quiet: config.get("ui.quiet")?,
+            output_format: OutputFormat::Human,
+            error_format: OutputFormat::Human,
             formatter_factory,
             pager: PagerConfig::from_config(config)?,
             progress_indicator: config.get("ui.progress-indicator")?,
@@ -367,12 +385,34 @@ impl Ui {
         Ok(())
     }
 
+    /// Format of the primary command output, as requested by `--output`.
+    pub fn output_format(&self) -> OutputFormat {
+        self.output_format
+    }
+
+    pub fn set_output_format(&mut self, output_format: OutputFormat) {
+        self.output_format = output_format;
+    }
+
+    /// Format of error messages, as requested by `--error-format`.
+    pub fn error_format(&self) -> OutputFormat {
+        self.error_format
+    }
+
+    pub fn set_error_format(&mut self, error_format: OutputFormat) {
+        self.error_format = error_format;
+    }
+
     /// Switches the output to use the pager, if allowed.
     #[instrument(skip_all)]
     pub fn request_pager(&mut self) {
         if !matches!(&self.output, UiOutput::Terminal { stdout, .. } if stdout.is_terminal()) {
             return;
         }
+        // Structured output is meant to be consumed by other programs.
+        if self.output_format != OutputFormat::Human {
+            return;
+        }
 
         let new_output = match &self.pager {
             PagerConfig::Disabled => {
@@ -390,20 +430,46 @@ impl Ui {
                     })
                     .ok()
             }
-            PagerConfig::External(command_name_and_args) => {
-                UiOutput::new_paged(command_name_and_args)
-                    .inspect_err(|err| {
-                        // The pager executable couldn't be found or couldn't be run
+            PagerConfig::External(command_name_and_args, streampager_config) => {
+                match UiOutput::new_paged(command_name_and_args) {
+                    Ok(output) => Some(output),
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                        // Don't require e.g. `less` to be installed.
+                        writeln!(
+                            self.warning_default(),
+                            "Pager '{name}' was not found, using the builtin pager instead",
+                            name = command_name_and_args.split_name(),
+                        )
+                        .ok();
+                        writeln!(
+                            self.hint_default(),
+                            "Set `ui.pager = \":builtin\"` to silence this warning."
+                        )
+                        .ok();
+                        UiOutput::new_builtin_paged(streampager_config)
+                            .inspect_err(|err| {
+                                writeln!(
+                                    self.warning_default(),
+                                    "Failed to set up builtin pager: {err}",
+                                    err = format_error_with_sources(err),
+                                )
+                                .ok();
+                            })
+                            .ok()
+                    }
+                    Err(err) => {
+                        // The pager executable couldn't be run
                         writeln!(
                             self.warning_default(),
                             "Failed to spawn pager '{name}': {err}",
                             name = command_name_and_args.split_name(),
-                            err = format_error_with_sources(err),
+                            err = format_error_with_sources(&err),
                         )
                         .ok();
                         writeln!(self.hint_default(), "Consider using the `:builtin` pager.").ok();
-                    })
-                    .ok()
+                        None
+                    }
+                }
             }
         };
         if let Some(output) = new_output {
@@ -432,6 +498,15 @@ impl Ui {
         }
     }
 
+    /// Writes `value` to stdout as a single line of JSON.
+    ///
+    /// This is the primary output of commands in `--output json` mode.
+    pub fn write_json(&self, value: &impl serde::Serialize) -> io::Result<()> {
+        let mut stdout = self.stdout();
+        serde_json::to_writer(&mut stdout, value)?;
+        writeln!(stdout)
+    }
+
     /// Creates a formatter for the locked stdout stream.
     ///
     /// Labels added to the returned formatter should be removed by caller.
@@ -17,6 +17,7 @@ mod test_backout_command;
 mod test_bisect_command;
 mod test_bookmark_command;
 mod test_builtin_aliases;
+mod test_changelog_command;
 mod test_commit_command;
 mod test_commit_template;
 mod test_completion;
@@ -34,6 +35,7 @@ mod test_edit_command;
 mod test_evolog_command;
 mod test_file_annotate_command;
 mod test_file_chmod_command;
+mod test_file_copy_move_commands;
 mod test_file_show_command;
 mod test_file_track_untrack_commands;
 mod test_fix_command;
@@ -50,9 +52,12 @@ mod test_git_remotes;
 mod test_git_root;
 mod test_gitignores;
 mod test_global_opts;
+mod test_grep_command;
 mod test_help_command;
+mod test_hooks;
 mod test_immutable_commits;
 mod test_interdiff_command;
+mod test_json_output;
 mod test_log_command;
 mod test_metaedit_command;
 mod test_new_command;
@@ -76,7 +81,9 @@ mod test_squash_command;
 mod test_status_command;
 mod test_tag_command;
 mod test_templater;
+mod test_test_command;
 mod test_undo_redo_commands;
 mod test_util_command;
+mod test_verify_command;
 mod test_working_copy;
 mod test_workspaces;
@@ -16,6 +16,7 @@ use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
 use crate::common::TestWorkDir;
 use crate::common::create_commit;
+use crate::common::force_interactive;
 
 #[test]
 fn test_basics() {
@@ -523,6 +524,95 @@ fn test_abandon_tracking_bookmarks() {
     ");
 }
 
+#[test]
+fn test_abandon_confirm() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("ui.confirm.abandon = 2");
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+
+    // Below the threshold
+    let output = work_dir.run_jj(["abandon", "--retain-bookmarks", "b"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Abandoned 1 commits:
+      zsuskuln 123b4d91 b | b
+    Working copy  (@) now at: royxmykx 2144134b (empty) (no description set)
+    Parent commit (@-)      : rlvkpnrz 7d980be7 a b | a
+    Added 0 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+    work_dir.run_jj(["undo"]).success();
+
+    // Not interactive
+    let output = work_dir.run_jj(["abandon", "a::"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    About to abandon 2 commits:
+      zsuskuln 123b4d91 b | b
+      rlvkpnrz 7d980be7 a | a
+    Error: Confirmation is required, but the terminal is not interactive
+    Hint: Use `--yes` to confirm, or set `ui.confirm.abandon = 0` to disable this check.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Declined
+    let output = work_dir.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .args(["abandon", "a::"])
+            .write_stdin("n\n")
+    });
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    About to abandon 2 commits:
+      zsuskuln 123b4d91 b | b
+      rlvkpnrz 7d980be7 a | a
+    Continue? (yN): Error: Canceled by user
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Confirmed
+    let output = work_dir.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .args(["abandon", "a::"])
+            .write_stdin("y\n")
+    });
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    About to abandon 2 commits:
+      zsuskuln 123b4d91 b | b
+      rlvkpnrz 7d980be7 a | a
+    Continue? (yN): Abandoned 2 commits:
+      zsuskuln 123b4d91 b | b
+      rlvkpnrz 7d980be7 a | a
+    Deleted bookmarks: a, b
+    Working copy  (@) now at: znkkpsqq 2b2f7cb0 (empty) (no description set)
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    Added 0 files, modified 0 files, removed 2 files
+    [EOF]
+    ");
+    work_dir.run_jj(["undo"]).success();
+
+    // Confirmed by --yes
+    let output = work_dir.run_jj(["abandon", "--yes", "a::"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Abandoned 2 commits:
+      zsuskuln 123b4d91 b | b
+      rlvkpnrz 7d980be7 a | a
+    Deleted bookmarks: a, b
+    Working copy  (@) now at: kmkuslsw a36a913b (empty) (no description set)
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    Added 0 files, modified 0 files, removed 2 files
+    [EOF]
+    ");
+}
+
 #[must_use]
 fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
     let template = r#"separate(" ", "[" ++ change_id.short(3) ++ "]", bookmarks)"#;
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::common::TestEnvironment;
+use crate::common::to_toml_value;
 
 #[test]
 fn test_alias_basic() {
@@ -282,7 +283,7 @@ fn test_alias_invalid_definition() {
     Hint: Check the config file: $TEST_ENV/config/config0002.toml
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
     ");
     let output = test_env.run_jj_in(".", ["non-string-list"]);
     insta::assert_snapshot!(output, @r"
@@ -293,7 +294,7 @@ fn test_alias_invalid_definition() {
     Hint: Check the config file: $TEST_ENV/config/config0002.toml
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
     ");
 }
 
@@ -431,17 +432,6 @@ fn test_aliases_overriding_friendly_errors() {
     [EOF]
     [exit status: 2]
     "#);
-    let output = test_env.run_jj_in(".", ["clone", "https://example.org/repo"]);
-    insta::assert_snapshot!(output, @r#"
-    ------- stderr -------
-    error: unrecognized subcommand 'clone'
-
-    For more information, try '--help'.
-    Hint: You probably want `jj git clone`. See also `jj help git`.
-    Hint: You can configure `aliases.clone = ["git", "clone"]` if you want `jj clone` to work and always use the Git backend.
-    [EOF]
-    [exit status: 2]
-    "#);
     let output = test_env.run_jj_in(".", ["init", "--help"]);
     insta::assert_snapshot!(output, @r#"
     ------- stderr -------
@@ -463,3 +453,132 @@ fn test_aliases_overriding_friendly_errors() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_external_subcommand_configured() {
+    let test_env = TestEnvironment::default();
+    let formatter_path = assert_cmd::cargo::cargo_bin("fake-formatter");
+    test_env.add_config(format!(
+        "plugins.fmt = [{}, '--append']",
+        to_toml_value(formatter_path.to_str().unwrap())
+    ));
+    let output = test_env.run_jj_in(".", ["fmt", "hello"]);
+    insta::assert_snapshot!(output, @"hello[EOF]");
+
+    // Arguments and exit status are forwarded
+    let output = test_env.run_jj_in(".", ["fmt", "--badopt"]);
+    insta::assert_snapshot!(output.normalize_stderr_with(|s| s.replace(".exe", "")), @r"
+    ------- stderr -------
+    error: unexpected argument '--badopt' found
+
+    Usage: fake-formatter [OPTIONS]
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+
+    // Built-in commands can't be overridden
+    test_env.add_config(r#"plugins.root = ["jj-test-missing-program"]"#);
+    let output = test_env.run_jj_in(".", ["root"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Error: There is no jj repo in "."
+    [EOF]
+    [exit status: 1]
+    "#);
+}
+
+#[test]
+fn test_external_subcommand_configured_errors() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(
+        r#"
+    plugins.empty = []
+    plugins.missing = "jj-test-missing-program"
+    "#,
+    );
+
+    let output = test_env.run_jj_in(".", ["empty"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Config error: Empty command for plugin `empty`
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 4]
+    ");
+
+    let output = test_env.run_jj_in(".", ["missing"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to execute external command 'jj-test-missing-program'
+    Caused by: No such file or directory (os error 2)
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Unconfigured programs not found in PATH are unknown subcommands
+    let output = test_env.run_jj_in(".", ["nonexistent-plugin"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: unrecognized subcommand 'nonexistent-plugin'
+
+      tip: a similar subcommand exists: 'next'
+
+    Usage: jj [OPTIONS] <COMMAND>
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_external_subcommand_in_path() {
+    use std::os::unix::fs::PermissionsExt as _;
+
+    let mut test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+
+    let bin_dir = test_env.env_root().join("bin");
+    std::fs::create_dir(&bin_dir).unwrap();
+    let script_path = bin_dir.join("jj-foo");
+    std::fs::write(
+        &script_path,
+        indoc::indoc! {r#"
+            #!/bin/sh
+            echo "args: [$*]"
+            echo "workspace: [$JJ_WORKSPACE_ROOT]"
+            echo "repo: [$JJ_REPO_PATH]"
+            exit 3
+        "#},
+    )
+    .unwrap();
+    std::fs::set_permissions(&script_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let path = std::env::join_paths([bin_dir].into_iter().chain(std::env::split_paths(
+        &std::env::var_os("PATH").unwrap_or_default(),
+    )))
+    .unwrap();
+    test_env.add_env_var("PATH", path.to_str().unwrap());
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["foo", "bar", "--baz"]);
+    insta::assert_snapshot!(output, @r"
+    args: [bar --baz]
+    workspace: [$TEST_ENV/repo]
+    repo: [$TEST_ENV/repo/.jj/repo]
+    [EOF]
+    [exit status: 3]
+    ");
+
+    // Outside a workspace, the environment variables are unset
+    let output = test_env.run_jj_in(".", ["foo"]);
+    insta::assert_snapshot!(output, @r"
+    args: []
+    workspace: []
+    repo: []
+    [EOF]
+    [exit status: 3]
+    ");
+}
@@ -201,6 +201,122 @@ fn test_bisect_run_jj_command() {
     ");
 }
 
+#[test]
+fn test_bisect_manual() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+    create_commit(&work_dir, "d", &["c"]);
+    create_commit(&work_dir, "e", &["d"]);
+    create_commit(&work_dir, "f", &["e"]);
+
+    // Marking requires a bisection in progress
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "good"]), @r"
+    ------- stderr -------
+    Error: No bisection in progress
+    Hint: Run `jj bisect start` to start one.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "start", "--range=a..f"]), @r"
+    Now evaluating: royxmykx dffaa0d4 c | c
+    [EOF]
+    ------- stderr -------
+    Working copy  (@) now at: kxryzmor 076636e4 (empty) (no description set)
+    Parent commit (@-)      : royxmykx dffaa0d4 c | c
+    Added 0 files, modified 0 files, removed 3 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "start", "--range=a..f"]), @r"
+    ------- stderr -------
+    Error: A bisection is already in progress
+    Hint: Run `jj bisect reset` to end it first.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Revisions outside the range can't be marked, and the heads of the range
+    // are assumed to be bad
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "good", "-r=a"]), @r"
+    ------- stderr -------
+    Error: Revision rlvkpnrz 7d980be7 a | a is not in the range being bisected: a..f
+    [EOF]
+    [exit status: 1]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "good", "-r=f"]), @r"
+    ------- stderr -------
+    Error: Revision kmkuslsw 8b67af28 f | f is a head of the range being bisected, which is assumed to be bad
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // The revision being evaluated is marked by default
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "good"]), @r"
+    Now evaluating: vruxwmqv 86be7a22 d | d
+    [EOF]
+    ------- stderr -------
+    Working copy  (@) now at: xtnwkqum c8ce5080 (empty) (no description set)
+    Parent commit (@-)      : vruxwmqv 86be7a22 d | d
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "bad"]), @r"
+    Search complete.
+    The first bad revision is: vruxwmqv 86be7a22 d | d
+    [EOF]
+    ------- stderr -------
+    Hint: Run `jj bisect reset` to end the bisection.
+    [EOF]
+    ");
+
+    // Marking a revision again replaces its previous mark
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "skip", "-r=d"]), @r"
+    Now evaluating: znkkpsqq 62d30ded e | e
+    [EOF]
+    ------- stderr -------
+    Working copy  (@) now at: tlkvzzqu 13b36114 (empty) (no description set)
+    Parent commit (@-)      : znkkpsqq 62d30ded e | e
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "bad"]), @r"
+    Search complete.
+    The first bad revision is: znkkpsqq 62d30ded e | e
+    [EOF]
+    ------- stderr -------
+    Hint: Run `jj bisect reset` to end the bisection.
+    [EOF]
+    ");
+
+    insta::assert_snapshot!(work_dir.run_jj(["op", "log", "-n=6", "-T=description"]), @r"
+    @  mark revision 62d30ded0e8fdf8cf87012e6223898b97977fc8e as bad for bisection
+    ○  mark revision 86be7a223919b5526822f5553b4c42a859ced507 as skipped for bisection
+    ○  mark revision 86be7a223919b5526822f5553b4c42a859ced507 as bad for bisection
+    ○  mark revision dffaa0d4daccf6cee70bac3498fae3b3fd5d6b5b as good for bisection
+    ○  start bisection of a..f
+    ○  create bookmark f pointing to commit 8b67af2884669a7718bc480a54cdc1ff69af91f2
+    [EOF]
+    ");
+
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "reset"]), @r"
+    Bisection ended. To discard any revisions created during bisection, run:
+      jj op restore 9152b6b19cce
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "bad"]), @r"
+    ------- stderr -------
+    Error: No bisection in progress
+    Hint: Run `jj bisect start` to start one.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[must_use]
 fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
     let template = r#"separate(" ",
@@ -608,6 +608,14 @@ fn test_bookmark_rename() {
 
     work_dir.run_jj(["describe", "-m=commit-0"]).success();
     work_dir.run_jj(["bookmark", "create", "blocal"]).success();
+    let output = work_dir.run_jj(["bookmark", "rename", "blocl", "blocal1"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: No such bookmark: blocl
+    Hint: Did you mean `blocal`?
+    [EOF]
+    [exit status: 1]
+    ");
     let output = work_dir.run_jj(["bookmark", "rename", "blocal", "blocal1"]);
     insta::assert_snapshot!(output, @"");
 
@@ -681,6 +689,8 @@ fn test_bookmark_forget_glob() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Forgot 2 local bookmarks.
+      foo-1
+      foo-3
     [EOF]
     ");
     work_dir.run_jj(["op", "restore", &setup_opid]).success();
@@ -688,6 +698,8 @@ fn test_bookmark_forget_glob() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Forgot 2 local bookmarks.
+      foo-1
+      foo-3
     [EOF]
     ");
     insta::assert_snapshot!(get_log_output(&work_dir), @r"
@@ -702,6 +714,7 @@ fn test_bookmark_forget_glob() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Forgot 1 local bookmarks.
+      foo-4
     [EOF]
     ");
     insta::assert_snapshot!(get_log_output(&work_dir), @r"
@@ -769,6 +782,8 @@ fn test_bookmark_delete_glob() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Deleted 2 bookmarks.
+      foo-1
+      foo-3
     [EOF]
     ");
     work_dir.run_jj(["op", "restore", &setup_opid]).success();
@@ -776,6 +791,8 @@ fn test_bookmark_delete_glob() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Deleted 2 bookmarks.
+      foo-1
+      foo-3
     [EOF]
     ");
     insta::assert_snapshot!(get_log_output(&work_dir), @r"
@@ -800,6 +817,7 @@ fn test_bookmark_delete_glob() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Deleted 1 bookmarks.
+      foo-4
     [EOF]
     ");
     insta::assert_snapshot!(get_log_output(&work_dir), @r"
@@ -845,6 +863,44 @@ fn test_bookmark_delete_glob() {
     ");
 }
 
+#[test]
+fn test_bookmark_delete_confirm() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("ui.confirm.bookmark-delete = 2");
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "foo-1", "foo-2", "bar"])
+        .success();
+
+    let output = work_dir.run_jj(["bookmark", "delete", "glob:foo-*"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    About to delete 2 bookmarks:
+      foo-1
+      foo-2
+    Error: Confirmation is required, but the terminal is not interactive
+    Hint: Use `--yes` to confirm, or set `ui.confirm.bookmark-delete = 0` to disable this check.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["bookmark", "delete", "--yes", "glob:foo-*"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Deleted 2 bookmarks.
+      foo-1
+      foo-2
+    [EOF]
+    ");
+    // Below the threshold
+    let output = work_dir.run_jj(["bookmark", "delete", "bar"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Deleted 1 bookmarks.
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_bookmark_delete_export() {
     let test_env = TestEnvironment::default();
@@ -1391,6 +1447,13 @@ fn test_bookmark_track_untrack_patterns() {
     [EOF]
     [exit status: 1]
     ");
+    insta::assert_snapshot!(work_dir.run_jj(["bookmark", "track", "feature@origin"]), @"
+    ------- stderr -------
+    Error: No such remote bookmark: feature@origin
+    Hint: Did you mean `feature1@origin`, `feature2@origin`?
+    [EOF]
+    [exit status: 1]
+    ");
     insta::assert_snapshot!(work_dir.run_jj(["bookmark", "track", "glob:maine@*"]), @r"
     ------- stderr -------
     Error: No matching remote bookmarks for patterns: maine@*
@@ -1453,6 +1516,7 @@ fn test_bookmark_track_untrack_patterns() {
     Warning: Remote bookmark not tracked yet: feature2@origin
     Warning: Git-tracking bookmark cannot be untracked: main@git
     Stopped tracking 1 remote bookmarks.
+      feature1@origin
     [EOF]
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
@@ -1470,6 +1534,8 @@ fn test_bookmark_track_untrack_patterns() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Started tracking 2 remote bookmarks.
+      feature1@origin
+      feature2@origin
     [EOF]
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
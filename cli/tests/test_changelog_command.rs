@@ -0,0 +1,89 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_changelog() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    for description in [
+        "feat: add a feature",
+        "fix(cli): fix a bug",
+        "refactor: clean up",
+        "docs: describe a thing\n\nChangelog: fix",
+        "feat!: break things",
+    ] {
+        work_dir.run_jj(["commit", "-m", description]).success();
+    }
+
+    let output = work_dir.run_jj(["changelog", "-r", "..@-"]);
+    insta::assert_snapshot!(output, @"
+    ### New features
+
+    * break things (mzvwutvl)
+    * add a feature (qpvuntsm)
+
+    ### Fixed bugs
+
+    * fix a bug (rlvkpnrz)
+
+    ### Other changes
+
+    * describe a thing (zsuskuln)
+    * clean up (kkmpptxz)
+    [EOF]
+    ");
+
+    // Sections are configurable, and unmatched commits can be omitted.
+    test_env.add_config(
+        r#"
+        changelog.other-title = ""
+        [[changelog.sections]]
+        title = "Fixes"
+        trailers = ["Changelog: fix"]
+        prefixes = ["fix"]
+        "#,
+    );
+    let output = work_dir.run_jj([
+        "changelog",
+        "-r",
+        "..@-",
+        "--reversed",
+        "-T",
+        r#""- " ++ description.first_line() ++ "\n""#,
+    ]);
+    insta::assert_snapshot!(output, @"
+    ### Fixes
+
+    - fix(cli): fix a bug
+    - docs: describe a thing
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["changelog"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: the following required arguments were not provided:
+      --revisions <REVSETS>
+
+    Usage: jj changelog --revisions <REVSETS>
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
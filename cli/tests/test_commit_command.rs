@@ -289,6 +289,37 @@ fn test_commit_interactive_with_paths() {
     ");
 }
 
+#[test]
+fn test_commit_interactive_no_changes_selected() {
+    let mut test_env = TestEnvironment::default();
+    let diff_editor = test_env.set_up_fake_diff_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "foo\n");
+    std::fs::write(diff_editor, "reset file1").unwrap();
+
+    let output = work_dir.run_jj(["commit", "-i", "-m=empty"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: No changes have been selected, so the new revision will be empty
+    Working copy  (@) now at: rlvkpnrz 95139f9e (no description set)
+    Parent commit (@-)      : qpvuntsm 1f6c4037 (empty) empty
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["log", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    @  rlvkpnrz test.user@example.com 2001-02-03 08:05:08 95139f9e
+    │  (no description set)
+    │  A file1
+    ○  qpvuntsm test.user@example.com 2001-02-03 08:05:08 1f6c4037
+    │  (empty) empty
+    ◆  zzzzzzzz root() 00000000
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_commit_with_default_description() {
     let mut test_env = TestEnvironment::default();
@@ -512,6 +543,30 @@ fn test_commit_reset_author() {
     ");
 }
 
+#[test]
+fn test_commit_signoff() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file1", "foo\n");
+
+    work_dir
+        .run_jj([
+            "commit",
+            "--config=user.email=foo@bar.org",
+            "-m=first",
+            "--signoff",
+        ])
+        .success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@-", "-Tdescription"]);
+    insta::assert_snapshot!(output, @r"
+    first
+
+    Signed-off-by: Test User <foo@bar.org>
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_commit_trailers() {
     let mut test_env = TestEnvironment::default();
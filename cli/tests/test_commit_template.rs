@@ -854,7 +854,7 @@ fn test_log_immutable() {
       = Function `unknown_fn` doesn't exist
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
     ");
 
     test_env.add_config("revset-aliases.'immutable_heads()' = 'unknown_symbol'");
@@ -199,7 +199,7 @@ fn test_bookmark_names() {
     let work_dir = test_env.work_dir("repo");
 
     let output = work_dir.complete_fish(["bookmark", "rename", ""]);
-    insta::assert_snapshot!(output, @r"
+    insta::assert_snapshot!(output, @"
     aaa-local	x
     aaa-tracked	x
     bbb-local	x
@@ -207,13 +207,18 @@ fn test_bookmark_names() {
     --repository	Path to repository to operate on
     --ignore-working-copy	Don't snapshot the working copy, and don't update it
     --ignore-immutable	Allow rewriting immutable commits
+    --sign	Sign the commits created or rewritten by this command
+    --no-sign	Don't sign the commits created or rewritten by this command
     --at-operation	Operation to load the repo at
+    --output	Format of the command output
     --debug	Enable debug logging
+    --profile	Write timing spans of the command to a file for performance analysis
     --color	When to colorize output
     --quiet	Silence non-primary command output
     --no-pager	Disable the pager
     --config	Additional configuration options (can be repeated)
     --config-file	Additional configuration files (can be repeated)
+    --error-format	Format of error messages
     --help	Print help (see more with '--help')
     [EOF]
     ");
@@ -419,21 +424,21 @@ fn test_default_command_is_resolved(shell: Shell) {
         Shell::Bash => {
             insta::assert_snapshot!(output, @r"
             --revisions
-            --limit
+            --follow
             [EOF]
             ");
         }
         Shell::Zsh => {
             insta::assert_snapshot!(output, @r"
             --revisions:Which revisions to show
-            --limit:Limit number of revisions to show
+            --follow:Follow the history of the given file across renames and copies
             [EOF]
             ");
         }
         Shell::Fish => {
             insta::assert_snapshot!(output, @r"
             --revisions	Which revisions to show
-            --limit	Limit number of revisions to show
+            --follow	Follow the history of the given file across renames and copies
             [EOF]
             ");
         }
@@ -643,6 +648,12 @@ fn test_remote_names() {
     origin
     [EOF]
     ");
+
+    let output = test_env.complete_fish(["gerrit", "upload", "--remote", "o"]);
+    insta::assert_snapshot!(output, @"
+    origin
+    [EOF]
+    ");
 }
 
 #[test_case(Shell::Bash; "bash")]
@@ -1045,6 +1056,14 @@ fn test_workspaces() {
     default	initial	
     [EOF]
     ");
+
+    // working-copy commits of workspaces are completed as revisions
+    let output = main_dir.complete_fish(["new", "def"]);
+    insta::assert_snapshot!(output, @"
+    def-second@	(no description set)
+    default@	initial
+    [EOF]
+    ");
 }
 
 #[test]
@@ -1148,6 +1167,7 @@ fn test_merge_tools() {
     :name-only
     :git
     :color-words
+    :side-by-side
     diffedit3
     diffedit3-ssh
     difft
@@ -1371,6 +1391,13 @@ fn test_files() {
     [EOF]
     ");
 
+    let output = work_dir.complete_fish(["status", "f_"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @"
+    f_added_2	Added
+    f_modified	Modified
+    [EOF]
+    ");
+
     let output = work_dir.complete_fish(["file", "annotate", "-r@-", "f_"]);
     insta::assert_snapshot!(output.normalize_backslash(), @r"
     f_added
@@ -554,11 +554,11 @@ fn test_config_set_bad_opts() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     error: the following required arguments were not provided:
-      <--user|--repo>
+      <--user|--repo|--workspace>
       <NAME>
       <VALUE>
 
-    Usage: jj config set <--user|--repo> <NAME> <VALUE>
+    Usage: jj config set <--user|--repo|--workspace> <NAME> <VALUE>
 
     For more information, try '--help'.
     [EOF]
@@ -702,6 +702,42 @@ fn test_config_set_for_repo() {
     "#);
 }
 
+#[test]
+fn test_config_set_for_workspace() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["workspace", "add", "../secondary"])
+        .success();
+    let secondary_dir = test_env.work_dir("secondary");
+    work_dir
+        .run_jj(["config", "set", "--repo", "test-key", "repo-val"])
+        .success();
+    secondary_dir
+        .run_jj(["config", "set", "--workspace", "test-key", "workspace-val"])
+        .success();
+    let workspace_config_toml = secondary_dir.read_file(".jj/config.toml");
+    insta::assert_snapshot!(workspace_config_toml, @r#"
+    #:schema https://jj-vcs.github.io/jj/latest/config-schema.json
+
+    test-key = "workspace-val"
+    "#);
+
+    // The workspace config overrides the repo config only in that workspace
+    let output = work_dir.run_jj(["config", "get", "test-key"]);
+    insta::assert_snapshot!(output, @r"
+    repo-val
+    [EOF]
+    ");
+    let output = secondary_dir.run_jj(["config", "list", "test-key", "--include-overridden"]);
+    insta::assert_snapshot!(output, @r#"
+    # test-key = "repo-val"
+    test-key = "workspace-val"
+    [EOF]
+    "#);
+}
+
 #[test]
 fn test_config_set_toml_types() {
     let mut test_env = TestEnvironment::default();
@@ -921,9 +957,9 @@ fn test_config_edit_missing_opt() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     error: the following required arguments were not provided:
-      <--user|--repo>
+      <--user|--repo|--workspace>
 
-    Usage: jj config edit <--user|--repo>
+    Usage: jj config edit <--user|--repo|--workspace>
 
     For more information, try '--help'.
     [EOF]
@@ -1166,6 +1202,14 @@ fn test_config_path() {
     [EOF]
     [exit status: 1]
     ");
+    insta::assert_snapshot!(work_dir.run_jj(["config", "path", "--workspace"]), @r"
+    $TEST_ENV/repo/.jj/config.toml
+    [EOF]
+    ");
+    assert!(
+        !work_dir.root().join(".jj").join("config.toml").exists(),
+        "jj config path shouldn't create new file"
+    );
 }
 
 #[test]
@@ -1236,7 +1280,7 @@ fn test_config_get() {
     Config error: Value not found for nonexistent
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
     ");
 
     let output = test_env.run_jj_in(".", ["config", "get", "table.string"]);
@@ -1270,6 +1314,124 @@ fn test_config_get() {
     ");
 }
 
+#[test]
+fn test_config_get_level() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config("test.key = 'user value'");
+    work_dir
+        .run_jj(["config", "set", "--repo", "test.key", "repo value"])
+        .success();
+    work_dir
+        .run_jj(["config", "set", "--repo", "test.repo-only", "1"])
+        .success();
+
+    let output = work_dir.run_jj(["config", "get", "test.key"]);
+    insta::assert_snapshot!(output, @"
+    repo value
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["config", "get", "--user", "test.key"]);
+    insta::assert_snapshot!(output, @"
+    user value
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["config", "get", "--repo", "test.key"]);
+    insta::assert_snapshot!(output, @"
+    repo value
+    [EOF]
+    ");
+
+    // Set only at the other level
+    let output = work_dir.run_jj(["config", "get", "--user", "test.repo-only"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Config error: Value not found for test.repo-only
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 4]
+    ");
+}
+
+#[test]
+fn test_config_get_expanded() {
+    let mut test_env = TestEnvironment::default();
+    test_env.add_env_var("JJ_TEST_VAR", "value");
+    test_env.add_config(
+        r#"ui.editor = ["~/a", "${JJ_TEST_VAR}", "$${JJ_TEST_VAR}", "${JJ_TEST_UNSET}"]"#,
+    );
+    let output = test_env.run_jj_in(".", ["config", "get", "ui.editor"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r#"
+    ["$TEST_ENV/home/a", "value", "${JJ_TEST_VAR}", "${JJ_TEST_UNSET}"]
+    [EOF]
+    "#);
+
+    // Values other than paths and commands aren't expanded
+    test_env.add_config(r#"aliases.sh = ["util", "exec", "--", "sh", "-c", 'echo "${1}"', "sh"]"#);
+    let output = test_env.run_jj_in(".", ["config", "get", "aliases.sh"]);
+    insta::assert_snapshot!(output, @r#"
+    ["util", "exec", "--", "sh", "-c", 'echo "${1}"', "sh"]
+    [EOF]
+    "#);
+    let output = test_env.run_jj_in(".", ["git", "init", "repo"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Initialized repo in "repo"
+    [EOF]
+    "#);
+}
+
+#[test]
+fn test_config_validate() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["config", "validate"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No problems found in config
+    [EOF]
+    ");
+
+    test_env.add_config(indoc! {"
+        [ui]
+        colour = 'never'
+        log-word-wrap = 'yes'
+
+        [colors]
+        error = { fgg = 'red' }
+    "});
+    work_dir
+        .run_jj(["config", "set", "--repo", "ui.editor", "1"])
+        .success();
+    let output = work_dir.run_jj(["config", "validate", "--config=ui.fooo=1"]);
+    insta::assert_snapshot!(output, @r"
+    $TEST_ENV/config/config0002.toml:2: Unknown config key ui.colour (did you mean `ui.color`?)
+    $TEST_ENV/config/config0002.toml:3: Invalid type or value for ui.log-word-wrap: expected boolean
+    $TEST_ENV/config/config0002.toml:6: Unknown config key colors.error.fgg (did you mean `colors.error.fg`?)
+    $TEST_ENV/repo/.jj/repo/config.toml:4: Invalid type or value for ui.editor: expected string or array of string or table
+    <cli>: Unknown config key ui.fooo
+    [EOF]
+    ------- stderr -------
+    Error: Found 5 problem(s) in config
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Only the specified level is checked
+    let output = work_dir.run_jj(["config", "validate", "--repo"]);
+    insta::assert_snapshot!(output, @r"
+    $TEST_ENV/repo/.jj/repo/config.toml:4: Invalid type or value for ui.editor: expected string or array of string or table
+    [EOF]
+    ------- stderr -------
+    Error: Found 1 problem(s) in config
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_config_get_yields_values_consistent_with_schema_defaults() {
     let mut test_env = TestEnvironment::default();
@@ -1411,7 +1573,7 @@ fn test_config_path_syntax() {
     Config error: Value not found for a.'b()'.x
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
     ");
 
     // "-" and "_" are valid TOML keys
@@ -1632,6 +1794,43 @@ fn test_config_conditional() {
 
 // Minimal test for Windows where the home directory can't be switched.
 // (Can be removed if test_config_conditional() is enabled on Windows.)
+#[test]
+fn test_config_conditional_workspaces() {
+    let test_env = TestEnvironment::default();
+    let home_dir = test_env.work_dir(test_env.home_dir());
+    home_dir.run_jj(["git", "init", "repo"]).success();
+    home_dir
+        .dir("repo")
+        .run_jj(["workspace", "add", "../secondary"])
+        .success();
+    test_env.add_config(indoc! {"
+        foo = 'global'
+        [[--scope]]
+        --when.workspaces = ['~/secondary']
+        foo = 'secondary'
+        [[--scope]]
+        --when.repositories = ['~/repo']
+        bar = 'repo'
+    "});
+
+    let output = home_dir.dir("repo").run_jj(["config", "get", "foo"]);
+    insta::assert_snapshot!(output, @"
+    global
+    [EOF]
+    ");
+    let output = home_dir.dir("secondary").run_jj(["config", "get", "foo"]);
+    insta::assert_snapshot!(output, @"
+    secondary
+    [EOF]
+    ");
+    // The repository path of a secondary workspace is in the main workspace
+    let output = home_dir.dir("secondary").run_jj(["config", "get", "bar"]);
+    insta::assert_snapshot!(output, @"
+    repo
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_config_conditional_without_home_dir() {
     let mut test_env = TestEnvironment::default();
@@ -1685,7 +1884,7 @@ fn test_config_show_paths() {
     Hint: Check the config file: $TEST_ENV/config/config0001.toml
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
     ");
 }
 
@@ -115,6 +115,30 @@ fn test_debug_revset() {
     [EOF]
     ");
 
+    let output = work_dir.run_jj(["debug", "revset", "--no-commit-ids", "all()"]);
+    assert_snapshot!(output, @r"
+    -- Parsed:
+    All
+
+    -- Resolved:
+    All
+
+    -- Optimized:
+    All
+
+    -- Backend:
+    Ancestors {
+        ..
+    }
+
+    -- Evaluated:
+    RevsetImpl {
+        ..
+    }
+
+    [EOF]
+    ");
+
     let output = work_dir.run_jj(["debug", "revset", "--no-resolve", "foo & ~bar"]);
     assert_snapshot!(output, @r"
     -- Parsed:
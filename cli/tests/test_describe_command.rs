@@ -1038,6 +1038,47 @@ fn test_add_trailer() {
     ");
 }
 
+#[test]
+fn test_describe_signoff() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir
+        .run_jj(["describe", "-m", "Message from CLI", "--signoff"])
+        .success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-Tdescription"]);
+    insta::assert_snapshot!(output, @r"
+    Message from CLI
+
+    Signed-off-by: Test User <test.user@example.com>
+    [EOF]
+    ");
+
+    // the sign-off follows the configured trailers, and isn't duplicated
+    work_dir
+        .run_jj([
+            "describe",
+            "-m",
+            "Message from CLI",
+            "--signoff",
+            "--config",
+            r#"templates.commit_trailers='"CC: alice@example.com"'"#,
+        ])
+        .success();
+    work_dir
+        .run_jj(["describe", "--no-edit", "--signoff"])
+        .success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-Tdescription"]);
+    insta::assert_snapshot!(output, @r"
+    Message from CLI
+
+    CC: alice@example.com
+    Signed-off-by: Test User <test.user@example.com>
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_add_trailer_committer() {
     let mut test_env = TestEnvironment::default();
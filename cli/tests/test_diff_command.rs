@@ -337,6 +337,7 @@ fn test_diff_basic() {
     error: the argument '--template <TEMPLATE>' cannot be used with:
       --git
       --color-words
+      --side-by-side
 
     Usage: jj diff --template <TEMPLATE> --git [FILESETS]...
 
@@ -364,7 +365,7 @@ fn test_diff_basic() {
     Caused by: Invalid builtin diff format: unknown
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
     ");
 }
 
@@ -954,6 +955,134 @@ fn test_diff_hunks() {
     [38;5;2m<<diff added::+baz >>[4m<<diff added token::quux>>[24m<<diff added:: blah blah>>[39m
     [EOF]
     ");
+
+    // Changed words aren't highlighted if disabled
+    let output = work_dir.run_jj([
+        "diff",
+        "--git",
+        "--color=debug",
+        "--config=diff.git.highlight-words=false",
+        "file3",
+    ]);
+    insta::assert_snapshot!(output, @"
+    [1m<<diff file_header::diff --git a/file3 b/file3>>[0m
+    [1m<<diff file_header::index 221a95a095..a543ef3892 100644>>[0m
+    [1m<<diff file_header::--- a/file3>>[0m
+    [1m<<diff file_header::+++ b/file3>>[0m
+    [38;5;6m<<diff hunk_header::@@ -1,2 +1,3 @@>>[39m
+    <<diff context:: foo>>
+    [38;5;1m<<diff removed::-baz qux blah blah>>[39m
+    [38;5;2m<<diff added::+bar>>[39m
+    [38;5;2m<<diff added::+baz quux blah blah>>[39m
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_diff_side_by_side() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "a\nb\nc\nd\n");
+    work_dir.write_file("file2", "foo\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file(
+        "file1",
+        "a\nB\nc\nd\nthis line is too long to fit in the column width\n",
+    );
+    work_dir.remove_file("file2");
+    work_dir.write_file("file3", "");
+
+    let output =
+        work_dir.run_jj_with(|cmd| cmd.args(["diff", "--side-by-side"]).env("COLUMNS", "60"));
+    insta::assert_snapshot!(output, @"
+    Modified regular file file1:
+    @@ -1,4 +1,5 @@
+       1 a                       │    1 a
+       2 b                       │    2 B
+       3 c                       │    3 c
+       4 d                       │    4 d
+                                 │    5 this line is too long …
+    Removed regular file file2:
+    @@ -1,1 +0,0 @@
+       1 foo                     │
+    Added regular file file3:
+        (empty)
+    [EOF]
+    ");
+
+    // Configured as the default format
+    let output = work_dir.run_jj_with(|cmd| {
+        cmd.args([
+            "diff",
+            "--color=debug",
+            "--config=ui.diff-formatter=:side-by-side",
+            "--context=0",
+            "file1",
+        ])
+        .env("COLUMNS", "60")
+    });
+    insta::assert_snapshot!(output, @"
+    [38;5;3m<<diff header::Modified regular file file1:>>[39m
+    [38;5;6m<<diff hunk_header::@@ -2,1 +2,1 @@>>[39m
+    [38;5;1m<<diff removed line_number::   2>>[39m<<diff:: >>[4m[38;5;1m<<diff removed token::b>>[24m[39m<<diff::                       │ >>[38;5;2m<<diff added line_number::   2>>[39m<<diff:: >>[4m[38;5;2m<<diff added token::B>>[24m[39m<<diff::>>
+    [38;5;6m<<diff hunk_header::@@ -4,0 +5,1 @@>>[39m
+    <<diff::                             │ >>[38;5;2m<<diff added line_number::   5>>[39m<<diff:: >>[4m[38;5;2m<<diff added token::this line is too long >>[24m<<diff added::…>>[39m<<diff::>>
+    [EOF]
+    ");
+
+    // Cannot be combined with other long formats
+    let output = work_dir.run_jj(["diff", "--side-by-side", "--git"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: the argument '--side-by-side' cannot be used with '--git'
+
+    Usage: jj diff --side-by-side [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_diff_git_syntax_highlight() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("main.rs", "fn main() {\n    // TODO\n}\n");
+    work_dir.write_file("notes.unknown", "fn main\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("main.rs", "fn main() {\n    println!(\"hello\");\n}\n");
+    work_dir.write_file("notes.unknown", "fn main()\n");
+
+    let output = work_dir.run_jj([
+        "diff",
+        "--git",
+        "--color=debug",
+        "--config=diff.syntax-highlight=true",
+    ]);
+    insta::assert_snapshot!(output, @r#"
+    [1m<<diff file_header::diff --git a/main.rs b/main.rs>>[0m
+    [1m<<diff file_header::index 171cfe286b..7527576062 100644>>[0m
+    [1m<<diff file_header::--- a/main.rs>>[0m
+    [1m<<diff file_header::+++ b/main.rs>>[0m
+    [38;5;6m<<diff hunk_header::@@ -1,3 +1,3 @@>>[39m
+    <<diff context:: >>[38;5;5m<<diff context syntax keyword::fn>>[39m<<diff context:: >>[38;5;4m<<diff context syntax function::main>>[39m<<diff context::() {>>
+    [38;5;1m<<diff removed::-    >>[4m[38;5;8m<<diff removed token syntax comment::// TODO>>[24m[38;5;1m<<diff removed::>>[39m
+    [38;5;2m<<diff added::+    >>[4m<<diff added token::println!(>>[38;5;3m<<diff added token syntax string::"hello">>[38;5;2m<<diff added token::);>>[24m<<diff added::>>[39m
+    <<diff context:: }>>
+    [1m<<diff file_header::diff --git a/notes.unknown b/notes.unknown>>[0m
+    [1m<<diff file_header::index 009c88aab9..d955fb2df4 100644>>[0m
+    [1m<<diff file_header::--- a/notes.unknown>>[0m
+    [1m<<diff file_header::+++ b/notes.unknown>>[0m
+    [38;5;6m<<diff hunk_header::@@ -1,1 +1,1 @@>>[39m
+    [38;5;1m<<diff removed::-fn main>>[39m
+    [38;5;2m<<diff added::+fn main>>[4m<<diff added token::()>>[24m<<diff added::>>[39m
+    [EOF]
+    "#);
 }
 
 #[test]
@@ -3157,6 +3286,17 @@ fn test_diff_external_tool() {
     [EOF]
     ");
 
+    // --tool overrides the default for this invocation
+    let config = "--config=ui.diff-formatter=:git";
+    insta::assert_snapshot!(work_dir.run_jj(["diff", config, "--tool=fake-diff-editor"]), @"
+    file1
+    file2
+    --
+    file2
+    file3
+    [EOF]
+    ");
+
     // Inlined command arguments
     let command_toml = to_toml_value(fake_diff_editor_path());
     let config = format!("--config=ui.diff-formatter=[{command_toml}, '$right', '$left']");
@@ -3848,6 +3988,75 @@ fn test_diff_binary() {
     ");
 }
 
+#[test]
+fn test_diff_binary_files_config() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(r#"diff.binary-files = "glob:'*.svg'""#);
+
+    work_dir.write_file("image.svg", "<svg>\n</svg>\n");
+    work_dir.write_file("text.txt", "foo\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("image.svg", "<svg>\n<rect/>\n</svg>\n");
+    work_dir.write_file("text.txt", "bar\n");
+
+    let output = work_dir.run_jj(["diff"]);
+    insta::assert_snapshot!(output, @r"
+    Modified regular file image.svg:
+        (binary)
+    Modified regular file text.txt:
+       1    1: foobar
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--git"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/image.svg b/image.svg
+    index 35ecfd6dbc..850b9889b1 100644
+    Binary files a/image.svg and b/image.svg differ
+    diff --git a/text.txt b/text.txt
+    index 257cc5642c..5716ca5987 100644
+    --- a/text.txt
+    +++ b/text.txt
+    @@ -1,1 +1,1 @@
+    -foo
+    +bar
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--stat"]);
+    insta::assert_snapshot!(output, @r"
+    image.svg | (binary) +8 bytes
+    text.txt  | 2 +-
+    2 files changed, 1 insertion(+), 1 deletion(-)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-T", "self.diff().stat(80)"]);
+    insta::assert_snapshot!(output, @r"
+    image.svg | (binary) +8 bytes
+    text.txt  | 2 +-
+    2 files changed, 1 insertion(+), 1 deletion(-)
+    [EOF]
+    ");
+
+    // invalid fileset is reported as a config error
+    let output = work_dir.run_jj(["diff", "--config=diff.binary-files='bad:x'"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Config error: Invalid type or value for diff.binary-files
+    Caused by:
+    1:  --> 1:1
+      |
+    1 | bad:x
+      | ^---^
+      |
+      = Invalid file pattern
+    2: Invalid file pattern kind `bad:`
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 4]
+    ");
+}
+
 /// Test diff --stat output width for diffs that have different cases of right
 /// side text: solely "(binary)", a mixture of text and binary diffs, and binary
 /// size changes.
@@ -476,6 +476,10 @@ fn test_diffedit_external_tool_conflict_marker_style() {
     Parent commit (@-): zsuskuln 6982bce7 side-b
     Warning: There are unresolved conflicts at these paths:
     file    2-sided conflict
+    Hint: To resolve the conflicts, use a merge tool, e.g.:
+      jj resolve file
+    or edit the conflict markers in the files directly.
+    Once the conflicts are resolved, you can inspect the result with `jj diff`.
     [EOF]
     ");
 }
@@ -204,6 +204,77 @@ fn test_evolog_with_or_without_diff() {
     ");
 }
 
+#[test]
+fn test_evolog_with_paths() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "foo\n");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir.write_file("file2", "bar\n");
+    work_dir.run_jj(["describe", "-m", "second"]).success();
+    work_dir.write_file("file1", "foo\nbaz\n");
+
+    let output = work_dir.run_jj(["evolog"]);
+    insta::assert_snapshot!(output, @r"
+    @  qpvuntsm test.user@example.com 2001-02-03 08:05:10 bfffd960
+    │  second
+    │  -- operation 0d3d8f6300cf snapshot working copy
+    ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:09 5817f230
+    │  second
+    │  -- operation 19ca30a5b163 describe commit 8cdb911e3f129f543162b33bbb8fab5f754ff3fb
+    ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:09 8cdb911e
+    │  first
+    │  -- operation 2dffc051ebf2 snapshot working copy
+    ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:08 46de3a2c
+    │  first
+    │  -- operation c30eb3ab4739 describe commit c664a51bff8c9375136612b706f7706a775e0f32
+    ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:08 c664a51b
+    │  (no description set)
+    │  -- operation d41efd97c291 snapshot working copy
+    ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:07 e8849ae1
+       (empty) (no description set)
+       -- operation 8f47435a3990 add workspace 'default'
+    [EOF]
+    ");
+
+    // Versions which didn't modify the paths are elided from the graph
+    let output = work_dir.run_jj(["evolog", "file1"]);
+    insta::assert_snapshot!(output, @r"
+    @  qpvuntsm test.user@example.com 2001-02-03 08:05:10 bfffd960
+    ╷  second
+    ╷  -- operation 0d3d8f6300cf snapshot working copy
+    ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:08 c664a51b
+       (no description set)
+       -- operation d41efd97c291 snapshot working copy
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["evolog", "--no-graph", "file2"]);
+    insta::assert_snapshot!(output, @r"
+    qpvuntsm hidden test.user@example.com 2001-02-03 08:05:09 8cdb911e
+    first
+    -- operation 2dffc051ebf2 snapshot working copy
+    [EOF]
+    ");
+
+    // The patch is limited to the paths
+    let output = work_dir.run_jj(["evolog", "-p", "--git", "file2"]);
+    insta::assert_snapshot!(output, @r"
+    ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:09 8cdb911e
+       first
+       -- operation 2dffc051ebf2 snapshot working copy
+       diff --git a/file2 b/file2
+       new file mode 100644
+       index 0000000000..5716ca5987
+       --- /dev/null
+       +++ b/file2
+       @@ -0,0 +1,1 @@
+       +bar
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_evolog_template() {
     let test_env = TestEnvironment::default();
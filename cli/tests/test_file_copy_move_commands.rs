@@ -0,0 +1,158 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_file_copy() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "contents\n");
+    work_dir.create_dir("dir");
+    work_dir.write_file("dir/file", "dir contents\n");
+    work_dir.run_jj(["new"]).success();
+
+    let output = work_dir.run_jj(["file", "copy", "file", "copied"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Working copy  (@) now at: rlvkpnrz 1a5b8543 (no description set)
+    Parent commit (@-)      : qpvuntsm 0255899a (no description set)
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("copied"), @"contents");
+    let output = work_dir.run_jj(["diff", "--summary"]);
+    insta::assert_snapshot!(output, @"
+    A copied
+    [EOF]
+    ");
+
+    // Copy into an existing directory
+    let output = work_dir.run_jj(["file", "cp", "copied", "dir"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Working copy  (@) now at: rlvkpnrz f588ce68 (no description set)
+    Parent commit (@-)      : qpvuntsm 0255899a (no description set)
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+    // Copy a directory
+    let output = work_dir.run_jj(["file", "cp", "dir", "dir2"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Working copy  (@) now at: rlvkpnrz 258b11f9 (no description set)
+    Parent commit (@-)      : qpvuntsm 0255899a (no description set)
+    Added 2 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "list"]);
+    insta::assert_snapshot!(output, @"
+    copied
+    dir/copied
+    dir/file
+    dir2/copied
+    dir2/file
+    file
+    [EOF]
+    ");
+
+    // Errors
+    let output = work_dir.run_jj(["file", "copy", "nonexistent", "new"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: No such path: nonexistent
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["file", "copy", "file", "copied"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Destination already exists: copied
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["file", "copy", "dir", "dir/sub"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Destination is inside the source: dir/sub
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_file_move() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "contents\n");
+    work_dir.create_dir("dir");
+    work_dir.write_file("dir/file", "dir contents\n");
+    work_dir.run_jj(["new"]).success();
+
+    let output = work_dir.run_jj(["file", "move", "file", "renamed"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Working copy  (@) now at: rlvkpnrz 5dc433a0 (no description set)
+    Parent commit (@-)      : qpvuntsm 0255899a (no description set)
+    Added 1 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+    assert!(!work_dir.root().join("file").exists());
+    insta::assert_snapshot!(work_dir.read_file("renamed"), @"contents");
+    let output = work_dir.run_jj(["diff", "--summary"]);
+    insta::assert_snapshot!(output, @"
+    R {file => renamed}
+    [EOF]
+    ");
+
+    // Move a directory into an existing directory
+    work_dir.create_dir("other");
+    work_dir.write_file("other/file", "other contents\n");
+    let output = work_dir.run_jj(["file", "mv", "dir", "other"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Working copy  (@) now at: rlvkpnrz 08228923 (no description set)
+    Parent commit (@-)      : qpvuntsm 0255899a (no description set)
+    Added 1 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--summary"]);
+    insta::assert_snapshot!(output, @"
+    R {dir => other/dir}/file
+    A other/file
+    R {file => renamed}
+    [EOF]
+    ");
+
+    // Move in another revision
+    let output = work_dir.run_jj(["file", "mv", "-r=@-", "file", "moved"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Rebased 1 descendant commits
+    Working copy  (@) now at: rlvkpnrz abf14eac (no description set)
+    Parent commit (@-)      : qpvuntsm c88e8c25 (no description set)
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "list", "-r=@-"]);
+    insta::assert_snapshot!(output, @"
+    dir/file
+    moved
+    [EOF]
+    ");
+}
@@ -116,6 +116,66 @@ fn test_show() {
     ");
 }
 
+#[test]
+fn test_show_output_dir() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "a\n");
+    work_dir.create_dir("dir");
+    work_dir.write_file("dir/file2", "b\n");
+    work_dir.write_file("dir/file3", "c\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.remove_file("file1");
+
+    // Files are written to the same relative paths
+    let output = work_dir.run_jj([
+        "file",
+        "show",
+        "-r@-",
+        "-o",
+        "../out",
+        "file1",
+        "glob:dir/*2",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Wrote 2 files to ../out
+    [EOF]
+    ");
+    let out_dir = test_env.work_dir("out");
+    insta::assert_snapshot!(out_dir.read_file("file1"), @r"
+    a
+    ");
+    insta::assert_snapshot!(out_dir.read_file("dir/file2"), @r"
+    b
+    ");
+    assert!(!out_dir.root().join("dir/file3").exists());
+
+    // Unmatched paths should generate warnings
+    let output = work_dir.run_jj(["file", "show", "-o", "../out", "file1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: No matching entries for paths: file1
+    Wrote 0 files to ../out
+    [EOF]
+    ");
+
+    // Templates can't be used with --output-dir
+    let output = work_dir.run_jj(["file", "show", "-o", "../out", "-T", "path", "dir"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--output-dir <DIR>' cannot be used with '--template <TEMPLATE>'
+
+    Usage: jj file show --output-dir <DIR> <FILESETS>...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[cfg(unix)]
 #[test]
 fn test_show_symlink() {
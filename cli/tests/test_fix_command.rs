@@ -53,7 +53,7 @@ fn test_config_no_tools() {
     Config error: No `fix.tools` are configured
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
     ");
 
     let output = work_dir.run_jj(["file", "show", "file", "-r", "@"]);
@@ -145,7 +145,7 @@ fn test_config_multiple_tools_with_same_name() {
     Hint: Check the config file: $TEST_ENV/config/config0002.toml
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
     ");
 
     test_env.set_config_path("/dev/null");
@@ -237,7 +237,7 @@ fn test_config_disabled_tools_warning_when_all_tools_are_disabled() {
     Config error: At least one entry of `fix.tools` must be enabled.
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
     ");
 }
 
@@ -312,7 +312,7 @@ fn test_config_tables_all_commands_missing() {
     Hint: Check the config file: $TEST_ENV/config/config0002.toml
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
     ");
 
     let output = work_dir.run_jj(["file", "show", "foo", "-r", "@"]);
@@ -352,7 +352,7 @@ fn test_config_tables_some_commands_missing() {
     Hint: Check the config file: $TEST_ENV/config/config0002.toml
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
     ");
 
     let output = work_dir.run_jj(["file", "show", "foo", "-r", "@"]);
@@ -159,6 +159,35 @@ fn test_git_clone() {
     "#);
 }
 
+#[test]
+fn test_clone_top_level_command() {
+    let test_env = TestEnvironment::default();
+    let root_dir = test_env.work_dir("");
+    let git_repo_path = test_env.env_root().join("source");
+    let git_repo = git::init(git_repo_path);
+    set_up_non_empty_git_repo(&git_repo);
+
+    // `jj clone` is the same as `jj git clone`
+    let output = root_dir.run_jj(["clone", "source", "clone"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Fetching into new repo in "$TEST_ENV/clone"
+    bookmark: main@origin [new] tracked
+    Setting the revset alias `trunk()` to `main@origin`
+    Working copy  (@) now at: sqpuoqvx 1ca44815 (empty) (no description set)
+    Parent commit (@-)      : qomsplrm ebeb70d8 main | message
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    "#);
+    let clone_dir = test_env.work_dir("clone");
+    assert!(clone_dir.root().join("file").exists());
+    let output = clone_dir.run_jj(["git", "remote", "list"]);
+    insta::assert_snapshot!(output, @r"
+    origin $TEST_ENV/source
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_git_clone_bad_source() {
     let test_env = TestEnvironment::default();
@@ -1039,7 +1068,7 @@ fn test_git_clone_invalid_immutable_heads() {
     Caused by: Revision `unknown` doesn't exist
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
     "#);
 }
 
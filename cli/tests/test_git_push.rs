@@ -83,6 +83,51 @@ fn test_git_push_nothing() {
     ");
 }
 
+#[test]
+fn test_git_push_dry_run_stat() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let work_dir = test_env.work_dir("local");
+    work_dir
+        .run_jj(["new", "bookmark1", "-m=add file1"])
+        .success();
+    work_dir.write_file("file1", "foo\n");
+    work_dir.run_jj(["new", "-m=add file2"]).success();
+    work_dir.write_file("file2", "bar\nbaz\n");
+    work_dir
+        .run_jj(["bookmark", "set", "bookmark1", "-r@"])
+        .success();
+
+    let output = work_dir.run_jj(["git", "push", "--dry-run", "--stat"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Changes to push to origin:
+      Move forward bookmark bookmark1 from 9b2e76de3920 to 0d0b465970d5
+    Commits to push to origin:
+      yostqsxw 0d0b4659 bookmark1* | add file2
+      vruxwmqv 1dca6572 add file1
+    file1 | 1 +
+    file2 | 2 ++
+    2 files changed, 3 insertions(+), 0 deletions(-)
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+
+    // --stat requires --dry-run
+    let output = work_dir.run_jj(["git", "push", "--stat"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: the following required arguments were not provided:
+      --dry-run
+
+    Usage: jj git push --dry-run --stat
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_git_push_current_bookmark() {
     let test_env = TestEnvironment::default();
@@ -113,11 +158,13 @@ fn test_git_push_current_bookmark() {
     ");
     // First dry-run. `bookmark1` should not get pushed.
     let output = work_dir.run_jj(["git", "push", "--allow-new", "--dry-run"]);
-    insta::assert_snapshot!(output, @r"
+    insta::assert_snapshot!(output, @"
     ------- stderr -------
     Changes to push to origin:
       Move forward bookmark bookmark2 from 38a204733702 to 88ca14a7d46f
       Add bookmark my-bookmark to 88ca14a7d46f
+    Commits to push to origin:
+      yostqsxw 88ca14a7 bookmark2* my-bookmark | (empty) foo
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -591,10 +638,12 @@ fn test_git_push_locally_created_and_rewritten() {
     ");
     // Either --allow-new or git.push-new-bookmarks=true should work
     let output = work_dir.run_jj(["git", "push", "--allow-new", "--dry-run"]);
-    insta::assert_snapshot!(output, @r"
+    insta::assert_snapshot!(output, @"
     ------- stderr -------
     Changes to push to origin:
       Add bookmark my to e0cba5e497ee
+    Commits to push to origin:
+      vruxwmqv e0cba5e4 my | (empty) local 1
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -653,12 +702,14 @@ fn test_git_push_multiple() {
     ");
     // First dry-run
     let output = work_dir.run_jj(["git", "push", "--all", "--deleted", "--dry-run"]);
-    insta::assert_snapshot!(output, @r"
+    insta::assert_snapshot!(output, @"
     ------- stderr -------
     Changes to push to origin:
       Delete bookmark bookmark1 from 9b2e76de3920
       Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75
       Add bookmark my-bookmark to 352fa1879f75
+    Commits to push to origin:
+      yqosqzyt 352fa187 bookmark2* my-bookmark | (empty) foo
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -671,11 +722,13 @@ fn test_git_push_multiple() {
         "-b=my-bookmark",
         "--dry-run",
     ]);
-    insta::assert_snapshot!(output, @r"
+    insta::assert_snapshot!(output, @"
     ------- stderr -------
     Changes to push to origin:
       Delete bookmark bookmark1 from 9b2e76de3920
       Add bookmark my-bookmark to 352fa1879f75
+    Commits to push to origin:
+      yqosqzyt 352fa187 bookmark2* my-bookmark | (empty) foo
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -690,21 +743,25 @@ fn test_git_push_multiple() {
         "-b=glob:my-*",
         "--dry-run",
     ]);
-    insta::assert_snapshot!(output, @r"
+    insta::assert_snapshot!(output, @"
     ------- stderr -------
     Changes to push to origin:
       Delete bookmark bookmark1 from 9b2e76de3920
       Add bookmark my-bookmark to 352fa1879f75
+    Commits to push to origin:
+      yqosqzyt 352fa187 bookmark2* my-bookmark | (empty) foo
     Dry-run requested, not pushing.
     [EOF]
     ");
     // Dry run with glob pattern
     let output = work_dir.run_jj(["git", "push", "-b=glob:bookmark?", "--dry-run"]);
-    insta::assert_snapshot!(output, @r"
+    insta::assert_snapshot!(output, @"
     ------- stderr -------
     Changes to push to origin:
       Delete bookmark bookmark1 from 9b2e76de3920
       Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75
+    Commits to push to origin:
+      yqosqzyt 352fa187 bookmark2* my-bookmark | (empty) foo
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -727,23 +784,27 @@ fn test_git_push_multiple() {
 
     // --deleted is required to push deleted bookmarks even with --all
     let output = work_dir.run_jj(["git", "push", "--all", "--dry-run"]);
-    insta::assert_snapshot!(output, @r"
+    insta::assert_snapshot!(output, @"
     ------- stderr -------
     Warning: Refusing to push deleted bookmark bookmark1
     Hint: Push deleted bookmarks with --deleted or forget the bookmark to suppress this warning.
     Changes to push to origin:
       Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75
       Add bookmark my-bookmark to 352fa1879f75
+    Commits to push to origin:
+      yqosqzyt 352fa187 bookmark2* my-bookmark | (empty) foo
     Dry-run requested, not pushing.
     [EOF]
     ");
     let output = work_dir.run_jj(["git", "push", "--all", "--deleted", "--dry-run"]);
-    insta::assert_snapshot!(output, @r"
+    insta::assert_snapshot!(output, @"
     ------- stderr -------
     Changes to push to origin:
       Delete bookmark bookmark1 from 9b2e76de3920
       Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75
       Add bookmark my-bookmark to 352fa1879f75
+    Commits to push to origin:
+      yqosqzyt 352fa187 bookmark2* my-bookmark | (empty) foo
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -1279,39 +1340,52 @@ fn test_git_push_revisions() {
     ");
     // Push a revision with a single bookmark
     let output = work_dir.run_jj(["git", "push", "--allow-new", "-r=@-", "--dry-run"]);
-    insta::assert_snapshot!(output, @r"
+    insta::assert_snapshot!(output, @"
     ------- stderr -------
     Changes to push to origin:
       Add bookmark bookmark-1 to e76139e55e1e
+    Commits to push to origin:
+      yostqsxw e76139e5 bookmark-1 | bar
+      yqosqzyt 0f8164cd foo
     Dry-run requested, not pushing.
     [EOF]
     ");
     // Push multiple revisions of which some have bookmarks
     let output = work_dir.run_jj(["git", "push", "--allow-new", "-r=@--", "-r=@-", "--dry-run"]);
-    insta::assert_snapshot!(output, @r"
+    insta::assert_snapshot!(output, @"
     ------- stderr -------
     Warning: No bookmarks point to the specified revisions: @--
     Changes to push to origin:
       Add bookmark bookmark-1 to e76139e55e1e
+    Commits to push to origin:
+      yostqsxw e76139e5 bookmark-1 | bar
+      yqosqzyt 0f8164cd foo
     Dry-run requested, not pushing.
     [EOF]
     ");
     // Push a revision with a multiple bookmarks
     let output = work_dir.run_jj(["git", "push", "--allow-new", "-r=@", "--dry-run"]);
-    insta::assert_snapshot!(output, @r"
+    insta::assert_snapshot!(output, @"
     ------- stderr -------
     Changes to push to origin:
       Add bookmark bookmark-2a to 57d822f901bb
       Add bookmark bookmark-2b to 57d822f901bb
+    Commits to push to origin:
+      kpqxywon 57d822f9 bookmark-2a bookmark-2b | baz
+      yostqsxw e76139e5 bookmark-1 | bar
+      yqosqzyt 0f8164cd foo
     Dry-run requested, not pushing.
     [EOF]
     ");
     // Repeating a commit doesn't result in repeated messages about the bookmark
     let output = work_dir.run_jj(["git", "push", "--allow-new", "-r=@-", "-r=@-", "--dry-run"]);
-    insta::assert_snapshot!(output, @r"
+    insta::assert_snapshot!(output, @"
     ------- stderr -------
     Changes to push to origin:
       Add bookmark bookmark-1 to e76139e55e1e
+    Commits to push to origin:
+      yostqsxw e76139e5 bookmark-1 | bar
+      yqosqzyt 0f8164cd foo
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -1481,10 +1555,12 @@ fn test_git_push_no_description_in_immutable() {
         "--bookmark=my-bookmark",
         "--dry-run",
     ]);
-    insta::assert_snapshot!(output, @r"
+    insta::assert_snapshot!(output, @"
     ------- stderr -------
     Changes to push to origin:
       Add bookmark my-bookmark to 240e2e89abb2
+    Commits to push to origin:
+      znkkpsqq 240e2e89 my-bookmark | foo
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -1566,10 +1642,12 @@ fn test_git_push_missing_author_in_immutable() {
         "--bookmark=my-bookmark",
         "--dry-run",
     ]);
-    insta::assert_snapshot!(output, @r"
+    insta::assert_snapshot!(output, @"
     ------- stderr -------
     Changes to push to origin:
       Add bookmark my-bookmark to 96080b93b4ce
+    Commits to push to origin:
+      kpqxywon 96080b93 my-bookmark | foo
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -1669,10 +1747,12 @@ fn test_git_push_missing_committer_in_immutable() {
         "--bookmark=my-bookmark",
         "--dry-run",
     ]);
-    insta::assert_snapshot!(output, @r"
+    insta::assert_snapshot!(output, @"
     ------- stderr -------
     Changes to push to origin:
       Add bookmark my-bookmark to e0dff9c29479
+    Commits to push to origin:
+      kmkuslsw e0dff9c2 my-bookmark | foo
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -2082,10 +2162,14 @@ fn test_git_push_sign_on_push() {
     "#,
     );
     let output = work_dir.run_jj(["git", "push", "--dry-run"]);
-    insta::assert_snapshot!(output, @r"
+    insta::assert_snapshot!(output, @"
     ------- stderr -------
+    Would update signatures of 2 commits
     Changes to push to origin:
       Move forward bookmark bookmark2 from 38a204733702 to 3779ed7f18df
+    Commits to push to origin:
+      yostqsxw 3779ed7f bookmark2* | (empty) commit to be signed 2
+      vruxwmqv 665da64c (empty) commit to be signed 1
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -2174,6 +2258,40 @@ fn test_git_push_sign_on_push() {
     ");
 }
 
+#[test]
+fn test_git_push_sign_on_push_no_sign() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let work_dir = test_env.work_dir("local");
+    work_dir
+        .run_jj(["new", "bookmark2", "-m", "commit not to be signed"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "set", "bookmark2", "-r@"])
+        .success();
+    test_env.add_config(
+        r#"
+    signing.backend = "test"
+    signing.key = "impeccable"
+    git.sign-on-push = true
+    "#,
+    );
+    let output = work_dir.run_jj(["git", "push", "--no-sign"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Move forward bookmark bookmark2 from 38a204733702 to d9c42d7d0953
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-r@", "-T", "if(signature, 'signed', 'unsigned')"]);
+    insta::assert_snapshot!(output, @r"
+    @  unsigned
+    │
+    ~
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_git_push_rejected_by_remote() {
     let test_env = TestEnvironment::default();
@@ -2249,3 +2367,112 @@ fn get_bookmark_output(work_dir: &TestWorkDir) -> CommandOutput {
     // --quiet to suppress deleted bookmarks hint
     work_dir.run_jj(["bookmark", "list", "--all-remotes", "--quiet"])
 }
+
+#[test]
+fn test_git_push_pre_push_hook() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let work_dir = test_env.work_dir("local");
+    let formatter_path = assert_cmd::cargo::cargo_bin("fake-formatter");
+    let input_path = test_env.env_root().join("hook-input");
+    work_dir
+        .run_jj(["describe", "bookmark1", "-m", "modified bookmark1 commit"])
+        .success();
+
+    // The hook can veto the push
+    let output = work_dir.run_jj([
+        "git",
+        "push",
+        "-b=bookmark1",
+        &format!(
+            "--config=hooks.pre-push=[{}, '--fail', '--stdout', '', '--stderr', {}]",
+            to_toml_value(formatter_path.to_str().unwrap()),
+            to_toml_value("Not now\n")
+        ),
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Move sideways bookmark bookmark1 from 9b2e76de3920 to e5ce6d9a0991
+    Not now
+    Error: The pre-push hook rejected the operation (exit status: 1)
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj([
+        "git",
+        "push",
+        "-b=bookmark1",
+        &format!(
+            "--config=hooks.pre-push=[{}, '--tee', {}]",
+            to_toml_value(formatter_path.to_str().unwrap()),
+            to_toml_value(input_path.to_str().unwrap())
+        ),
+    ]);
+    output.success();
+    let input: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&input_path).unwrap()).unwrap();
+    insta::assert_snapshot!(serde_json::to_string_pretty(&input).unwrap(), @r#"
+    {
+      "hook": "pre-push",
+      "remote": "origin",
+      "updates": [
+        {
+          "bookmark": "bookmark1",
+          "new_target": "e5ce6d9a0991cb61b260b78c1e1e1d7e455ea0ae",
+          "old_target": "9b2e76de39209592527956784ca134072f70850f"
+        }
+      ]
+    }
+    "#);
+}
+
+#[test]
+fn test_git_push_sign_on_push_pre_commit_rewrite_hook() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let work_dir = test_env.work_dir("local");
+    let formatter_path = assert_cmd::cargo::cargo_bin("fake-formatter");
+    work_dir
+        .run_jj(["new", "bookmark2", "-m", "commit to be signed"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "set", "bookmark2", "-r@"])
+        .success();
+    test_env.add_config(
+        r#"
+    signing.backend = "test"
+    signing.key = "impeccable"
+    git.sign-on-push = true
+    "#,
+    );
+
+    // The hook rejects the signed commits before anything is pushed
+    let output = work_dir.run_jj([
+        "git",
+        "push",
+        &format!(
+            "--config=hooks.pre-commit-rewrite=[{}, '--fail', '--stdout', '', '--stderr', {}]",
+            to_toml_value(formatter_path.to_str().unwrap()),
+            to_toml_value("Not now\n")
+        ),
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Updated signatures of 1 commits
+    Changes to push to origin:
+      Move forward bookmark bookmark2 from 38a204733702 to ecd1a474dc52
+    Not now
+    Error: The pre-commit-rewrite hook rejected the operation (exit status: 1)
+    [EOF]
+    [exit status: 1]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    bookmark1: qpvuntsm 9b2e76de (empty) description 1
+      @origin: qpvuntsm 9b2e76de (empty) description 1
+    bookmark2: vruxwmqv 3a54ae71 (empty) commit to be signed
+      @origin (behind by 1 commits): zsuskuln 38a20473 (empty) description 2
+    [EOF]
+    ");
+}
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fs;
+
 use testutils::git;
 
 use crate::common::TestEnvironment;
@@ -100,3 +102,21 @@ fn test_git_root_simple_backend() {
     [exit status: 1]
     "#);
 }
+
+#[test]
+fn test_git_root_does_not_load_index() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // Remove the index so that loading it would rebuild it
+    let index_dir = work_dir.root().join(".jj/repo/index");
+    fs::remove_dir_all(index_dir.join("op_links")).unwrap();
+    fs::remove_dir_all(index_dir.join("operations")).unwrap();
+
+    work_dir.run_jj(["git", "root"]).success();
+    assert!(!index_dir.join("op_links").exists());
+
+    work_dir.run_jj(["log"]).success();
+    assert!(index_dir.join("op_links").exists());
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashSet;
 use std::ffi::OsString;
 
 use indoc::indoc;
@@ -520,7 +521,53 @@ fn test_color_config() {
 
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
+    ");
+}
+
+#[test]
+fn test_color_theme() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // The light theme doesn't use bright colors.
+    let output = work_dir.run_jj([
+        "--color=always",
+        "--config=ui.theme=light",
+        "log",
+        "-T",
+        "commit_id",
+    ]);
+    insta::assert_snapshot!(output, @"
+    [1m[38;5;2m@[0m  [38;5;4me8849ae12c709f2321908879bc724fdb2ab8a781[39m
+    [1m[38;5;6m◆[0m  [38;5;4m0000000000000000000000000000000000000000[39m
+    [EOF]
+    ");
+
+    // User colors override the theme.
+    let output = work_dir.run_jj([
+        "--color=always",
+        "--config=ui.theme=light",
+        "--config=colors.'node immutable'=red",
+        "log",
+        "-T",
+        "commit_id",
+    ]);
+    insta::assert_snapshot!(output, @"
+    [1m[38;5;2m@[0m  [38;5;4me8849ae12c709f2321908879bc724fdb2ab8a781[39m
+    [1m[38;5;1m◆[0m  [38;5;4m0000000000000000000000000000000000000000[39m
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["--color=always", "--config=ui.theme=unknown", "log"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Config error: Invalid type or value for ui.theme
+    Caused by: No such theme: unknown
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 4]
     ");
 }
 
@@ -746,7 +793,7 @@ fn test_config_args() {
     Config error: --config must be specified as NAME=VALUE
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
     ");
 
     let output = test_env.run_jj_in(".", ["config", "list", "--config-file=unknown.toml"]);
@@ -761,7 +808,7 @@ fn test_config_args() {
         2: <redacted>
         For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
         [EOF]
-        [exit status: 1]
+        [exit status: 4]
         ");
     });
 }
@@ -785,7 +832,7 @@ fn test_invalid_config() {
     Hint: Check the config file: $TEST_ENV/config/config0002.toml
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
     ");
 }
 
@@ -804,10 +851,57 @@ fn test_invalid_config_value() {
 
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
     ");
 }
 
+#[test]
+fn test_error_format_json() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["describe", "-r", "root()", "--error-format=json"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    {"category":"user","causes":[],"code":"immutable-commit","hints":[],"message":"The root commit 000000000000 is immutable"}
+    [EOF]
+    [exit status: 1]
+    "#);
+
+    let output = work_dir.run_jj(["log", "-r", "foo", "--error-format=json"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    {"category":"user","causes":[],"code":"no-such-revision","hints":[],"message":"Revision `foo` doesn't exist"}
+    [EOF]
+    [exit status: 1]
+    "#);
+
+    let output = work_dir.run_jj([
+        "status",
+        "--config=snapshot.auto-track=[0]",
+        "--error-format=json",
+    ]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    {"category":"config","causes":["invalid type: sequence, expected a string"],"code":null,"hints":[],"message":"Invalid type or value for snapshot.auto-track"}
+    [EOF]
+    [exit status: 4]
+    "#);
+
+    let output = work_dir.run_jj(["--error-format=json", "log", "--no-such-flag"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    {"category":"cli","causes":[],"code":null,"hints":[],"message":"error: unexpected argument '--no-such-flag' found\n\n  tip: a similar argument exists: '--ignore-space-change'\n\nUsage: jj log --ignore-space-change [FILESETS]...\n\nFor more information, try '--help'."}
+    [EOF]
+    [exit status: 2]
+    "#);
+
+    // Help isn't an error
+    let output = work_dir.run_jj(["log", "--help", "--error-format=json"]);
+    assert!(output.status.success());
+}
+
 #[test]
 #[cfg_attr(windows, ignore = "dirs::home_dir() can't be overridden by $HOME")] // TODO
 fn test_conditional_config() {
@@ -1038,7 +1132,7 @@ fn test_help() {
     let test_env = TestEnvironment::default();
 
     let output = test_env.run_jj_in(".", ["diffedit", "-h"]);
-    insta::assert_snapshot!(output, @r"
+    insta::assert_snapshot!(output, @"
     Touch up the content changes in a revision with a diff editor
 
     Usage: jj diffedit [OPTIONS] [FILESETS]...
@@ -1058,14 +1152,21 @@ fn test_help() {
       -R, --repository <REPOSITORY>      Path to repository to operate on
           --ignore-working-copy          Don't snapshot the working copy, and don't update it
           --ignore-immutable             Allow rewriting immutable commits
+          --sign                         Sign the commits created or rewritten by this command
+          --no-sign                      Don't sign the commits created or rewritten by this command
           --at-operation <AT_OPERATION>  Operation to load the repo at [aliases: --at-op]
+          --output <FORMAT>              Format of the command output [default: human] [possible values:
+                                         human, json]
           --debug                        Enable debug logging
+          --profile[=<FILE>]             Write timing spans of the command to a file for performance
+                                         analysis
           --color <WHEN>                 When to colorize output [possible values: always, never, debug,
                                          auto]
           --quiet                        Silence non-primary command output
           --no-pager                     Disable the pager
           --config <NAME=VALUE>          Additional configuration options (can be repeated)
           --config-file <PATH>           Additional configuration files (can be repeated)
+          --error-format <FORMAT>        Format of error messages [possible values: human, json]
     [EOF]
     ");
 }
@@ -1090,3 +1191,42 @@ fn test_debug_logging_enabled() {
     // Luckily, insta will print this in color when reviewing.
     insta::assert_snapshot!(log_line, @"[32m INFO[0m [2mjj_cli::cli_util[0m[2m:[0m debug logging enabled");
 }
+
+#[test]
+fn test_profile() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let profile_path = test_env.env_root().join("profile.json");
+
+    let profile_arg = format!("--profile={}", profile_path.display());
+    work_dir.run_jj(["log", "-r@", &profile_arg]).success();
+    let profile = std::fs::read_to_string(&profile_path).unwrap();
+    let events: Vec<serde_json::Value> = serde_json::from_str(&profile).unwrap();
+    let span_names: HashSet<_> = events
+        .iter()
+        .filter_map(|event| event["name"].as_str())
+        .collect();
+    assert!(span_names.contains("snapshot"), "{span_names:?}");
+    assert!(span_names.contains("evaluate"), "{span_names:?}");
+    assert!(span_names.contains("read_commit"), "{span_names:?}");
+
+    // The file name is optional, so it must be attached with "="
+    work_dir
+        .run_jj(["--profile", "log", "-r@", "--ignore-working-copy"])
+        .success();
+    let profile_names = std::fs::read_dir(work_dir.root())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .filter(|name| name.starts_with("jj-profile-"))
+        .collect_vec();
+    assert_eq!(profile_names.len(), 1, "{profile_names:?}");
+
+    let output = work_dir.run_jj(["log", "--profile=nonexistent/profile.json"]);
+    insta::assert_snapshot!(output.strip_stderr_last_line(), @r"
+    ------- stderr -------
+    Error: Failed to create profile file nonexistent/profile.json
+    [EOF]
+    [exit status: 1]
+    ");
+}
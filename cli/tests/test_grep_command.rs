@@ -0,0 +1,101 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_grep() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "foo\nbar\n");
+    work_dir.create_dir("dir");
+    work_dir.write_file("dir/file2", "foobar\nbaz\n");
+    work_dir.write_file("binary", b"foo\0");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file1", "foo\nqux\n");
+
+    // Searches the working-copy commit by default
+    let output = work_dir.run_jj(["grep", "foo"]);
+    insta::assert_snapshot!(output, @r"
+    rlvkpnrzqnoo:dir/file2:1:foobar
+    rlvkpnrzqnoo:file1:1:foo
+    [EOF]
+    ");
+
+    // Can search multiple revisions
+    let output = work_dir.run_jj(["grep", "ba", "-r@", "-r@-"]);
+    insta::assert_snapshot!(output, @r"
+    rlvkpnrzqnoo:dir/file2:1:foobar
+    rlvkpnrzqnoo:dir/file2:2:baz
+    qpvuntsmwlqt:dir/file2:1:foobar
+    qpvuntsmwlqt:dir/file2:2:baz
+    qpvuntsmwlqt:file1:2:bar
+    [EOF]
+    ");
+
+    // Can restrict the search to some paths
+    let output = work_dir.run_jj(["grep", "foo", "dir"]);
+    insta::assert_snapshot!(output, @r"
+    rlvkpnrzqnoo:dir/file2:1:foobar
+    [EOF]
+    ");
+
+    // Supports other kinds of string patterns
+    let output = work_dir.run_jj(["grep", "regex:^ba[rz]$", "-r@-"]);
+    insta::assert_snapshot!(output, @r"
+    qpvuntsmwlqt:dir/file2:2:baz
+    qpvuntsmwlqt:file1:2:bar
+    [EOF]
+    ");
+
+    // Can list only the matching files
+    let output = work_dir.run_jj(["grep", "-l", "o", "-r@|@-"]);
+    insta::assert_snapshot!(output, @r"
+    rlvkpnrzqnoo:dir/file2
+    rlvkpnrzqnoo:file1
+    qpvuntsmwlqt:dir/file2
+    qpvuntsmwlqt:file1
+    [EOF]
+    ");
+
+    // Prints nothing if there are no matches
+    let output = work_dir.run_jj(["grep", "nonexistent"]);
+    insta::assert_snapshot!(output, @"");
+}
+
+#[test]
+fn test_grep_conflict() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "base\n");
+    work_dir.write_file("other", "base\n");
+    work_dir.run_jj(["new", "-mleft"]).success();
+    work_dir.write_file("file", "left\n");
+    work_dir.run_jj(["new", "-mright", "@-"]).success();
+    work_dir.write_file("file", "right\n");
+    work_dir
+        .run_jj(["new", "-mmerge", "description(left)", "description(right)"])
+        .success();
+
+    // Conflicted files are skipped
+    let output = work_dir.run_jj(["grep", "regex:."]);
+    insta::assert_snapshot!(output, @r"
+    zsuskulnrvyr:other:1:base
+    [EOF]
+    ");
+}
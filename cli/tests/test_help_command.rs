@@ -165,11 +165,11 @@ fn test_help_keyword() {
     // It shouldn't show help for a certain keyword if the `--keyword` is not
     // present
     let output = test_env.run_jj_in(".", ["help", "revsets"]);
-    insta::assert_snapshot!(output, @r"
+    insta::assert_snapshot!(output, @"
     ------- stderr -------
     error: unrecognized subcommand 'revsets'
 
-      tip: some similar subcommands exist: 'resolve', 'prev', 'restore', 'rebase', 'revert'
+      tip: some similar subcommands exist: 'resolve', 'prev', 'test', 'restore', 'rebase', 'revert'
 
     Usage: jj [OPTIONS] [COMMAND]
 
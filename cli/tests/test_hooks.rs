@@ -0,0 +1,165 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::common::TestEnvironment;
+use crate::common::to_toml_value;
+
+/// Configures the hook to record its input to `path` and succeed.
+fn set_up_recording_hook(test_env: &TestEnvironment, name: &str, path: &Path) {
+    let formatter_path = assert_cmd::cargo::cargo_bin("fake-formatter");
+    test_env.add_config(format!(
+        "hooks.{name} = [{formatter}, '--tee', {path}]",
+        formatter = to_toml_value(formatter_path.to_str().unwrap()),
+        path = to_toml_value(path.to_str().unwrap()),
+    ));
+}
+
+/// Configures the hook to print `message` and fail.
+fn set_up_failing_hook(test_env: &TestEnvironment, name: &str, message: &str) {
+    let formatter_path = assert_cmd::cargo::cargo_bin("fake-formatter");
+    test_env.add_config(format!(
+        "hooks.{name} = [{formatter}, '--fail', '--stdout', '', '--stderr', {message}]",
+        formatter = to_toml_value(formatter_path.to_str().unwrap()),
+        message = to_toml_value(message),
+    ));
+}
+
+fn read_hook_input(path: &Path) -> serde_json::Value {
+    let content = std::fs::read_to_string(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    serde_json::from_str(&content).unwrap()
+}
+
+#[test]
+fn test_hook_pre_commit_rewrite() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let old_commit_id = work_dir
+        .run_jj(["log", "--no-graph", "-r@", "-Tcommit_id"])
+        .success()
+        .stdout
+        .into_raw();
+
+    let input_path = test_env.env_root().join("hook-input");
+    set_up_recording_hook(&test_env, "pre-commit-rewrite", &input_path);
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    let new_commit_id = work_dir
+        .run_jj(["log", "--no-graph", "-r@", "-Tcommit_id"])
+        .success()
+        .stdout
+        .into_raw();
+
+    let input = read_hook_input(&input_path);
+    assert_eq!(input["hook"], "pre-commit-rewrite");
+    assert!(
+        input["operation"]
+            .as_str()
+            .unwrap()
+            .starts_with("describe commit")
+    );
+    assert_eq!(input["new_commits"].as_array().unwrap().len(), 1);
+    let new_commit = &input["new_commits"][0];
+    assert_eq!(new_commit["commit_id"], new_commit_id);
+    assert_eq!(new_commit["description"], "first\n");
+    assert_eq!(new_commit["predecessor_ids"][0], old_commit_id);
+    assert_eq!(input["hidden_commit_ids"][0], old_commit_id);
+
+    // Transactions which don't add or hide commits don't run the hook
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "foo"])
+        .success();
+    assert!(!input_path.exists());
+}
+
+#[test]
+fn test_hook_pre_commit_rewrite_veto() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    set_up_failing_hook(&test_env, "pre-commit-rewrite", "Policy violation\n");
+    let output = work_dir.run_jj(["describe", "-m", "first"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Policy violation
+    Error: The pre-commit-rewrite hook rejected the operation (exit status: 1)
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // The operation wasn't committed
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-Tdescription ++ '|'"]);
+    insta::assert_snapshot!(output, @"|[EOF]");
+}
+
+#[test]
+fn test_hook_post_operation_and_checkout() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let old_commit_id = work_dir
+        .run_jj(["log", "--no-graph", "-r@", "-Tcommit_id"])
+        .success()
+        .stdout
+        .into_raw();
+
+    let operation_input_path = test_env.env_root().join("operation-input");
+    let checkout_input_path = test_env.env_root().join("checkout-input");
+    set_up_recording_hook(&test_env, "post-operation", &operation_input_path);
+    set_up_recording_hook(&test_env, "post-checkout", &checkout_input_path);
+    work_dir.run_jj(["new", "-m", "second"]).success();
+    let new_commit_id = work_dir
+        .run_jj(["log", "--no-graph", "-r@", "-Tcommit_id"])
+        .success()
+        .stdout
+        .into_raw();
+    let operation_id = work_dir
+        .run_jj(["op", "log", "--no-graph", "-n1", "-Tid"])
+        .success()
+        .stdout
+        .into_raw();
+
+    let input = read_hook_input(&operation_input_path);
+    assert_eq!(input["hook"], "post-operation");
+    assert_eq!(input["operation_id"], operation_id);
+    assert_eq!(input["description"], "new empty commit");
+
+    let input = read_hook_input(&checkout_input_path);
+    assert_eq!(input["hook"], "post-checkout");
+    assert_eq!(input["workspace"], "default");
+    assert_eq!(input["old_commit_id"], old_commit_id);
+    assert_eq!(input["new_commit_id"], new_commit_id);
+}
+
+#[test]
+fn test_hook_post_operation_failure() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // Failures of post-* hooks don't fail the command
+    set_up_failing_hook(&test_env, "post-operation", "Notification failed\n");
+    let output = work_dir.run_jj(["describe", "-m", "first"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: qpvuntsm 68a50538 (empty) first
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    Notification failed
+    Warning: The post-operation hook failed (exit status: 1)
+    [EOF]
+    ");
+}
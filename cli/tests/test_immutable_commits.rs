@@ -91,7 +91,7 @@ fn test_rewrite_immutable_generic() {
     Caused by: Revision `bookmark_that_does_not_exist` doesn't exist
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
     ");
 
     // Can use --ignore-immutable to override
@@ -0,0 +1,229 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_json_output_log() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir.run_jj(["new", "-m", "second"]).success();
+
+    let output = work_dir.run_jj(["log", "--output", "json", "-r", "::@ ~ root()"]);
+    insta::assert_snapshot!(output, @r#"
+    {"commit_id":"b1cb6b2f9141e6ffee18532a8bf9a2075ca02606","parents":["68a505386f936fff6d718f55005e77ea72589bc1"],"change_id":"kkmpptxzrspxrzommnulwmwkkqwworpl","description":"second\n","author":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:09+07:00"},"committer":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:09+07:00"}}
+    {"commit_id":"68a505386f936fff6d718f55005e77ea72589bc1","parents":["0000000000000000000000000000000000000000"],"change_id":"qpvuntsmwlqtpsluzzsnyyzlmlwvmlnu","description":"first\n","author":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:08+07:00"},"committer":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:08+07:00"}}
+    [EOF]
+    "#);
+
+    let output = work_dir.run_jj(["log", "--output", "json", "-n1", "--reversed"]);
+    insta::assert_snapshot!(output, @r#"
+    {"commit_id":"b1cb6b2f9141e6ffee18532a8bf9a2075ca02606","parents":["68a505386f936fff6d718f55005e77ea72589bc1"],"change_id":"kkmpptxzrspxrzommnulwmwkkqwworpl","description":"second\n","author":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:09+07:00"},"committer":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:09+07:00"}}
+    [EOF]
+    "#);
+
+    let output = work_dir.run_jj(["log", "--output", "json", "-T", "description"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: `--output json` cannot be combined with templates or diffs
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["log", "--output", "json", "-p"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: `--output json` cannot be combined with templates or diffs
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_json_output_status() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file1", "foo\n");
+    work_dir.write_file("file2", "foo\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.remove_file("file1");
+    work_dir.write_file("file2", "bar\n");
+    work_dir.write_file("file3", "baz\n");
+
+    let output = work_dir.run_jj(["status", "--output", "json"]);
+    insta::assert_snapshot!(output, @r#"
+    {"working_copy":{"commit_id":"060128ce8d601d972b6224b004b0c1675ebea7c8","parents":["74c18ac3d42dd3983bbae08898454275c0a3b250"],"change_id":"rlvkpnrzqnoowoytxnquwvuryrwnrmlp","description":"","author":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:09+07:00"},"committer":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:09+07:00"}},"parents":[{"commit_id":"74c18ac3d42dd3983bbae08898454275c0a3b250","parents":["0000000000000000000000000000000000000000"],"change_id":"qpvuntsmwlqtpsluzzsnyyzlmlwvmlnu","description":"","author":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:08+07:00"},"committer":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:08+07:00"}}],"changes":[{"status":"removed","path":"file1"},{"status":"modified","path":"file2"},{"status":"added","path":"file3"}],"untracked":[],"conflicts":[],"conflicted_bookmarks":[],"conflicted_remote_bookmarks":[]}
+    [EOF]
+    "#);
+
+    let output = work_dir.run_jj(["status", "--output", "json", "file2"]);
+    insta::assert_snapshot!(output, @r#"
+    {"working_copy":{"commit_id":"060128ce8d601d972b6224b004b0c1675ebea7c8","parents":["74c18ac3d42dd3983bbae08898454275c0a3b250"],"change_id":"rlvkpnrzqnoowoytxnquwvuryrwnrmlp","description":"","author":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:09+07:00"},"committer":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:09+07:00"}},"parents":[{"commit_id":"74c18ac3d42dd3983bbae08898454275c0a3b250","parents":["0000000000000000000000000000000000000000"],"change_id":"qpvuntsmwlqtpsluzzsnyyzlmlwvmlnu","description":"","author":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:08+07:00"},"committer":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:08+07:00"}}],"changes":[{"status":"modified","path":"file2"}],"untracked":[],"conflicts":[],"conflicted_bookmarks":[],"conflicted_remote_bookmarks":[]}
+    [EOF]
+    "#);
+}
+
+#[test]
+fn test_json_output_bookmark_list() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "foo", "bar"])
+        .success();
+    work_dir.run_jj(["new"]).success();
+    work_dir.run_jj(["bookmark", "set", "-r@", "foo"]).success();
+    // Make "foo" conflicted by deleting it concurrently
+    work_dir
+        .run_jj(["bookmark", "delete", "foo", "--at-op=@-"])
+        .success();
+
+    let output = work_dir.run_jj(["bookmark", "list", "--output", "json"]);
+    insta::assert_snapshot!(output, @r#"
+    {"name":"bar","target":["e8849ae12c709f2321908879bc724fdb2ab8a781"]}
+    {"name":"foo","target":["1c1c95df80e53b1e654608d7589f5baabb10ebb2","e8849ae12c709f2321908879bc724fdb2ab8a781",null]}
+    [EOF]
+    ------- stderr -------
+    Concurrent modification detected, resolving automatically.
+    [EOF]
+    "#);
+
+    let output = work_dir.run_jj(["bookmark", "list", "--output", "json", "-T", "name"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: `--output json` cannot be combined with templates
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_json_output_op_log() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+
+    let output = work_dir.run_jj(["op", "log", "--output", "json"]);
+    insta::assert_snapshot!(output, @r#"
+    {"id":"75545f7ff2df1bbfca2c35c53c34ffa4526eb005c60e8bd18a5199ec320ce1a5425bc8070dcfc688cabf15d857b7e1843accde980b9cd07cd689b77062632265","parents":["8f47435a3990362feaf967ca6de2eb0a31c8b883dfcb66fba5c22200d12bbe61e3dc8bc855f1f6879285fcafaf85ac792f9a43bcc36e57d28737d18347d5e752"],"time":{"start":"2001-02-03T04:05:08+07:00","end":"2001-02-03T04:05:08+07:00"},"description":"describe commit e8849ae12c709f2321908879bc724fdb2ab8a781","hostname":"host.example.com","username":"test-username","is_snapshot":false,"tags":{"args":"jj describe -m first"}}
+    {"id":"8f47435a3990362feaf967ca6de2eb0a31c8b883dfcb66fba5c22200d12bbe61e3dc8bc855f1f6879285fcafaf85ac792f9a43bcc36e57d28737d18347d5e752","parents":["00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"],"time":{"start":"2001-02-03T04:05:07+07:00","end":"2001-02-03T04:05:07+07:00"},"description":"add workspace 'default'","hostname":"host.example.com","username":"test-username","is_snapshot":false,"tags":{}}
+    {"id":"00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","parents":[],"time":{"start":"1970-01-01T00:00:00Z","end":"1970-01-01T00:00:00Z"},"description":"","hostname":"","username":"","is_snapshot":false,"tags":{}}
+    [EOF]
+    "#);
+
+    let output = work_dir.run_jj(["op", "log", "--output", "json", "--op-diff"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: `--output json` cannot be combined with templates or diffs
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_json_output_diff_summary() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file1", "foo\n");
+    work_dir.write_file("file2", "foo\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.remove_file("file1");
+    work_dir.write_file("file2", "bar\n");
+    work_dir.write_file("file3", "foo\n");
+
+    let output = work_dir.run_jj(["diff", "--summary", "--output", "json"]);
+    insta::assert_snapshot!(output, @r#"
+    {"status":"modified","path":"file2"}
+    {"status":"renamed","path":"file3","source":"file1"}
+    [EOF]
+    "#);
+
+    let output = work_dir.run_jj(["diff", "--output", "json"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: `--output json` is only supported for `jj diff --summary`
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_json_output_unsupported_command() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["show", "--output", "json"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: This command doesn't support `--output json`
+    [EOF]
+    [exit status: 2]
+    ");
+
+    let output = work_dir.run_jj(["log", "--output", "yaml"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: invalid value 'yaml' for '--output <FORMAT>'
+      [possible values: human, json]
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_json_output_workspace_list() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    main_dir
+        .run_jj([
+            "workspace",
+            "add",
+            "--sparse-patterns=empty",
+            "../secondary",
+        ])
+        .success();
+    main_dir
+        .run_jj(["workspace", "add", "../deleted"])
+        .success();
+    std::fs::remove_dir_all(test_env.env_root().join("deleted")).unwrap();
+    // Make the secondary workspace stale
+    main_dir.write_file("file", "contents\n");
+    main_dir
+        .run_jj(["squash", "--into", "secondary@"])
+        .success();
+
+    let output = main_dir.run_jj(["workspace", "list", "--output", "json"]);
+    insta::assert_snapshot!(output, @r#"
+    {"name":"default","current":true,"root":"$TEST_ENV/main","working_copy":{"commit_id":"c2934cfbfb196d2c473959667beffcc19e71e5e8","parents":["0000000000000000000000000000000000000000"],"change_id":"zsuskulnrvyrovkzqrwmxqlsskqntxvp","description":"","author":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:10+07:00"},"committer":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:10+07:00"}},"stale":false,"sparse_patterns":[""]}
+    {"name":"deleted","current":false,"root":"$TEST_ENV/deleted","working_copy":{"commit_id":"337ba39f440ed5303225529cd74b3ffb790d486b","parents":["0000000000000000000000000000000000000000"],"change_id":"pmmvwywvzvvnvnzntqnqknuzpwttyvkr","description":"","author":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:09+07:00"},"committer":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:09+07:00"}},"stale":null,"sparse_patterns":null}
+    {"name":"secondary","current":false,"root":"$TEST_ENV/secondary","working_copy":{"commit_id":"89ef564e647dd3c2e5db52b190ed5784fd7dc3a3","parents":["0000000000000000000000000000000000000000"],"change_id":"uuqppmxqsxsnvoxuwlkqvswowpyprsmr","description":"","author":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:10+07:00"},"committer":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:10+07:00"}},"stale":true,"sparse_patterns":[]}
+    [EOF]
+    "#);
+
+    let output = main_dir.run_jj(["workspace", "list", "--output", "json", "-T", "name"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: `--output json` cannot be combined with templates
+    [EOF]
+    [exit status: 1]
+    ");
+}
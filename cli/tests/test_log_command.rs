@@ -522,7 +522,7 @@ fn test_log_bad_short_prefixes() {
       = expected <strict_identifier> or <expression>
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
     ");
 
     // Warn on resolution of short prefixes
@@ -930,6 +930,245 @@ fn test_log_filtered_by_path() {
     ");
 }
 
+#[test]
+fn test_log_line_range() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\nb\nc\nd\n");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir.run_jj(["new", "-m", "second"]).success();
+    work_dir.write_file("file", "a\nb2\nc\nd2\n");
+    work_dir.run_jj(["new", "-m", "third"]).success();
+    work_dir.write_file("file", "a0\na\nb2\nc\nd2\n");
+    work_dir.write_file("other", "x\n");
+    work_dir.run_jj(["new", "-m", "fourth"]).success();
+    work_dir.write_file("file", "a0\na\nb2\nc3\nd2\n");
+
+    // Lines "b2" and "c" in the third revision. The insertion before them
+    // isn't shown.
+    let output = work_dir.run_jj(["log", "-T", "description", "-L", "3,4:file"]);
+    insta::assert_snapshot!(output, @"
+    @  fourth
+    │  --- a/file
+    │  +++ b/file
+    │  @@ -1,5 +1,5 @@
+    │   a0
+    │   a
+    │   b2
+    │  -c
+    │  +c3
+    │   d2
+    ~  (elided revisions)
+    ○  second
+    │  --- a/file
+    │  +++ b/file
+    │  @@ -1,4 +1,4 @@
+    │   a
+    │  -b
+    │  +b2
+    │   c
+    │  -d
+    │  +d2
+    ○  first
+    │  --- /dev/null
+    ~  +++ b/file
+       @@ -0,0 +1,4 @@
+       +a
+       +b
+       +c
+       +d
+    [EOF]
+    ");
+
+    // The range can be given as a number of lines, and the diff options apply.
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        "description",
+        "--no-graph",
+        "-L",
+        "2,+1:file",
+        "--context=0",
+    ]);
+    insta::assert_snapshot!(output, @"
+    first
+    --- /dev/null
+    +++ b/file
+    @@ -0,0 +1,4 @@
+    +a
+    +b
+    +c
+    +d
+    [EOF]
+    ");
+
+    // The revisions to search can be limited.
+    let output = work_dir.run_jj(["log", "-T", "description", "-L", "3,4:file", "-r", "@-::"]);
+    insta::assert_snapshot!(output, @"
+    @  fourth
+    │  --- a/file
+    ~  +++ b/file
+       @@ -1,5 +1,5 @@
+        a0
+        a
+        b2
+       -c
+       +c3
+        d2
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["log", "-L", "3,9:file"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Line range 3,9 is out of bounds: file has 5 lines
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["log", "-L", "1,2:missing"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: No such path: missing
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["log", "-L", "0,2:file"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: invalid value '0,2:file' for '--line-range <RANGE>': Line numbers start at 1
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+    let output = work_dir.run_jj(["log", "-L", "1,2:file", "-p"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: the argument '--line-range <RANGE>' cannot be used with '--patch'
+
+    Usage: jj log --line-range <RANGE> [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_log_line_range_renamed() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\nb\nc\nd\ne\n");
+    work_dir.run_jj(["describe", "-m", "add file"]).success();
+    work_dir.run_jj(["new", "-m", "rename file"]).success();
+    work_dir.remove_file("file");
+    work_dir.write_file("renamed", "a\nb\nc\nd\nE\n");
+    work_dir.run_jj(["new", "-m", "modify file"]).success();
+    work_dir.write_file("renamed", "A\nb\nc\nd\nE\n");
+
+    // The lines are followed to the old path.
+    let output = work_dir.run_jj(["log", "-T", "description", "-L", "1,5:renamed"]);
+    insta::assert_snapshot!(output, @r"
+    @  modify file
+    │  --- a/renamed
+    │  +++ b/renamed
+    │  @@ -1,4 +1,4 @@
+    │  -a
+    │  +A
+    │   b
+    │   c
+    │   d
+    ○  rename file
+    │  --- a/file
+    │  +++ b/renamed
+    │  @@ -2,4 +2,4 @@
+    │   b
+    │   c
+    │   d
+    │  -e
+    │  +E
+    ○  add file
+    │  --- /dev/null
+    ~  +++ b/file
+       @@ -0,0 +1,5 @@
+       +a
+       +b
+       +c
+       +d
+       +e
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_log_follow() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let content = "a\nb\nc\nd\ne\nf\n";
+    work_dir.write_file("file", content);
+    work_dir.run_jj(["describe", "-m", "add file"]).success();
+    work_dir.run_jj(["new", "-m", "unrelated"]).success();
+    work_dir.write_file("other", "x\n");
+    work_dir.run_jj(["new", "-m", "rename file"]).success();
+    work_dir.remove_file("file");
+    work_dir.create_dir("dir");
+    work_dir.write_file("dir/file", content);
+    work_dir.run_jj(["new", "-m", "modify file"]).success();
+    work_dir.write_file("dir/file", format!("{content}g\n"));
+    work_dir
+        .run_jj(["new", "-m", "new file at old path"])
+        .success();
+    work_dir.write_file("file", "new\n");
+
+    let output = work_dir.run_jj(["log", "-T", "description", "dir/file"]);
+    insta::assert_snapshot!(output, @"
+    ○  modify file
+    ○  rename file
+    │
+    ~
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["log", "-T", "description", "-s", "--follow", "dir/file"]);
+    insta::assert_snapshot!(output, @"
+    ○  modify file
+    │  M dir/file
+    ○  rename file
+    │  R {file => dir/file}
+    ~  (elided revisions)
+    ○  add file
+    │  A file
+    ~
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["log", "--follow", "file", "other"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: --follow requires a single file path
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["log", "--follow"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: the following required arguments were not provided:
+      <FILESETS>...
+
+    Usage: jj log --follow <FILESETS>...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_log_limit() {
     let test_env = TestEnvironment::default();
@@ -1009,6 +1248,186 @@ fn test_log_limit() {
     ");
 }
 
+#[test]
+fn test_log_first_parent() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+    work_dir.run_jj(["new", "-m", "b"]).success();
+    work_dir
+        .run_jj(["new", "-m", "c", "description(a)"])
+        .success();
+    work_dir
+        .run_jj(["new", "-m", "d", "description(c)", "description(b)"])
+        .success();
+    work_dir.run_jj(["new", "-m", "e"]).success();
+
+    let output = work_dir.run_jj(["log", "-T", "description", "--first-parent"]);
+    insta::assert_snapshot!(output, @r"
+    @  e
+    ○  d
+    ○  c
+    ○  a
+    ◆
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-T", "description", "--first-parent", "--no-graph"]);
+    insta::assert_snapshot!(output, @r"
+    e
+    d
+    c
+    a
+    [EOF]
+    ");
+
+    // First parents are followed from the heads of the given revisions
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        "description",
+        "--first-parent",
+        "-r",
+        "::description(b)",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ○  b
+    ○  a
+    ◆
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_log_filter_args() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "fix: a"]).success();
+    work_dir.run_jj(["new", "-m", "b"]).success();
+    work_dir
+        .run_jj(["metaedit", "--author", "Other <other@example.org>"])
+        .success();
+    work_dir.run_jj(["new", "-m", "fix: c"]).success();
+    let template = r#"description.first_line() ++ " " ++ author.email() ++ " " ++ committer.timestamp() ++ "\n""#;
+
+    let output = work_dir.run_jj(["log", "--no-graph", "-T", template, "-r", "::"]);
+    insta::assert_snapshot!(output, @r"
+    fix: c test.user@example.com 2001-02-03 04:05:11.000 +07:00
+    b other@example.org 2001-02-03 04:05:10.000 +07:00
+    fix: a test.user@example.com 2001-02-03 04:05:08.000 +07:00
+      1970-01-01 00:00:00.000 +00:00
+    [EOF]
+    ");
+
+    // Filters apply to all revisions if no revisions are specified
+    let output = work_dir.run_jj(["log", "--no-graph", "-T", template, "--grep", "fix:"]);
+    insta::assert_snapshot!(output, @r"
+    fix: c test.user@example.com 2001-02-03 04:05:11.000 +07:00
+    fix: a test.user@example.com 2001-02-03 04:05:08.000 +07:00
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "--no-graph", "-T", template, "--author", "other"]);
+    insta::assert_snapshot!(output, @r"
+    b other@example.org 2001-02-03 04:05:10.000 +07:00
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "log",
+        "--no-graph",
+        "-T",
+        template,
+        "--author=exact:other@example.org",
+        "--author=test.user",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    fix: c test.user@example.com 2001-02-03 04:05:11.000 +07:00
+    b other@example.org 2001-02-03 04:05:10.000 +07:00
+    fix: a test.user@example.com 2001-02-03 04:05:08.000 +07:00
+    [EOF]
+    ");
+
+    // Filters are combined with each other and with -r
+    let output = work_dir.run_jj([
+        "log",
+        "--no-graph",
+        "-T",
+        template,
+        "--grep=fix",
+        "--since=2001-02-03T04:05:09+07:00",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    fix: c test.user@example.com 2001-02-03 04:05:11.000 +07:00
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "log",
+        "--no-graph",
+        "-T",
+        template,
+        "-r=@-",
+        "--until=2001-02-03T04:05:11+07:00",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    b other@example.org 2001-02-03 04:05:10.000 +07:00
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["log", "--since=foo"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Invalid date for --since: foo
+    Caused by: expected unsupported identifier as position 0..3
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_log_many_commits() {
+    // More commits than the first batch of commits loaded by `jj log`
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    for i in 1..=20 {
+        work_dir.run_jj(["new", "-m", &i.to_string()]).success();
+    }
+
+    let output = work_dir.run_jj([
+        "log",
+        "-r",
+        "::@",
+        "-T",
+        r#"description.first_line() ++ " ""#,
+    ]);
+    let descriptions = output
+        .stdout
+        .raw()
+        .lines()
+        .map(|line| line.trim_start_matches(['@', '○', '◆', ' ']).trim_end())
+        .collect::<Vec<_>>();
+    let expected = [
+        "20", "19", "18", "17", "16", "15", "14", "13", "12", "11", "10", "9", "8", "7", "6", "5",
+        "4", "3", "2", "1", "", "",
+    ];
+    assert_eq!(descriptions, expected);
+
+    let output = work_dir.run_jj([
+        "log",
+        "-r",
+        "::@",
+        "--no-graph",
+        "--reversed",
+        "-T",
+        r#"description.first_line() ++ "\n""#,
+    ]);
+    let descriptions = output.stdout.raw().lines().collect::<Vec<_>>();
+    let expected = expected.into_iter().rev().collect::<Vec<_>>();
+    assert_eq!(descriptions, expected);
+}
+
 #[test]
 fn test_log_warn_path_might_be_revset() {
     let test_env = TestEnvironment::default();
@@ -1320,16 +1739,34 @@ fn test_graph_styles() {
     [EOF]
     ");
 
+    // Compact style
+    test_env.add_config(r#"ui.graph.style = "compact""#);
+    let output = work_dir.run_jj(["log", "-T=description"]);
+    insta::assert_snapshot!(output, @"
+    @  merge
+    ├╮
+    │○ side bookmark
+    ││ with
+    ││ long
+    ││ description
+    │○ main bookmark 2
+    ├╯
+    ○ main bookmark 1
+    ○ initial
+    ◆
+    [EOF]
+    ");
+
     // Invalid style name
     let output = work_dir.run_jj(["log", "--config=ui.graph.style=unknown"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Config error: Invalid type or value for ui.graph.style
-    Caused by: unknown variant `unknown`, expected one of `ascii`, `ascii-large`, `curved`, `square`
+    Caused by: unknown variant `unknown`, expected one of `ascii`, `ascii-large`, `curved`, `square`, `compact`
 
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
     ");
 }
 
@@ -22,6 +22,7 @@ use testutils::git;
 use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
 use crate::common::TestWorkDir;
+use crate::common::force_interactive;
 use crate::common::to_toml_value;
 
 #[test]
@@ -906,6 +907,45 @@ fn test_op_abandon_multiple_heads() {
     ");
 }
 
+#[test]
+fn test_op_abandon_confirm() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("ui.confirm.operation-abandon = 1");
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["commit", "-m", "commit 1"]).success();
+    work_dir.run_jj(["commit", "-m", "commit 2"]).success();
+
+    let output = work_dir.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .args(["op", "abandon", "..@-"])
+            .write_stdin("n\n")
+    });
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    About to abandon 2 operations and reparent 1 descendant operations.
+    Continue? (yN): Error: Canceled by user
+    [EOF]
+    [exit status: 1]
+    ");
+    // Nothing was abandoned
+    let output = work_dir.run_jj(["op", "log", "--no-graph", "-T", r#"description ++ "\n""#]);
+    insta::assert_snapshot!(output, @"
+    commit 4e0592f3dd52e7a4998a97d9a1f354e2727a856b
+    commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    add workspace 'default'
+
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["op", "abandon", "--yes", "..@-"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Abandoned 2 operations and reparented 1 descendant operations.
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_op_recover_from_bad_gc() {
     let test_env = TestEnvironment::default();
@@ -2861,6 +2901,109 @@ fn test_op_log_anonymize() {
     ");
 }
 
+#[test]
+fn test_op_notify_file() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let notify_path = work_dir.root().join(".jj/repo/last_operation.json");
+
+    // Disabled by default
+    work_dir.run_jj(["new"]).success();
+    assert!(!notify_path.exists());
+
+    test_env.add_config("notify.operation-file = true");
+    work_dir.run_jj(["new"]).success();
+    let op_id = work_dir
+        .run_jj(["op", "log", "--no-graph", "-n1", "-Tid"])
+        .success()
+        .stdout
+        .into_raw();
+    let content = std::fs::read_to_string(&notify_path).unwrap();
+    assert_eq!(
+        content,
+        format!("{{\"operation_id\":\"{op_id}\",\"workspaces\":[\"default\"]}}\n")
+    );
+
+    // Operations not touching the working copy don't list workspaces
+    work_dir
+        .run_jj(["bookmark", "create", "-r@-", "foo"])
+        .success();
+    let op_id = work_dir
+        .run_jj(["op", "log", "--no-graph", "-n1", "-Tid"])
+        .success()
+        .stdout
+        .into_raw();
+    let content = std::fs::read_to_string(&notify_path).unwrap();
+    assert_eq!(
+        content,
+        format!("{{\"operation_id\":\"{op_id}\",\"workspaces\":[]}}\n")
+    );
+
+    // Snapshots are also notified
+    work_dir.write_file("file", "contents");
+    let op_id = work_dir
+        .run_jj(["op", "log", "--no-graph", "-n1", "-Tid"])
+        .success()
+        .stdout
+        .into_raw();
+    let content = std::fs::read_to_string(&notify_path).unwrap();
+    assert_eq!(
+        content,
+        format!("{{\"operation_id\":\"{op_id}\",\"workspaces\":[\"default\"]}}\n")
+    );
+
+    // Abandoning operations reparents the head operation
+    work_dir.run_jj(["op", "abandon", "@-"]).success();
+    let op_id = work_dir
+        .run_jj(["op", "log", "--no-graph", "-n1", "-Tid"])
+        .success()
+        .stdout
+        .into_raw();
+    let content = std::fs::read_to_string(&notify_path).unwrap();
+    assert_eq!(
+        content,
+        format!("{{\"operation_id\":\"{op_id}\",\"workspaces\":[]}}\n")
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_op_notify_socket() {
+    use std::io::BufRead as _;
+    use std::io::BufReader;
+    use std::os::unix::net::UnixListener;
+
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let socket_path = test_env.env_root().join("notify.sock");
+    let listener = UnixListener::bind(&socket_path).unwrap();
+    test_env.add_config(format!(
+        "notify.operation-socket = {}",
+        to_toml_value(socket_path.to_str().unwrap())
+    ));
+
+    work_dir.run_jj(["describe", "-m", "foo"]).success();
+    let (stream, _) = listener.accept().unwrap();
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line).unwrap();
+    let op_id = work_dir
+        .run_jj(["op", "log", "--no-graph", "-n1", "-Tid"])
+        .success()
+        .stdout
+        .into_raw();
+    assert_eq!(
+        line,
+        format!("{{\"operation_id\":\"{op_id}\",\"workspaces\":[\"default\"]}}\n")
+    );
+
+    // Failure to connect doesn't fail the command
+    drop(listener);
+    std::fs::remove_file(&socket_path).unwrap();
+    work_dir.run_jj(["describe", "-m", "bar"]).success();
+}
+
 fn init_bare_git_repo(git_repo_path: &Path) -> gix::Repository {
     let git_repo = git::init_bare(git_repo_path);
     let commit_result = git::add_commit(
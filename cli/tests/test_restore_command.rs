@@ -338,6 +338,58 @@ fn test_restore_restore_descendants() {
     ");
 }
 
+#[test]
+fn test_restore_abandon_if_empty() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(&work_dir, "a", &[], &[("file1", "a\n")]);
+    create_commit_with_files(
+        &work_dir,
+        "b",
+        &["a"],
+        &[("file1", "b\n"), ("file2", "b\n")],
+    );
+    create_commit_with_files(&work_dir, "c", &["b"], &[("file3", "c\n")]);
+
+    // The revision isn't abandoned if it still has changes
+    let output = work_dir.run_jj(["restore", "-c=b", "file1", "--abandon-if-empty"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 descendant commits
+    Working copy  (@) now at: royxmykx c86c9b65 c | c
+    Parent commit (@-)      : zsuskuln a23782cb b | b
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  c
+    ○  b
+    ○  a
+    ◆
+    [EOF]
+    ");
+
+    // The revision is abandoned once it becomes empty
+    let output = work_dir.run_jj(["restore", "-c=b", "--abandon-if-empty"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Abandoned emptied commit zsuskuln a23782cb b | b
+    Rebased 1 descendant commits
+    Working copy  (@) now at: royxmykx 86d0ee4f c | c
+    Parent commit (@-)      : rlvkpnrz 76920e02 a b | a
+    Added 0 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  c
+    ○  a b
+    ◆
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_restore_interactive() {
     let mut test_env = TestEnvironment::default();
@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use testutils::git;
+
 use crate::common::TestEnvironment;
+use crate::common::force_interactive;
 
 #[test]
 fn test_syntax_error() {
@@ -446,6 +449,56 @@ fn test_function_name_hint() {
     ");
 }
 
+#[test]
+fn test_bad_symbol_similarity_hint() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "feature"])
+        .success();
+    work_dir
+        .run_jj(["workspace", "add", "--name", "features", "../secondary"])
+        .success();
+    work_dir.run_jj(["git", "export"]).success();
+    let git_repo = git::open(work_dir.root().join(".jj/repo/store/git"));
+    let target = git_repo.find_reference("refs/heads/feature").unwrap();
+    git_repo
+        .tag_reference(
+            "v1-feature",
+            target.target().id(),
+            gix::refs::transaction::PreviousValue::Any,
+        )
+        .unwrap();
+    work_dir.run_jj(["git", "import"]).success();
+
+    // Bookmarks, tags, and workspaces are suggested.
+    let output = work_dir.run_jj(["log", "-r", "featur"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Revision `featur` doesn't exist
+    Hint: Did you mean `feature`, `features@`, `v1-feature`?
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["log", "-r", "v1-featur"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Revision `v1-featur` doesn't exist
+    Hint: Did you mean `feature`, `features@`, `v1-feature`?
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["log", "-r", "feature@"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Workspace `feature` doesn't have a working-copy commit
+    Hint: Did you mean `default@`, `features@`?
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_bad_symbol_or_argument_should_not_be_optimized_out() {
     let test_env = TestEnvironment::default();
@@ -891,6 +944,81 @@ fn test_all_modifier() {
       = Modifier `all:` is not allowed in sub expression
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
+    [exit status: 4]
+    ");
+}
+
+#[test]
+fn test_pick_revision() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-mfirst"]).success();
+    work_dir.run_jj(["new", "-msecond"]).success();
+
+    // The picked revision is used as the argument
+    let output = work_dir.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .args(["log", "-r?", "-Tdescription"])
+            .write_stdin("2\n")
+    });
+    insta::assert_snapshot!(output, @"
+    ○  first
+    │
+    ~
+    [EOF]
+    ------- stderr -------
+    1: kkmpptxz b1cb6b2f (empty) second
+    2: qpvuntsm 68a50538 (empty) first
+    3: zzzzzzzz 00000000 (empty) (no description set)
+    q: quit the prompt
+    Enter the number of the revision: [EOF]
+    ");
+
+    // Multiple `?` arguments each open the picker
+    let output = work_dir.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .args(["log", "-r?", "-r?", "-Tdescription"])
+            .write_stdin("1\n2\n")
+    });
+    insta::assert_snapshot!(output, @"
+    @  second
+    ○  first
+    │
+    ~
+    [EOF]
+    ------- stderr -------
+    1: kkmpptxz b1cb6b2f (empty) second
+    2: qpvuntsm 68a50538 (empty) first
+    3: zzzzzzzz 00000000 (empty) (no description set)
+    q: quit the prompt
+    Enter the number of the revision: 1: kkmpptxz b1cb6b2f (empty) second
+    2: qpvuntsm 68a50538 (empty) first
+    3: zzzzzzzz 00000000 (empty) (no description set)
+    q: quit the prompt
+    Enter the number of the revision: [EOF]
+    ");
+
+    let output =
+        work_dir.run_jj_with(|cmd| force_interactive(cmd).args(["new", "?"]).write_stdin("q\n"));
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    1: kkmpptxz b1cb6b2f (empty) second
+    2: qpvuntsm 68a50538 (empty) first
+    3: zzzzzzzz 00000000 (empty) (no description set)
+    q: quit the prompt
+    Enter the number of the revision: Error: No revision was picked
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Can't pick without a terminal
+    let output = work_dir.run_jj(["new", "?"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Cannot pick a revision since the output is not connected to a terminal
+    Hint: Specify the revision instead of `?`.
+    [EOF]
     [exit status: 1]
     ");
 }
@@ -293,6 +293,52 @@ fn test_show_with_no_patch() {
     ");
 }
 
+#[test]
+fn test_show_multiple_revisions() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["new", "-m", "first"]).success();
+    work_dir.write_file("file1", "foo\n");
+    work_dir.run_jj(["new", "-m", "second"]).success();
+    work_dir.write_file("file1", "bar\n");
+    work_dir.write_file("file2", "baz\n");
+
+    let output = work_dir.run_jj(["show", "-T", "description", "--stat", "@-", "@"]);
+    insta::assert_snapshot!(output, @"
+    second
+    file1 | 2 +-
+    file2 | 1 +
+    2 files changed, 2 insertions(+), 1 deletion(-)
+
+    first
+    file1 | 1 +
+    1 file changed, 1 insertion(+), 0 deletions(-)
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["show", "-T", "description", "--summary", "::@ & ~root()"]);
+    insta::assert_snapshot!(output, @"
+    second
+    M file1
+    A file2
+
+    first
+    A file1
+
+    [EOF]
+    ");
+
+    // Empty revset is an error
+    let output = work_dir.run_jj(["show", "none()"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Revset didn't resolve to any revisions
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_show_with_no_template() {
     let test_env = TestEnvironment::default();
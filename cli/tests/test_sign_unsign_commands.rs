@@ -428,3 +428,82 @@ backend = "test"
     [EOF]
     ");
 }
+
+#[test]
+fn test_sign_and_no_sign_global_flags() {
+    let test_env = TestEnvironment::default();
+
+    test_env.add_config(
+        r#"
+[ui]
+show-cryptographic-signatures = true
+
+[signing]
+behavior = "drop"
+backend = "test"
+"#,
+    );
+
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "one"]).success();
+    let output = work_dir.run_jj(["log", "-r", "@"]);
+    insta::assert_snapshot!(output, @r"
+    @  qpvuntsm test.user@example.com 2001-02-03 08:05:08 401ea16f
+    │  (empty) one
+    ~
+    [EOF]
+    ");
+
+    // --sign overrides signing.behavior = "drop"
+    work_dir
+        .run_jj(["describe", "--sign", "-m", "two"])
+        .success();
+    let output = work_dir.run_jj(["log", "-r", "@"]);
+    insta::assert_snapshot!(output, @r"
+    @  qpvuntsm test.user@example.com 2001-02-03 08:05:10 bdc9f22c [✓︎]
+    │  (empty) two
+    ~
+    [EOF]
+    ");
+
+    // --no-sign drops the existing signature
+    work_dir
+        .run_jj(["describe", "--no-sign", "-m", "three"])
+        .success();
+    let output = work_dir.run_jj(["log", "-r", "@"]);
+    insta::assert_snapshot!(output, @r"
+    @  qpvuntsm test.user@example.com 2001-02-03 08:05:12 2b2b7e43
+    │  (empty) three
+    ~
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["describe", "--sign", "--no-sign", "-m", "four"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--sign' cannot be used with '--no-sign'
+
+    Usage: jj describe --sign --message <MESSAGE> [REVSETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+
+    // --sign requires a signing backend
+    let output = work_dir.run_jj([
+        "describe",
+        "--config=signing.backend=none",
+        "--sign",
+        "-m",
+        "four",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No signing backend configured
+    Hint: For configuring a signing backend, see https://jj-vcs.github.io/jj/latest/config/#commit-signing
+    [EOF]
+    [exit status: 1]
+    ");
+}
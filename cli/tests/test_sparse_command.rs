@@ -201,3 +201,19 @@ fn test_sparse_editor_avoids_unc() {
     // over 260 chars.
     assert_eq!(edited_path, dunce::simplified(&edited_path));
 }
+
+#[test]
+fn test_sparse_edit_interactive_without_terminal() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["sparse", "edit", "--interactive"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot select sparse patterns since the output is not connected to a terminal
+    Hint: Run `jj sparse edit` without `--interactive` to edit the patterns in an editor.
+    [EOF]
+    [exit status: 1]
+    ");
+}
@@ -478,3 +478,87 @@ fn test_status_untracked_files() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_status_merge_conflict_hint() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("a file", "base\n");
+    work_dir.run_jj(["new", "-m=left"]).success();
+    work_dir.write_file("a file", "left\n");
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "left"])
+        .success();
+    work_dir.run_jj(["new", "@-", "-m=right"]).success();
+    work_dir.write_file("a file", "right\n");
+    work_dir.run_jj(["new", "left", "@"]).success();
+
+    // The conflict was created by the merge, so it can be resolved in place
+    let output = work_dir.run_jj(["status"]);
+    insta::assert_snapshot!(output, @"
+    The working copy has no changes.
+    Working copy  (@) : mzvwutvl 2655c0e0 (conflict) (empty) (no description set)
+    Parent commit (@-): rlvkpnrz 3cb351ee left | left
+    Parent commit (@-): zsuskuln 73fdf47b right
+    Warning: There are unresolved conflicts at these paths:
+    a file    2-sided conflict
+    Hint: To resolve the conflicts, use a merge tool, e.g.:
+      jj resolve 'a file'
+    or edit the conflict markers in the files directly.
+    Once the conflicts are resolved, you can inspect the result with `jj diff`.
+    [EOF]
+    ");
+
+    test_env.add_config("hints.resolving-conflicts = false");
+    let output = work_dir.run_jj(["status"]);
+    insta::assert_snapshot!(output, @"
+    The working copy has no changes.
+    Working copy  (@) : mzvwutvl 2655c0e0 (conflict) (empty) (no description set)
+    Parent commit (@-): rlvkpnrz 3cb351ee left | left
+    Parent commit (@-): zsuskuln 73fdf47b right
+    Warning: There are unresolved conflicts at these paths:
+    a file    2-sided conflict
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_status_sections() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "contents\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file", "new contents\n");
+
+    // Sections are shown in the configured order
+    let output = work_dir.run_jj(["status", "--config=status.sections=['commits', 'changes']"]);
+    insta::assert_snapshot!(output, @"
+    Working copy  (@) : rlvkpnrz 9760ca94 (no description set)
+    Parent commit (@-): qpvuntsm 9a462e35 (no description set)
+    Working copy changes:
+    M file
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["status", "--config=status.sections=['commits']"]);
+    insta::assert_snapshot!(output, @"
+    Working copy  (@) : rlvkpnrz 9760ca94 (no description set)
+    Parent commit (@-): qpvuntsm 9a462e35 (no description set)
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["status", "--config=status.sections=['unknown']"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Config error: Invalid type or value for status.sections
+    Caused by: unknown variant `unknown`, expected one of `changes`, `untracked`, `commits`, `conflicts`, `bookmark-conflicts`
+
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 4]
+    ");
+}
@@ -0,0 +1,95 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+use crate::common::create_commit;
+
+#[test]
+fn test_test_command() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+
+    // No command configured
+    let output = work_dir.run_jj(["test"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: No test command given
+    Hint: Pass it with `--command`, or set `test.command` in the config.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Fails on revisions which contain the file "c"
+    test_env.add_config(r#"test.command = ["test", "!", "-f", "c"]"#);
+    let output = work_dir.run_jj(["test", "-r=a|b|c"]);
+    insta::assert_snapshot!(output, @"
+    Passed: rlvkpnrz 7d980be7 a | a
+    Passed: zsuskuln 123b4d91 b | b
+    Failed: royxmykx dffaa0d4 c | c
+    [EOF]
+    ------- stderr -------
+    Error: 1 of 3 revisions failed the test
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Results are cached
+    let output = work_dir.run_jj(["test", "-r=a|b|c"]);
+    insta::assert_snapshot!(output, @"
+    Passed (cached): rlvkpnrz 7d980be7 a | a
+    Passed (cached): zsuskuln 123b4d91 b | b
+    Failed (cached): royxmykx dffaa0d4 c | c
+    [EOF]
+    ------- stderr -------
+    Error: 1 of 3 revisions failed the test
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Only the contents matter, so rewriting the description keeps the result
+    work_dir.run_jj(["describe", "-r=b", "-m=b2"]).success();
+    let output = work_dir.run_jj([
+        "log",
+        "-r=a|b|c|@",
+        "-T",
+        r#"description.first_line() ++ ": " ++ test_result ++ "\n""#,
+    ]);
+    insta::assert_snapshot!(output, @"
+    @  c: failed
+    ○  b2: passed
+    ○  a: passed
+    │
+    ~
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["test", "-r=b", "--rerun"]);
+    insta::assert_snapshot!(output, @"
+    Passed: zsuskuln 30e1d854 b | b2
+    [EOF]
+    ");
+
+    // Results of other commands are stored separately
+    let output = work_dir.run_jj(["test", "-r=b|c", "--command=true"]);
+    insta::assert_snapshot!(output, @"
+    Passed: zsuskuln 30e1d854 b | b2
+    Passed: royxmykx 3aef331f c | c
+    [EOF]
+    ");
+}
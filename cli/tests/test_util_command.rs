@@ -64,6 +64,98 @@ fn test_gc_args() {
     ");
 }
 
+#[test]
+fn test_gc_auto() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("gc.auto-operations = 4");
+    let work_dir = test_env.work_dir("repo");
+
+    // Not enough operations yet
+    let output = work_dir.run_jj(["util", "gc", "--auto"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing to garbage collect
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["new"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: kkmpptxz 1c1c95df (empty) (no description set)
+    Parent commit (@-)      : qpvuntsm e8849ae1 (empty) (no description set)
+    [EOF]
+    ");
+
+    // The threshold is exceeded after a mutating command
+    let output = work_dir.run_jj(["new"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: zsuskuln 42d696f0 (empty) (no description set)
+    Parent commit (@-)      : kkmpptxz 1c1c95df (empty) (no description set)
+    Running automatic garbage collection
+    [EOF]
+    ");
+
+    // Automatic garbage collection is rate-limited
+    work_dir.run_jj(["new"]).success();
+    work_dir.run_jj(["new"]).success();
+    let output = work_dir.run_jj(["new"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: yqosqzyt c2ec78fa (empty) (no description set)
+    Parent commit (@-)      : royxmykx f6531535 (empty) (no description set)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["util", "gc", "--auto"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing to garbage collect
+    [EOF]
+    ");
+
+    // ... but the operations since the last collection are counted
+    test_env.add_config("gc.auto-min-interval-hours = 0");
+    let output = work_dir.run_jj(["new"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: yostqsxw 8582d047 (empty) (no description set)
+    Parent commit (@-)      : yqosqzyt c2ec78fa (empty) (no description set)
+    Running automatic garbage collection
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["new"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: znkkpsqq da9f48a0 (empty) (no description set)
+    Parent commit (@-)      : yostqsxw 8582d047 (empty) (no description set)
+    [EOF]
+    ");
+
+    // Operations before the last collection aren't counted even if the
+    // recorded operation has been abandoned
+    work_dir.run_jj(["op", "abandon", "@-"]).success();
+    let output = work_dir.run_jj(["new"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: kmkuslsw d6e651e1 (empty) (no description set)
+    Parent commit (@-)      : znkkpsqq da9f48a0 (empty) (no description set)
+    [EOF]
+    ");
+
+    // It can be disabled
+    test_env.add_config("gc.auto = false");
+    for _ in 0..4 {
+        work_dir.run_jj(["new"]).success();
+    }
+    let output = work_dir.run_jj(["new"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: uyznsvlq b34e018b (empty) (no description set)
+    Parent commit (@-)      : nkmrtpmo 900b76ea (empty) (no description set)
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_gc_operation_log() {
     let test_env = TestEnvironment::default();
@@ -103,6 +195,223 @@ fn test_gc_operation_log() {
     ");
 }
 
+#[test]
+fn test_util_stats() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("small", "a\n");
+    work_dir.write_file("large", "a\n".repeat(1000));
+    work_dir.run_jj(["commit", "-m", "first"]).success();
+    work_dir.write_file("large", "a\n");
+    work_dir
+        .run_jj(["metaedit", "--author", "Other <other@example.com>"])
+        .success();
+    work_dir.run_jj(["commit", "-m", "second"]).success();
+    work_dir.run_jj(["new", "@-", "@--"]).success();
+
+    let output = work_dir.run_jj(["util", "stats"]);
+    // Disk usage depends on the backend's storage format.
+    insta::with_settings!({filters => vec![(r"(?m)^( +\S+:) +[0-9.]+ [KM]?i?B *$", "$1 [SIZE]")]}, {
+        assert_snapshot!(output, @"
+        === Commits ===
+        Number of commits: 3
+        Number of merges: 1
+        Number of changes: 3
+        Commits per author:
+               2 Test User <test.user@example.com>
+               1 Other <other@example.com>
+        Commits per month:
+          2001-02: 3
+        === Largest files ===
+                2 B large
+                2 B small
+        === Storage ===
+        Backend: git
+          index: [SIZE]
+            changed_paths: [SIZE]
+            op_links: [SIZE]
+            operations: [SIZE]
+            segments: [SIZE]
+          op_heads: [SIZE]
+            heads: [SIZE]
+          op_store: [SIZE]
+            operations: [SIZE]
+            views: [SIZE]
+          store: [SIZE]
+            extra: [SIZE]
+            git: [SIZE]
+          submodule_store: [SIZE]
+          workspace_store: [SIZE]
+        === Index ===
+        Indexed commits: 10
+        Number of heads: 7
+        Commit index levels: 2
+        Commits with changed-path index: 0
+        [EOF]
+        ");
+    });
+
+    let output = work_dir.run_jj(["util", "stats", "-r", "description(first)", "-n", "1"]);
+    insta::with_settings!({filters => vec![(r"(?m)(^=== Storage ===$(\r?\n.*)*)", "[...]")]}, {
+        assert_snapshot!(output, @"
+        === Commits ===
+        Number of commits: 1
+        Number of merges: 0
+        Number of changes: 1
+        Commits per author:
+               1 Test User <test.user@example.com>
+        Commits per month:
+          2001-02: 1
+        === Largest files ===
+            2.0 KiB large
+        [...]
+        ");
+    });
+}
+
+#[test]
+fn test_util_fsck() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    let secondary_dir = test_env.work_dir("secondary");
+
+    main_dir.write_file("file", "contents\n");
+    main_dir.run_jj(["new"]).success();
+    main_dir
+        .run_jj(["workspace", "add", "../secondary"])
+        .success();
+
+    let output = main_dir.run_jj(["util", "fsck"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    No problems found.
+    [EOF]
+    ");
+
+    // Make the secondary workspace stale.
+    main_dir.write_file("file", "changed in main\n");
+    main_dir.run_jj(["squash"]).success();
+    let output = secondary_dir.run_jj(["util", "fsck"]);
+    insta::assert_snapshot!(output, @"
+    Warning: The working copy is stale (not updated since operation bd4f780d0422)
+    [EOF]
+    ------- stderr -------
+    Found 0 errors and 1 warnings.
+    [EOF]
+    ");
+    let output = secondary_dir.run_jj(["util", "fsck", "--repair"]);
+    insta::assert_snapshot!(output, @"
+    Warning: The working copy is stale (not updated since operation bd4f780d0422)
+    [EOF]
+    ------- stderr -------
+    Found 0 errors and 1 warnings.
+    Working copy  (@) now at: pmmvwywv 90f3d42e (empty) (no description set)
+    Parent commit (@-)      : qpvuntsm b853f7c8 (no description set)
+    Added 0 files, modified 1 files, removed 0 files
+    Updated working copy to fresh commit 90f3d42e0bff
+    [EOF]
+    ");
+    let output = secondary_dir.run_jj(["util", "fsck"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    No problems found.
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_util_fsck_broken_operation() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir.run_jj(["describe", "-m", "second"]).success();
+
+    // Remove the view of an old operation.
+    let output = work_dir
+        .run_jj(["op", "log", "--no-graph", "-T", "id ++ ' '"])
+        .success();
+    let old_op_id = output.stdout.raw().split_whitespace().nth(1).unwrap();
+    let output = work_dir
+        .run_jj(["debug", "object", "operation", old_op_id])
+        .success();
+    let view_id = regex::Regex::new(r#"view_id: ViewId\(\s*"([0-9a-f]+)""#)
+        .unwrap()
+        .captures(output.stdout.raw())
+        .unwrap()[1]
+        .to_owned();
+    std::fs::remove_file(
+        work_dir
+            .root()
+            .join(".jj/repo/op_store/views")
+            .join(view_id),
+    )
+    .unwrap();
+
+    let output = work_dir.run_jj(["util", "fsck"]);
+    insta::assert_snapshot!(output, @"
+    Error: View of operation 75545f7ff2df is unreadable: Object 3596ae71f3178cdd8fe74eca348c64155453098ecd3a60e2ff900bf9a002f8742b3d753bcf143b50a3a477816fd2e501560a53c6e614533232042b7a2a2d0b6a of type view not found
+    [EOF]
+    ------- stderr -------
+    Found 1 errors and 0 warnings.
+    Error: The repository has problems
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_util_unlock() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["util", "unlock"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No locks found
+    [EOF]
+    ");
+
+    // Lock left behind by a process on another host
+    work_dir.write_file(
+        ".jj/repo/op_heads/lock",
+        "hostname=other-host\npid=1234\ntimestamp=0\n",
+    );
+    // Lock without owner information
+    work_dir.write_file(".jj/working_copy/working_copy.lock", "");
+    let output = work_dir.run_jj(["util", "unlock"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Lock .jj/repo/op_heads/lock is held by process 1234 on other-host, which can't be checked from this host
+    Hint: Use `--force` to remove it if the owner process isn't running.
+    Lock .jj/working_copy/working_copy.lock has no owner information
+    Hint: Use `--force` to remove it if the owner process isn't running.
+    [EOF]
+    ");
+    assert!(work_dir.root().join(".jj/repo/op_heads/lock").exists());
+
+    let output = work_dir.run_jj(["util", "unlock", "--force"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Lock .jj/repo/op_heads/lock is held by process 1234 on other-host, which can't be checked from this host
+    Removed lock .jj/repo/op_heads/lock
+    Lock .jj/working_copy/working_copy.lock has no owner information
+    Removed lock .jj/working_copy/working_copy.lock
+    [EOF]
+    ");
+    assert!(!work_dir.root().join(".jj/repo/op_heads/lock").exists());
+    assert!(
+        !work_dir
+            .root()
+            .join(".jj/working_copy/working_copy.lock")
+            .exists()
+    );
+}
+
 #[test]
 fn test_shell_completions() {
     #[track_caller]
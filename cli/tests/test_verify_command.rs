@@ -0,0 +1,75 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_verify() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(
+        r#"
+[signing]
+behavior = "keep"
+backend = "test"
+"#,
+    );
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["commit", "-m", "one"]).success();
+    work_dir.run_jj(["commit", "-m", "two"]).success();
+    work_dir.run_jj(["commit", "-m", "three"]).success();
+    work_dir
+        .run_jj(["sign", "-r", "description(one)", "--key", "alice"])
+        .success();
+    work_dir
+        .run_jj(["sign", "-r", "description(two)", "--key", "mallory"])
+        .success();
+
+    // Any good signature is accepted by default
+    let output = work_dir.run_jj(["verify", "-r", "description(one)|description(two)"]);
+    insta::assert_snapshot!(output, @"
+    Good: rlvkpnrz 5bb61bc8 (empty) two (key: mallory)
+    Good: qpvuntsm ae5eba2c (empty) one (key: alice)
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["verify", "-r", "::@- & ~root()"]);
+    insta::assert_snapshot!(output, @"
+    Unsigned: kkmpptxz fee63405 (empty) three
+    Good: rlvkpnrz 5bb61bc8 (empty) two (key: mallory)
+    Good: qpvuntsm ae5eba2c (empty) one (key: alice)
+    [EOF]
+    ------- stderr -------
+    Error: 1 of 3 revisions failed verification
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Only signatures by trusted keys are accepted
+    let output = work_dir.run_jj([
+        "verify",
+        "-r",
+        "description(one)|description(two)",
+        "--config=signing.trusted-keys=['alice']",
+    ]);
+    insta::assert_snapshot!(output, @"
+    Untrusted: rlvkpnrz 5bb61bc8 (empty) two (key: mallory)
+    Good: qpvuntsm ae5eba2c (empty) one (key: alice)
+    [EOF]
+    ------- stderr -------
+    Error: 1 of 2 revisions failed verification
+    [EOF]
+    [exit status: 1]
+    ");
+}
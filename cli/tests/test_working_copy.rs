@@ -95,7 +95,7 @@ fn test_snapshot_large_file() {
     Caused by: Expected a positive integer or a string in '<number><unit>' form
     For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
     [EOF]
-    [exit status: 1]
+    [exit status: 4]
     ");
 
     // No error if we disable auto-tracking of the path
@@ -115,6 +115,68 @@ fn test_snapshot_large_file() {
     ");
 }
 
+#[test]
+fn test_snapshot_large_file_overrides() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(indoc! {r#"
+        snapshot.max-new-file-size = 10
+
+        [snapshot.max-new-file-size-overrides.assets]
+        patterns = ["assets"]
+        max-size = "1KiB"
+
+        [snapshot.max-new-file-size-overrides.unlimited]
+        patterns = ["glob:'**/*.bin'"]
+        max-size = 0
+    "#});
+    work_dir.create_dir("assets");
+    work_dir.write_file("assets/large", "a lot of text");
+    work_dir.write_file("assets/huge", vec![b'a'; 2048]);
+    work_dir.write_file("assets/huge.bin", vec![b'a'; 2048]);
+    work_dir.write_file("large", "a lot of text");
+    let output = work_dir.run_jj(["file", "list"]);
+    insta::assert_snapshot!(output, @r"
+    assets/huge.bin
+    assets/large
+    [EOF]
+    ------- stderr -------
+    Warning: Refused to snapshot some files:
+      assets/huge: 2.0KiB (2048 bytes); the maximum size allowed is 1.0KiB (1024 bytes)
+      large: 13.0B (13 bytes); the maximum size allowed is 10.0B (10 bytes)
+    Hint: This is to prevent large files from being added by accident. You can fix this by:
+      - Adding the file to `.gitignore`
+      - Run `jj config set --repo snapshot.max-new-file-size 2048`
+        This will increase the maximum file size allowed for new files, in this repository only.
+      - Run `jj --config snapshot.max-new-file-size=2048 st`
+        This will increase the maximum file size allowed for new files, for this command only.
+    [EOF]
+    ");
+
+    // invalid patterns are reported
+    let output = work_dir.run_jj([
+        "file",
+        "list",
+        "--config=snapshot.max-new-file-size-overrides.assets.patterns=['bad:x']",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to parse fileset: Invalid file pattern
+    Caused by:
+    1:  --> 1:1
+      |
+    1 | bad:x
+      | ^---^
+      |
+      = Invalid file pattern
+    2: Invalid file pattern kind `bad:`
+    Hint: See https://jj-vcs.github.io/jj/latest/filesets/#file-patterns or `jj help -k filesets` for valid prefixes.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_snapshot_large_file_restore() {
     let test_env = TestEnvironment::default();
@@ -404,6 +466,10 @@ fn test_conflict_marker_length_stored_in_working_copy() {
     Parent commit (@-): zsuskuln d7acaf48 side-b
     Warning: There are unresolved conflicts at these paths:
     file    2-sided conflict
+    Hint: To resolve the conflicts, use a merge tool, e.g.:
+      jj resolve file
+    or edit the conflict markers in the files directly.
+    Once the conflicts are resolved, you can inspect the result with `jj diff`.
     [EOF]
     ");
     insta::assert_snapshot!(work_dir.run_jj(["diff", "--git"]), @r"
@@ -127,6 +127,153 @@ fn test_workspaces_sparse_patterns() {
     insta::assert_snapshot!(output, @"");
 }
 
+/// Test sparse patterns given by presets and --include
+#[test]
+fn test_workspaces_sparse_patterns_preset() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "ws1"]).success();
+    let ws1_dir = test_env.work_dir("ws1");
+    ws1_dir.write_file("docs/index.md", "");
+    ws1_dir.write_file("lib/lib.rs", "");
+    ws1_dir.write_file("src/main.rs", "");
+    ws1_dir.run_jj(["new"]).success();
+    test_env.add_config(r#"sparse.presets.code = ["lib", "src"]"#);
+
+    ws1_dir
+        .run_jj(["workspace", "add", "--include=docs", "../ws2"])
+        .success();
+    let ws2_dir = test_env.work_dir("ws2");
+    let output = ws2_dir.run_jj(["sparse", "list"]);
+    insta::assert_snapshot!(output, @r"
+    docs
+    [EOF]
+    ");
+    assert!(ws2_dir.root().join("docs/index.md").exists());
+    assert!(!ws2_dir.root().join("src").exists());
+
+    ws1_dir
+        .run_jj([
+            "workspace",
+            "add",
+            "--sparse-patterns=code",
+            "--include=docs",
+            "../ws3",
+        ])
+        .success();
+    let ws3_dir = test_env.work_dir("ws3");
+    let output = ws3_dir.run_jj(["sparse", "list"]);
+    insta::assert_snapshot!(output, @r"
+    docs
+    lib
+    src
+    [EOF]
+    ");
+
+    let output = ws1_dir.run_jj(["workspace", "add", "--sparse-patterns=nope", "../ws4"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Sparse patterns preset `nope` doesn't exist
+    Hint: Use `copy`, `full`, `empty`, or a preset defined in `sparse.presets`.
+    [EOF]
+    [exit status: 1]
+    ");
+    assert!(!test_env.env_root().join("ws4").exists());
+
+    let output = ws1_dir.run_jj([
+        "workspace",
+        "add",
+        "--sparse-patterns=full",
+        "--include=docs",
+        "../ws4",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: --include cannot be used with --sparse-patterns=full
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+/// Test that a read-only workspace isn't snapshotted or rewritten
+#[test]
+fn test_workspaces_read_only() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    main_dir.write_file("file", "contents\n");
+    main_dir.run_jj(["new"]).success();
+    main_dir
+        .run_jj(["workspace", "add", "../secondary"])
+        .success();
+    let secondary_dir = test_env.work_dir("secondary");
+    secondary_dir
+        .run_jj([
+            "config",
+            "set",
+            "--workspace",
+            "workspace.read-only",
+            "true",
+        ])
+        .success();
+
+    // Changes in the working copy aren't snapshotted
+    secondary_dir.write_file("file", "modified\n");
+    let output = secondary_dir.run_jj(["status"]);
+    insta::assert_snapshot!(output, @r"
+    The working copy has no changes.
+    Working copy  (@) : pmmvwywv 54703666 (empty) (no description set)
+    Parent commit (@-): qpvuntsm 9a462e35 (no description set)
+    [EOF]
+    ");
+
+    // The working-copy commit can't be changed from the read-only workspace
+    let output = secondary_dir.run_jj(["new"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Workspace secondary is read-only, so its working-copy commit can't be changed
+    Hint: Run the command in another workspace, or set `workspace.read-only = false`.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = secondary_dir.run_jj(["describe", "-m", "foo"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Workspace secondary is read-only, so its working-copy commit can't be changed
+    Hint: Run the command in another workspace, or set `workspace.read-only = false`.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Other commits can be rewritten
+    let output = secondary_dir.run_jj(["describe", "-r", "default@", "-m", "main"]);
+    insta::assert_snapshot!(output, @"");
+
+    // The working-copy commit can be rewritten from another workspace, and
+    // the read-only workspace can be updated to it
+    secondary_dir.write_file("file", "contents\n");
+    main_dir.write_file("file", "changed in main\n");
+    main_dir.run_jj(["squash"]).success();
+    let output = secondary_dir.run_jj(["status"]);
+    insta::assert_snapshot!(output, @r"
+    The working copy has no changes.
+    Working copy  (@) : pmmvwywv e95e3268 (empty) (no description set)
+    Parent commit (@-): qpvuntsm efabfc13 main
+    [EOF]
+    ");
+    let output = secondary_dir.run_jj(["workspace", "update-stale"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: pmmvwywv e95e3268 (empty) (no description set)
+    Parent commit (@-)      : qpvuntsm efabfc13 main
+    Added 0 files, modified 1 files, removed 0 files
+    Updated working copy to fresh commit e95e3268530f
+    [EOF]
+    ");
+    insta::assert_snapshot!(secondary_dir.read_file("file"), @r"
+    changed in main
+    ");
+}
+
 /// Test adding a second workspace while the current workspace is editing a
 /// merge
 #[test]
@@ -712,6 +859,7 @@ fn test_workspaces_updated_by_other_automatic() {
     Parent commit (@-): qpvuntsm b853f7c8 (no description set)
     [EOF]
     ------- stderr -------
+    The working copy is stale; updating it automatically
     Working copy  (@) now at: pmmvwywv 90f3d42e (empty) (no description set)
     Parent commit (@-)      : qpvuntsm b853f7c8 (no description set)
     Added 0 files, modified 1 files, removed 0 files
@@ -821,6 +969,7 @@ fn test_workspaces_current_op_discarded_by_other(automatic: bool) {
         Parent commit (@-): rzvqmyuk 891f0006 (empty) (no description set)
         [EOF]
         ------- stderr -------
+        The working copy is stale; updating it automatically
         Failed to read working copy's current operation; attempting recovery. Error message from read attempt: Object 778c9aae54957e842bede2223fda227be33e08061732276a4cfb7b431a3e146e5c62187d640aa883095d3b2c6cf43d31ad5fde72076bb9a88b8594fb8b5e6606 of type operation not found
         Created and checked out recovery commit 866928d1e0fd
         [EOF]
@@ -1101,6 +1250,77 @@ fn test_workspaces_forget_multi_transaction() {
     ");
 }
 
+#[test]
+fn test_workspaces_forget_delete_working_copy() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    let secondary_dir = test_env.work_dir("secondary");
+
+    main_dir.write_file("file", "contents");
+    main_dir
+        .run_jj(["workspace", "add", "../secondary"])
+        .success();
+
+    // The workspace containing the repo can't be deleted
+    let output = main_dir.run_jj(["workspace", "forget", "--delete-working-copy"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot delete workspace default: it contains the repository
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Unsnapshotted changes would be lost
+    secondary_dir.write_file("new-file", "new contents");
+    let output = main_dir.run_jj(["workspace", "forget", "--delete-working-copy", "secondary"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot delete workspace secondary: it has changes that haven't been snapshotted
+    Hint: Run `jj status` in $TEST_ENV/secondary to snapshot them first.
+    [EOF]
+    [exit status: 1]
+    ");
+    assert!(secondary_dir.root().exists());
+
+    secondary_dir.run_jj(["status"]).success();
+
+    // Ignored files require confirmation
+    secondary_dir.write_file(".gitignore", "ignored\n");
+    secondary_dir.run_jj(["status"]).success();
+    secondary_dir.write_file("ignored", "");
+    let output = main_dir.run_jj(["workspace", "forget", "--delete-working-copy", "secondary"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    About to delete 1 untracked or ignored files:
+      ../secondary/ignored
+    Error: Confirmation is required, but the terminal is not interactive
+    Hint: Use `--yes` to confirm, or set `ui.confirm.workspace-delete = 0` to disable this check.
+    [EOF]
+    [exit status: 1]
+    ");
+    assert!(secondary_dir.root().exists());
+
+    let output = main_dir.run_jj([
+        "workspace",
+        "forget",
+        "--delete-working-copy",
+        "--yes",
+        "secondary",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Deleted workspace directory $TEST_ENV/secondary
+    [EOF]
+    ");
+    assert!(!secondary_dir.root().exists());
+    let output = main_dir.run_jj(["workspace", "list"]);
+    insta::assert_snapshot!(output, @r"
+    default: qpvuntsm 006bd113 (no description set)
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_workspaces_forget_abandon_commits() {
     let test_env = TestEnvironment::default();
@@ -1354,7 +1574,11 @@ fn test_workspaces_rename_workspace() {
     ");
 
     let output = secondary_dir.run_jj(["workspace", "rename", "third"]);
-    insta::assert_snapshot!(output, @"");
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Renamed workspace second to third
+    [EOF]
+    ");
 
     let output = main_dir.run_jj(["workspace", "list"]);
     insta::assert_snapshot!(output, @r"
@@ -0,0 +1,129 @@
+/*
+ * Copyright 2025 The Jujutsu Authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/*
+ * C bindings for the core operations of jj. See the documentation of the
+ * jj-ffi crate for details.
+ *
+ * Functions return JjResult, and write their output to the location pointed
+ * to by the `out` argument. Output allocated by the library must be released
+ * by the corresponding `*_free()` function. On failure, the error message can
+ * be retrieved by jj_last_error().
+ */
+
+#ifndef JJ_FFI_H
+#define JJ_FFI_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define JJ_FFI_ABI_VERSION 1
+
+typedef enum JjResult {
+  JJ_RESULT_OK = 0,
+  JJ_RESULT_ERROR = 1,
+  JJ_RESULT_INVALID_ARGUMENT = 2,
+} JjResult;
+
+typedef struct JjRepo JjRepo;
+
+typedef struct JjStringList {
+  char **items;
+  size_t len;
+} JjStringList;
+
+typedef struct JjLogEntry {
+  char *commit_id;
+  char *change_id;
+  JjStringList parent_ids;
+  char *description;
+  char *author_name;
+  char *author_email;
+  int64_t author_timestamp_millis;
+  int32_t author_tz_offset_minutes;
+  JjStringList bookmarks;
+  JjStringList working_copies;
+  bool has_conflict;
+} JjLogEntry;
+
+typedef struct JjLogEntryList {
+  JjLogEntry *items;
+  size_t len;
+} JjLogEntryList;
+
+typedef enum JjFileChangeKind {
+  JJ_FILE_CHANGE_ADDED = 0,
+  JJ_FILE_CHANGE_MODIFIED = 1,
+  JJ_FILE_CHANGE_REMOVED = 2,
+} JjFileChangeKind;
+
+typedef struct JjFileChange {
+  char *path;
+  JjFileChangeKind kind;
+  bool has_conflict;
+} JjFileChange;
+
+typedef struct JjFileChangeList {
+  JjFileChange *items;
+  size_t len;
+} JjFileChangeList;
+
+typedef struct JjStatus {
+  JjLogEntry working_copy;
+  JjFileChangeList changes;
+} JjStatus;
+
+/* Returns JJ_FFI_ABI_VERSION of the loaded library. */
+uint32_t jj_ffi_abi_version(void);
+
+/*
+ * Returns the error message of the last failed call on this thread, or NULL.
+ * The string is valid until the next call on this thread.
+ */
+const char *jj_last_error(void);
+
+/* Loads the workspace and its repo at the latest operation. */
+JjResult jj_repo_open(const char *workspace_path, JjRepo **out);
+void jj_repo_free(JjRepo *repo);
+
+/* Evaluates the revset and returns the hex ids of the commits. */
+JjResult jj_evaluate_revset(const JjRepo *repo, const char *revset,
+                            JjStringList *out);
+void jj_string_list_free(JjStringList *list);
+
+/* Evaluates the revset and returns the log data of the commits. */
+JjResult jj_log(const JjRepo *repo, const char *revset, JjLogEntryList *out);
+void jj_log_entry_list_free(JjLogEntryList *list);
+
+/* Returns the files changed by the single commit the revset resolves to. */
+JjResult jj_diff(const JjRepo *repo, const char *revset,
+                 JjFileChangeList *out);
+void jj_file_change_list_free(JjFileChangeList *list);
+
+/* Returns the status of the workspace as of the last snapshot. */
+JjResult jj_status(const JjRepo *repo, JjStatus *out);
+void jj_status_free(JjStatus *status);
+
+#ifdef __cplusplus
+} /* extern "C" */
+#endif
+
+#endif /* JJ_FFI_H */
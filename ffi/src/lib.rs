@@ -0,0 +1,594 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! C bindings for the core operations of jj.
+//!
+//! This crate exposes [`jj_lib::api`] over a C ABI so that editors and
+//! programs written in other languages can read jj repos without linking Rust
+//! code directly. The declarations are in `include/jj_ffi.h`.
+//!
+//! Functions return [`JjResult`], and write their output to the location
+//! pointed to by the `out` argument. Output allocated by this library must be
+//! released by the corresponding `*_free()` function. On failure, the error
+//! message can be retrieved by [`jj_last_error()`].
+//!
+//! The layout of the `#[repr(C)]` types is stable within the same
+//! [`JJ_FFI_ABI_VERSION`]. Fields may only be added in a new ABI version.
+
+#![warn(missing_docs)]
+
+use std::cell::RefCell;
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::ffi::c_char;
+use std::panic;
+use std::path::Path;
+use std::ptr;
+use std::sync::Arc;
+
+use jj_lib::api;
+use jj_lib::api::Repo as _;
+use jj_lib::object_id::ObjectId as _;
+
+/// Version of the ABI of this library. Incremented when the layout of any
+/// `#[repr(C)]` type or the signature of any function is changed.
+pub const JJ_FFI_ABI_VERSION: u32 = 1;
+
+/// Result of a function call.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JjResult {
+    /// The call succeeded.
+    Ok = 0,
+    /// The operation failed. See [`jj_last_error()`] for details.
+    Error = 1,
+    /// An argument was null or not valid UTF-8.
+    InvalidArgument = 2,
+}
+
+/// Workspace and its repo loaded at the latest operation.
+///
+/// This is an opaque type for C.
+pub struct JjRepo {
+    workspace: api::Workspace,
+    repo: Arc<api::ReadonlyRepo>,
+}
+
+/// Owned array of strings.
+#[repr(C)]
+#[derive(Debug)]
+pub struct JjStringList {
+    /// Pointer to `len` null-terminated strings.
+    pub items: *mut *mut c_char,
+    /// Number of strings.
+    pub len: usize,
+}
+
+/// Commit data as shown by `jj log`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct JjLogEntry {
+    /// Hex id of the commit.
+    pub commit_id: *mut c_char,
+    /// Change id of the commit in jj's reverse hex format.
+    pub change_id: *mut c_char,
+    /// Hex ids of the parent commits.
+    pub parent_ids: JjStringList,
+    /// Full description of the commit.
+    pub description: *mut c_char,
+    /// Name of the author.
+    pub author_name: *mut c_char,
+    /// Email of the author.
+    pub author_email: *mut c_char,
+    /// Author timestamp in milliseconds since the Unix epoch.
+    pub author_timestamp_millis: i64,
+    /// Time zone offset of the author timestamp in minutes.
+    pub author_tz_offset_minutes: i32,
+    /// Names of the local bookmarks pointing to the commit.
+    pub bookmarks: JjStringList,
+    /// Names of the workspaces whose working-copy commit is this commit.
+    pub working_copies: JjStringList,
+    /// Whether the commit has conflicts.
+    pub has_conflict: bool,
+}
+
+/// Owned array of [`JjLogEntry`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct JjLogEntryList {
+    /// Pointer to `len` entries.
+    pub items: *mut JjLogEntry,
+    /// Number of entries.
+    pub len: usize,
+}
+
+/// Kind of change to a file.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JjFileChangeKind {
+    /// The file was added.
+    Added = 0,
+    /// The file was modified.
+    Modified = 1,
+    /// The file was removed.
+    Removed = 2,
+}
+
+/// File changed between two trees.
+#[repr(C)]
+#[derive(Debug)]
+pub struct JjFileChange {
+    /// Path of the file relative to the workspace root, using `/` as
+    /// separator.
+    pub path: *mut c_char,
+    /// Kind of the change.
+    pub kind: JjFileChangeKind,
+    /// Whether the file has conflicts after the change.
+    pub has_conflict: bool,
+}
+
+/// Owned array of [`JjFileChange`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct JjFileChangeList {
+    /// Pointer to `len` changes.
+    pub items: *mut JjFileChange,
+    /// Number of changes.
+    pub len: usize,
+}
+
+/// State of a workspace as shown by `jj status`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct JjStatus {
+    /// The working-copy commit.
+    pub working_copy: JjLogEntry,
+    /// Files changed in the working-copy commit compared to its parents.
+    pub changes: JjFileChangeList,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+enum FfiError {
+    InvalidArgument(String),
+    Other(String),
+}
+
+impl From<api::Error> for FfiError {
+    fn from(err: api::Error) -> Self {
+        Self::Other(format_error(&err))
+    }
+}
+
+impl From<api::BackendError> for FfiError {
+    fn from(err: api::BackendError) -> Self {
+        Self::Other(format_error(&err))
+    }
+}
+
+fn format_error(err: &dyn std::error::Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+        message.push_str(": ");
+        message.push_str(&err.to_string());
+        source = err.source();
+    }
+    message
+}
+
+/// Runs `f`, and records the error message if any.
+fn call(f: impl FnOnce() -> Result<(), FfiError>) -> JjResult {
+    // The library state isn't shared across calls except through `JjRepo`,
+    // which is immutable.
+    let (result, message) = match panic::catch_unwind(panic::AssertUnwindSafe(f)) {
+        Ok(Ok(())) => (JjResult::Ok, None),
+        Ok(Err(FfiError::InvalidArgument(message))) => (JjResult::InvalidArgument, Some(message)),
+        Ok(Err(FfiError::Other(message))) => (JjResult::Error, Some(message)),
+        Err(_) => (JjResult::Error, Some("jj panicked".to_owned())),
+    };
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = message.map(to_c_string_inner));
+    result
+}
+
+/// Converts the C string argument to `&str`.
+///
+/// # Safety
+///
+/// `s` must be null or point to a null-terminated string which outlives the
+/// returned reference.
+unsafe fn str_arg<'a>(s: *const c_char, name: &str) -> Result<&'a str, FfiError> {
+    if s.is_null() {
+        return Err(FfiError::InvalidArgument(format!("`{name}` is null")));
+    }
+    unsafe { CStr::from_ptr(s) }
+        .to_str()
+        .map_err(|_| FfiError::InvalidArgument(format!("`{name}` is not valid UTF-8")))
+}
+
+/// Converts the pointer argument to a reference.
+///
+/// # Safety
+///
+/// `p` must be null or valid for the lifetime of the returned reference.
+unsafe fn ref_arg<'a, T>(p: *const T, name: &str) -> Result<&'a T, FfiError> {
+    unsafe { p.as_ref() }.ok_or_else(|| FfiError::InvalidArgument(format!("`{name}` is null")))
+}
+
+fn out_arg<T>(p: *mut T) -> Result<*mut T, FfiError> {
+    if p.is_null() {
+        Err(FfiError::InvalidArgument("`out` is null".to_owned()))
+    } else {
+        Ok(p)
+    }
+}
+
+fn to_c_string_inner(s: String) -> CString {
+    CString::new(s).unwrap_or_else(|err| {
+        let mut bytes = err.into_vec();
+        bytes.retain(|&b| b != 0);
+        CString::new(bytes).unwrap()
+    })
+}
+
+fn to_c_string(s: impl Into<String>) -> *mut c_char {
+    to_c_string_inner(s.into()).into_raw()
+}
+
+/// Frees a string allocated by [`to_c_string()`].
+///
+/// # Safety
+///
+/// `s` must be null or allocated by [`to_c_string()`].
+unsafe fn free_c_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+/// Leaks `items` as a pointer and length pair.
+fn into_raw_parts<T>(items: Vec<T>) -> (*mut T, usize) {
+    let items = Box::into_raw(items.into_boxed_slice());
+    (items.cast(), items.len())
+}
+
+/// Reclaims items leaked by [`into_raw_parts()`].
+///
+/// # Safety
+///
+/// `items` and `len` must be returned by [`into_raw_parts()`], or `items` must
+/// be null.
+unsafe fn from_raw_parts<T>(items: *mut T, len: usize) -> Vec<T> {
+    if items.is_null() {
+        return vec![];
+    }
+    unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(items, len)) }.into_vec()
+}
+
+impl JjStringList {
+    fn new(strings: impl IntoIterator<Item = String>) -> Self {
+        let (items, len) = into_raw_parts(strings.into_iter().map(to_c_string).collect());
+        Self { items, len }
+    }
+
+    /// # Safety
+    ///
+    /// `self` must be allocated by this library.
+    unsafe fn free(&mut self) {
+        for s in unsafe { from_raw_parts(self.items, self.len) } {
+            unsafe { free_c_string(s) };
+        }
+        self.items = ptr::null_mut();
+        self.len = 0;
+    }
+}
+
+impl JjLogEntry {
+    fn new(entry: api::LogEntry) -> Self {
+        Self {
+            commit_id: to_c_string(entry.commit_id.hex()),
+            change_id: to_c_string(entry.change_id.reverse_hex()),
+            parent_ids: JjStringList::new(entry.parent_ids.iter().map(|id| id.hex())),
+            description: to_c_string(entry.description),
+            author_name: to_c_string(entry.author.name),
+            author_email: to_c_string(entry.author.email),
+            author_timestamp_millis: entry.author.timestamp.timestamp.0,
+            author_tz_offset_minutes: entry.author.timestamp.tz_offset,
+            bookmarks: JjStringList::new(entry.bookmarks),
+            working_copies: JjStringList::new(entry.working_copies),
+            has_conflict: entry.has_conflict,
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `self` must be allocated by this library.
+    unsafe fn free(&mut self) {
+        unsafe {
+            free_c_string(self.commit_id);
+            free_c_string(self.change_id);
+            self.parent_ids.free();
+            free_c_string(self.description);
+            free_c_string(self.author_name);
+            free_c_string(self.author_email);
+            self.bookmarks.free();
+            self.working_copies.free();
+        }
+        self.commit_id = ptr::null_mut();
+        self.change_id = ptr::null_mut();
+        self.description = ptr::null_mut();
+        self.author_name = ptr::null_mut();
+        self.author_email = ptr::null_mut();
+    }
+}
+
+impl JjFileChangeList {
+    fn new(changes: Vec<api::FileChange>) -> Self {
+        let changes = changes
+            .into_iter()
+            .map(|change| JjFileChange {
+                path: to_c_string(change.path),
+                kind: match change.kind {
+                    api::FileChangeKind::Added => JjFileChangeKind::Added,
+                    api::FileChangeKind::Removed => JjFileChangeKind::Removed,
+                    // Unknown kinds added to the API in the future are
+                    // reported as modifications.
+                    _ => JjFileChangeKind::Modified,
+                },
+                has_conflict: change.has_conflict,
+            })
+            .collect();
+        let (items, len) = into_raw_parts(changes);
+        Self { items, len }
+    }
+
+    /// # Safety
+    ///
+    /// `self` must be allocated by this library.
+    unsafe fn free(&mut self) {
+        for change in unsafe { from_raw_parts(self.items, self.len) } {
+            unsafe { free_c_string(change.path) };
+        }
+        self.items = ptr::null_mut();
+        self.len = 0;
+    }
+}
+
+/// Returns [`JJ_FFI_ABI_VERSION`] of the loaded library.
+#[unsafe(no_mangle)]
+pub extern "C" fn jj_ffi_abi_version() -> u32 {
+    JJ_FFI_ABI_VERSION
+}
+
+/// Returns the error message of the last failed call on this thread, or null.
+///
+/// The string is owned by the library, and is valid until the next call on
+/// this thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn jj_last_error() -> *const c_char {
+    LAST_ERROR.with(|last_error| {
+        last_error
+            .borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Loads the workspace at `workspace_path` and its repo at the latest
+/// operation.
+///
+/// The user configuration isn't loaded. The returned repo must be freed by
+/// [`jj_repo_free()`].
+///
+/// # Safety
+///
+/// `workspace_path` must be a null-terminated string, and `out` must be valid
+/// for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn jj_repo_open(
+    workspace_path: *const c_char,
+    out: *mut *mut JjRepo,
+) -> JjResult {
+    call(|| {
+        let workspace_path = unsafe { str_arg(workspace_path, "workspace_path") }?;
+        let out = out_arg(out)?;
+        let config = api::StackedConfig::with_defaults();
+        let settings = api::UserSettings::from_config(config)
+            .map_err(|err| FfiError::Other(format_error(&err)))?;
+        let (workspace, repo) = api::open(&settings, Path::new(workspace_path))?;
+        let repo = Box::new(JjRepo { workspace, repo });
+        unsafe { out.write(Box::into_raw(repo)) };
+        Ok(())
+    })
+}
+
+/// Frees the repo returned by [`jj_repo_open()`].
+///
+/// # Safety
+///
+/// `repo` must be null or returned by [`jj_repo_open()`], and must not be used
+/// after this call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn jj_repo_free(repo: *mut JjRepo) {
+    if !repo.is_null() {
+        drop(unsafe { Box::from_raw(repo) });
+    }
+}
+
+/// Evaluates `revset` and returns the hex ids of the commits, children before
+/// parents.
+///
+/// Only the built-in revset functions are available. The returned list must
+/// be freed by [`jj_string_list_free()`].
+///
+/// # Safety
+///
+/// `repo` must be returned by [`jj_repo_open()`], `revset` must be a
+/// null-terminated string, and `out` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn jj_evaluate_revset(
+    repo: *const JjRepo,
+    revset: *const c_char,
+    out: *mut JjStringList,
+) -> JjResult {
+    call(|| {
+        let repo = unsafe { ref_arg(repo, "repo") }?;
+        let revset = unsafe { str_arg(revset, "revset") }?;
+        let out = out_arg(out)?;
+        let ids = api::evaluate_revset(repo.repo.as_ref(), Some(&repo.workspace), revset)?;
+        unsafe { out.write(JjStringList::new(ids.iter().map(|id| id.hex()))) };
+        Ok(())
+    })
+}
+
+/// Frees the strings in `list`, and resets it to empty.
+///
+/// # Safety
+///
+/// `list` must be null or filled by this library.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn jj_string_list_free(list: *mut JjStringList) {
+    if let Some(list) = unsafe { list.as_mut() } {
+        unsafe { list.free() };
+    }
+}
+
+/// Evaluates `revset` and returns the log data of the commits, children before
+/// parents.
+///
+/// The returned list must be freed by [`jj_log_entry_list_free()`].
+///
+/// # Safety
+///
+/// `repo` must be returned by [`jj_repo_open()`], `revset` must be a
+/// null-terminated string, and `out` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn jj_log(
+    repo: *const JjRepo,
+    revset: *const c_char,
+    out: *mut JjLogEntryList,
+) -> JjResult {
+    call(|| {
+        let repo = unsafe { ref_arg(repo, "repo") }?;
+        let revset = unsafe { str_arg(revset, "revset") }?;
+        let out = out_arg(out)?;
+        let entries = api::log(repo.repo.as_ref(), Some(&repo.workspace), revset)?;
+        let (items, len) = into_raw_parts(entries.into_iter().map(JjLogEntry::new).collect());
+        unsafe { out.write(JjLogEntryList { items, len }) };
+        Ok(())
+    })
+}
+
+/// Frees the entries in `list`, and resets it to empty.
+///
+/// # Safety
+///
+/// `list` must be null or filled by this library.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn jj_log_entry_list_free(list: *mut JjLogEntryList) {
+    if let Some(list) = unsafe { list.as_mut() } {
+        for mut entry in unsafe { from_raw_parts(list.items, list.len) } {
+            unsafe { entry.free() };
+        }
+        list.items = ptr::null_mut();
+        list.len = 0;
+    }
+}
+
+/// Returns the files changed by the commit compared to its parents, sorted by
+/// path. `revset` must resolve to exactly one commit.
+///
+/// The returned list must be freed by [`jj_file_change_list_free()`].
+///
+/// # Safety
+///
+/// `repo` must be returned by [`jj_repo_open()`], `revset` must be a
+/// null-terminated string, and `out` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn jj_diff(
+    repo: *const JjRepo,
+    revset: *const c_char,
+    out: *mut JjFileChangeList,
+) -> JjResult {
+    call(|| {
+        let repo = unsafe { ref_arg(repo, "repo") }?;
+        let revset = unsafe { str_arg(revset, "revset") }?;
+        let out = out_arg(out)?;
+        let ids = api::evaluate_revset(repo.repo.as_ref(), Some(&repo.workspace), revset)?;
+        let [id] = ids.as_slice() else {
+            return Err(FfiError::Other(format!(
+                "Revset `{revset}` resolved to {} commits, expected 1",
+                ids.len()
+            )));
+        };
+        let commit = repo.repo.store().get_commit(id)?;
+        let changes = api::diff(repo.repo.as_ref(), &commit)?;
+        unsafe { out.write(JjFileChangeList::new(changes)) };
+        Ok(())
+    })
+}
+
+/// Frees the changes in `list`, and resets it to empty.
+///
+/// # Safety
+///
+/// `list` must be null or filled by this library.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn jj_file_change_list_free(list: *mut JjFileChangeList) {
+    if let Some(list) = unsafe { list.as_mut() } {
+        unsafe { list.free() };
+    }
+}
+
+/// Returns the status of the workspace.
+///
+/// Unlike `jj status`, this doesn't snapshot the working copy, so changes made
+/// in the file system since the last jj command aren't included. The returned
+/// status must be freed by [`jj_status_free()`].
+///
+/// # Safety
+///
+/// `repo` must be returned by [`jj_repo_open()`], and `out` must be valid for
+/// writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn jj_status(repo: *const JjRepo, out: *mut JjStatus) -> JjResult {
+    call(|| {
+        let repo = unsafe { ref_arg(repo, "repo") }?;
+        let out = out_arg(out)?;
+        let status = api::status(repo.repo.as_ref(), &repo.workspace)?;
+        let status = JjStatus {
+            working_copy: JjLogEntry::new(status.working_copy),
+            changes: JjFileChangeList::new(status.changes),
+        };
+        unsafe { out.write(status) };
+        Ok(())
+    })
+}
+
+/// Frees the data in `status`.
+///
+/// # Safety
+///
+/// `status` must be null or filled by [`jj_status()`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn jj_status_free(status: *mut JjStatus) {
+    if let Some(status) = unsafe { status.as_mut() } {
+        unsafe {
+            status.working_copy.free();
+            status.changes.free();
+        }
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::ffi::c_char;
+use std::ptr;
+use std::slice;
+
+use jj_ffi::*;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo as _;
+use testutils::TestRepoBackend;
+use testutils::TestWorkspace;
+use testutils::create_tree;
+use testutils::repo_path;
+
+fn to_str<'a>(s: *const c_char) -> &'a str {
+    unsafe { CStr::from_ptr(s) }.to_str().unwrap()
+}
+
+fn last_error() -> String {
+    to_str(jj_last_error()).to_owned()
+}
+
+fn strings(list: &JjStringList) -> Vec<&str> {
+    if list.len == 0 {
+        return vec![];
+    }
+    unsafe { slice::from_raw_parts(list.items, list.len) }
+        .iter()
+        .map(|&s| to_str(s))
+        .collect()
+}
+
+fn open(test_workspace: &TestWorkspace) -> *mut JjRepo {
+    let path = CString::new(test_workspace.workspace.workspace_root().to_str().unwrap()).unwrap();
+    let mut repo = ptr::null_mut();
+    assert_eq!(
+        unsafe { jj_repo_open(path.as_ptr(), &mut repo) },
+        JjResult::Ok
+    );
+    repo
+}
+
+#[test]
+fn test_open_errors() {
+    assert_eq!(jj_ffi_abi_version(), JJ_FFI_ABI_VERSION);
+
+    let mut repo = ptr::null_mut();
+    assert_eq!(
+        unsafe { jj_repo_open(ptr::null(), &mut repo) },
+        JjResult::InvalidArgument
+    );
+    assert_eq!(last_error(), "`workspace_path` is null");
+
+    let temp_dir = testutils::new_temp_dir();
+    let path = CString::new(temp_dir.path().to_str().unwrap()).unwrap();
+    assert_eq!(
+        unsafe { jj_repo_open(path.as_ptr(), &mut repo) },
+        JjResult::Error
+    );
+    assert!(
+        last_error().starts_with("There is no Jujutsu repo in"),
+        "{}",
+        last_error()
+    );
+    assert!(repo.is_null());
+}
+
+#[test]
+fn test_log_and_revset() {
+    let test_workspace = TestWorkspace::init_with_backend(TestRepoBackend::Git);
+    let wc_commit_id = test_workspace
+        .repo
+        .view()
+        .get_wc_commit_id(test_workspace.workspace.workspace_name())
+        .unwrap()
+        .clone();
+    let repo = open(&test_workspace);
+
+    let revset = CString::new("@").unwrap();
+    let mut ids = JjStringList {
+        items: ptr::null_mut(),
+        len: 0,
+    };
+    assert_eq!(
+        unsafe { jj_evaluate_revset(repo, revset.as_ptr(), &mut ids) },
+        JjResult::Ok
+    );
+    assert_eq!(strings(&ids), [wc_commit_id.hex()]);
+    unsafe { jj_string_list_free(&mut ids) };
+    assert!(ids.items.is_null());
+
+    let revset = CString::new("::@").unwrap();
+    let mut entries = JjLogEntryList {
+        items: ptr::null_mut(),
+        len: 0,
+    };
+    assert_eq!(
+        unsafe { jj_log(repo, revset.as_ptr(), &mut entries) },
+        JjResult::Ok
+    );
+    let items = unsafe { slice::from_raw_parts(entries.items, entries.len) };
+    assert_eq!(items.len(), 2);
+    assert_eq!(to_str(items[0].commit_id), wc_commit_id.hex());
+    assert_eq!(
+        strings(&items[0].working_copies),
+        [test_workspace.workspace.workspace_name().as_str()]
+    );
+    assert_eq!(
+        strings(&items[0].parent_ids),
+        [test_workspace.repo.store().root_commit_id().hex()]
+    );
+    assert!(!items[0].has_conflict);
+    unsafe { jj_log_entry_list_free(&mut entries) };
+
+    let revset = CString::new("nonexistent").unwrap();
+    assert_eq!(
+        unsafe { jj_log(repo, revset.as_ptr(), &mut entries) },
+        JjResult::Error
+    );
+    assert!(last_error().contains("nonexistent"), "{}", last_error());
+
+    unsafe { jj_repo_free(repo) };
+}
+
+#[test]
+fn test_diff_and_status() {
+    let test_workspace = TestWorkspace::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_workspace.repo;
+    let tree = create_tree(repo, &[(repo_path("file"), "contents")]);
+    let mut tx = repo.start_transaction();
+    let commit = tx
+        .repo_mut()
+        .new_commit(vec![repo.store().root_commit_id().clone()], tree.id())
+        .write()
+        .unwrap();
+    tx.repo_mut()
+        .edit(
+            test_workspace.workspace.workspace_name().to_owned(),
+            &commit,
+        )
+        .unwrap();
+    tx.repo_mut().rebase_descendants().unwrap();
+    tx.commit("test").unwrap();
+    let repo = open(&test_workspace);
+
+    let revset = CString::new("@").unwrap();
+    let mut changes = JjFileChangeList {
+        items: ptr::null_mut(),
+        len: 0,
+    };
+    assert_eq!(
+        unsafe { jj_diff(repo, revset.as_ptr(), &mut changes) },
+        JjResult::Ok
+    );
+    let items = unsafe { slice::from_raw_parts(changes.items, changes.len) };
+    assert_eq!(items.len(), 1);
+    assert_eq!(to_str(items[0].path), "file");
+    assert_eq!(items[0].kind, JjFileChangeKind::Added);
+    unsafe { jj_file_change_list_free(&mut changes) };
+
+    let revset = CString::new("all()").unwrap();
+    assert_eq!(
+        unsafe { jj_diff(repo, revset.as_ptr(), &mut changes) },
+        JjResult::Error
+    );
+    assert_eq!(
+        last_error(),
+        "Revset `all()` resolved to 2 commits, expected 1"
+    );
+
+    let mut status = std::mem::MaybeUninit::<JjStatus>::uninit();
+    assert_eq!(
+        unsafe { jj_status(repo, status.as_mut_ptr()) },
+        JjResult::Ok
+    );
+    let mut status = unsafe { status.assume_init() };
+    assert_eq!(to_str(status.working_copy.commit_id), commit.id().hex());
+    assert_eq!(status.changes.len, 1);
+    unsafe { jj_status_free(&mut status) };
+
+    unsafe { jj_repo_free(repo) };
+}
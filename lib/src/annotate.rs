@@ -19,6 +19,7 @@
 //! Like commit metadata and more.
 
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::collections::hash_map;
 use std::iter;
 use std::ops::Range;
@@ -26,6 +27,7 @@ use std::sync::Arc;
 
 use bstr::BStr;
 use bstr::BString;
+use futures::executor::block_on_stream;
 use itertools::Itertools as _;
 use pollster::FutureExt as _;
 
@@ -407,6 +409,241 @@ fn process_commit(
     Ok(())
 }
 
+/// Commit which changed the tracked range of lines.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LineRangeChange {
+    /// Commit ID which changed the lines.
+    pub commit_id: CommitId,
+    /// Path of the file at the commit.
+    pub path: RepoPathBuf,
+    /// Path of the file at the parents of the commit. Differs from `path` if
+    /// the file was renamed or copied by the commit.
+    pub parent_path: RepoPathBuf,
+    /// 0-based range of the tracked lines in the file at the commit.
+    pub line_range: Range<usize>,
+}
+
+/// Commit from which the tracked range of lines is followed.
+struct LineRangeStart {
+    commit: Commit,
+    path: RepoPathBuf,
+    text: BString,
+    line_range: Range<usize>,
+    /// Whether the commit is reported if it changed the lines, even if it
+    /// isn't in the domain.
+    is_head: bool,
+}
+
+/// Finds the commits which changed the lines in `line_range` of the file in
+/// the `starting_commit`, and the corresponding ranges of lines in these
+/// commits.
+///
+/// The range of lines is followed through the ancestors within the `domain`,
+/// adjusting it for lines inserted or removed before and within the range. A
+/// commit is considered to change the lines if they differ from all of its
+/// parents. If the lines are unchanged from one of the parents, only that
+/// parent is followed. If the file was renamed or copied, the lines are
+/// followed to the source file. The `starting_commit` is included in the
+/// result if it changed the lines, regardless of the `domain`.
+///
+/// The returned changes of each file path are ordered topologically, children
+/// first. Changes to the source of a renamed file follow the change which
+/// renamed it.
+pub fn find_line_range_changes(
+    repo: &dyn Repo,
+    starting_commit: &Commit,
+    file_path: &RepoPath,
+    line_range: Range<usize>,
+    domain: &Arc<ResolvedRevsetExpression>,
+) -> Result<Vec<LineRangeChange>, RevsetEvaluationError> {
+    let mut starts = vec![LineRangeStart {
+        commit: starting_commit.clone(),
+        path: file_path.to_owned(),
+        text: Source::load(starting_commit, file_path)?.text,
+        line_range,
+        is_head: true,
+    }];
+    let mut visited = HashSet::new();
+    let mut changes = vec![];
+    while let Some(start) = starts.pop() {
+        if visited.insert((start.commit.id().clone(), start.path.clone())) {
+            follow_line_range(repo, start, domain, &mut changes, &mut starts)?;
+        }
+    }
+    Ok(changes)
+}
+
+/// Follows the range of lines of a single file path from `start`, and queues
+/// the parents of the commits which renamed the file in `renames`.
+fn follow_line_range(
+    repo: &dyn Repo,
+    start: LineRangeStart,
+    domain: &Arc<ResolvedRevsetExpression>,
+    changes: &mut Vec<LineRangeChange>,
+    renames: &mut Vec<LineRangeStart>,
+) -> Result<(), RevsetEvaluationError> {
+    let file_path = &start.path;
+    let predicate = RevsetFilterPredicate::File(FilesetExpression::file_path(file_path.clone()));
+    let heads = RevsetExpression::commit(start.commit.id().clone());
+    let filtered = domain.intersection(&heads.ancestors()).filtered(predicate);
+    let revset = heads.union(&filtered).evaluate(repo)?;
+    let in_domain = start.is_head || !filtered.intersection(&heads).evaluate(repo)?.is_empty();
+
+    let mut pending: HashMap<CommitId, (BString, Range<usize>)> =
+        HashMap::from([(start.commit.id().clone(), (start.text, start.line_range))]);
+    for node in revset.iter_graph() {
+        let (commit_id, edges) = node?;
+        let Some((text, line_range)) = pending.remove(&commit_id) else {
+            continue;
+        };
+        let mut parent_ranges = Vec::with_capacity(edges.len());
+        let mut unchanged_parent = None;
+        for edge in &edges {
+            let parent_text = match pending.get(&edge.target) {
+                Some((text, _)) => text.clone(),
+                None => {
+                    let commit = repo.store().get_commit(&edge.target)?;
+                    Source::load(&commit, file_path)?.text
+                }
+            };
+            let (parent_range, changed) =
+                map_line_range_to_parent(&text, &parent_text, line_range.clone());
+            if !changed {
+                unchanged_parent = Some((edge, parent_text, parent_range));
+                break;
+            }
+            parent_ranges.push((edge, parent_text, parent_range));
+        }
+        if let Some(parent) = unchanged_parent {
+            parent_ranges = vec![parent];
+        } else {
+            let mut parent_path = file_path.clone();
+            let mut unchanged_source = false;
+            for (parent, source_path) in find_copy_sources(repo, &commit_id, file_path)? {
+                let parent_text = Source::load(&parent, &source_path)?.text;
+                let (parent_range, changed) =
+                    map_line_range_to_parent(&text, &parent_text, line_range.clone());
+                unchanged_source |= !changed;
+                parent_path = source_path.clone();
+                if !parent_range.is_empty() {
+                    renames.push(LineRangeStart {
+                        commit: parent,
+                        path: source_path,
+                        text: parent_text,
+                        line_range: parent_range,
+                        is_head: false,
+                    });
+                }
+            }
+            if unchanged_source {
+                // The lines were moved along with the file.
+                parent_ranges.clear();
+            } else if commit_id != *start.commit.id() || in_domain {
+                changes.push(LineRangeChange {
+                    commit_id: commit_id.clone(),
+                    path: file_path.clone(),
+                    parent_path,
+                    line_range,
+                });
+            }
+        }
+        for (edge, parent_text, parent_range) in parent_ranges {
+            if parent_range.is_empty() || edge.is_missing() {
+                continue;
+            }
+            match pending.entry(edge.target.clone()) {
+                hash_map::Entry::Occupied(mut entry) => {
+                    let (_, range) = entry.get_mut();
+                    range.start = range.start.min(parent_range.start);
+                    range.end = range.end.max(parent_range.end);
+                }
+                hash_map::Entry::Vacant(entry) => {
+                    entry.insert((parent_text, parent_range));
+                }
+            }
+        }
+        if pending.is_empty() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the parents of the commit which didn't have the file at
+/// `file_path`, along with the path the file was renamed or copied from.
+fn find_copy_sources(
+    repo: &dyn Repo,
+    commit_id: &CommitId,
+    file_path: &RepoPath,
+) -> BackendResult<Vec<(Commit, RepoPathBuf)>> {
+    let store = repo.store();
+    let commit = store.get_commit(commit_id)?;
+    let mut sources = vec![];
+    for parent in commit.parents() {
+        let parent = parent?;
+        if !parent.tree()?.path_value(file_path)?.is_absent() {
+            continue;
+        }
+        let paths = [file_path.to_owned()];
+        let records = store.get_copy_records(Some(&paths), parent.id(), commit_id)?;
+        for record in block_on_stream(records) {
+            let record = record?;
+            if record.target.as_ref() == file_path {
+                sources.push((parent.clone(), record.source));
+            }
+        }
+    }
+    Ok(sources)
+}
+
+/// Maps the `line_range` in the current file to the parent file. Returns the
+/// range of lines in the parent file, and whether any lines within the range
+/// differ.
+fn map_line_range_to_parent(
+    current_contents: &[u8],
+    parent_contents: &[u8],
+    line_range: Range<usize>,
+) -> (Range<usize>, bool) {
+    let diff = ContentDiff::by_line([current_contents, parent_contents]);
+    let mut current_line_counter: usize = 0;
+    let mut parent_line_counter: usize = 0;
+    let mut parent_start = None;
+    let mut parent_end = 0;
+    let mut changed = false;
+    for hunk in diff.hunks() {
+        let current_count = hunk.contents[0].split_inclusive(|b| *b == b'\n').count();
+        let parent_count = hunk.contents[1].split_inclusive(|b| *b == b'\n').count();
+        let current_lines = current_line_counter..current_line_counter + current_count;
+        let parent_lines = parent_line_counter..parent_line_counter + parent_count;
+        current_line_counter = current_lines.end;
+        parent_line_counter = parent_lines.end;
+        match hunk.kind {
+            DiffHunkKind::Matching => {
+                let start = line_range.start.max(current_lines.start);
+                let end = line_range.end.min(current_lines.end);
+                if start < end {
+                    parent_start.get_or_insert(parent_lines.start + (start - current_lines.start));
+                    parent_end = parent_lines.start + (end - current_lines.start);
+                }
+            }
+            DiffHunkKind::Different => {
+                let overlaps = if current_lines.is_empty() {
+                    // Lines removed between the tracked lines
+                    line_range.start < current_lines.start && current_lines.start < line_range.end
+                } else {
+                    current_lines.start < line_range.end && line_range.start < current_lines.end
+                };
+                if overlaps {
+                    changed = true;
+                    parent_start.get_or_insert(parent_lines.start);
+                    parent_end = parent_lines.end;
+                }
+            }
+        }
+    }
+    (parent_start.unwrap_or(parent_end)..parent_end, changed)
+}
+
 /// For two files, calls `copy(current_start, parent_start, count)` for each
 /// range of contiguous lines in common (e.g. line 8-10 maps to line 9-11.)
 fn copy_same_lines_with(
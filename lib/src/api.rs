@@ -0,0 +1,366 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! High-level API for applications built on jj-lib.
+//!
+//! The other modules of this crate expose the internals of jj, and they change
+//! whenever the implementation does. This module is a small facade covering
+//! what GUIs and bots typically need: opening a repo, running transactions,
+//! evaluating revsets, reading log data and changed files, and rewriting
+//! commits.
+//!
+//! The functions and types defined here, such as [`log()`] and [`LogEntry`],
+//! won't be removed or changed incompatibly without being deprecated for at
+//! least one release first. Their signatures refer to types re-exported from
+//! the rest of the crate, such as [`Commit`], [`MutableRepo`], and the error
+//! types. Those are jj's internal types, and they may still change between
+//! releases like any other part of the crate. The facade keeps such changes
+//! rare for code that only uses what's shown here, but it doesn't follow
+//! semantic versioning.
+//!
+//! ```no_run
+//! use jj_lib::api;
+//! use jj_lib::api::Repo as _;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let settings = api::UserSettings::from_config(api::StackedConfig::with_defaults())?;
+//! let (workspace, repo) = api::open(&settings, "/path/to/workspace".as_ref())?;
+//! for entry in api::log(repo.as_ref(), Some(&workspace), "::@")? {
+//!     println!("{} {}", entry.commit_id, entry.description);
+//! }
+//!
+//! let mut tx = repo.start_transaction();
+//! let wc_commit_id = tx.repo().view().get_wc_commit_id(workspace.workspace_name());
+//! let commit = tx.repo().store().get_commit(wc_commit_id.unwrap())?;
+//! api::describe_commit(tx.repo_mut(), &commit, "new description\n")?;
+//! tx.commit("describe commit")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use futures::StreamExt as _;
+use itertools::Itertools as _;
+use pollster::FutureExt as _;
+use thiserror::Error;
+
+pub use crate::backend::BackendError;
+pub use crate::backend::BackendResult;
+pub use crate::backend::ChangeId;
+pub use crate::backend::CommitId;
+pub use crate::backend::Signature;
+pub use crate::backend::Timestamp;
+pub use crate::commit::Commit;
+pub use crate::config::StackedConfig;
+use crate::matchers::EverythingMatcher;
+use crate::merged_tree::MergedTree;
+use crate::merged_tree::TreeDiffEntry;
+pub use crate::repo::MutableRepo;
+pub use crate::repo::ReadonlyRepo;
+pub use crate::repo::Repo;
+pub use crate::repo::RepoLoaderError;
+use crate::repo::StoreFactories;
+use crate::repo_path::RepoPathUiConverter;
+use crate::revset;
+use crate::revset::RevsetAliasesMap;
+use crate::revset::RevsetDiagnostics;
+pub use crate::revset::RevsetEvaluationError;
+use crate::revset::RevsetExtensions;
+use crate::revset::RevsetParseContext;
+pub use crate::revset::RevsetParseError;
+pub use crate::revset::RevsetResolutionError;
+use crate::revset::RevsetWorkspaceContext;
+use crate::revset::SymbolResolver;
+use crate::revset::SymbolResolverExtension;
+pub use crate::rewrite::MoveCommitsTarget;
+pub use crate::rewrite::duplicate_commits;
+pub use crate::rewrite::move_commits;
+pub use crate::rewrite::rebase_commit;
+pub use crate::settings::UserSettings;
+pub use crate::transaction::Transaction;
+pub use crate::transaction::TransactionCommitError;
+pub use crate::workspace::Workspace;
+pub use crate::workspace::WorkspaceLoadError;
+use crate::workspace::default_working_copy_factories;
+
+/// Error that may occur in the functions of this module.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// Failed to load the workspace.
+    #[error(transparent)]
+    WorkspaceLoad(#[from] WorkspaceLoadError),
+    /// Failed to load the repo.
+    #[error(transparent)]
+    RepoLoad(#[from] RepoLoaderError),
+    /// The revset couldn't be parsed.
+    #[error(transparent)]
+    RevsetParse(#[from] RevsetParseError),
+    /// A symbol in the revset couldn't be resolved.
+    #[error(transparent)]
+    RevsetResolution(#[from] RevsetResolutionError),
+    /// The revset couldn't be evaluated.
+    #[error(transparent)]
+    RevsetEvaluation(#[from] RevsetEvaluationError),
+    /// Failed to read or write objects.
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+}
+
+/// Loads the workspace at `workspace_path`, and its repo at the latest
+/// operation.
+///
+/// Only the backends built into jj-lib are supported.
+pub fn open(
+    settings: &UserSettings,
+    workspace_path: &Path,
+) -> Result<(Workspace, Arc<ReadonlyRepo>), Error> {
+    let workspace = Workspace::load(
+        settings,
+        workspace_path,
+        &StoreFactories::default(),
+        &default_working_copy_factories(),
+    )?;
+    let repo = workspace.repo_loader().load_at_head()?;
+    Ok((workspace, repo))
+}
+
+/// Evaluates `revset_str` in `repo`, and returns the commit ids in the order
+/// they would be listed by `jj log`, children before parents.
+///
+/// Only the built-in revset functions are available. Aliases defined in the
+/// user config, such as `trunk()`, aren't. `@` refers to the working-copy
+/// commit of the `workspace`, if specified.
+pub fn evaluate_revset(
+    repo: &dyn Repo,
+    workspace: Option<&Workspace>,
+    revset_str: &str,
+) -> Result<Vec<CommitId>, Error> {
+    let path_converter = workspace.map(|workspace| RepoPathUiConverter::Fs {
+        cwd: workspace.workspace_root().to_owned(),
+        base: workspace.workspace_root().to_owned(),
+    });
+    let context = RevsetParseContext {
+        aliases_map: &RevsetAliasesMap::default(),
+        local_variables: HashMap::new(),
+        user_email: repo.base_repo().settings().user_email(),
+        date_pattern_context: chrono::Local::now().into(),
+        extensions: &RevsetExtensions::default(),
+        workspace: workspace
+            .zip(path_converter.as_ref())
+            .map(|(workspace, path_converter)| RevsetWorkspaceContext {
+                path_converter,
+                workspace_name: workspace.workspace_name(),
+            }),
+    };
+    let expression = revset::parse(&mut RevsetDiagnostics::new(), revset_str, &context)?;
+    let symbol_resolver = SymbolResolver::new(repo, &([] as [Box<dyn SymbolResolverExtension>; 0]));
+    let commit_ids = expression
+        .resolve_user_expression(repo, &symbol_resolver)?
+        .evaluate(repo)?
+        .iter()
+        .try_collect()?;
+    Ok(commit_ids)
+}
+
+/// Data of a commit as shown by `jj log`, without depending on templates.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[non_exhaustive]
+pub struct LogEntry {
+    /// Id of the commit.
+    pub commit_id: CommitId,
+    /// Change id of the commit.
+    pub change_id: ChangeId,
+    /// Ids of the parent commits.
+    pub parent_ids: Vec<CommitId>,
+    /// Full description of the commit.
+    pub description: String,
+    /// Author of the commit.
+    pub author: Signature,
+    /// Committer of the commit.
+    pub committer: Signature,
+    /// Names of the local bookmarks pointing to the commit.
+    pub bookmarks: Vec<String>,
+    /// Names of the tags pointing to the commit.
+    pub tags: Vec<String>,
+    /// Names of the workspaces whose working-copy commit is this commit.
+    pub working_copies: Vec<String>,
+    /// Whether the commit has conflicts.
+    pub has_conflict: bool,
+}
+
+impl LogEntry {
+    /// Collects the log data of `commit` in `repo`.
+    pub fn from_commit(repo: &dyn Repo, commit: &Commit) -> BackendResult<Self> {
+        let view = repo.view();
+        let id = commit.id();
+        let bookmarks = view
+            .local_bookmarks_for_commit(id)
+            .map(|(name, _)| name.as_str().to_owned())
+            .collect();
+        let tags = view
+            .tags()
+            .iter()
+            .filter(|(_, target)| target.added_ids().contains(id))
+            .map(|(name, _)| name.as_str().to_owned())
+            .collect();
+        let working_copies = view
+            .wc_commit_ids()
+            .iter()
+            .filter(|(_, wc_commit_id)| *wc_commit_id == id)
+            .map(|(name, _)| name.as_str().to_owned())
+            .collect();
+        Ok(Self {
+            commit_id: id.clone(),
+            change_id: commit.change_id().clone(),
+            parent_ids: commit.parent_ids().to_vec(),
+            description: commit.description().to_owned(),
+            author: commit.author().clone(),
+            committer: commit.committer().clone(),
+            bookmarks,
+            tags,
+            working_copies,
+            has_conflict: commit.has_conflict()?,
+        })
+    }
+}
+
+/// Evaluates `revset_str` as [`evaluate_revset()`] does, and returns the log
+/// data of the commits.
+pub fn log(
+    repo: &dyn Repo,
+    workspace: Option<&Workspace>,
+    revset_str: &str,
+) -> Result<Vec<LogEntry>, Error> {
+    let entries = evaluate_revset(repo, workspace, revset_str)?
+        .iter()
+        .map(|id| {
+            let commit = repo.store().get_commit(id)?;
+            LogEntry::from_commit(repo, &commit)
+        })
+        .try_collect()?;
+    Ok(entries)
+}
+
+/// Kind of change to a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum FileChangeKind {
+    /// The file was added.
+    Added,
+    /// The file was modified, or its type was changed.
+    Modified,
+    /// The file was removed.
+    Removed,
+}
+
+/// File changed between two trees.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[non_exhaustive]
+pub struct FileChange {
+    /// Path of the file relative to the workspace root, using `/` as
+    /// separator.
+    pub path: String,
+    /// Kind of the change.
+    pub kind: FileChangeKind,
+    /// Whether the file has conflicts after the change.
+    pub has_conflict: bool,
+}
+
+/// Returns the files changed by `commit` compared to its parents, sorted by
+/// path.
+pub fn diff(repo: &dyn Repo, commit: &Commit) -> BackendResult<Vec<FileChange>> {
+    diff_trees(&commit.parent_tree(repo)?, &commit.tree()?)
+}
+
+fn diff_trees(from_tree: &MergedTree, to_tree: &MergedTree) -> BackendResult<Vec<FileChange>> {
+    async {
+        let mut diff_stream = from_tree.diff_stream(to_tree, &EverythingMatcher);
+        let mut changes = vec![];
+        while let Some(TreeDiffEntry { path, values }) = diff_stream.next().await {
+            let values = values?;
+            let kind = if values.before.is_absent() {
+                FileChangeKind::Added
+            } else if values.after.is_absent() {
+                FileChangeKind::Removed
+            } else {
+                FileChangeKind::Modified
+            };
+            changes.push(FileChange {
+                path: path.into_internal_string(),
+                kind,
+                has_conflict: !values.after.is_resolved(),
+            });
+        }
+        Ok(changes)
+    }
+    .block_on()
+}
+
+/// State of a workspace as shown by `jj status`.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[non_exhaustive]
+pub struct Status {
+    /// The working-copy commit.
+    pub working_copy: LogEntry,
+    /// Files changed in the working-copy commit compared to its parents.
+    pub changes: Vec<FileChange>,
+}
+
+/// Returns the status of the `workspace` in `repo`.
+///
+/// Unlike `jj status`, this doesn't snapshot the working copy, so changes made
+/// in the file system since the last jj command aren't included.
+pub fn status(repo: &dyn Repo, workspace: &Workspace) -> Result<Status, Error> {
+    let name = workspace.workspace_name();
+    let wc_commit_id = repo.view().get_wc_commit_id(name).ok_or_else(|| {
+        RevsetResolutionError::WorkspaceMissingWorkingCopy {
+            name: name.to_owned(),
+            candidates: vec![],
+        }
+    })?;
+    let wc_commit = repo.store().get_commit(wc_commit_id)?;
+    Ok(Status {
+        working_copy: LogEntry::from_commit(repo, &wc_commit)?,
+        changes: diff(repo, &wc_commit)?,
+    })
+}
+
+/// Sets the description of `commit`, and rebases its descendants onto the
+/// rewritten commit.
+pub fn describe_commit(
+    repo: &mut MutableRepo,
+    commit: &Commit,
+    description: impl Into<String>,
+) -> BackendResult<Commit> {
+    let new_commit = repo
+        .rewrite_commit(commit)
+        .set_description(description)
+        .write()?;
+    repo.rebase_descendants()?;
+    Ok(new_commit)
+}
+
+/// Abandons `commits`, and rebases their descendants onto their parents.
+pub fn abandon_commits(repo: &mut MutableRepo, commits: &[Commit]) -> BackendResult<()> {
+    for commit in commits {
+        repo.record_abandoned_commit(commit);
+    }
+    repo.rebase_descendants()?;
+    Ok(())
+}
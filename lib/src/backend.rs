@@ -22,6 +22,7 @@ use std::time::SystemTime;
 
 use async_trait::async_trait;
 use chrono::TimeZone as _;
+use futures::future::try_join_all;
 use futures::stream::BoxStream;
 use thiserror::Error;
 use tokio::io::AsyncRead;
@@ -477,6 +478,21 @@ pub trait Backend: Any + Send + Sync + Debug {
         id: &FileId,
     ) -> BackendResult<Pin<Box<dyn AsyncRead + Send>>>;
 
+    /// Returns the size of the file contents in bytes.
+    ///
+    /// Backends which can look up the size without reading the contents
+    /// should override this. The default implementation reads the whole file.
+    async fn file_size(&self, path: &RepoPath, id: &FileId) -> BackendResult<u64> {
+        let mut reader = self.read_file(path, id).await?;
+        tokio::io::copy(&mut reader, &mut tokio::io::sink())
+            .await
+            .map_err(|err| BackendError::ReadFile {
+                path: path.to_owned(),
+                id: id.clone(),
+                source: err.into(),
+            })
+    }
+
     async fn write_file(
         &self,
         path: &RepoPath,
@@ -512,6 +528,16 @@ pub trait Backend: Any + Send + Sync + Debug {
 
     async fn read_tree(&self, path: &RepoPath, id: &TreeId) -> BackendResult<Tree>;
 
+    /// Reads multiple trees at once. The trees are returned in the same order
+    /// as `ids`.
+    ///
+    /// Backends which can answer several reads in a single request may
+    /// override this. The default implementation calls `read_tree()` for each
+    /// tree concurrently.
+    async fn read_trees(&self, ids: &[(&RepoPath, &TreeId)]) -> BackendResult<Vec<Tree>> {
+        try_join_all(ids.iter().map(|&(path, id)| self.read_tree(path, id))).await
+    }
+
     async fn write_tree(&self, path: &RepoPath, contents: &Tree) -> BackendResult<TreeId>;
 
     async fn read_commit(&self, id: &CommitId) -> BackendResult<Commit>;
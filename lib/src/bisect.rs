@@ -15,6 +15,7 @@
 //! Bisect a range of commits.
 
 use std::collections::HashSet;
+use std::slice;
 use std::sync::Arc;
 
 use itertools::Itertools as _;
@@ -22,6 +23,10 @@ use thiserror::Error;
 
 use crate::backend::CommitId;
 use crate::commit::Commit;
+use crate::object_id::ObjectId as _;
+use crate::op_store::OpStoreResult;
+use crate::op_walk;
+use crate::operation::Operation;
 use crate::repo::Repo;
 use crate::revset::ResolvedRevsetExpression;
 use crate::revset::RevsetEvaluationError;
@@ -38,7 +43,7 @@ pub enum BisectionError {
 
 /// Indicates whether a given commit was good, bad, or if it could not be
 /// determined.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Evaluation {
     /// The commit was good
     Good,
@@ -48,6 +53,101 @@ pub enum Evaluation {
     Skip,
 }
 
+impl Evaluation {
+    /// The name of the evaluation as recorded in the operation log.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Good => "good",
+            Self::Bad => "bad",
+            Self::Skip => "skip",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "good" => Some(Self::Good),
+            "bad" => Some(Self::Bad),
+            "skip" => Some(Self::Skip),
+            _ => None,
+        }
+    }
+}
+
+/// Operation tag recording a step of a bisection session. The value is
+/// `start`, `reset`, or an evaluation name followed by the marked commit id
+/// (e.g. `good 0123...`).
+pub const BISECT_TAG: &str = "bisect";
+/// Operation tag holding the revset of the range to bisect. It is set by the
+/// operation that started the session.
+pub const BISECT_RANGE_TAG: &str = "bisect-range";
+/// Operation tag holding the id of the commit checked out for evaluation, or
+/// an empty string if bisection is complete.
+pub const BISECT_TARGET_TAG: &str = "bisect-target";
+
+/// A bisection session recorded in the operation log.
+#[derive(Clone, Debug)]
+pub struct BisectSession {
+    /// The operation that started the session.
+    pub start_operation: Operation,
+    /// The revset of the range to bisect. Symbols should be resolved at the
+    /// start operation.
+    pub range: String,
+    /// The commit currently checked out for evaluation, if any.
+    pub target: Option<CommitId>,
+    /// The marks in the order they were recorded. If a commit was marked more
+    /// than once, only the latest mark is kept.
+    pub marks: Vec<(CommitId, Evaluation)>,
+}
+
+impl BisectSession {
+    /// Looks up the bisection session in progress at `op` by walking the
+    /// operation log back to the operation that started it.
+    pub fn find(op: &Operation) -> OpStoreResult<Option<Self>> {
+        let mut target = None;
+        let mut marks: Vec<(CommitId, Evaluation)> = vec![];
+        for op in op_walk::walk_ancestors(slice::from_ref(op)) {
+            let op = op?;
+            let tags = &op.metadata().tags;
+            if target.is_none()
+                && let Some(hex) = tags.get(BISECT_TARGET_TAG)
+            {
+                target = Some(CommitId::try_from_hex(hex).filter(|_| !hex.is_empty()));
+            }
+            let Some(value) = tags.get(BISECT_TAG) else {
+                continue;
+            };
+            match value.split_once(' ') {
+                None if value == "start" => {
+                    let range = tags.get(BISECT_RANGE_TAG).cloned().unwrap_or_default();
+                    marks.reverse();
+                    return Ok(Some(Self {
+                        start_operation: op,
+                        range,
+                        target: target.flatten(),
+                        marks,
+                    }));
+                }
+                None if value == "reset" => return Ok(None),
+                Some((name, hex)) => {
+                    if let (Some(evaluation), Some(id)) =
+                        (Evaluation::from_name(name), CommitId::try_from_hex(hex))
+                        && !marks.iter().any(|(marked_id, _)| *marked_id == id)
+                    {
+                        marks.push((id, evaluation));
+                    }
+                }
+                None => {}
+            }
+        }
+        Ok(None)
+    }
+
+    /// Formats the value of [`BISECT_TAG`] recording that `id` was marked.
+    pub fn mark_tag_value(id: &CommitId, evaluation: Evaluation) -> String {
+        format!("{} {}", evaluation.name(), id.hex())
+    }
+}
+
 /// Performs bisection to find the first bad commit in a range.
 pub struct Bisector<'repo> {
     repo: &'repo dyn Repo,
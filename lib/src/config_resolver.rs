@@ -45,6 +45,9 @@ pub struct ConfigResolutionContext<'a> {
     pub home_dir: Option<&'a Path>,
     /// Repository path, which is usually `<workspace_root>/.jj/repo`.
     pub repo_path: Option<&'a Path>,
+    /// Workspace root path. This differs from the parent of `repo_path` if
+    /// the workspace was added by `jj workspace add`.
+    pub workspace_path: Option<&'a Path>,
     /// Space-separated subcommand. `jj file show ...` should result in `"file
     /// show"`.
     pub command: Option<&'a str>,
@@ -62,6 +65,8 @@ pub struct ConfigResolutionContext<'a> {
 struct ScopeCondition {
     /// Paths to match the repository path prefix.
     pub repositories: Option<Vec<PathBuf>>,
+    /// Paths to match the workspace root path prefix.
+    pub workspaces: Option<Vec<PathBuf>>,
     /// Commands to match. Subcommands are matched space-separated.
     /// - `--when.commands = ["foo"]` -> matches "foo", "foo bar", "foo bar baz"
     /// - `--when.commands = ["foo bar"]` -> matches "foo bar", "foo bar baz",
@@ -70,7 +75,6 @@ struct ScopeCondition {
     /// Platforms to match. The values are defined by `std::env::consts::FAMILY`
     /// and `std::env::consts::OS`.
     pub platforms: Option<Vec<String>>,
-    // TODO: maybe add "workspaces"?
 }
 
 impl ScopeCondition {
@@ -87,7 +91,8 @@ impl ScopeCondition {
         // It might make some sense to compare paths in canonicalized form, but
         // be careful to not resolve relative path patterns against cwd, which
         // wouldn't be what the user would expect.
-        for path in self.repositories.as_mut().into_iter().flatten() {
+        let paths = [&mut self.repositories, &mut self.workspaces];
+        for path in paths.into_iter().flatten().flatten() {
             if let Some(new_path) = expand_home(path, context.home_dir)? {
                 *path = new_path;
             }
@@ -97,6 +102,7 @@ impl ScopeCondition {
 
     fn matches(&self, context: &ConfigResolutionContext) -> bool {
         matches_path_prefix(self.repositories.as_deref(), context.repo_path)
+            && matches_path_prefix(self.workspaces.as_deref(), context.workspace_path)
             && matches_platform(self.platforms.as_deref())
             && matches_command(self.commands.as_deref(), context.command)
     }
@@ -440,12 +446,14 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: None,
             repo_path: None,
+            workspace_path: None,
             command: None,
         };
         assert!(condition.matches(&context));
         let context = ConfigResolutionContext {
             home_dir: None,
             repo_path: Some(Path::new("/foo")),
+            workspace_path: None,
             command: None,
         };
         assert!(condition.matches(&context));
@@ -455,6 +463,7 @@ mod tests {
     fn test_condition_repo_path() {
         let condition = ScopeCondition {
             repositories: Some(["/foo", "/bar"].map(PathBuf::from).into()),
+            workspaces: None,
             commands: None,
             platforms: None,
         };
@@ -462,30 +471,35 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: None,
             repo_path: None,
+            workspace_path: None,
             command: None,
         };
         assert!(!condition.matches(&context));
         let context = ConfigResolutionContext {
             home_dir: None,
             repo_path: Some(Path::new("/foo")),
+            workspace_path: None,
             command: None,
         };
         assert!(condition.matches(&context));
         let context = ConfigResolutionContext {
             home_dir: None,
             repo_path: Some(Path::new("/fooo")),
+            workspace_path: None,
             command: None,
         };
         assert!(!condition.matches(&context));
         let context = ConfigResolutionContext {
             home_dir: None,
             repo_path: Some(Path::new("/foo/baz")),
+            workspace_path: None,
             command: None,
         };
         assert!(condition.matches(&context));
         let context = ConfigResolutionContext {
             home_dir: None,
             repo_path: Some(Path::new("/bar")),
+            workspace_path: None,
             command: None,
         };
         assert!(condition.matches(&context));
@@ -495,6 +509,7 @@ mod tests {
     fn test_condition_repo_path_windows() {
         let condition = ScopeCondition {
             repositories: Some(["c:/foo", r"d:\bar/baz"].map(PathBuf::from).into()),
+            workspaces: None,
             commands: None,
             platforms: None,
         };
@@ -502,29 +517,65 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: None,
             repo_path: Some(Path::new(r"c:\foo")),
+            workspace_path: None,
             command: None,
         };
         assert_eq!(condition.matches(&context), cfg!(windows));
         let context = ConfigResolutionContext {
             home_dir: None,
             repo_path: Some(Path::new(r"c:\foo\baz")),
+            workspace_path: None,
             command: None,
         };
         assert_eq!(condition.matches(&context), cfg!(windows));
         let context = ConfigResolutionContext {
             home_dir: None,
             repo_path: Some(Path::new(r"d:\foo")),
+            workspace_path: None,
             command: None,
         };
         assert!(!condition.matches(&context));
         let context = ConfigResolutionContext {
             home_dir: None,
             repo_path: Some(Path::new(r"d:/bar\baz")),
+            workspace_path: None,
             command: None,
         };
         assert_eq!(condition.matches(&context), cfg!(windows));
     }
 
+    #[test]
+    fn test_condition_workspace_path() {
+        let condition = ScopeCondition {
+            repositories: None,
+            workspaces: Some(["/foo"].map(PathBuf::from).into()),
+            commands: None,
+            platforms: None,
+        };
+
+        let context = ConfigResolutionContext {
+            home_dir: None,
+            repo_path: Some(Path::new("/foo/.jj/repo")),
+            workspace_path: None,
+            command: None,
+        };
+        assert!(!condition.matches(&context));
+        let context = ConfigResolutionContext {
+            home_dir: None,
+            repo_path: Some(Path::new("/bar/.jj/repo")),
+            workspace_path: Some(Path::new("/foo/ws")),
+            command: None,
+        };
+        assert!(condition.matches(&context));
+        let context = ConfigResolutionContext {
+            home_dir: None,
+            repo_path: Some(Path::new("/foo/.jj/repo")),
+            workspace_path: Some(Path::new("/bar")),
+            command: None,
+        };
+        assert!(!condition.matches(&context));
+    }
+
     fn new_user_layer(text: &str) -> ConfigLayer {
         ConfigLayer::parse(ConfigSource::User, text).unwrap()
     }
@@ -538,6 +589,7 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: None,
             repo_path: None,
+            workspace_path: None,
             command: None,
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
@@ -575,6 +627,7 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: None,
             repo_path: None,
+            workspace_path: None,
             command: None,
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
@@ -614,6 +667,7 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: Some(Path::new("/home/dir")),
             repo_path: None,
+            workspace_path: None,
             command: None,
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
@@ -623,6 +677,7 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: Some(Path::new("/home/dir")),
             repo_path: Some(Path::new("/foo/.jj/repo")),
+            workspace_path: None,
             command: None,
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
@@ -634,6 +689,7 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: Some(Path::new("/home/dir")),
             repo_path: Some(Path::new("/bar/.jj/repo")),
+            workspace_path: None,
             command: None,
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
@@ -644,6 +700,7 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: Some(Path::new("/home/dir")),
             repo_path: Some(Path::new("/home/dir/baz/.jj/repo")),
+            workspace_path: None,
             command: None,
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
@@ -674,6 +731,7 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: None,
             repo_path: None,
+            workspace_path: None,
             command: None,
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
@@ -683,6 +741,7 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: None,
             repo_path: None,
+            workspace_path: None,
             command: Some("foo"),
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
@@ -694,6 +753,7 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: None,
             repo_path: None,
+            workspace_path: None,
             command: Some("bar"),
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
@@ -704,6 +764,7 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: None,
             repo_path: None,
+            workspace_path: None,
             command: Some("foo baz"),
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
@@ -717,6 +778,7 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: None,
             repo_path: None,
+            workspace_path: None,
             command: Some("fooqux"),
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
@@ -747,6 +809,7 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: Some(Path::new("/home/dir")),
             repo_path: None,
+            workspace_path: None,
             command: None,
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
@@ -784,6 +847,7 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: Some(Path::new("/home/dir")),
             repo_path: None,
+            workspace_path: None,
             command: None,
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
@@ -794,6 +858,7 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: Some(Path::new("/home/dir")),
             repo_path: Some(Path::new("/foo")),
+            workspace_path: None,
             command: Some("other"),
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
@@ -804,6 +869,7 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: Some(Path::new("/home/dir")),
             repo_path: Some(Path::new("/qux")),
+            workspace_path: None,
             command: Some("ABC"),
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
@@ -814,6 +880,7 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: Some(Path::new("/home/dir")),
             repo_path: Some(Path::new("/bar")),
+            workspace_path: None,
             command: Some("DEF"),
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
@@ -832,6 +899,7 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: Some(Path::new("/home/dir")),
             repo_path: Some(Path::new("/foo/.jj/repo")),
+            workspace_path: None,
             command: None,
         };
         assert_matches!(
@@ -850,6 +918,7 @@ mod tests {
         let context = ConfigResolutionContext {
             home_dir: Some(Path::new("/home/dir")),
             repo_path: Some(Path::new("/foo/.jj/repo")),
+            workspace_path: None,
             command: None,
         };
         assert_matches!(
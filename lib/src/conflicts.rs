@@ -17,10 +17,13 @@
 use std::io;
 use std::io::Write;
 use std::iter::zip;
+use std::num::NonZeroUsize;
 use std::pin::Pin;
+use std::sync::Mutex;
 
 use bstr::BString;
 use bstr::ByteSlice as _;
+use clru::CLruCache;
 use futures::Stream;
 use futures::StreamExt as _;
 use futures::stream::BoxStream;
@@ -38,6 +41,8 @@ use crate::backend::FileId;
 use crate::backend::SymlinkId;
 use crate::backend::TreeId;
 use crate::backend::TreeValue;
+use crate::content_hash::ContentHash;
+use crate::content_hash::blake2b_hash;
 use crate::copies::CopiesTreeDiffEntry;
 use crate::copies::CopiesTreeDiffEntryPath;
 use crate::diff::ContentDiff;
@@ -283,7 +288,7 @@ pub fn resolve_file_executable(merge: &Merge<Option<bool>>) -> Option<bool> {
 }
 
 /// Describes what style should be used when materializing conflicts.
-#[derive(Clone, Copy, PartialEq, Eq, Debug, serde::Deserialize)]
+#[derive(ContentHash, Clone, Copy, PartialEq, Eq, Debug, serde::Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum ConflictMarkerStyle {
     /// Style which shows a snapshot and a series of diffs to apply.
@@ -437,6 +442,81 @@ pub fn materialize_merge_result_to_bytes<T: AsRef<[u8]>>(
     }
 }
 
+/// Materializes the file conflict like [`materialize_merge_result_to_bytes()`],
+/// reusing the rendering cached by the `store` if any.
+///
+/// The `file` contents have already been read at this point, so a cache hit
+/// only saves merging and rendering them.
+pub fn materialize_file_conflict_to_bytes(
+    store: &Store,
+    file: &MaterializedFileConflictValue,
+    options: &ConflictMaterializeOptions,
+) -> BString {
+    let cache = store.conflict_cache();
+    if let Some(contents) = cache.get(&file.ids, options) {
+        return contents;
+    }
+    let contents = materialize_merge_result_to_bytes(&file.contents, options);
+    cache.insert(&file.ids, options, contents.clone());
+    contents
+}
+
+/// In-memory cache of materialized file conflicts keyed by the conflict's term
+/// ids and the materialization options.
+///
+/// The cache lives as long as the [`Store`], so it only helps when a conflict
+/// is materialized more than once within a command. File ids are content
+/// hashes, so a cached rendering never goes stale.
+#[derive(Debug)]
+pub struct MaterializedConflictCache {
+    entries: Mutex<CLruCache<Vec<u8>, BString>>,
+}
+
+impl MaterializedConflictCache {
+    /// Creates cache which holds up to `capacity` renderings.
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            entries: Mutex::new(CLruCache::new(capacity)),
+        }
+    }
+
+    /// Looks up the rendering of the conflict between the file `ids`.
+    pub fn get(
+        &self,
+        ids: &Merge<Option<FileId>>,
+        options: &ConflictMaterializeOptions,
+    ) -> Option<BString> {
+        let key = Self::key(ids, options);
+        self.entries.lock().unwrap().get(&key).cloned()
+    }
+
+    /// Records the rendering of the conflict between the file `ids`.
+    pub fn insert(
+        &self,
+        ids: &Merge<Option<FileId>>,
+        options: &ConflictMaterializeOptions,
+        contents: BString,
+    ) {
+        let key = Self::key(ids, options);
+        self.entries.lock().unwrap().put(key, contents);
+    }
+
+    /// Removes all entries.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    fn key(ids: &Merge<Option<FileId>>, options: &ConflictMaterializeOptions) -> Vec<u8> {
+        let key = (
+            ids.clone(),
+            options.marker_style,
+            options.marker_len.map(|len| len as u64),
+            options.merge.clone(),
+        );
+        blake2b_hash(&key).to_vec()
+    }
+}
+
 fn materialize_conflict_hunks(
     hunks: &[Merge<BString>],
     conflict_marker_style: ConflictMarkerStyle,
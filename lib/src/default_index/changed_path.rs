@@ -17,8 +17,8 @@
 use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Debug;
-use std::fs::File;
-use std::io::Read;
+use std::fs;
+use std::io::Read as _;
 use std::io::Write as _;
 use std::path::Path;
 use std::sync::Arc;
@@ -129,23 +129,24 @@ impl ReadonlyChangedPathIndexSegment {
         dir: &Path,
         id: ChangedPathIndexSegmentId,
     ) -> Result<Arc<Self>, ReadonlyIndexLoadError> {
-        let mut file = File::open(dir.join(id.hex()))
+        let data = fs::read(dir.join(id.hex()))
             .map_err(|err| ReadonlyIndexLoadError::from_io_err("changed-path", id.hex(), err))?;
-        Self::load_from(&mut file, id)
+        Self::load_from(data, id)
     }
 
     pub(super) fn load_from(
-        file: &mut dyn Read,
+        data: Vec<u8>,
         id: ChangedPathIndexSegmentId,
     ) -> Result<Arc<Self>, ReadonlyIndexLoadError> {
         let from_io_err = |err| ReadonlyIndexLoadError::from_io_err("changed-path", id.hex(), err);
-        let read_u32 = |file: &mut dyn Read| {
+        let read_u32 = |file: &mut &[u8]| {
             let mut buf = [0; 4];
             file.read_exact(&mut buf).map_err(from_io_err)?;
             Ok(u32::from_le_bytes(buf))
         };
 
-        let format_version = read_u32(file)?;
+        let mut file = data.as_slice();
+        let format_version = read_u32(&mut file)?;
         if format_version != FILE_FORMAT_VERSION {
             return Err(ReadonlyIndexLoadError::UnexpectedVersion {
                 kind: "changed-path",
@@ -154,18 +155,16 @@ impl ReadonlyChangedPathIndexSegment {
             });
         }
 
-        let num_local_commits = read_u32(file)?;
-        let num_changed_paths = read_u32(file)?;
-        let num_paths = read_u32(file)?;
-        let num_path_bytes = read_u32(file)?;
-        let mut data = vec![];
-        file.read_to_end(&mut data).map_err(from_io_err)?;
+        let num_local_commits = read_u32(&mut file)?;
+        let num_changed_paths = read_u32(&mut file)?;
+        let num_paths = read_u32(&mut file)?;
+        let num_path_bytes = read_u32(&mut file)?;
 
         let commit_lookup_size = (num_local_commits as usize + 1) * 4;
         let changed_path_lookup_size = (num_changed_paths as usize) * 4;
         let path_lookup_size = (num_paths as usize + 1) * 4;
 
-        let commit_lookup_base = 0;
+        let commit_lookup_base = data.len() - file.len();
         let changed_path_lookup_base = commit_lookup_base + commit_lookup_size;
         let path_lookup_base = changed_path_lookup_base + changed_path_lookup_size;
         let path_bytes_base = path_lookup_base + path_lookup_size;
@@ -362,7 +361,7 @@ impl MutableChangedPathIndexSegment {
         file.as_file_mut().write_all(&buf).context(file.path())?;
         persist_content_addressed_temp_file(file, &file_path).context(&file_path)?;
 
-        let segment = ReadonlyChangedPathIndexSegment::load_from(&mut &buf[..], file_id)
+        let segment = ReadonlyChangedPathIndexSegment::load_from(buf, file_id)
             .expect("in-memory index data should be valid and readable");
         Ok(segment)
     }
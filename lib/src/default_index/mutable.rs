@@ -368,7 +368,8 @@ impl MutableCommitIndexSegment {
             .context(&index_file_path)?;
 
         Ok(ReadonlyCommitIndexSegment::load_with_parent_file(
-            &mut &buf[local_entries_offset..],
+            buf,
+            local_entries_offset,
             index_file_id,
             self.parent_file,
             self.field_lengths,
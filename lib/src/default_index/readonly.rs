@@ -18,9 +18,9 @@ use std::cmp::Ordering;
 use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Debug;
-use std::fs::File;
+use std::fs;
 use std::io;
-use std::io::Read;
+use std::io::Read as _;
 use std::iter;
 use std::ops::Range;
 use std::path::Path;
@@ -259,6 +259,7 @@ pub(super) struct ReadonlyCommitIndexSegment {
     num_local_change_ids: u32,
     num_change_overflow_entries: u32,
     // Base data offsets in bytes:
+    graph_base: usize,
     commit_lookup_base: usize,
     change_id_table_base: usize,
     change_pos_table_base: usize,
@@ -283,25 +284,28 @@ impl ReadonlyCommitIndexSegment {
         id: CommitIndexSegmentId,
         lengths: FieldLengths,
     ) -> Result<Arc<Self>, ReadonlyIndexLoadError> {
-        let mut file = File::open(dir.join(id.hex()))
+        // The whole file is read at once so the entries can be parsed in place.
+        let data = fs::read(dir.join(id.hex()))
             .map_err(|err| ReadonlyIndexLoadError::from_io_err("commit", id.hex(), err))?;
-        Self::load_from(&mut file, dir, id, lengths)
+        Self::load_from(data, dir, id, lengths)
     }
 
-    /// Loads both parent segments and local entries from the given `file`.
+    /// Loads both parent segments and local entries from the given file
+    /// `data`.
     pub(super) fn load_from(
-        file: &mut dyn Read,
+        data: Vec<u8>,
         dir: &Path,
         id: CommitIndexSegmentId,
         lengths: FieldLengths,
     ) -> Result<Arc<Self>, ReadonlyIndexLoadError> {
         let from_io_err = |err| ReadonlyIndexLoadError::from_io_err("commit", id.hex(), err);
-        let read_u32 = |file: &mut dyn Read| {
+        let read_u32 = |file: &mut &[u8]| {
             let mut buf = [0; 4];
             file.read_exact(&mut buf).map_err(from_io_err)?;
             Ok(u32::from_le_bytes(buf))
         };
-        let format_version = read_u32(file)?;
+        let mut file = data.as_slice();
+        let format_version = read_u32(&mut file)?;
         if format_version != COMMIT_INDEX_SEGMENT_FILE_FORMAT_VERSION {
             return Err(ReadonlyIndexLoadError::UnexpectedVersion {
                 kind: "commit",
@@ -309,11 +313,11 @@ impl ReadonlyCommitIndexSegment {
                 expected_version: COMMIT_INDEX_SEGMENT_FILE_FORMAT_VERSION,
             });
         }
-        let parent_filename_len = read_u32(file)?;
+        let parent_filename_len = read_u32(&mut file)?;
         let maybe_parent_file = if parent_filename_len > 0 {
-            let mut parent_filename_bytes = vec![0; parent_filename_len as usize];
-            file.read_exact(&mut parent_filename_bytes)
-                .map_err(from_io_err)?;
+            let parent_filename_bytes = file
+                .split_off(..parent_filename_len as usize)
+                .ok_or_else(|| from_io_err(io::ErrorKind::UnexpectedEof.into()))?;
             let parent_file_id = CommitIndexSegmentId::try_from_hex(parent_filename_bytes)
                 .ok_or_else(|| {
                     ReadonlyIndexLoadError::invalid_data(
@@ -327,19 +331,21 @@ impl ReadonlyCommitIndexSegment {
         } else {
             None
         };
-        Self::load_with_parent_file(file, id, maybe_parent_file, lengths)
+        let local_entries_offset = data.len() - file.len();
+        Self::load_with_parent_file(data, local_entries_offset, id, maybe_parent_file, lengths)
     }
 
-    /// Loads local entries from the given `file`, returns new segment linked to
-    /// the given `parent_file`.
+    /// Loads local entries stored in `data` from the `local_entries_offset`,
+    /// returns new segment linked to the given `parent_file`.
     pub(super) fn load_with_parent_file(
-        file: &mut dyn Read,
+        data: Vec<u8>,
+        local_entries_offset: usize,
         id: CommitIndexSegmentId,
         parent_file: Option<Arc<Self>>,
         lengths: FieldLengths,
     ) -> Result<Arc<Self>, ReadonlyIndexLoadError> {
         let from_io_err = |err| ReadonlyIndexLoadError::from_io_err("commit", id.hex(), err);
-        let read_u32 = |file: &mut dyn Read| {
+        let read_u32 = |file: &mut &[u8]| {
             let mut buf = [0; 4];
             file.read_exact(&mut buf).map_err(from_io_err)?;
             Ok(u32::from_le_bytes(buf))
@@ -347,12 +353,11 @@ impl ReadonlyCommitIndexSegment {
         let num_parent_commits = parent_file
             .as_ref()
             .map_or(0, |segment| segment.as_composite().num_commits());
-        let num_local_commits = read_u32(file)?;
-        let num_local_change_ids = read_u32(file)?;
-        let num_parent_overflow_entries = read_u32(file)?;
-        let num_change_overflow_entries = read_u32(file)?;
-        let mut data = vec![];
-        file.read_to_end(&mut data).map_err(from_io_err)?;
+        let mut file = &data[local_entries_offset..];
+        let num_local_commits = read_u32(&mut file)?;
+        let num_local_change_ids = read_u32(&mut file)?;
+        let num_parent_overflow_entries = read_u32(&mut file)?;
+        let num_change_overflow_entries = read_u32(&mut file)?;
 
         let commit_graph_entry_size = CommitGraphEntry::size(lengths.commit_id);
         let graph_size = (num_local_commits as usize) * commit_graph_entry_size;
@@ -362,7 +367,7 @@ impl ReadonlyCommitIndexSegment {
         let parent_overflow_size = (num_parent_overflow_entries as usize) * 4;
         let change_overflow_size = (num_change_overflow_entries as usize) * 4;
 
-        let graph_base = 0;
+        let graph_base = data.len() - file.len();
         let commit_lookup_base = graph_base + graph_size;
         let change_id_table_base = commit_lookup_base + commit_lookup_size;
         let change_pos_table_base = change_id_table_base + change_id_table_size;
@@ -386,6 +391,7 @@ impl ReadonlyCommitIndexSegment {
             num_local_commits,
             num_local_change_ids,
             num_change_overflow_entries,
+            graph_base,
             commit_lookup_base,
             change_id_table_base,
             change_pos_table_base,
@@ -408,7 +414,7 @@ impl ReadonlyCommitIndexSegment {
     }
 
     fn graph_entry(&self, local_pos: LocalCommitPosition) -> CommitGraphEntry<'_> {
-        let table = &self.data[..self.commit_lookup_base];
+        let table = &self.data[self.graph_base..self.commit_lookup_base];
         let entry_size = CommitGraphEntry::size(self.field_lengths.commit_id);
         let offset = (local_pos.0 as usize) * entry_size;
         CommitGraphEntry {
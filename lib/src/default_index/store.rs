@@ -178,6 +178,7 @@ impl DefaultIndexStore {
         self.dir.join("changed_paths")
     }
 
+    #[tracing::instrument(skip(self, lengths))]
     fn load_index_at_operation(
         &self,
         op_id: &OperationId,
@@ -558,6 +559,7 @@ impl IndexStore for DefaultIndexStore {
         Self::name()
     }
 
+    #[tracing::instrument(skip_all)]
     fn get_index_at_op(
         &self,
         op: &Operation,
@@ -24,6 +24,7 @@ use bstr::BString;
 use either::Either;
 use itertools::Itertools as _;
 
+use crate::content_hash::ContentHash;
 use crate::diff::ContentDiff;
 use crate::diff::DiffHunk;
 use crate::diff::DiffHunkKind;
@@ -263,7 +264,7 @@ where
 }
 
 /// Granularity of hunks when merging files.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[derive(ContentHash, Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum FileMergeHunkLevel {
     /// Splits into line hunks.
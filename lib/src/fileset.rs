@@ -69,7 +69,7 @@ pub enum FilePatternParseError {
 }
 
 /// Basic pattern to match `RepoPath`.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum FilePattern {
     /// Matches file (or exact) path.
     FilePath(RepoPathBuf),
@@ -258,7 +258,7 @@ fn split_glob_path_i(input: &str) -> (&str, &str) {
 }
 
 /// AST-level representation of the fileset expression.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum FilesetExpression {
     /// Matches nothing.
     None,
@@ -439,6 +439,26 @@ pub fn import_refs(
     import_some_refs(mut_repo, git_settings, |_, _| true)
 }
 
+/// Returns true if the refs in the underlying Git repo differ from the ones
+/// last imported to `repo`.
+///
+/// This doesn't need the index, so it can be checked before starting a
+/// transaction to import the refs.
+pub fn has_refs_to_import(repo: &dyn Repo) -> Result<bool, GitImportError> {
+    let git_backend = get_git_backend(repo.store())?;
+    let git_repo = git_backend.git_repo();
+    let RefsToImport {
+        changed_git_refs,
+        changed_remote_bookmarks,
+        changed_remote_tags,
+        failed_ref_names,
+    } = diff_refs_to_import(repo.view(), &git_repo, |_, _| true)?;
+    Ok(!(changed_git_refs.is_empty()
+        && changed_remote_bookmarks.is_empty()
+        && changed_remote_tags.is_empty()
+        && failed_ref_names.is_empty()))
+}
+
 /// Reflect changes made in the underlying Git repo in the Jujutsu repo.
 ///
 /// Only bookmarks and tags whose remote symbol pass the filter will be
@@ -787,11 +807,7 @@ pub fn import_head(mut_repo: &mut MutableRepo) -> Result<(), GitImportError> {
     let git_repo = git_backend.git_repo();
 
     let old_git_head = mut_repo.view().git_head();
-    let new_git_head_id = if let Ok(oid) = git_repo.head_id() {
-        Some(CommitId::from_bytes(oid.as_bytes()))
-    } else {
-        None
-    };
+    let new_git_head_id = read_git_head_id(&git_repo);
     if old_git_head.as_resolved() == Some(&new_git_head_id) {
         return Ok(());
     }
@@ -819,6 +835,22 @@ pub fn import_head(mut_repo: &mut MutableRepo) -> Result<(), GitImportError> {
     Ok(())
 }
 
+/// Returns true if the HEAD of the underlying Git repo differs from the one
+/// last imported to `repo`.
+///
+/// This doesn't need the index, so it can be checked before starting a
+/// transaction to import the HEAD.
+pub fn has_head_to_import(repo: &dyn Repo) -> Result<bool, GitImportError> {
+    let git_backend = get_git_backend(repo.store())?;
+    let new_git_head_id = read_git_head_id(&git_backend.git_repo());
+    Ok(repo.view().git_head().as_resolved() != Some(&new_git_head_id))
+}
+
+fn read_git_head_id(git_repo: &gix::Repository) -> Option<CommitId> {
+    let oid = git_repo.head_id().ok()?;
+    Some(CommitId::from_bytes(oid.as_bytes()))
+}
+
 #[derive(Error, Debug)]
 pub enum GitExportError {
     #[error(transparent)]
@@ -2338,8 +2370,7 @@ impl<'a> GitFetch<'a> {
     ) -> Result<Self, UnexpectedGitBackendError> {
         let git_backend = get_git_backend(mut_repo.store())?;
         let git_repo = Box::new(git_backend.git_repo());
-        let git_ctx =
-            GitSubprocessContext::from_git_backend(git_backend, &git_settings.executable_path);
+        let git_ctx = GitSubprocessContext::from_git_backend(git_backend, git_settings);
         Ok(GitFetch {
             mut_repo,
             git_repo,
@@ -2577,8 +2608,7 @@ pub fn push_updates(
 
     let git_backend = get_git_backend(repo.store())?;
     let git_repo = git_backend.git_repo();
-    let git_ctx =
-        GitSubprocessContext::from_git_backend(git_backend, &git_settings.executable_path);
+    let git_ctx = GitSubprocessContext::from_git_backend(git_backend, git_settings);
 
     // check the remote exists
     if git_repo.try_find_remote(remote_name.as_str()).is_none() {
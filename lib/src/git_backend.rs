@@ -22,6 +22,7 @@ use std::fmt::Formatter;
 use std::fs;
 use std::io;
 use std::io::Cursor;
+use std::ops::Deref;
 use std::path::Path;
 use std::path::PathBuf;
 use std::pin::Pin;
@@ -31,6 +32,7 @@ use std::str::Utf8Error;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::MutexGuard;
+use std::sync::TryLockError;
 use std::time::SystemTime;
 
 use async_trait::async_trait;
@@ -178,6 +180,22 @@ pub struct GitBackend {
     write_change_id_header: bool,
 }
 
+enum GitRepoForRead<'a> {
+    Cached(MutexGuard<'a, gix::Repository>),
+    New(Box<gix::Repository>),
+}
+
+impl Deref for GitRepoForRead<'_> {
+    type Target = gix::Repository;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Cached(repo) => repo,
+            Self::New(repo) => repo,
+        }
+    }
+}
+
 impl GitBackend {
     pub fn name() -> &'static str {
         "git"
@@ -337,6 +355,18 @@ impl GitBackend {
         self.repo.lock().unwrap()
     }
 
+    /// Returns the cached repo instance, or a new thread-local instance if the
+    /// cached one is in use. This allows objects to be read in parallel.
+    fn git_repo_for_read(&self) -> GitRepoForRead<'_> {
+        match self.repo.try_lock() {
+            Ok(repo) => GitRepoForRead::Cached(repo),
+            Err(TryLockError::WouldBlock) => {
+                GitRepoForRead::New(Box::new(self.base_repo.to_thread_local()))
+            }
+            Err(TryLockError::Poisoned(err)) => panic!("{err}"),
+        }
+    }
+
     /// Returns new thread-local instance to access to the underlying Git repo.
     pub fn git_repo(&self) -> gix::Repository {
         self.base_repo.to_thread_local()
@@ -1010,6 +1040,15 @@ impl Backend for GitBackend {
         Ok(Box::pin(Cursor::new(data)))
     }
 
+    async fn file_size(&self, _path: &RepoPath, id: &FileId) -> BackendResult<u64> {
+        let git_blob_id = validate_git_object_id(id)?;
+        let locked_repo = self.lock_git_repo();
+        let header = locked_repo
+            .find_header(git_blob_id)
+            .map_err(|err| map_not_found_err(err, id))?;
+        Ok(header.size())
+    }
+
     async fn write_file(
         &self,
         _path: &RepoPath,
@@ -1076,8 +1115,8 @@ impl Backend for GitBackend {
         }
         let git_tree_id = validate_git_object_id(id)?;
 
-        let locked_repo = self.lock_git_repo();
-        let git_tree = locked_repo
+        let git_repo = self.git_repo_for_read();
+        let git_tree = git_repo
             .find_object(git_tree_id)
             .map_err(|err| map_not_found_err(err, id))?
             .try_into_tree()
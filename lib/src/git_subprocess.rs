@@ -39,6 +39,9 @@ use crate::git_backend::GitBackend;
 use crate::ref_name::GitRefNameBuf;
 use crate::ref_name::RefNameBuf;
 use crate::ref_name::RemoteName;
+use crate::secret_provider::SecretProvider;
+use crate::secret_provider::SecretProviderError;
+use crate::settings::GitSettings;
 
 // This is not the minimum required version, that would be 2.29.0, which
 // introduced the `--no-write-fetch-head` option. However, that by itself
@@ -48,6 +51,9 @@ use crate::ref_name::RemoteName;
 // 2.40 still receives security patches (latest one was in Jan/2025)
 const MINIMUM_GIT_VERSION: &str = "2.40.4";
 
+/// Environment variable passing the remote password to the credential helper.
+const CREDENTIAL_ENV_VAR: &str = "JJ_GIT_CREDENTIAL_PASSWORD";
+
 /// Error originating by a Git subprocess
 #[derive(Error, Debug)]
 pub enum GitSubprocessError {
@@ -74,12 +80,15 @@ pub enum GitSubprocessError {
     UnsupportedGitOption(String),
     #[error("Git process failed: {0}")]
     External(String),
+    #[error("Failed to look up remote credentials")]
+    Secret(#[source] SecretProviderError),
 }
 
 /// Context for creating Git subprocesses
 pub(crate) struct GitSubprocessContext<'a> {
     git_dir: PathBuf,
     git_executable_path: &'a Path,
+    secret_provider: Option<&'a dyn SecretProvider>,
 }
 
 impl<'a> GitSubprocessContext<'a> {
@@ -87,14 +96,18 @@ impl<'a> GitSubprocessContext<'a> {
         Self {
             git_dir: git_dir.into(),
             git_executable_path,
+            secret_provider: None,
         }
     }
 
     pub(crate) fn from_git_backend(
         git_backend: &GitBackend,
-        git_executable_path: &'a Path,
+        git_settings: &'a GitSettings,
     ) -> Self {
-        Self::new(git_backend.git_repo_path(), git_executable_path)
+        Self {
+            secret_provider: git_settings.secret_provider.as_deref(),
+            ..Self::new(git_backend.git_repo_path(), &git_settings.executable_path)
+        }
     }
 
     /// Create the Git command
@@ -139,6 +152,37 @@ impl<'a> GitSubprocessContext<'a> {
         git_cmd
     }
 
+    /// Passes the credentials of the remote stored in the secret provider to
+    /// the Git command.
+    ///
+    /// The secret is passed through an environment variable, and a credential
+    /// helper printing it is appended, so helpers configured in Git take
+    /// precedence.
+    fn add_remote_credentials(
+        &self,
+        command: &mut Command,
+        remote_name: &RemoteName,
+    ) -> Result<(), GitSubprocessError> {
+        let Some(provider) = self.secret_provider else {
+            return Ok(());
+        };
+        let key = format!("remote/{}", remote_name.as_str());
+        let Some(password) = provider
+            .get_secret(&key)
+            .map_err(GitSubprocessError::Secret)?
+        else {
+            return Ok(());
+        };
+        command
+            .arg("-c")
+            .arg(format!(
+                "credential.helper=!f() {{ test \"$1\" = get && \
+                 printf 'password=%s\\n' \"${CREDENTIAL_ENV_VAR}\"; }}; f"
+            ))
+            .env(CREDENTIAL_ENV_VAR, password);
+        Ok(())
+    }
+
     /// Spawn the git command
     fn spawn_cmd(&self, mut git_cmd: Command) -> Result<Child, GitSubprocessError> {
         tracing::debug!(cmd = ?git_cmd, "spawning a git subprocess");
@@ -174,6 +218,7 @@ impl<'a> GitSubprocessContext<'a> {
             return Ok(None);
         }
         let mut command = self.create_command();
+        self.add_remote_credentials(&mut command, remote_name)?;
         command.stdout(Stdio::piped());
         // attempt to prune stale refs with --prune
         // --no-write-fetch-head ensures our request is invisible to other parties
@@ -265,6 +310,7 @@ impl<'a> GitSubprocessContext<'a> {
         callbacks: &mut RemoteCallbacks<'_>,
     ) -> Result<GitPushStats, GitSubprocessError> {
         let mut command = self.create_command();
+        self.add_remote_credentials(&mut command, remote_name)?;
         command.stdout(Stdio::piped());
         // Currently jj does not support commit hooks, so we prevent git from running
         // them
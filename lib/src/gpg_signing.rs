@@ -21,10 +21,14 @@ use std::io::Write as _;
 use std::process::Command;
 use std::process::ExitStatus;
 use std::process::Stdio;
+use std::sync::Arc;
 
 use thiserror::Error;
 
 use crate::config::ConfigGetError;
+use crate::secret_provider::SecretProvider;
+use crate::secret_provider::SecretProviderError;
+use crate::secret_provider::secret_provider_from_settings;
 use crate::settings::UserSettings;
 use crate::signing::SigStatus;
 use crate::signing::SignError;
@@ -38,7 +42,7 @@ use crate::signing::Verification;
 //  [GNUPG:] BADSIG <long keyid> <primary uid..>
 // in the output from --status-fd=1
 // Assume signature is invalid if none of the above was found
-fn parse_gpg_verify_output(
+pub(crate) fn parse_gpg_verify_output(
     output: &[u8],
     allow_expired_keys: bool,
 ) -> Result<Verification, SignError> {
@@ -77,10 +81,22 @@ fn parse_gpg_verify_output(
         .ok_or(SignError::InvalidSignatureFormat)
 }
 
-fn run_sign_command(command: &mut Command, input: &[u8]) -> Result<Vec<u8>, GpgError> {
+pub(crate) fn run_sign_command(
+    command: &mut Command,
+    passphrase: Option<&str>,
+    input: &[u8],
+) -> Result<Vec<u8>, GpgError> {
+    if passphrase.is_some() {
+        // GPG reads the first line of stdin as the passphrase, and the data
+        // after that.
+        command.args(["--pinentry-mode", "loopback", "--passphrase-fd", "0"]);
+    }
     tracing::info!(?command, "running GPG signing command");
     let process = command.stderr(Stdio::piped()).spawn()?;
-    let write_result = process.stdin.as_ref().unwrap().write_all(input);
+    let mut stdin = process.stdin.as_ref().unwrap();
+    let write_result = passphrase
+        .map_or(Ok(()), |passphrase| writeln!(stdin, "{passphrase}"))
+        .and_then(|()| stdin.write_all(input));
     let output = process.wait_with_output()?;
     tracing::info!(?command, ?output.status, "GPG signing command exited");
     if output.status.success() {
@@ -94,7 +110,7 @@ fn run_sign_command(command: &mut Command, input: &[u8]) -> Result<Vec<u8>, GpgE
     }
 }
 
-fn run_verify_command(command: &mut Command, input: &[u8]) -> Result<Vec<u8>, GpgError> {
+pub(crate) fn run_verify_command(command: &mut Command, input: &[u8]) -> Result<Vec<u8>, GpgError> {
     tracing::info!(?command, "running GPG signing command");
     let process = command.stderr(Stdio::null()).spawn()?;
     let write_result = process.stdin.as_ref().unwrap().write_all(input);
@@ -115,6 +131,7 @@ pub struct GpgBackend {
     allow_expired_keys: bool,
     extra_args: Vec<OsString>,
     default_key: String,
+    secret_provider: Option<Arc<dyn SecretProvider>>,
 }
 
 #[derive(Debug, Error)]
@@ -126,6 +143,18 @@ pub enum GpgError {
     },
     #[error("Failed to run GPG")]
     Io(#[from] std::io::Error),
+    #[error("Failed to look up signing key passphrase")]
+    Secret(#[from] SecretProviderError),
+}
+
+fn get_passphrase(
+    secret_provider: Option<&dyn SecretProvider>,
+    key: &str,
+) -> Result<Option<String>, GpgError> {
+    let Some(provider) = secret_provider else {
+        return Ok(None);
+    };
+    Ok(provider.get_secret(&format!("signing/{key}"))?)
 }
 
 impl From<GpgError> for SignError {
@@ -141,6 +170,7 @@ impl GpgBackend {
             allow_expired_keys,
             extra_args: vec![],
             default_key,
+            secret_provider: None,
         }
     }
 
@@ -150,11 +180,18 @@ impl GpgBackend {
         self
     }
 
+    /// Looks up the passphrase of the signing key in the given provider.
+    pub fn with_secret_provider(mut self, provider: Option<Arc<dyn SecretProvider>>) -> Self {
+        self.secret_provider = provider;
+        self
+    }
+
     pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
         let program = settings.get_string("signing.backends.gpg.program")?;
         let allow_expired_keys = settings.get_bool("signing.backends.gpg.allow-expired-keys")?;
         let default_key = settings.user_email().to_owned();
-        Ok(Self::new(program.into(), allow_expired_keys, default_key))
+        Ok(Self::new(program.into(), allow_expired_keys, default_key)
+            .with_secret_provider(secret_provider_from_settings(settings)?))
     }
 
     fn create_command(&self) -> Command {
@@ -186,8 +223,10 @@ impl SigningBackend for GpgBackend {
 
     fn sign(&self, data: &[u8], key: Option<&str>) -> Result<Vec<u8>, SignError> {
         let key = key.unwrap_or(&self.default_key);
+        let passphrase = get_passphrase(self.secret_provider.as_deref(), key)?;
         Ok(run_sign_command(
             self.create_command().args(["-abu", key]),
+            passphrase.as_deref(),
             data,
         )?)
     }
@@ -220,6 +259,7 @@ pub struct GpgsmBackend {
     allow_expired_keys: bool,
     extra_args: Vec<OsString>,
     default_key: String,
+    secret_provider: Option<Arc<dyn SecretProvider>>,
 }
 
 impl GpgsmBackend {
@@ -229,6 +269,7 @@ impl GpgsmBackend {
             allow_expired_keys,
             extra_args: vec![],
             default_key,
+            secret_provider: None,
         }
     }
 
@@ -238,11 +279,18 @@ impl GpgsmBackend {
         self
     }
 
+    /// Looks up the passphrase of the signing key in the given provider.
+    pub fn with_secret_provider(mut self, provider: Option<Arc<dyn SecretProvider>>) -> Self {
+        self.secret_provider = provider;
+        self
+    }
+
     pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
         let program = settings.get_string("signing.backends.gpgsm.program")?;
         let allow_expired_keys = settings.get_bool("signing.backends.gpgsm.allow-expired-keys")?;
         let default_key = settings.user_email().to_owned();
-        Ok(Self::new(program.into(), allow_expired_keys, default_key))
+        Ok(Self::new(program.into(), allow_expired_keys, default_key)
+            .with_secret_provider(secret_provider_from_settings(settings)?))
     }
 
     fn create_command(&self) -> Command {
@@ -274,8 +322,10 @@ impl SigningBackend for GpgsmBackend {
 
     fn sign(&self, data: &[u8], key: Option<&str>) -> Result<Vec<u8>, SignError> {
         let key = key.unwrap_or(&self.default_key);
+        let passphrase = get_passphrase(self.secret_provider.as_deref(), key)?;
         Ok(run_sign_command(
             self.create_command().args(["-abu", key]),
+            passphrase.as_deref(),
             data,
         )?)
     }
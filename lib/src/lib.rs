@@ -30,6 +30,7 @@ pub mod content_hash;
 
 pub mod absorb;
 pub mod annotate;
+pub mod api;
 pub mod backend;
 pub mod bisect;
 pub mod commit;
@@ -99,8 +100,10 @@ mod revset_parser;
 pub mod rewrite;
 #[cfg(feature = "testing")]
 pub mod secret_backend;
+pub mod secret_provider;
 pub mod settings;
 pub mod signing;
+pub mod sigstore_signing;
 pub mod tree_merge;
 // TODO: This file is mostly used for testing, whenever we no longer require it
 // in the lib it should be moved to the examples (e.g
@@ -124,6 +127,7 @@ pub mod union_find;
 pub mod view;
 pub mod working_copy;
 pub mod workspace;
+pub mod workspace_store;
 
 #[cfg(test)]
 mod tests {
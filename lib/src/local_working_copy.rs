@@ -73,7 +73,7 @@ use crate::conflicts::ConflictMaterializeOptions;
 use crate::conflicts::MIN_CONFLICT_MARKER_LEN;
 use crate::conflicts::MaterializedTreeValue;
 use crate::conflicts::choose_materialized_conflict_marker_len;
-use crate::conflicts::materialize_merge_result_to_bytes;
+use crate::conflicts::materialize_file_conflict_to_bytes;
 use crate::conflicts::materialize_tree_value;
 pub use crate::eol::EolConversionMode;
 use crate::eol::TargetEolStrategy;
@@ -743,6 +743,9 @@ pub struct TreeStateSettings {
     pub eol_conversion_mode: EolConversionMode,
     /// The fsmonitor (e.g. Watchman) to use, if any.
     pub fsmonitor_settings: FsmonitorSettings,
+    /// Whether to skip paths containing names reserved by the filesystem (such
+    /// as "CON" on Windows) instead of failing the checkout.
+    pub skip_reserved_names: bool,
 }
 
 impl TreeStateSettings {
@@ -752,6 +755,7 @@ impl TreeStateSettings {
             conflict_marker_style: user_settings.get("ui.conflict-marker-style")?,
             eol_conversion_mode: EolConversionMode::try_from_settings(user_settings)?,
             fsmonitor_settings: FsmonitorSettings::from_settings(user_settings)?,
+            skip_reserved_names: user_settings.get_bool("working-copy.skip-reserved-names")?,
         })
     }
 }
@@ -775,6 +779,7 @@ pub struct TreeState {
     conflict_marker_style: ConflictMarkerStyle,
     fsmonitor_settings: FsmonitorSettings,
     target_eol_strategy: TargetEolStrategy,
+    skip_reserved_names: bool,
 }
 
 #[derive(Debug, Error)]
@@ -834,6 +839,7 @@ impl TreeState {
             conflict_marker_style,
             eol_conversion_mode,
             ref fsmonitor_settings,
+            skip_reserved_names,
         }: &TreeStateSettings,
     ) -> Self {
         let tree_id = store.empty_merged_tree_id();
@@ -850,6 +856,7 @@ impl TreeState {
             conflict_marker_style,
             fsmonitor_settings: fsmonitor_settings.clone(),
             target_eol_strategy: TargetEolStrategy::new(eol_conversion_mode),
+            skip_reserved_names,
         }
     }
 
@@ -1023,6 +1030,7 @@ impl TreeState {
             progress,
             start_tracking_matcher,
             max_new_file_size,
+            ref max_new_file_size_overrides,
         } = options;
 
         let sparse_matcher = self.sparse_matcher();
@@ -1064,6 +1072,10 @@ impl TreeState {
                 error: OnceLock::new(),
                 progress,
                 max_new_file_size,
+                max_new_file_size_overrides: max_new_file_size_overrides
+                    .iter()
+                    .map(|(expression, size)| (expression.to_matcher(), *size))
+                    .collect(),
             };
             let directory_to_visit = DirectoryToVisit {
                 dir: RepoPathBuf::root(),
@@ -1211,6 +1223,7 @@ struct FileSnapshotter<'a> {
     error: OnceLock<SnapshotError>,
     progress: Option<&'a SnapshotProgress<'a>>,
     max_new_file_size: u64,
+    max_new_file_size_overrides: Vec<(Box<dyn Matcher>, u64)>,
 }
 
 impl FileSnapshotter<'_> {
@@ -1229,6 +1242,16 @@ impl FileSnapshotter<'_> {
         });
     }
 
+    /// Returns the size of the largest new file that may be tracked at `path`.
+    fn max_new_file_size(&self, path: &RepoPath) -> u64 {
+        self.max_new_file_size_overrides
+            .iter()
+            .filter(|(matcher, _)| matcher.matches(path))
+            .map(|&(_, size)| size)
+            .max()
+            .unwrap_or(self.max_new_file_size)
+    }
+
     /// Extracts the result of the snapshot.
     fn into_result(self) -> Result<(), SnapshotError> {
         match self.error.into_inner() {
@@ -1352,11 +1375,12 @@ impl FileSnapshotter<'_> {
                     message: format!("Failed to stat file {}", entry.path().display()),
                     err: err.into(),
                 })?;
-                if maybe_current_file_state.is_none() && metadata.len() > self.max_new_file_size {
+                let max_new_file_size = self.max_new_file_size(&path);
+                if maybe_current_file_state.is_none() && metadata.len() > max_new_file_size {
                     // Leave the large file untracked
                     let reason = UntrackedReason::FileTooLarge {
                         size: metadata.len(),
-                        max_size: self.max_new_file_size,
+                        max_size: max_new_file_size,
                     };
                     self.untracked_paths_tx.send((path, reason)).ok();
                     Ok(None)
@@ -1854,6 +1878,7 @@ impl TreeState {
             added_files: added_stats.added_files,
             removed_files: removed_stats.removed_files,
             skipped_files: added_stats.skipped_files,
+            skipped_reserved_paths: added_stats.skipped_reserved_paths,
         })
     }
 
@@ -1870,6 +1895,7 @@ impl TreeState {
             added_files: 0,
             removed_files: 0,
             skipped_files: 0,
+            skipped_reserved_paths: vec![],
         };
         let mut changed_file_states = Vec::new();
         let mut deleted_files = HashSet::new();
@@ -1909,6 +1935,20 @@ impl TreeState {
                 continue;
             }
 
+            // Paths with reserved names can't be checked out. Skip them unless
+            // told to fail, in which case create_parent_dirs() will report the
+            // invalid path component.
+            if self.skip_reserved_names
+                && path
+                    .components()
+                    .any(RepoPathComponent::is_reserved_fs_name)
+            {
+                changed_file_states.push((path.clone(), FileState::placeholder()));
+                stats.skipped_files += 1;
+                stats.skipped_reserved_paths.push(path);
+                continue;
+            }
+
             // Create parent directories no matter if after.is_present(). This
             // ensures that the path never traverses symlinks.
             let Some(disk_path) = create_parent_dirs(&self.working_copy_path, &path)? else {
@@ -1965,7 +2005,7 @@ impl TreeState {
                         marker_len: Some(conflict_marker_len),
                         merge: self.store.merge_options().clone(),
                     };
-                    let contents = materialize_merge_result_to_bytes(&file.contents, &options);
+                    let contents = materialize_file_conflict_to_bytes(&self.store, &file, &options);
                     let mut file_state = self
                         .write_conflict(&disk_path, &contents, file.executable.unwrap_or(false))
                         .await?;
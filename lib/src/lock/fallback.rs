@@ -14,12 +14,15 @@
 
 use std::fs::File;
 use std::fs::OpenOptions;
+use std::path::Path;
 use std::path::PathBuf;
 use std::time::Duration;
 
 use tracing::instrument;
 
 use super::FileLockError;
+use super::LockOwner;
+use super::break_lock;
 
 pub struct FileLock {
     path: PathBuf,
@@ -55,8 +58,6 @@ impl Iterator for BackoffIterator {
     }
 }
 
-// Suppress warning on platforms where specialized lock impl is available
-#[cfg_attr(all(unix, not(test)), expect(dead_code))]
 impl FileLock {
     pub fn lock(path: PathBuf) -> Result<Self, FileLockError> {
         let mut options = OpenOptions::new();
@@ -66,6 +67,14 @@ impl FileLock {
         loop {
             match options.open(&path) {
                 Ok(file) => {
+                    // The owner is informational. A lock file without owner
+                    // metadata is still a valid lock.
+                    LockOwner::current()
+                        .write_to(&file)
+                        .inspect_err(|err| {
+                            tracing::warn!(?err, ?path, "Failed to write lock owner");
+                        })
+                        .ok();
                     return Ok(Self { path, _file: file });
                 }
                 Err(err)
@@ -73,6 +82,9 @@ impl FileLock {
                         || (cfg!(windows)
                             && err.kind() == std::io::ErrorKind::PermissionDenied) =>
                 {
+                    if remove_stale_lock(&path) {
+                        continue;
+                    }
                     if let Some(duration) = backoff_iterator.next() {
                         std::thread::sleep(duration);
                     } else {
@@ -95,6 +107,21 @@ impl FileLock {
     }
 }
 
+/// Removes the lock file at `path` if it was left behind by a process on this
+/// host that no longer exists. Returns `true` if the lock file was removed.
+fn remove_stale_lock(path: &Path) -> bool {
+    let Ok(Some(owner)) = LockOwner::read(path) else {
+        return false;
+    };
+    if owner.is_stale() != Some(true) {
+        return false;
+    }
+    tracing::warn!(?path, ?owner, "Removing stale lock file");
+    break_lock(path, Some(&owner))
+        .inspect_err(|err| tracing::warn!(?err, ?path, "Failed to remove stale lock file"))
+        .unwrap_or(false)
+}
+
 impl Drop for FileLock {
     #[instrument(skip_all)]
     fn drop(&mut self) {
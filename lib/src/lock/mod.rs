@@ -18,8 +18,12 @@ mod fallback;
 #[cfg(unix)]
 mod unix;
 
+use std::fs;
 use std::io;
+use std::io::Write as _;
+use std::path::Path;
 use std::path::PathBuf;
+use std::time::SystemTime;
 
 use thiserror::Error;
 
@@ -37,6 +41,194 @@ pub struct FileLockError {
     pub err: io::Error,
 }
 
+/// Process holding a lock.
+///
+/// The owner is recorded in the lock file when the lock is acquired, so that
+/// locks left behind by crashed processes can be detected. This is important
+/// on network filesystems, where locks aren't released automatically.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LockOwner {
+    /// Name of the host the process runs on.
+    pub hostname: String,
+    /// Process ID.
+    pub pid: u32,
+    /// When the lock was acquired, in seconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+impl LockOwner {
+    /// Returns the owner representing the current process.
+    pub fn current() -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self {
+            hostname: current_hostname(),
+            pid: std::process::id(),
+            timestamp,
+        }
+    }
+
+    /// Reads the owner recorded in the lock file at `path`. Returns `None` if
+    /// the file doesn't exist or has no valid owner metadata, which is the
+    /// case while the lock is being acquired.
+    pub fn read(path: &Path) -> io::Result<Option<Self>> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) if err.kind() == io::ErrorKind::InvalidData => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        Ok(Self::parse(&text))
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        let mut hostname = None;
+        let mut pid = None;
+        let mut timestamp = None;
+        for line in text.lines() {
+            match line.split_once('=')? {
+                ("hostname", value) => hostname = Some(value.to_owned()),
+                ("pid", value) => pid = Some(value.parse().ok()?),
+                ("timestamp", value) => timestamp = Some(value.parse().ok()?),
+                _ => {}
+            }
+        }
+        Some(Self {
+            hostname: hostname?,
+            pid: pid?,
+            timestamp: timestamp?,
+        })
+    }
+
+    fn write_to(&self, mut file: &fs::File) -> io::Result<()> {
+        let Self {
+            hostname,
+            pid,
+            timestamp,
+        } = self;
+        file.write_all(
+            format!("hostname={hostname}\npid={pid}\ntimestamp={timestamp}\n").as_bytes(),
+        )
+    }
+
+    /// Returns `true` if the owner process has exited, `false` if it's still
+    /// running, or `None` if that can't be determined because the process runs
+    /// on another host.
+    pub fn is_stale(&self) -> Option<bool> {
+        if self.hostname != current_hostname() {
+            return None;
+        }
+        process_exists(self.pid).map(|exists| !exists)
+    }
+}
+
+/// Removes the lock file at `path` if it's still owned by `owner`, which is
+/// `None` for a lock file without owner metadata. Returns `true` if the lock
+/// file was removed.
+///
+/// Several processes may find the same stale lock at once, and one of them
+/// may break it and acquire a new lock before the others try to remove it.
+/// To not remove the new lock, the processes breaking locks are serialized by
+/// an exclusively created `<path>.break` file, and the lock file is renamed to
+/// a unique name and checked again before it's removed.
+pub fn break_lock(path: &Path, owner: Option<&LockOwner>) -> io::Result<bool> {
+    let break_path = path_with_suffix(path, ".break");
+    let break_file = match fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&break_path)
+    {
+        Ok(file) => file,
+        // Another process is breaking the lock. The break file is only held
+        // for a few file operations, so it can be removed if its owner has
+        // crashed.
+        Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+            if let Some(break_owner) = LockOwner::read(&break_path)?
+                && break_owner.is_stale() == Some(true)
+            {
+                take_over(&break_path, Some(&break_owner))?;
+            }
+            return Ok(false);
+        }
+        Err(err) => return Err(err),
+    };
+    LockOwner::current().write_to(&break_file).ok();
+    let result = take_over(path, owner);
+    drop(break_file);
+    fs::remove_file(&break_path)?;
+    result
+}
+
+/// Moves the lock file at `path` out of the way if it's owned by `owner`, and
+/// removes it. If the lock file was replaced in the meantime, it's restored.
+fn take_over(path: &Path, owner: Option<&LockOwner>) -> io::Result<bool> {
+    if LockOwner::read(path)?.as_ref() != owner {
+        return Ok(false);
+    }
+    let unique_suffix = format!(
+        ".broken-{}-{:016x}",
+        std::process::id(),
+        rand::random::<u64>()
+    );
+    let broken_path = path_with_suffix(path, &unique_suffix);
+    match fs::rename(path, &broken_path) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(err) => return Err(err),
+    }
+    // Renaming is atomic, so only the file we moved is checked from here on.
+    if LockOwner::read(&broken_path)?.as_ref() != owner {
+        // Put back the lock which was acquired after we read the owner. This
+        // fails only if yet another process has created a lock file in the
+        // meantime.
+        let result = fs::hard_link(&broken_path, path);
+        fs::remove_file(&broken_path)?;
+        result?;
+        return Ok(false);
+    }
+    fs::remove_file(&broken_path)?;
+    Ok(true)
+}
+
+fn path_with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_owned();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+fn current_hostname() -> String {
+    #[cfg(unix)]
+    {
+        rustix::system::uname()
+            .nodename()
+            .to_string_lossy()
+            .into_owned()
+    }
+    #[cfg(not(unix))]
+    {
+        std::env::var("COMPUTERNAME").unwrap_or_default()
+    }
+}
+
+fn process_exists(pid: u32) -> Option<bool> {
+    #[cfg(unix)]
+    {
+        let pid = rustix::process::Pid::from_raw(pid.try_into().ok()?)?;
+        match rustix::process::test_kill_process(pid) {
+            Ok(()) => Some(true),
+            Err(rustix::io::Errno::SRCH) => Some(false),
+            // The process exists, but is owned by another user.
+            Err(rustix::io::Errno::PERM) => Some(true),
+            Err(_) => None,
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        (pid == std::process::id()).then_some(true)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::cmp::max;
@@ -85,4 +277,86 @@ mod tests {
         let value = u32::from_le_bytes(data.try_into().unwrap());
         assert_eq!(value, num_threads as u32);
     }
+
+    #[test_case(FileLock::lock)]
+    #[cfg_attr(unix, test_case(fallback::FileLock::lock))]
+    fn lock_records_owner<T>(lock_fn: fn(PathBuf) -> Result<T, FileLockError>) {
+        let temp_dir = new_temp_dir();
+        let lock_path = temp_dir.path().join("test.lock");
+        let _lock = lock_fn(lock_path.clone()).unwrap();
+        let owner = LockOwner::read(&lock_path).unwrap().unwrap();
+        assert_eq!(owner.pid, std::process::id());
+        assert_eq!(owner.is_stale(), Some(false));
+    }
+
+    #[test]
+    fn lock_owner_on_other_host() {
+        let owner = LockOwner {
+            hostname: "some other host".to_owned(),
+            ..LockOwner::current()
+        };
+        assert_eq!(owner.is_stale(), None);
+        assert_eq!(LockOwner::parse("pid=1\n"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn lock_file_removes_stale_lock() {
+        let temp_dir = new_temp_dir();
+        let lock_path = temp_dir.path().join("test.lock");
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let pid = child.id();
+        child.wait().unwrap();
+        let owner = LockOwner {
+            pid,
+            ..LockOwner::current()
+        };
+        owner
+            .write_to(&fs::File::create(&lock_path).unwrap())
+            .unwrap();
+        assert_eq!(LockOwner::read(&lock_path).unwrap(), Some(owner.clone()));
+        assert_eq!(owner.is_stale(), Some(true));
+
+        let _lock = fallback::FileLock::lock(lock_path.clone()).unwrap();
+        let owner = LockOwner::read(&lock_path).unwrap().unwrap();
+        assert_eq!(owner.pid, std::process::id());
+    }
+
+    #[test]
+    fn break_lock_keeps_new_lock() {
+        let temp_dir = new_temp_dir();
+        let lock_path = temp_dir.path().join("test.lock");
+        let stale_owner = LockOwner {
+            hostname: "some other host".to_owned(),
+            ..LockOwner::current()
+        };
+
+        // The lock was broken and acquired by another process after the stale
+        // owner was read.
+        let _lock = fallback::FileLock::lock(lock_path.clone()).unwrap();
+        assert!(!break_lock(&lock_path, Some(&stale_owner)).unwrap());
+        assert!(!break_lock(&lock_path, None).unwrap());
+        let owner = LockOwner::read(&lock_path).unwrap().unwrap();
+        assert_eq!(owner.pid, std::process::id());
+
+        // Only the lock file remains.
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn break_lock_removes_owned_lock() {
+        let temp_dir = new_temp_dir();
+        let lock_path = temp_dir.path().join("test.lock");
+        let owner = LockOwner {
+            hostname: "some other host".to_owned(),
+            ..LockOwner::current()
+        };
+        owner
+            .write_to(&fs::File::create(&lock_path).unwrap())
+            .unwrap();
+        assert!(break_lock(&lock_path, Some(&owner)).unwrap());
+        assert!(!lock_path.exists());
+        assert!(!break_lock(&lock_path, Some(&owner)).unwrap());
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+    }
 }
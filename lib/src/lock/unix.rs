@@ -15,27 +15,103 @@
 #![expect(missing_docs)]
 
 use std::fs::File;
+use std::fs::OpenOptions;
+use std::path::Path;
 use std::path::PathBuf;
 
 use rustix::fs::FlockOperation;
 use tracing::instrument;
 
 use super::FileLockError;
+use super::LockOwner;
+use super::fallback;
 
 pub struct FileLock {
-    path: PathBuf,
-    file: File,
+    _inner: FileLockImpl,
+}
+
+enum FileLockImpl {
+    Flock { _lock: FlockLock },
+    // flock() isn't reliable on network filesystems. It may be a no-op, or
+    // the lock may never be released if the client disappears. Exclusively
+    // created lock files work there, and stale ones can be detected by the
+    // owner metadata.
+    //
+    // Older versions of jj only use flock(), which doesn't see these lock
+    // files. Repos on network filesystems shouldn't be used by older and newer
+    // versions at the same time.
+    LockFile { _lock: fallback::FileLock },
 }
 
 impl FileLock {
     pub fn lock(path: PathBuf) -> Result<Self, FileLockError> {
+        let inner = if path.parent().is_some_and(is_network_filesystem) {
+            FileLockImpl::LockFile {
+                _lock: fallback::FileLock::lock(path)?,
+            }
+        } else {
+            FileLockImpl::Flock {
+                _lock: FlockLock::lock(path)?,
+            }
+        };
+        Ok(Self { _inner: inner })
+    }
+}
+
+/// Returns `true` if the directory is on NFS or SMB.
+fn is_network_filesystem(dir: &Path) -> bool {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        const NFS_SUPER_MAGIC: u32 = 0x6969;
+        const SMB_SUPER_MAGIC: u32 = 0x517b;
+        const CIFS_SUPER_MAGIC: u32 = 0xff534d42;
+        const SMB2_SUPER_MAGIC: u32 = 0xfe534d42;
+        rustix::fs::statfs(dir).is_ok_and(|stat| {
+            matches!(
+                stat.f_type as u32,
+                NFS_SUPER_MAGIC | SMB_SUPER_MAGIC | CIFS_SUPER_MAGIC | SMB2_SUPER_MAGIC
+            )
+        })
+    }
+    #[cfg(target_os = "macos")]
+    {
+        rustix::fs::statfs(dir).is_ok_and(|stat| {
+            let name: Vec<u8> = stat
+                .f_fstypename
+                .iter()
+                .take_while(|&&c| c != 0)
+                .map(|&c| c as u8)
+                .collect();
+            matches!(name.as_slice(), b"nfs" | b"smbfs")
+        })
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+    {
+        let _ = dir;
+        false
+    }
+}
+
+struct FlockLock {
+    path: PathBuf,
+    file: File,
+}
+
+impl FlockLock {
+    fn lock(path: PathBuf) -> Result<Self, FileLockError> {
         loop {
-            // Create lockfile, or open pre-existing one
-            let file = File::create(&path).map_err(|err| FileLockError {
-                message: "Failed to open lock file",
-                path: path.clone(),
-                err,
-            })?;
+            // Create lockfile, or open pre-existing one. Don't truncate it
+            // since the owner metadata belongs to the current holder.
+            let file = OpenOptions::new()
+                .create(true)
+                .write(true)
+                .truncate(false)
+                .open(&path)
+                .map_err(|err| FileLockError {
+                    message: "Failed to open lock file",
+                    path: path.clone(),
+                    err,
+                })?;
             // If the lock was already held, wait for it to be released
             rustix::fs::flock(&file, FlockOperation::LockExclusive).map_err(|errno| {
                 FileLockError {
@@ -72,12 +148,18 @@ impl FileLock {
                 }
             }
 
+            // The owner is informational. Waiters block on flock() regardless.
+            _ = file.set_len(0);
+            LockOwner::current()
+                .write_to(&file)
+                .inspect_err(|err| tracing::warn!(?err, ?path, "Failed to write lock owner"))
+                .ok();
             return Ok(Self { path, file });
         }
     }
 }
 
-impl Drop for FileLock {
+impl Drop for FlockLock {
     #[instrument(skip_all)]
     fn drop(&mut self) {
         // Removing the file isn't strictly necessary, but reduces confusion.
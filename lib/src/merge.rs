@@ -77,7 +77,7 @@ impl<T: Eq> Diff<T> {
 }
 
 /// Whether to resolve conflict that makes the same change at all sides.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[derive(ContentHash, Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum SameChange {
     /// Leaves same-change conflict unresolved.
@@ -662,17 +662,12 @@ where
             Some(_) => Err(()),
         });
         if let Ok(tree_id_merge) = tree_id_merge {
-            Ok(Some(
-                tree_id_merge
-                    .try_map_async(async |id| {
-                        if let Some(id) = id {
-                            store.get_tree_async(dir.to_owned(), id).await
-                        } else {
-                            Ok(Tree::empty(store.clone(), dir.to_owned()))
-                        }
-                    })
-                    .await?,
-            ))
+            let ids = tree_id_merge.iter().flatten().copied().collect_vec();
+            let mut trees = store.get_trees_async(dir, &ids).await?.into_iter();
+            Ok(Some(tree_id_merge.map(|id| match id {
+                Some(_) => trees.next().unwrap(),
+                None => Tree::empty(store.clone(), dir.to_owned()),
+            })))
         } else {
             Ok(None)
         }
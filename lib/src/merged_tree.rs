@@ -27,8 +27,8 @@ use std::vec;
 use either::Either;
 use futures::Stream;
 use futures::StreamExt as _;
+use futures::channel::oneshot;
 use futures::future::BoxFuture;
-use futures::future::try_join;
 use futures::stream::BoxStream;
 use itertools::EitherOrBoth;
 use itertools::Itertools as _;
@@ -165,20 +165,9 @@ impl MergedTree {
                 if !merge.is_tree() {
                     return Ok(None);
                 }
-                let trees = merge
-                    .try_map_async(async |value| match value {
-                        Some(TreeValue::Tree(sub_tree_id)) => {
-                            let subdir = self.dir().join(name);
-                            self.store().get_tree_async(subdir, sub_tree_id).await
-                        }
-                        Some(_) => unreachable!(),
-                        None => {
-                            let subdir = self.dir().join(name);
-                            Ok(Tree::empty(self.store().clone(), subdir))
-                        }
-                    })
-                    .await?;
-                Ok(Some(Self { trees }))
+                let subdir = self.dir().join(name);
+                let trees = merge.to_tree_merge(self.store(), &subdir).await?;
+                Ok(trees.map(|trees| Self { trees }))
             }
         }
     }
@@ -257,11 +246,23 @@ impl MergedTree {
     ) -> TreeDiffStream<'matcher> {
         let concurrency = self.store().concurrency();
         if concurrency <= 1 {
-            Box::pin(futures::stream::iter(TreeDiffIterator::new(
-                &self.trees,
-                &other.trees,
-                matcher,
-            )))
+            // Reads of the backend complete synchronously, so trees have to
+            // be read on other threads to diff subdirectories in parallel. If
+            // we're already on a thread-pool thread, waiting for the other
+            // threads could deadlock.
+            if rayon::current_num_threads() > 1 && rayon::current_thread_index().is_none() {
+                Box::pin(TreeDiffStreamImpl::new_parallel(
+                    &self.trees,
+                    &other.trees,
+                    matcher,
+                ))
+            } else {
+                Box::pin(futures::stream::iter(TreeDiffIterator::new(
+                    &self.trees,
+                    &other.trees,
+                    matcher,
+                )))
+            }
         } else {
             Box::pin(TreeDiffStreamImpl::new(
                 &self.trees,
@@ -602,6 +603,7 @@ impl<'matcher> TreeDiffIterator<'matcher> {
 }
 
 impl TreeDiffDir {
+    #[tracing::instrument(level = "debug", skip_all)]
     fn from_trees(
         dir: &RepoPath,
         trees1: &Merge<Tree>,
@@ -713,6 +715,10 @@ pub struct TreeDiffStreamImpl<'matcher> {
     /// limit because we have a file item that's blocked by pending subdirectory
     /// items.
     max_queued_items: usize,
+    /// Whether to read trees on the rayon thread pool. This is useful if the
+    /// backend reads synchronously, in which case the trees would otherwise be
+    /// read one by one.
+    read_in_parallel: bool,
 }
 
 impl<'matcher> TreeDiffStreamImpl<'matcher> {
@@ -723,6 +729,31 @@ impl<'matcher> TreeDiffStreamImpl<'matcher> {
         trees2: &Merge<Tree>,
         matcher: &'matcher dyn Matcher,
         max_concurrent_reads: usize,
+    ) -> Self {
+        Self::new_impl(trees1, trees2, matcher, max_concurrent_reads, false)
+    }
+
+    /// Creates a iterator over the differences between two trees, which reads
+    /// the trees on the rayon thread pool. Up to as many subdirectories as the
+    /// pool has threads are read in parallel.
+    ///
+    /// The stream must not be polled from a thread of the pool. Otherwise, it
+    /// could deadlock waiting for the reads.
+    pub fn new_parallel(
+        trees1: &Merge<Tree>,
+        trees2: &Merge<Tree>,
+        matcher: &'matcher dyn Matcher,
+    ) -> Self {
+        let max_concurrent_reads = rayon::current_num_threads();
+        Self::new_impl(trees1, trees2, matcher, max_concurrent_reads, true)
+    }
+
+    fn new_impl(
+        trees1: &Merge<Tree>,
+        trees2: &Merge<Tree>,
+        matcher: &'matcher dyn Matcher,
+        max_concurrent_reads: usize,
+        read_in_parallel: bool,
     ) -> Self {
         assert!(Arc::ptr_eq(trees1.first().store(), trees2.first().store()));
         let mut stream = Self {
@@ -732,37 +763,49 @@ impl<'matcher> TreeDiffStreamImpl<'matcher> {
             pending_trees: BTreeMap::new(),
             max_concurrent_reads,
             max_queued_items: 10000,
+            read_in_parallel,
         };
         stream.add_dir_diff_items(RepoPath::root(), trees1, trees2);
         stream
     }
 
-    async fn single_tree(
-        store: &Arc<Store>,
-        dir: RepoPathBuf,
-        value: Option<&TreeValue>,
-    ) -> BackendResult<Tree> {
-        match value {
-            Some(TreeValue::Tree(tree_id)) => store.get_tree_async(dir, tree_id).await,
-            _ => Ok(Tree::empty(store.clone(), dir.clone())),
-        }
-    }
-
     /// Gets the given trees if `values` are trees, otherwise an empty tree.
+    /// The trees of both sides are read in a single batch.
     async fn trees(
         store: Arc<Store>,
         dir: RepoPathBuf,
-        values: MergedTreeValue,
-    ) -> BackendResult<Merge<Tree>> {
-        if values.is_tree() {
-            values
-                .try_map_async(|value| Self::single_tree(&store, dir.clone(), value.as_ref()))
-                .await
-        } else {
-            Ok(Merge::resolved(Tree::empty(store, dir)))
+        before: MergedTreeValue,
+        after: MergedTreeValue,
+    ) -> BackendResult<(Merge<Tree>, Merge<Tree>)> {
+        fn tree_ids(values: &MergedTreeValue) -> Option<Merge<Option<&TreeId>>> {
+            values.is_tree().then(|| {
+                values.map(|value| match value {
+                    Some(TreeValue::Tree(id)) => Some(id),
+                    _ => None,
+                })
+            })
         }
+        let before_ids = tree_ids(&before);
+        let after_ids = tree_ids(&after);
+        let ids = [&before_ids, &after_ids]
+            .into_iter()
+            .flatten()
+            .flat_map(|ids| ids.iter().flatten().copied())
+            .collect_vec();
+        let mut trees = store.get_trees_async(&dir, &ids).await?.into_iter();
+        let mut to_trees = |ids: Option<Merge<Option<&TreeId>>>| match ids {
+            Some(ids) => ids.map(|id| match id {
+                Some(_) => trees.next().unwrap(),
+                None => Tree::empty(store.clone(), dir.clone()),
+            }),
+            None => Merge::resolved(Tree::empty(store.clone(), dir.clone())),
+        };
+        let before_trees = to_trees(before_ids);
+        let after_trees = to_trees(after_ids);
+        Ok((before_trees, after_trees))
     }
 
+    #[tracing::instrument(level = "debug", skip_all)]
     fn add_dir_diff_items(&mut self, dir: &RepoPath, trees1: &Merge<Tree>, trees2: &Merge<Tree>) {
         for (basename, diff) in merged_tree_entry_diff(trees1, trees2) {
             let path = dir.join(basename);
@@ -791,13 +834,26 @@ impl<'matcher> TreeDiffStreamImpl<'matcher> {
 
             // If the path was a tree on either side of the diff, read those trees.
             if tree_matches {
-                let before_tree_future =
-                    Self::trees(self.store.clone(), path.clone(), before.cloned());
-                let after_tree_future =
-                    Self::trees(self.store.clone(), path.clone(), after.cloned());
-                let both_trees_future = try_join(before_tree_future, after_tree_future);
-                self.pending_trees
-                    .insert(path.clone(), Box::pin(both_trees_future));
+                let store = self.store.clone();
+                let (before, after) = (before.cloned(), after.cloned());
+                let dir = path.clone();
+                let read_trees = move || Self::trees(store, dir, before, after);
+                if self.read_in_parallel {
+                    // The read starts when the future is first polled, so the
+                    // number of reads in flight is limited by
+                    // `max_concurrent_reads`.
+                    let future = async {
+                        let (sender, receiver) = oneshot::channel();
+                        rayon::spawn(move || {
+                            sender.send(read_trees().block_on()).ok();
+                        });
+                        receiver.await.expect("tree read task shouldn't be dropped")
+                    };
+                    self.pending_trees.insert(path.clone(), Box::pin(future));
+                } else {
+                    self.pending_trees
+                        .insert(path.clone(), Box::pin(read_trees()));
+                }
             }
 
             if before.is_file_like() || after.is_file_like() {
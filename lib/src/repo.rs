@@ -143,7 +143,8 @@ pub trait Repo {
 pub struct ReadonlyRepo {
     loader: RepoLoader,
     operation: Operation,
-    index: Box<dyn ReadonlyIndex>,
+    /// Index at the operation, loaded on first access.
+    index: OnceCell<Box<dyn ReadonlyIndex>>,
     change_id_index: OnceCell<Box<dyn ChangeIdIndex>>,
     // TODO: This should eventually become part of the index and not be stored fully in memory.
     view: View,
@@ -262,7 +263,7 @@ impl ReadonlyRepo {
         Ok(Arc::new(Self {
             loader,
             operation: root_operation,
-            index,
+            index: OnceCell::with_value(index),
             change_id_index: OnceCell::new(),
             view: root_view,
         }))
@@ -284,8 +285,24 @@ impl ReadonlyRepo {
         &self.view
     }
 
+    /// Returns the index at this operation, loading it if not loaded yet.
+    ///
+    /// The index isn't loaded yet if the repo was loaded by
+    /// [`RepoLoader::load_at_without_index()`].
+    pub fn load_index(&self) -> Result<&dyn ReadonlyIndex, IndexReadError> {
+        let index = self.index.get_or_try_init(|| {
+            self.index_store()
+                .get_index_at_op(&self.operation, self.store())
+        })?;
+        Ok(index.as_ref())
+    }
+
+    /// Returns the index at this operation, loading it if not loaded yet.
+    ///
+    /// Panics if the index can't be loaded. Call [`Self::load_index()`] first
+    /// to handle the error.
     pub fn readonly_index(&self) -> &dyn ReadonlyIndex {
-        self.index.as_ref()
+        self.load_index().expect("failed to load index")
     }
 
     fn change_id_index(&self) -> &dyn ChangeIdIndex {
@@ -309,6 +326,10 @@ impl ReadonlyRepo {
         self.loader.settings()
     }
 
+    /// Starts a transaction on top of this operation.
+    ///
+    /// The transaction updates the index, so this panics if the index can't be
+    /// loaded. Call [`Self::load_index()`] first to handle the error.
     pub fn start_transaction(self: &Arc<Self>) -> Transaction {
         let mut_repo = MutableRepo::new(self.clone(), self.readonly_index(), &self.view);
         Transaction::new(mut_repo, self.settings())
@@ -741,11 +762,29 @@ impl RepoLoader {
             |op_heads| self._resolve_op_heads(op_heads),
         )?;
         let view = op.view()?;
-        self._finish_load(op, view)
+        let repo = self._finish_load(op, view)?;
+        repo.load_index()?;
+        Ok(repo)
     }
 
     #[instrument(skip(self))]
     pub fn load_at(&self, op: &Operation) -> Result<Arc<ReadonlyRepo>, RepoLoaderError> {
+        let repo = self.load_at_without_index(op)?;
+        repo.load_index()?;
+        Ok(repo)
+    }
+
+    /// Loads the repo at `op` without loading the index, which is loaded on
+    /// first access instead.
+    ///
+    /// Use this if the caller might not need the index, so it can skip the
+    /// cost of reading (or building) it. Call [`ReadonlyRepo::load_index()`]
+    /// before accessing the index to handle read errors.
+    #[instrument(skip(self))]
+    pub fn load_at_without_index(
+        &self,
+        op: &Operation,
+    ) -> Result<Arc<ReadonlyRepo>, RepoLoaderError> {
         let view = op.view()?;
         self._finish_load(op.clone(), view)
     }
@@ -759,7 +798,7 @@ impl RepoLoader {
         let repo = ReadonlyRepo {
             loader: self.clone(),
             operation,
-            index,
+            index: OnceCell::with_value(index),
             change_id_index: OnceCell::new(),
             view,
         };
@@ -823,11 +862,10 @@ impl RepoLoader {
         operation: Operation,
         view: View,
     ) -> Result<Arc<ReadonlyRepo>, RepoLoaderError> {
-        let index = self.index_store.get_index_at_op(&operation, &self.store)?;
         let repo = ReadonlyRepo {
             loader: self.clone(),
             operation,
-            index,
+            index: OnceCell::new(),
             change_id_index: OnceCell::new(),
             view,
         };
@@ -947,6 +985,11 @@ impl MutableRepo {
         self.commit_predecessors.insert(id, predecessors);
     }
 
+    /// Returns the predecessors of the commits written in this transaction.
+    pub fn commit_predecessors(&self) -> &BTreeMap<CommitId, Vec<CommitId>> {
+        &self.commit_predecessors
+    }
+
     /// Record a commit as having been rewritten to another commit in this
     /// transaction.
     ///
@@ -88,18 +88,32 @@ impl RepoPathComponent {
 
     /// Returns a normal filesystem entry name if this path component is valid
     /// as a file/directory name.
+    ///
+    /// On Windows, reserved device names such as "CON" or "nul.txt" are also
+    /// rejected since they don't refer to regular files.
     pub fn to_fs_name(&self) -> Result<&str, InvalidRepoPathComponentError> {
         let mut components = Path::new(&self.value).components().fuse();
         match (components.next(), components.next()) {
             // Trailing "." can be normalized by Path::components(), so compare
             // component name. e.g. "foo\." (on Windows) should be rejected.
-            (Some(Component::Normal(name)), None) if name == &self.value => Ok(&self.value),
-            // e.g. ".", "..", "foo\bar" (on Windows)
+            (Some(Component::Normal(name)), None)
+                if name == &self.value
+                    && !(cfg!(windows) && is_windows_reserved_name(&self.value)) =>
+            {
+                Ok(&self.value)
+            }
+            // e.g. ".", "..", "foo\bar" (on Windows), "CON" (on Windows)
             _ => Err(InvalidRepoPathComponentError {
                 component: self.value.into(),
             }),
         }
     }
+
+    /// Returns true if this path component is a reserved device name on the
+    /// current platform, such as "CON" or "nul.txt" on Windows.
+    pub fn is_reserved_fs_name(&self) -> bool {
+        cfg!(windows) && is_windows_reserved_name(&self.value)
+    }
 }
 
 impl Debug for RepoPathComponent {
@@ -569,6 +583,29 @@ pub struct FsPathParseError {
     pub source: RelativePathParseError,
 }
 
+/// Returns true if `name` is a reserved device name on Windows. Extensions
+/// and trailing spaces are ignored by Windows, so "nul.txt" and "CON " are
+/// reserved, too.
+fn is_windows_reserved_name(name: &str) -> bool {
+    let stem = name.split('.').next().unwrap().trim_end_matches(' ');
+    let is_reserved_stem = |prefix: &str, suffixes: &[&str]| {
+        stem.len() >= prefix.len()
+            && stem.is_char_boundary(prefix.len())
+            && stem[..prefix.len()].eq_ignore_ascii_case(prefix)
+            && suffixes.contains(&&stem[prefix.len()..])
+    };
+    const PORT_NUMBERS: &[&str] = &[
+        "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "\u{b9}", "\u{b2}", "\u{b3}",
+    ];
+    ["CON", "PRN", "AUX", "NUL"]
+        .iter()
+        .any(|device| is_reserved_stem(device, &[""]))
+        || is_reserved_stem("CONIN$", &[""])
+        || is_reserved_stem("CONOUT$", &[""])
+        || is_reserved_stem("COM", PORT_NUMBERS)
+        || is_reserved_stem("LPT", PORT_NUMBERS)
+}
+
 fn is_valid_repo_path_component_str(value: &str) -> bool {
     !value.is_empty() && !value.contains('/')
 }
@@ -1018,6 +1055,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_is_windows_reserved_name() {
+        assert!(is_windows_reserved_name("CON"));
+        assert!(is_windows_reserved_name("con"));
+        assert!(is_windows_reserved_name("nul.txt"));
+        assert!(is_windows_reserved_name("Aux.tar.gz"));
+        assert!(is_windows_reserved_name("PRN "));
+        assert!(is_windows_reserved_name("com1"));
+        assert!(is_windows_reserved_name("LPT9.log"));
+        assert!(is_windows_reserved_name("COM\u{b9}"));
+        assert!(is_windows_reserved_name("conin$"));
+        assert!(!is_windows_reserved_name("CONSOLE"));
+        assert!(!is_windows_reserved_name("null"));
+        assert!(!is_windows_reserved_name("xcon"));
+        assert!(!is_windows_reserved_name("COM10"));
+        assert!(!is_windows_reserved_name("LPT"));
+        assert!(!is_windows_reserved_name(".con"));
+        assert!(!is_windows_reserved_name("\u{e9}"));
+
+        assert_eq!(
+            RepoPathComponent::new_unchecked("nul.txt")
+                .to_fs_name()
+                .is_err(),
+            cfg!(windows)
+        );
+        assert_eq!(
+            RepoPathComponent::new_unchecked("nul.txt").is_reserved_fs_name(),
+            cfg!(windows)
+        );
+        assert!(!RepoPathComponent::new_unchecked("null.txt").is_reserved_fs_name());
+        assert_eq!(
+            repo_path("dir/CON/file")
+                .to_fs_path(Path::new("base"))
+                .is_err(),
+            cfg!(windows)
+        );
+    }
+
     #[test]
     fn test_to_fs_path_unchecked() {
         assert_eq!(
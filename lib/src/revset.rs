@@ -79,7 +79,10 @@ pub enum RevsetResolutionError {
         candidates: Vec<String>,
     },
     #[error("Workspace `{}` doesn't have a working-copy commit", name.as_symbol())]
-    WorkspaceMissingWorkingCopy { name: WorkspaceNameBuf },
+    WorkspaceMissingWorkingCopy {
+        name: WorkspaceNameBuf,
+        candidates: Vec<String>,
+    },
     #[error("An empty string is not a valid revision")]
     EmptyString,
     #[error("Commit ID prefix `{0}` is ambiguous")]
@@ -633,6 +636,7 @@ impl<St: ExpressionState<CommitRef = RevsetCommitRef>> RevsetExpression<St> {
 impl UserRevsetExpression {
     /// Resolve a user-provided expression. Symbols will be resolved using the
     /// provided [`SymbolResolver`].
+    #[tracing::instrument(skip_all)]
     pub fn resolve_user_expression(
         &self,
         repo: &dyn Repo,
@@ -644,10 +648,15 @@ impl UserRevsetExpression {
 
 impl ResolvedRevsetExpression {
     /// Optimizes and evaluates this expression.
+    #[tracing::instrument(skip_all)]
     pub fn evaluate<'index>(
         self: Arc<Self>,
         repo: &'index dyn Repo,
     ) -> Result<Box<dyn Revset + 'index>, RevsetEvaluationError> {
+        // Load the index upfront to report a read error instead of panicking.
+        repo.base_repo()
+            .load_index()
+            .map_err(|err| RevsetEvaluationError::Other(err.into()))?;
         let expr = optimize(self).to_backend_expression(repo);
         repo.index().evaluate_revset(&expr, repo.store())
     }
@@ -656,10 +665,15 @@ impl ResolvedRevsetExpression {
     ///
     /// Use this function if `self` is already optimized, or to debug
     /// optimization pass.
+    #[tracing::instrument(skip_all)]
     pub fn evaluate_unoptimized<'index>(
         self: &Arc<Self>,
         repo: &'index dyn Repo,
     ) -> Result<Box<dyn Revset + 'index>, RevsetEvaluationError> {
+        // Load the index upfront to report a read error instead of panicking.
+        repo.base_repo()
+            .load_index()
+            .map_err(|err| RevsetEvaluationError::Other(err.into()))?;
         // Since referenced commits change the evaluation result, they must be
         // collected no matter if optimization is disabled.
         let expr = resolve_referenced_commits(self)
@@ -760,6 +774,18 @@ pub type RevsetFunction = fn(
     &LoweringContext,
 ) -> Result<Arc<UserRevsetExpression>, RevsetParseError>;
 
+/// Revset function registered by extension.
+///
+/// Unlike [`RevsetFunction`], this may capture state, such as a client of an
+/// external service providing the set of commits.
+pub type RevsetFunctionExtension = dyn Fn(
+        &mut RevsetDiagnostics,
+        &FunctionCallNode,
+        &LoweringContext,
+    ) -> Result<Arc<UserRevsetExpression>, RevsetParseError>
+    + Send
+    + Sync;
+
 static BUILTIN_FUNCTION_MAP: LazyLock<HashMap<&str, RevsetFunction>> = LazyLock::new(|| {
     // Not using maplit::hashmap!{} or custom declarative macro here because
     // code completion inside macro is quite restricted.
@@ -1192,13 +1218,19 @@ fn lower_function_call(
     context: &LoweringContext,
 ) -> Result<Arc<UserRevsetExpression>, RevsetParseError> {
     let function_map = &context.extensions.function_map;
+    let custom_function_map = &context.extensions.custom_function_map;
     if let Some(func) = function_map.get(function.name) {
         func(diagnostics, function, context)
+    } else if let Some(func) = custom_function_map.get(function.name) {
+        func(diagnostics, function, context)
     } else {
         Err(RevsetParseError::with_span(
             RevsetParseErrorKind::NoSuchFunction {
                 name: function.name.to_owned(),
-                candidates: collect_similar(function.name, function_map.keys()),
+                candidates: collect_similar(
+                    function.name,
+                    function_map.keys().chain(custom_function_map.keys()),
+                ),
             },
             function.name_span,
         ))
@@ -2537,9 +2569,17 @@ fn all_formatted_bookmark_symbols(
 }
 
 fn make_no_such_symbol_error(repo: &dyn Repo, name: String) -> RevsetResolutionError {
-    // TODO: include tags?
+    let view = repo.view();
     let bookmark_names = all_formatted_bookmark_symbols(repo, name.contains('@'));
-    let candidates = collect_similar(&name, bookmark_names);
+    let tag_names = view.tags().keys().map(|name| format_symbol(name.as_str()));
+    let workspace_symbols = view
+        .wc_commit_ids()
+        .keys()
+        .map(|name| format!("{}@", format_symbol(name.as_str())));
+    let candidates = collect_similar(
+        &name,
+        bookmark_names.chain(tag_names).chain(workspace_symbols),
+    );
     RevsetResolutionError::NoSuchRevision { name, candidates }
 }
 
@@ -2613,6 +2653,9 @@ impl CommitPrefixResolver<'_> {
         repo: &dyn Repo,
         prefix: &HexPrefix,
     ) -> Result<Option<CommitId>, RevsetResolutionError> {
+        repo.base_repo()
+            .load_index()
+            .map_err(|err| RevsetResolutionError::Other(err.into()))?;
         let index = self
             .context
             .map(|ctx| ctx.populate(self.context_repo))
@@ -2654,6 +2697,9 @@ impl ChangePrefixResolver<'_> {
         repo: &dyn Repo,
         prefix: &HexPrefix,
     ) -> Result<Option<Vec<CommitId>>, RevsetResolutionError> {
+        repo.base_repo()
+            .load_index()
+            .map_err(|err| RevsetResolutionError::Other(err.into()))?;
         let index = self
             .context
             .map(|ctx| ctx.populate(self.context_repo))
@@ -2793,7 +2839,18 @@ fn resolve_commit_ref(
             if let Some(commit_id) = repo.view().get_wc_commit_id(name) {
                 Ok(vec![commit_id.clone()])
             } else {
-                Err(RevsetResolutionError::WorkspaceMissingWorkingCopy { name: name.clone() })
+                let workspace_symbols = repo
+                    .view()
+                    .wc_commit_ids()
+                    .keys()
+                    .map(|name| format!("{}@", format_symbol(name.as_str())));
+                Err(RevsetResolutionError::WorkspaceMissingWorkingCopy {
+                    name: name.clone(),
+                    candidates: collect_similar(
+                        &format!("{}@", format_symbol(name.as_str())),
+                        workspace_symbols,
+                    ),
+                })
             }
         }
         RevsetCommitRef::WorkingCopies => {
@@ -3292,6 +3349,7 @@ impl<I: Iterator<Item = Result<CommitId, RevsetEvaluationError>>> Iterator
 pub struct RevsetExtensions {
     symbol_resolvers: Vec<Box<dyn SymbolResolverExtension>>,
     function_map: HashMap<&'static str, RevsetFunction>,
+    custom_function_map: HashMap<&'static str, Box<RevsetFunctionExtension>>,
 }
 
 impl Default for RevsetExtensions {
@@ -3305,6 +3363,7 @@ impl RevsetExtensions {
         Self {
             symbol_resolvers: vec![],
             function_map: BUILTIN_FUNCTION_MAP.clone(),
+            custom_function_map: HashMap::new(),
         }
     }
 
@@ -3316,12 +3375,30 @@ impl RevsetExtensions {
         self.symbol_resolvers.push(symbol_resolver);
     }
 
-    pub fn add_custom_function(&mut self, name: &'static str, func: RevsetFunction) {
-        match self.function_map.entry(name) {
+    /// Registers revset function of the `name`.
+    ///
+    /// The function is called when the expression is parsed, and may capture
+    /// state such as a client of an external service. Panics if a function of
+    /// the same name has already been registered.
+    pub fn add_custom_function<F>(&mut self, name: &'static str, func: F)
+    where
+        F: Fn(
+                &mut RevsetDiagnostics,
+                &FunctionCallNode,
+                &LoweringContext,
+            ) -> Result<Arc<UserRevsetExpression>, RevsetParseError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        if self.function_map.contains_key(name) {
+            panic!("Conflict registering revset function '{name}'");
+        }
+        match self.custom_function_map.entry(name) {
             hash_map::Entry::Occupied(_) => {
                 panic!("Conflict registering revset function '{name}'")
             }
-            hash_map::Entry::Vacant(v) => v.insert(func),
+            hash_map::Entry::Vacant(v) => v.insert(Box::new(func)),
         };
     }
 }
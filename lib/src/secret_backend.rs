@@ -178,6 +178,10 @@ impl Backend for SecretBackend {
         self.inner.read_tree(path, id).await
     }
 
+    async fn read_trees(&self, ids: &[(&RepoPath, &TreeId)]) -> BackendResult<Vec<Tree>> {
+        self.inner.read_trees(ids).await
+    }
+
     async fn write_tree(&self, path: &RepoPath, contents: &Tree) -> BackendResult<TreeId> {
         self.inner.write_tree(path, contents).await
     }
@@ -0,0 +1,320 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Providers of secrets such as remote credentials and signing key
+//! passphrases.
+//!
+//! Secrets are looked up by key. Remote credentials use `remote/<name>`, and
+//! signing key passphrases use `signing/<key>`.
+
+use std::env;
+use std::ffi::OsString;
+use std::fmt::Debug;
+use std::process::Command;
+use std::process::ExitStatus;
+use std::process::Stdio;
+use std::sync::Arc;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::config::ConfigGetError;
+use crate::config::ConfigGetResultExt as _;
+use crate::settings::UserSettings;
+
+/// Error that may occur while looking up a secret.
+#[derive(Debug, Error)]
+pub enum SecretProviderError {
+    /// The helper program couldn't be run.
+    #[error("Failed to run secret helper {}", program.to_string_lossy())]
+    Io {
+        /// Helper program.
+        program: OsString,
+        /// Underlying error.
+        #[source]
+        error: std::io::Error,
+    },
+    /// The helper program exited with an error.
+    #[error("Secret helper {} failed with {exit_status}:\n{stderr}", program.to_string_lossy())]
+    Command {
+        /// Helper program.
+        program: OsString,
+        /// Exit status of the helper.
+        exit_status: ExitStatus,
+        /// Error output of the helper.
+        stderr: String,
+    },
+    /// The secret isn't valid UTF-8.
+    #[error("Secret for {key} is not valid UTF-8")]
+    InvalidUtf8 {
+        /// Key of the secret.
+        key: String,
+    },
+}
+
+/// Source of secrets.
+pub trait SecretProvider: Debug + Send + Sync {
+    /// Name of the provider, for display purposes.
+    fn name(&self) -> &str;
+
+    /// Looks up the secret of the given `key`. Returns `None` if the secret
+    /// isn't stored in this provider.
+    fn get_secret(&self, key: &str) -> Result<Option<String>, SecretProviderError>;
+}
+
+/// Reads secrets from `JJ_SECRET_<KEY>` environment variables.
+///
+/// The key is converted to upper case, and characters other than ASCII
+/// alphanumerics are replaced with `_`. For example, the secret of
+/// `remote/origin` is read from `JJ_SECRET_REMOTE_ORIGIN`.
+#[derive(Debug, Default)]
+pub struct EnvSecretProvider;
+
+impl EnvSecretProvider {
+    /// Returns the name of the environment variable storing the secret of the
+    /// given `key`.
+    pub fn var_name(key: &str) -> String {
+        let suffix: String = key
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        format!("JJ_SECRET_{suffix}")
+    }
+}
+
+impl SecretProvider for EnvSecretProvider {
+    fn name(&self) -> &str {
+        "env"
+    }
+
+    fn get_secret(&self, key: &str) -> Result<Option<String>, SecretProviderError> {
+        match env::var(Self::var_name(key)) {
+            Ok(value) => Ok(Some(value)),
+            Err(env::VarError::NotPresent) => Ok(None),
+            Err(env::VarError::NotUnicode(_)) => Err(SecretProviderError::InvalidUtf8 {
+                key: key.to_owned(),
+            }),
+        }
+    }
+}
+
+/// Runs an external helper as `<program> <args>... get <key>`, and reads the
+/// secret from its standard output.
+///
+/// A trailing newline is removed from the output. Empty output means the
+/// secret isn't stored.
+#[derive(Debug)]
+pub struct CommandSecretProvider {
+    program: OsString,
+    args: Vec<OsString>,
+}
+
+impl CommandSecretProvider {
+    /// Creates provider that runs the given helper program.
+    pub fn new(program: impl Into<OsString>, args: Vec<OsString>) -> Self {
+        Self {
+            program: program.into(),
+            args,
+        }
+    }
+
+    fn run(&self, args: &[&str], key: &str) -> Result<Option<String>, SecretProviderError> {
+        let mut command = Command::new(&self.program);
+        // Hide console window on Windows (https://stackoverflow.com/a/60958956)
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt as _;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            command.creation_flags(CREATE_NO_WINDOW);
+        }
+        command
+            .args(&self.args)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        // Don't log the output, which contains the secret.
+        tracing::info!(?command, "running secret helper command");
+        let output = command.output().map_err(|error| SecretProviderError::Io {
+            program: self.program.clone(),
+            error,
+        })?;
+        tracing::info!(?command, ?output.status, "secret helper command exited");
+        if !output.status.success() {
+            return Err(SecretProviderError::Command {
+                program: self.program.clone(),
+                exit_status: output.status,
+                stderr: String::from_utf8_lossy(&output.stderr).trim_end().into(),
+            });
+        }
+        let mut secret =
+            String::from_utf8(output.stdout).map_err(|_| SecretProviderError::InvalidUtf8 {
+                key: key.to_owned(),
+            })?;
+        if secret.ends_with('\n') {
+            secret.pop();
+            if secret.ends_with('\r') {
+                secret.pop();
+            }
+        }
+        Ok((!secret.is_empty()).then_some(secret))
+    }
+}
+
+impl SecretProvider for CommandSecretProvider {
+    fn name(&self) -> &str {
+        "command"
+    }
+
+    fn get_secret(&self, key: &str) -> Result<Option<String>, SecretProviderError> {
+        self.run(&["get", key], key)
+    }
+}
+
+/// Reads secrets from the OS keychain, stored under the service name `jj` and
+/// the account name of the key.
+///
+/// On macOS, the login keychain is queried by the `security` command. On
+/// other platforms, the Secret Service (e.g. GNOME Keyring or KWallet) is
+/// queried by the `secret-tool` command.
+#[derive(Debug)]
+pub struct KeychainSecretProvider {
+    inner: CommandSecretProvider,
+}
+
+impl KeychainSecretProvider {
+    /// Creates provider that queries the keychain of the current platform.
+    pub fn new() -> Self {
+        let program = if cfg!(target_os = "macos") {
+            "security"
+        } else {
+            "secret-tool"
+        };
+        Self {
+            inner: CommandSecretProvider::new(program, vec![]),
+        }
+    }
+}
+
+impl Default for KeychainSecretProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SecretProvider for KeychainSecretProvider {
+    fn name(&self) -> &str {
+        "keychain"
+    }
+
+    fn get_secret(&self, key: &str) -> Result<Option<String>, SecretProviderError> {
+        let result = if cfg!(target_os = "macos") {
+            self.inner
+                .run(&["find-generic-password", "-s", "jj", "-a", key, "-w"], key)
+        } else {
+            self.inner
+                .run(&["lookup", "service", "jj", "account", key], key)
+        };
+        match result {
+            // Both commands exit with 1 if the secret isn't found.
+            Err(SecretProviderError::Command { exit_status, .. })
+                if exit_status.code() == Some(1) =>
+            {
+                Ok(None)
+            }
+            result => result,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SecretHelperConfig {
+    Name(String),
+    Command(Vec<String>),
+}
+
+/// Creates secret provider configured by `auth.helper`. Returns `None` if the
+/// helper isn't configured.
+///
+/// The helper is either `"env"`, `"keychain"`, or a command to run.
+pub fn secret_provider_from_settings(
+    settings: &UserSettings,
+) -> Result<Option<Arc<dyn SecretProvider>>, ConfigGetError> {
+    let Some(config) = settings
+        .get::<SecretHelperConfig>("auth.helper")
+        .optional()?
+    else {
+        return Ok(None);
+    };
+    let mut args: Vec<OsString> = match config {
+        SecretHelperConfig::Name(name) if name == "env" => {
+            return Ok(Some(Arc::new(EnvSecretProvider)));
+        }
+        SecretHelperConfig::Name(name) if name == "keychain" => {
+            return Ok(Some(Arc::new(KeychainSecretProvider::new())));
+        }
+        SecretHelperConfig::Name(command) => {
+            command.split_whitespace().map(OsString::from).collect()
+        }
+        SecretHelperConfig::Command(args) => args.into_iter().map(OsString::from).collect(),
+    };
+    if args.is_empty() {
+        return Ok(None);
+    }
+    let program = args.remove(0);
+    Ok(Some(Arc::new(CommandSecretProvider::new(program, args))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_var_name() {
+        assert_eq!(
+            EnvSecretProvider::var_name("remote/origin"),
+            "JJ_SECRET_REMOTE_ORIGIN"
+        );
+        assert_eq!(
+            EnvSecretProvider::var_name("signing/me@example.com"),
+            "JJ_SECRET_SIGNING_ME_EXAMPLE_COM"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_provider() {
+        let provider = CommandSecretProvider::new("echo", vec!["secret".into()]);
+        assert_eq!(
+            provider.get_secret("remote/origin").unwrap().as_deref(),
+            Some("secret get remote/origin")
+        );
+
+        let provider = CommandSecretProvider::new("true", vec![]);
+        assert_eq!(provider.get_secret("remote/origin").unwrap(), None);
+
+        let provider = CommandSecretProvider::new("false", vec![]);
+        assert!(matches!(
+            provider.get_secret("remote/origin"),
+            Err(SecretProviderError::Command { .. })
+        ));
+    }
+}
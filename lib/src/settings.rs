@@ -35,6 +35,8 @@ use crate::config::ConfigValue;
 use crate::config::StackedConfig;
 use crate::config::ToConfigNamePath;
 use crate::fmt_util::binary_prefix;
+use crate::secret_provider::SecretProvider;
+use crate::secret_provider::secret_provider_from_settings;
 use crate::signing::SignBehavior;
 
 #[derive(Debug, Clone)]
@@ -63,6 +65,8 @@ pub struct GitSettings {
     pub executable_path: PathBuf,
     pub write_change_id_header: bool,
     pub colocate: bool,
+    /// Provider of remote credentials configured by `auth.helper`.
+    pub secret_provider: Option<Arc<dyn SecretProvider>>,
 }
 
 impl GitSettings {
@@ -73,6 +77,7 @@ impl GitSettings {
             executable_path: settings.get("git.executable-path")?,
             write_change_id_header: settings.get("git.write-change-id-header")?,
             colocate: settings.get("git.colocate")?,
+            secret_provider: secret_provider_from_settings(settings)?,
         })
     }
 }
@@ -27,6 +27,7 @@ use crate::config::ConfigGetError;
 use crate::gpg_signing::GpgBackend;
 use crate::gpg_signing::GpgsmBackend;
 use crate::settings::UserSettings;
+use crate::sigstore_signing::SigstoreBackend;
 use crate::ssh_signing::SshBackend;
 use crate::store::COMMIT_CACHE_CAPACITY;
 #[cfg(feature = "testing")]
@@ -178,6 +179,9 @@ impl Signer {
     /// Creates a signer based on user settings. Uses all known backends, and
     /// chooses one of them to be used for signing depending on the config.
     pub fn from_settings(settings: &UserSettings) -> Result<Self, SignInitError> {
+        let backend_name = settings
+            .signing_backend()
+            .map_err(SignInitError::BackendConfig)?;
         let mut backends: Vec<Box<dyn SigningBackend>> = vec![
             Box::new(GpgBackend::from_settings(settings).map_err(SignInitError::BackendConfig)?),
             Box::new(GpgsmBackend::from_settings(settings).map_err(SignInitError::BackendConfig)?),
@@ -185,10 +189,16 @@ impl Signer {
             #[cfg(feature = "testing")]
             Box::new(TestSigningBackend),
         ];
+        // Sigstore signatures use the same armor as gpgsm. Unless sigstore is
+        // configured, gitsign might not be installed, so don't fall back to it
+        // for signatures unknown to gpgsm.
+        if backend_name.as_deref() == Some("sigstore") {
+            backends.push(Box::new(
+                SigstoreBackend::from_settings(settings).map_err(SignInitError::BackendConfig)?,
+            ));
+        }
 
-        let main_backend = settings
-            .signing_backend()
-            .map_err(SignInitError::BackendConfig)?
+        let main_backend = backend_name
             .map(|backend| {
                 backends
                     .iter()
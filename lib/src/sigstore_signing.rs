@@ -0,0 +1,126 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Keyless signing with [sigstore](https://www.sigstore.dev/) through
+//! [gitsign](https://github.com/sigstore/gitsign).
+//!
+//! gitsign implements the command-line interface of gpgsm, so signatures are
+//! created and verified the same way as with the gpgsm backend. The signing
+//! certificate is issued for the identity of the OIDC login instead of a
+//! locally stored key.
+
+use std::ffi::OsString;
+use std::io::Write as _;
+use std::process::Command;
+use std::process::Stdio;
+
+use crate::config::ConfigGetError;
+use crate::gpg_signing::GpgError;
+use crate::gpg_signing::parse_gpg_verify_output;
+use crate::gpg_signing::run_sign_command;
+use crate::gpg_signing::run_verify_command;
+use crate::settings::UserSettings;
+use crate::signing::SignError;
+use crate::signing::SigningBackend;
+use crate::signing::Verification;
+
+/// Signing backend which runs gitsign.
+#[derive(Debug)]
+pub struct SigstoreBackend {
+    program: OsString,
+    extra_args: Vec<OsString>,
+}
+
+impl SigstoreBackend {
+    /// Creates backend which runs the gitsign `program`.
+    pub fn new(program: OsString) -> Self {
+        Self {
+            program,
+            extra_args: vec![],
+        }
+    }
+
+    /// Primarily intended for testing
+    pub fn with_extra_args(mut self, args: &[OsString]) -> Self {
+        self.extra_args.extend_from_slice(args);
+        self
+    }
+
+    /// Creates backend from the `signing.backends.sigstore` settings.
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        let program = settings.get_string("signing.backends.sigstore.program")?;
+        Ok(Self::new(program.into()))
+    }
+
+    fn create_command(&self) -> Command {
+        let mut command = Command::new(&self.program);
+        // Hide console window on Windows (https://stackoverflow.com/a/60958956)
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt as _;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            command.creation_flags(CREATE_NO_WINDOW);
+        }
+
+        command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .args(&self.extra_args);
+        command
+    }
+}
+
+impl SigningBackend for SigstoreBackend {
+    fn name(&self) -> &str {
+        "sigstore"
+    }
+
+    fn can_read(&self, signature: &[u8]) -> bool {
+        // Same armor as gpgsm. The signer only uses this backend if it's
+        // configured, so gitsign should be installed.
+        signature.starts_with(b"-----BEGIN SIGNED MESSAGE-----")
+    }
+
+    fn sign(&self, data: &[u8], key: Option<&str>) -> Result<Vec<u8>, SignError> {
+        // Keyless signing doesn't need a key, but gitsign accepts one to
+        // select the identity, like git does with user.signingKey.
+        let mut command = self.create_command();
+        command.arg("-bsa");
+        if let Some(key) = key {
+            command.args(["-u", key]);
+        }
+        Ok(run_sign_command(&mut command, None, data)?)
+    }
+
+    fn verify(&self, data: &[u8], signature: &[u8]) -> Result<Verification, SignError> {
+        let mut signature_file = tempfile::Builder::new()
+            .prefix(".jj-sigstore-sig-tmp-")
+            .tempfile()
+            .map_err(GpgError::Io)?;
+        signature_file.write_all(signature).map_err(GpgError::Io)?;
+        signature_file.flush().map_err(GpgError::Io)?;
+
+        let sig_path = signature_file.into_temp_path();
+
+        let output = run_verify_command(
+            self.create_command()
+                .args(["--status-fd=1", "--verify"])
+                .arg(&sig_path)
+                .arg("-"),
+            data,
+        )?;
+
+        parse_gpg_verify_output(&output, false)
+    }
+}
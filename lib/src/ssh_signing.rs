@@ -39,6 +39,7 @@ pub struct SshBackend {
     program: OsString,
     allowed_signers: Option<OsString>,
     revocation_list: Option<OsString>,
+    default_key_command: Option<Vec<String>>,
 }
 
 #[derive(Debug, Error)]
@@ -54,6 +55,8 @@ pub enum SshError {
     Io(#[from] std::io::Error),
     #[error("Signing key required")]
     MissingKey,
+    #[error("Default key command printed no key")]
+    NoDefaultKey,
 }
 
 impl From<SshError> for SignError {
@@ -121,9 +124,17 @@ impl SshBackend {
             program,
             allowed_signers,
             revocation_list,
+            default_key_command: None,
         }
     }
 
+    /// Sets the command which prints the key to sign with if no key is
+    /// specified, e.g. `ssh-add -L` to use the first key of the SSH agent.
+    pub fn with_default_key_command(mut self, command: Vec<String>) -> Self {
+        self.default_key_command = Some(command);
+        self
+    }
+
     pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
         let program = settings.get_string("signing.backends.ssh.program")?;
 
@@ -136,12 +147,54 @@ impl SshBackend {
 
         let allowed_signers = get_expanded_path("signing.backends.ssh.allowed-signers")?;
         let revocation_list = get_expanded_path("signing.backends.ssh.revocation-list")?;
+        let default_key_command = settings
+            .get::<Vec<String>>("signing.backends.ssh.default-key-command")
+            .optional()?
+            .filter(|command| !command.is_empty());
 
-        Ok(Self::new(
+        let backend = Self::new(
             program.into(),
             allowed_signers.map(Into::into),
             revocation_list.map(Into::into),
-        ))
+        );
+        Ok(match default_key_command {
+            Some(command) => backend.with_default_key_command(command),
+            None => backend,
+        })
+    }
+
+    /// Runs the default key command, and returns the first key it printed.
+    fn default_key(&self) -> SshResult<String> {
+        let Some((program, args)) = self
+            .default_key_command
+            .as_ref()
+            .and_then(|command| command.split_first())
+        else {
+            return Err(SshError::MissingKey);
+        };
+        let mut command = Command::new(program);
+        command
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        tracing::info!(?command, "running SSH default key command");
+        let output = command.output()?;
+        if !output.status.success() {
+            return Err(SshError::Command {
+                exit_status: output.status,
+                stderr: String::from_utf8_lossy(&output.stderr).trim_end().into(),
+            });
+        }
+        let stdout = parse_utf8_string(output.stdout)?;
+        // Like git's gpg.ssh.defaultKeyCommand, public keys may be prefixed
+        // with "key::".
+        stdout
+            .lines()
+            .map(|line| line.trim())
+            .find(|line| !line.is_empty())
+            .map(|line| line.strip_prefix("key::").unwrap_or(line).to_owned())
+            .ok_or(SshError::NoDefaultKey)
     }
 
     fn create_command(&self) -> Command {
@@ -210,8 +263,13 @@ impl SigningBackend for SshBackend {
     }
 
     fn sign(&self, data: &[u8], key: Option<&str>) -> Result<Vec<u8>, SignError> {
-        let Some(key) = key else {
-            return Err(SshError::MissingKey.into());
+        let default_key;
+        let key = match key {
+            Some(key) => key,
+            None => {
+                default_key = self.default_key()?;
+                &default_key
+            }
         };
 
         // The ssh-keygen `-f` flag expects to be given a file which contains either a
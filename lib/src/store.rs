@@ -16,15 +16,21 @@
 
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::iter;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::time::SystemTime;
 
 use clru::CLruCache;
+use futures::StreamExt as _;
+use futures::future;
+use futures::stream;
 use futures::stream::BoxStream;
+use itertools::Itertools as _;
 use pollster::FutureExt as _;
 use tokio::io::AsyncRead;
+use tracing::Instrument as _;
 
 use crate::backend;
 use crate::backend::Backend;
@@ -38,7 +44,9 @@ use crate::backend::SigningFn;
 use crate::backend::SymlinkId;
 use crate::backend::TreeId;
 use crate::commit::Commit;
+use crate::conflicts::MaterializedConflictCache;
 use crate::index::Index;
+use crate::merge::Merge;
 use crate::merged_tree::MergedTree;
 use crate::repo_path::RepoPath;
 use crate::repo_path::RepoPathBuf;
@@ -50,6 +58,7 @@ use crate::tree_merge::MergeOptions;
 // commits.
 pub(crate) const COMMIT_CACHE_CAPACITY: usize = 100;
 const TREE_CACHE_CAPACITY: usize = 1000;
+const CONFLICT_CACHE_CAPACITY: usize = 100;
 
 /// Wraps the low-level backend and makes it return more convenient types. Also
 /// adds caching.
@@ -59,6 +68,7 @@ pub struct Store {
     commit_cache: Mutex<CLruCache<CommitId, Arc<backend::Commit>>>,
     tree_cache: Mutex<CLruCache<(RepoPathBuf, TreeId), Arc<backend::Tree>>>,
     merge_options: MergeOptions,
+    conflict_cache: MaterializedConflictCache,
 }
 
 impl Debug for Store {
@@ -81,6 +91,9 @@ impl Store {
             commit_cache: Mutex::new(CLruCache::new(COMMIT_CACHE_CAPACITY.try_into().unwrap())),
             tree_cache: Mutex::new(CLruCache::new(TREE_CACHE_CAPACITY.try_into().unwrap())),
             merge_options,
+            conflict_cache: MaterializedConflictCache::new(
+                CONFLICT_CACHE_CAPACITY.try_into().unwrap(),
+            ),
         })
     }
 
@@ -102,6 +115,11 @@ impl Store {
         &self.merge_options
     }
 
+    /// Cache of materialized file conflicts.
+    pub fn conflict_cache(&self) -> &MaterializedConflictCache {
+        &self.conflict_cache
+    }
+
     pub fn get_copy_records(
         &self,
         paths: Option<&[RepoPathBuf]>,
@@ -159,13 +177,49 @@ impl Store {
                 return Ok(data);
             }
         }
-        let commit = self.backend.read_commit(id).await?;
+        let commit = self
+            .backend
+            .read_commit(id)
+            .instrument(tracing::debug_span!("read_commit"))
+            .await?;
         let data = Arc::new(commit);
         let mut locked_cache = self.commit_cache.lock().unwrap();
         locked_cache.put(id.clone(), data.clone());
         Ok(data)
     }
 
+    /// Starts reading the given commits and their root trees into the cache in
+    /// the background, so they're likely cached by the time they're needed.
+    ///
+    /// This is a no-op if the backend doesn't benefit from concurrent reads.
+    /// At most half of the commit cache is filled so that prefetched objects
+    /// don't evict the ones in use. Read errors are ignored since the objects
+    /// will be read again when they're actually needed.
+    pub fn prefetch_commits(self: &Arc<Self>, mut ids: Vec<CommitId>) {
+        let concurrency = self.concurrency();
+        if concurrency <= 1 || ids.is_empty() {
+            return;
+        }
+        ids.truncate(COMMIT_CACHE_CAPACITY / 2);
+        let store = self.clone();
+        rayon::spawn(move || {
+            stream::iter(&ids)
+                .map(|id| store.prefetch_commit(id))
+                .buffer_unordered(concurrency)
+                .for_each(|_| future::ready(()))
+                .instrument(tracing::debug_span!("prefetch_commits"))
+                .block_on();
+        });
+    }
+
+    async fn prefetch_commit(&self, id: &CommitId) -> BackendResult<()> {
+        let commit = self.get_backend_commit(id).await?;
+        for tree_id in commit.root_tree.to_merge() {
+            self.get_backend_tree(RepoPath::root(), &tree_id).await?;
+        }
+        Ok(())
+    }
+
     pub async fn write_commit(
         self: &Arc<Self>,
         commit: backend::Commit,
@@ -173,7 +227,11 @@ impl Store {
     ) -> BackendResult<Commit> {
         assert!(!commit.parents.is_empty());
 
-        let (commit_id, commit) = self.backend.write_commit(commit, sign_with).await?;
+        let (commit_id, commit) = self
+            .backend
+            .write_commit(commit, sign_with)
+            .instrument(tracing::debug_span!("write_commit"))
+            .await?;
         let data = Arc::new(commit);
         {
             let mut locked_cache = self.commit_cache.lock().unwrap();
@@ -196,6 +254,45 @@ impl Store {
         Ok(Tree::new(self.clone(), dir, id.clone(), data))
     }
 
+    /// Reads the trees with the given `ids` in directory `dir`. Trees which
+    /// aren't cached are read with a single `Backend::read_trees()` call.
+    pub async fn get_trees_async(
+        self: &Arc<Self>,
+        dir: &RepoPath,
+        ids: &[&TreeId],
+    ) -> BackendResult<Vec<Tree>> {
+        let mut data: Vec<Option<Arc<backend::Tree>>> = {
+            let mut locked_cache = self.tree_cache.lock().unwrap();
+            ids.iter()
+                .map(|&id| locked_cache.get(&(dir.to_owned(), id.clone())).cloned())
+                .collect()
+        };
+        let missing_ids = iter::zip(ids, &data)
+            .filter(|(_, data)| data.is_none())
+            .map(|(&id, _)| (dir, id))
+            .collect_vec();
+        if !missing_ids.is_empty() {
+            let read_trees = self
+                .backend
+                .read_trees(&missing_ids)
+                .instrument(tracing::debug_span!("read_trees"))
+                .await?;
+            let mut read_trees = read_trees.into_iter().map(Arc::new);
+            let mut locked_cache = self.tree_cache.lock().unwrap();
+            for (&id, data) in iter::zip(ids, &mut data) {
+                if data.is_none() {
+                    let tree = read_trees.next().unwrap();
+                    locked_cache.put((dir.to_owned(), id.clone()), tree.clone());
+                    *data = Some(tree);
+                }
+            }
+        }
+        let trees = iter::zip(ids, data)
+            .map(|(&id, data)| Tree::new(self.clone(), dir.to_owned(), id.clone(), data.unwrap()))
+            .collect();
+        Ok(trees)
+    }
+
     async fn get_backend_tree(
         &self,
         dir: &RepoPath,
@@ -208,7 +305,11 @@ impl Store {
                 return Ok(data);
             }
         }
-        let data = self.backend.read_tree(dir, id).await?;
+        let data = self
+            .backend
+            .read_tree(dir, id)
+            .instrument(tracing::debug_span!("read_tree"))
+            .await?;
         let data = Arc::new(data);
         let mut locked_cache = self.tree_cache.lock().unwrap();
         locked_cache.put(key, data.clone());
@@ -229,10 +330,10 @@ impl Store {
                 Ok(MergedTree::resolved(tree))
             }
             MergedTreeId::Merge(ids) => {
-                let trees = ids
-                    .try_map_async(|id| self.get_tree_async(RepoPathBuf::root(), id))
+                let trees = self
+                    .get_trees_async(RepoPath::root(), &ids.iter().collect_vec())
                     .await?;
-                Ok(MergedTree::new(trees))
+                Ok(MergedTree::new(Merge::from_vec(trees)))
             }
         }
     }
@@ -242,7 +343,11 @@ impl Store {
         path: &RepoPath,
         tree: backend::Tree,
     ) -> BackendResult<Tree> {
-        let tree_id = self.backend.write_tree(path, &tree).await?;
+        let tree_id = self
+            .backend
+            .write_tree(path, &tree)
+            .instrument(tracing::debug_span!("write_tree"))
+            .await?;
         let data = Arc::new(tree);
         {
             let mut locked_cache = self.tree_cache.lock().unwrap();
@@ -257,7 +362,14 @@ impl Store {
         path: &RepoPath,
         id: &FileId,
     ) -> BackendResult<Pin<Box<dyn AsyncRead + Send>>> {
-        self.backend.read_file(path, id).await
+        self.backend
+            .read_file(path, id)
+            .instrument(tracing::debug_span!("read_file"))
+            .await
+    }
+
+    pub async fn file_size(&self, path: &RepoPath, id: &FileId) -> BackendResult<u64> {
+        self.backend.file_size(path, id).await
     }
 
     pub async fn write_file(
@@ -265,24 +377,39 @@ impl Store {
         path: &RepoPath,
         contents: &mut (dyn AsyncRead + Send + Unpin),
     ) -> BackendResult<FileId> {
-        self.backend.write_file(path, contents).await
+        self.backend
+            .write_file(path, contents)
+            .instrument(tracing::debug_span!("write_file"))
+            .await
     }
 
     pub async fn read_symlink(&self, path: &RepoPath, id: &SymlinkId) -> BackendResult<String> {
-        self.backend.read_symlink(path, id).await
+        self.backend
+            .read_symlink(path, id)
+            .instrument(tracing::debug_span!("read_symlink"))
+            .await
     }
 
     pub async fn write_symlink(&self, path: &RepoPath, contents: &str) -> BackendResult<SymlinkId> {
-        self.backend.write_symlink(path, contents).await
+        self.backend
+            .write_symlink(path, contents)
+            .instrument(tracing::debug_span!("write_symlink"))
+            .await
     }
 
     pub fn gc(&self, index: &dyn Index, keep_newer: SystemTime) -> BackendResult<()> {
         self.backend.gc(index, keep_newer)
     }
 
+    /// Returns true if the commit is in the cache. Mainly intended for testing.
+    pub fn is_commit_cached(&self, id: &CommitId) -> bool {
+        self.commit_cache.lock().unwrap().contains(id)
+    }
+
     /// Clear cached objects. Mainly intended for testing.
     pub fn clear_caches(&self) {
         self.commit_cache.lock().unwrap().clear();
         self.tree_cache.lock().unwrap().clear();
+        self.conflict_cache.clear();
     }
 }
@@ -33,6 +33,7 @@ use crate::backend::BackendError;
 use crate::backend::BackendResult;
 use crate::backend::TreeValue;
 use crate::config::ConfigGetError;
+use crate::content_hash::ContentHash;
 use crate::files;
 use crate::files::FileMergeHunkLevel;
 use crate::merge::Merge;
@@ -49,7 +50,7 @@ use crate::store::Store;
 use crate::tree::Tree;
 
 /// Options for tree/file conflict resolution.
-#[derive(Clone, Debug)]
+#[derive(ContentHash, Clone, Debug)]
 pub struct MergeOptions {
     /// Granularity of hunks when merging files.
     pub hunk_level: FileMergeHunkLevel,
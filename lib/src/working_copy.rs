@@ -29,6 +29,7 @@ use crate::backend::BackendError;
 use crate::backend::MergedTreeId;
 use crate::commit::Commit;
 use crate::dag_walk;
+use crate::fileset::FilesetExpression;
 use crate::gitignore::GitIgnoreError;
 use crate::gitignore::GitIgnoreFile;
 use crate::matchers::Matcher;
@@ -224,6 +225,9 @@ pub struct SnapshotOptions<'a> {
     /// (depending on implementation)
     /// return `SnapshotError::NewFileTooLarge`.
     pub max_new_file_size: u64,
+    /// Per-path overrides of `max_new_file_size`. If a new file matches any of
+    /// the filesets, the largest of the matching sizes is used instead.
+    pub max_new_file_size_overrides: Vec<(FilesetExpression, u64)>,
 }
 
 /// A callback for getting progress updates.
@@ -263,8 +267,13 @@ pub struct CheckoutStats {
     pub removed_files: u32,
     /// The number of files that were supposed to be updated or added in the
     /// working copy but were skipped because there was an untracked (probably
-    /// ignored) file in its place.
+    /// ignored) file in its place, or because the path contains a name
+    /// reserved by the filesystem.
     pub skipped_files: u32,
+    /// Paths that were skipped because they contain a name reserved by the
+    /// filesystem, such as "CON" on Windows. These are also counted in
+    /// `skipped_files`.
+    pub skipped_reserved_paths: Vec<RepoPathBuf>,
 }
 
 /// The working-copy checkout failed.
@@ -60,6 +60,8 @@ use crate::working_copy::LockedWorkingCopy;
 use crate::working_copy::WorkingCopy;
 use crate::working_copy::WorkingCopyFactory;
 use crate::working_copy::WorkingCopyStateError;
+use crate::workspace_store::SimpleWorkspaceStore;
+use crate::workspace_store::WorkspaceStoreError;
 
 #[derive(Error, Debug)]
 pub enum WorkspaceInitError {
@@ -81,6 +83,8 @@ pub enum WorkspaceInitError {
     SignInit(#[from] SignInitError),
     #[error(transparent)]
     TransactionCommit(#[from] TransactionCommitError),
+    #[error(transparent)]
+    WorkspaceStore(#[from] WorkspaceStoreError),
 }
 
 #[derive(Error, Debug)]
@@ -320,6 +324,8 @@ impl Workspace {
             )?;
             let repo_loader = repo.loader().clone();
             let workspace = Self::new(workspace_root, repo_dir, working_copy, repo_loader)?;
+            SimpleWorkspaceStore::load(workspace.repo_path())
+                .add(workspace.workspace_name(), workspace.workspace_root())?;
             Ok((workspace, repo))
         })()
         .inspect_err(|_err| {
@@ -375,6 +381,8 @@ impl Workspace {
             working_copy,
             repo.loader().clone(),
         )?;
+        SimpleWorkspaceStore::load(workspace.repo_path())
+            .add(workspace.workspace_name(), workspace.workspace_root())?;
         Ok((workspace, repo))
     }
 
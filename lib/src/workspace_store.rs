@@ -0,0 +1,197 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Records the root paths of the workspaces sharing a repo.
+//!
+//! The workspace list in the view only knows workspace names. This store maps
+//! the names to the directories the workspaces were created in, so commands
+//! can inspect workspaces other than the current one.
+
+use std::fs;
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use tempfile::NamedTempFile;
+use thiserror::Error;
+use toml_edit::DocumentMut;
+
+use crate::file_util::IoResultExt as _;
+use crate::file_util::PathError;
+use crate::file_util::persist_temp_file;
+use crate::lock::FileLock;
+use crate::lock::FileLockError;
+use crate::ref_name::WorkspaceName;
+
+/// Error that may occur while reading or updating the workspace store.
+#[derive(Debug, Error)]
+pub enum WorkspaceStoreError {
+    /// Failed to read or write the store file.
+    #[error(transparent)]
+    Path(#[from] PathError),
+    /// Failed to lock the store.
+    #[error(transparent)]
+    Lock(#[from] FileLockError),
+    /// The store file is corrupt.
+    #[error("Failed to parse workspace store {path}")]
+    Parse {
+        /// Path to the store file.
+        path: PathBuf,
+        /// Underlying error.
+        #[source]
+        error: toml_edit::TomlError,
+    },
+}
+
+/// Stores the workspace root paths in `<repo>/workspace_store/index.toml`.
+///
+/// Paths that aren't valid UTF-8 aren't recorded.
+#[derive(Debug)]
+pub struct SimpleWorkspaceStore {
+    store_dir: PathBuf,
+}
+
+impl SimpleWorkspaceStore {
+    /// Creates store of the repo at `repo_path`. The store directory is
+    /// created lazily when the first path is recorded.
+    pub fn load(repo_path: &Path) -> Self {
+        Self {
+            store_dir: repo_path.join("workspace_store"),
+        }
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.store_dir.join("index.toml")
+    }
+
+    fn read_index(&self) -> Result<DocumentMut, WorkspaceStoreError> {
+        let path = self.index_path();
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(DocumentMut::new()),
+            Err(err) => return Err(PathError { path, source: err }.into()),
+        };
+        text.parse()
+            .map_err(|error| WorkspaceStoreError::Parse { path, error })
+    }
+
+    fn write_index(&self, doc: &DocumentMut) -> Result<(), WorkspaceStoreError> {
+        let mut temp_file = NamedTempFile::new_in(&self.store_dir).context(&self.store_dir)?;
+        temp_file
+            .write_all(doc.to_string().as_bytes())
+            .context(temp_file.path())?;
+        let path = self.index_path();
+        persist_temp_file(temp_file, &path).context(&path)?;
+        Ok(())
+    }
+
+    fn update_index(
+        &self,
+        f: impl FnOnce(&mut toml_edit::Table),
+    ) -> Result<(), WorkspaceStoreError> {
+        fs::create_dir_all(&self.store_dir).context(&self.store_dir)?;
+        let _lock = FileLock::lock(self.store_dir.join("lock"))?;
+        let mut doc = self.read_index()?;
+        let paths = doc
+            .entry("paths")
+            .or_insert_with(toml_edit::table)
+            .as_table_mut();
+        match paths {
+            Some(paths) => f(paths),
+            None => {
+                let mut paths = toml_edit::Table::new();
+                f(&mut paths);
+                doc["paths"] = toml_edit::Item::Table(paths);
+            }
+        }
+        self.write_index(&doc)
+    }
+
+    /// Returns the root path of the given workspace if recorded.
+    pub fn get_workspace_path(
+        &self,
+        workspace_name: &WorkspaceName,
+    ) -> Result<Option<PathBuf>, WorkspaceStoreError> {
+        let doc = self.read_index()?;
+        let path = doc
+            .get("paths")
+            .and_then(|paths| paths.get(workspace_name.as_str()))
+            .and_then(|path| path.as_str())
+            .map(PathBuf::from);
+        Ok(path)
+    }
+
+    /// Records the root path of the given workspace, replacing the existing
+    /// record.
+    pub fn add(
+        &self,
+        workspace_name: &WorkspaceName,
+        path: &Path,
+    ) -> Result<(), WorkspaceStoreError> {
+        let Some(path) = path.to_str() else {
+            return self.forget(&[workspace_name]);
+        };
+        self.update_index(|paths| {
+            paths.insert(workspace_name.as_str(), toml_edit::value(path));
+        })
+    }
+
+    /// Removes the records of the given workspaces.
+    pub fn forget(&self, workspace_names: &[&WorkspaceName]) -> Result<(), WorkspaceStoreError> {
+        if !self.index_path().exists() {
+            return Ok(());
+        }
+        self.update_index(|paths| {
+            for name in workspace_names {
+                paths.remove(name.as_str());
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::new_temp_dir;
+
+    #[test]
+    fn test_add_forget() {
+        let temp_dir = new_temp_dir();
+        let store = SimpleWorkspaceStore::load(temp_dir.path());
+        let foo: &WorkspaceName = "foo".as_ref();
+        let bar: &WorkspaceName = "bar".as_ref();
+        assert_eq!(store.get_workspace_path(foo).unwrap(), None);
+
+        store.add(foo, Path::new("/path/to/foo")).unwrap();
+        assert_eq!(
+            store.get_workspace_path(foo).unwrap(),
+            Some(PathBuf::from("/path/to/foo"))
+        );
+
+        store.add(bar, Path::new("/path/to/bar")).unwrap();
+        store.add(foo, Path::new("/path/to/new-foo")).unwrap();
+        assert_eq!(
+            store.get_workspace_path(foo).unwrap(),
+            Some(PathBuf::from("/path/to/new-foo"))
+        );
+
+        store.forget(&[foo]).unwrap();
+        assert_eq!(store.get_workspace_path(foo).unwrap(), None);
+        assert_eq!(
+            store.get_workspace_path(bar).unwrap(),
+            Some(PathBuf::from("/path/to/bar"))
+        );
+    }
+}
@@ -7,6 +7,7 @@ fn test_no_forgotten_test_files() {
 }
 
 mod test_annotate;
+mod test_api;
 mod test_bad_locking;
 mod test_bisect;
 mod test_commit_builder;
@@ -37,6 +38,7 @@ mod test_rewrite;
 mod test_rewrite_duplicate;
 mod test_rewrite_transform;
 mod test_signing;
+mod test_sigstore_signing;
 mod test_ssh_signing;
 mod test_view;
 mod test_workspace;
@@ -13,11 +13,13 @@
 // limitations under the License.
 
 use std::fmt::Write as _;
+use std::ops::Range;
 use std::sync::Arc;
 
 use itertools::Itertools as _;
 use jj_lib::annotate::FileAnnotation;
 use jj_lib::annotate::FileAnnotator;
+use jj_lib::annotate::find_line_range_changes;
 use jj_lib::backend::CommitId;
 use jj_lib::backend::MergedTreeId;
 use jj_lib::backend::MillisSinceEpoch;
@@ -31,6 +33,7 @@ use jj_lib::repo_path::RepoPath;
 use jj_lib::revset::ResolvedRevsetExpression;
 use jj_lib::revset::RevsetExpression;
 use testutils::TestRepo;
+use testutils::TestRepoBackend;
 use testutils::create_tree;
 use testutils::read_file;
 use testutils::repo_path;
@@ -86,6 +89,30 @@ fn annotate_parent_tree(repo: &dyn Repo, commit: &Commit, file_path: &RepoPath)
     format_annotation(repo, &annotator.to_annotation())
 }
 
+fn line_range_changes(
+    repo: &dyn Repo,
+    commit: &Commit,
+    file_path: &RepoPath,
+    line_range: Range<usize>,
+) -> String {
+    let domain = RevsetExpression::all();
+    let changes = find_line_range_changes(repo, commit, file_path, line_range, &domain).unwrap();
+    let mut output = String::new();
+    for change in changes {
+        let commit = repo.store().get_commit(&change.commit_id).unwrap();
+        let desc = commit.description().trim_end();
+        let Range { start, end } = change.line_range;
+        write!(output, "{desc}: {start}..{end}").unwrap();
+        if change.parent_path != change.path {
+            let [from, to] =
+                [&change.parent_path, &change.path].map(|path| path.as_internal_file_string());
+            write!(output, " ({from} => {to})").unwrap();
+        }
+        writeln!(output).unwrap();
+    }
+    output
+}
+
 fn format_annotation(repo: &dyn Repo, annotation: &FileAnnotation) -> String {
     let mut output = String::new();
     for (origin, line) in annotation.line_origins() {
@@ -410,3 +437,131 @@ fn test_annotate_file_directory_transition() {
 
     insta::assert_snapshot!(annotate(tx.repo(), &commit2, file_path2), @"commit2:1 : 2");
 }
+
+#[test]
+fn test_line_range_changes_linear() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let root_commit_id = repo.store().root_commit_id();
+    let file_path = repo_path("file");
+
+    let mut tx = repo.start_transaction();
+    let mut create_commit = create_commit_fn(tx.repo_mut());
+    let content1 = "a\nb\nc\n";
+    let content2 = "a\nB\nc\n";
+    let content3 = "new\na\nB\nc\n";
+    let content4 = "new\na\nB\nc\nd\n";
+    let content5 = "new\na\nc\nd\n";
+    let tree1 = create_tree(repo, &[(file_path, content1)]);
+    let tree2 = create_tree(repo, &[(file_path, content2)]);
+    let tree3 = create_tree(repo, &[(file_path, content3)]);
+    let tree4 = create_tree(repo, &[(file_path, content4)]);
+    let tree5 = create_tree(repo, &[(file_path, content5)]);
+    let commit1 = create_commit("commit1", &[root_commit_id], tree1.id());
+    let commit2 = create_commit("commit2", &[commit1.id()], tree2.id());
+    let commit3 = create_commit("commit3", &[commit2.id()], tree3.id());
+    let commit4 = create_commit("commit4", &[commit3.id()], tree4.id());
+    let commit5 = create_commit("commit5", &[commit4.id()], tree5.id());
+    drop(create_commit);
+
+    // Lines inserted before the range shift it, but don't change it.
+    insta::assert_snapshot!(line_range_changes(tx.repo(), &commit4, file_path, 1..4), @"
+    commit2: 0..3
+    commit1: 0..3
+    ");
+    // Lines after the range don't change it.
+    insta::assert_snapshot!(line_range_changes(tx.repo(), &commit4, file_path, 1..2), @"commit1: 0..1");
+    // Removed lines within the range change it.
+    insta::assert_snapshot!(line_range_changes(tx.repo(), &commit5, file_path, 1..3), @"
+    commit5: 1..3
+    commit2: 0..3
+    commit1: 0..3
+    ");
+    // Added lines
+    insta::assert_snapshot!(line_range_changes(tx.repo(), &commit5, file_path, 3..4), @"commit4: 4..5");
+}
+
+#[test]
+fn test_line_range_changes_merge() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let root_commit_id = repo.store().root_commit_id();
+    let file_path = repo_path("file");
+
+    // 4    "2 1 3"
+    // |\
+    // | 3  "1 3"
+    // | |
+    // 2 |  "2 1"
+    // |/
+    // 1    "1"
+    let mut tx = repo.start_transaction();
+    let mut create_commit = create_commit_fn(tx.repo_mut());
+    let content1 = "1\n";
+    let content2 = "2\n1\n";
+    let content3 = "1\n3\n";
+    let content4 = "2\n1\n3\n";
+    let tree1 = create_tree(repo, &[(file_path, content1)]);
+    let tree2 = create_tree(repo, &[(file_path, content2)]);
+    let tree3 = create_tree(repo, &[(file_path, content3)]);
+    let tree4 = create_tree(repo, &[(file_path, content4)]);
+    let commit1 = create_commit("commit1", &[root_commit_id], tree1.id());
+    let commit2 = create_commit("commit2", &[commit1.id()], tree2.id());
+    let commit3 = create_commit("commit3", &[commit1.id()], tree3.id());
+    let commit4 = create_commit("commit4", &[commit2.id(), commit3.id()], tree4.id());
+    drop(create_commit);
+
+    // The merge is unchanged from one of the parents.
+    insta::assert_snapshot!(line_range_changes(tx.repo(), &commit4, file_path, 0..1), @"commit2: 0..1");
+    insta::assert_snapshot!(line_range_changes(tx.repo(), &commit4, file_path, 2..3), @"commit3: 1..2");
+    // The merge differs from both parents, so both of them are followed.
+    insta::assert_snapshot!(line_range_changes(tx.repo(), &commit4, file_path, 0..3), @"
+    commit4: 0..3
+    commit3: 0..2
+    commit2: 0..2
+    commit1: 0..1
+    ");
+}
+
+#[test]
+fn test_line_range_changes_renamed() {
+    // Copy records are only supported by the Git backend
+    let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_repo.repo;
+
+    let root_commit_id = repo.store().root_commit_id();
+    let file_path1 = repo_path("file1");
+    let file_path2 = repo_path("file2");
+    let file_path3 = repo_path("file3");
+
+    let mut tx = repo.start_transaction();
+    let mut create_commit = create_commit_fn(tx.repo_mut());
+    let content1 = "a\nb\nc\nd\ne\n";
+    let content2 = "a\nB\nc\nd\ne\n";
+    let content3 = "a\nB\nc\nd\nE\n";
+    let tree1 = create_tree(repo, &[(file_path1, content1)]);
+    let tree2 = create_tree(repo, &[(file_path1, content2)]);
+    let tree3 = create_tree(repo, &[(file_path2, content2)]);
+    let tree4 = create_tree(repo, &[(file_path2, content3)]);
+    let commit1 = create_commit("commit1", &[root_commit_id], tree1.id());
+    let commit2 = create_commit("commit2", &[commit1.id()], tree2.id());
+    let commit3 = create_commit("commit3", &[commit2.id()], tree3.id());
+    let commit4 = create_commit("commit4", &[commit3.id()], tree4.id());
+    let tree5 = create_tree(repo, &[(file_path3, content3)]);
+    let commit5 = create_commit("commit5", &[commit2.id()], tree5.id());
+    drop(create_commit);
+
+    // The rename doesn't change the lines, which are followed to the source.
+    insta::assert_snapshot!(line_range_changes(tx.repo(), &commit4, file_path2, 0..5), @r"
+    commit4: 0..5
+    commit2: 0..5
+    commit1: 0..5
+    ");
+    // The lines are changed along with the rename.
+    insta::assert_snapshot!(line_range_changes(tx.repo(), &commit5, file_path3, 3..5), @r"
+    commit5: 3..5 (file1 => file3)
+    commit1: 3..5
+    ");
+}
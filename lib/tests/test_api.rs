@@ -0,0 +1,147 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use assert_matches::assert_matches;
+use itertools::Itertools as _;
+use jj_lib::api;
+use jj_lib::api::Repo as _;
+use jj_lib::op_store::RefTarget;
+use testutils::TestRepoBackend;
+use testutils::TestWorkspace;
+use testutils::create_tree;
+use testutils::repo_path;
+use testutils::write_random_commit;
+use testutils::write_random_commit_with_parents;
+
+#[test]
+fn test_open_and_log() {
+    let settings = testutils::user_settings();
+    let test_workspace = TestWorkspace::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_workspace.repo;
+
+    let mut tx = repo.start_transaction();
+    let commit_a = write_random_commit(tx.repo_mut());
+    let commit_b = write_random_commit_with_parents(tx.repo_mut(), &[&commit_a]);
+    tx.repo_mut()
+        .set_local_bookmark_target("main".as_ref(), RefTarget::normal(commit_b.id().clone()));
+    tx.commit("test").unwrap();
+
+    let (workspace, repo) =
+        api::open(&settings, test_workspace.workspace.workspace_root()).unwrap();
+    let entries = api::log(repo.as_ref(), Some(&workspace), "::main").unwrap();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].commit_id, *commit_b.id());
+    assert_eq!(entries[0].parent_ids, [commit_a.id().clone()]);
+    assert_eq!(entries[0].bookmarks, ["main"]);
+    assert_eq!(entries[1].commit_id, *commit_a.id());
+    assert!(entries[1].bookmarks.is_empty());
+    assert_eq!(entries[2].commit_id, *repo.store().root_commit_id());
+
+    let wc_entries = api::log(repo.as_ref(), Some(&workspace), "@").unwrap();
+    assert_eq!(wc_entries.len(), 1);
+    assert_eq!(wc_entries[0].working_copies, ["default"]);
+
+    // "@" can't be resolved without a workspace
+    assert_matches!(
+        api::evaluate_revset(repo.as_ref(), None, "@"),
+        Err(api::Error::RevsetParse(_))
+    );
+    assert_matches!(
+        api::evaluate_revset(repo.as_ref(), None, "nonexistent"),
+        Err(api::Error::RevsetResolution(_))
+    );
+}
+
+#[test]
+fn test_describe_and_abandon() {
+    let test_workspace = TestWorkspace::init();
+    let repo = &test_workspace.repo;
+
+    let mut tx = repo.start_transaction();
+    let commit_a = write_random_commit(tx.repo_mut());
+    let commit_b = write_random_commit_with_parents(tx.repo_mut(), &[&commit_a]);
+    let repo = tx.commit("test").unwrap();
+
+    let mut tx = repo.start_transaction();
+    let new_commit_a = api::describe_commit(tx.repo_mut(), &commit_a, "new description").unwrap();
+    assert_eq!(new_commit_a.description(), "new description");
+    let repo = tx.commit("describe").unwrap();
+    let entries = api::log(repo.as_ref(), None, &commit_b.change_id().reverse_hex()).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_ne!(entries[0].commit_id, *commit_b.id());
+    assert_eq!(entries[0].change_id, *commit_b.change_id());
+    assert_eq!(entries[0].parent_ids, [new_commit_a.id().clone()]);
+
+    let mut tx = repo.start_transaction();
+    api::abandon_commits(tx.repo_mut(), &[new_commit_a]).unwrap();
+    let repo = tx.commit("abandon").unwrap();
+    let entries = api::log(repo.as_ref(), None, &commit_b.change_id().reverse_hex()).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].change_id, *commit_b.change_id());
+    assert_eq!(
+        entries[0].parent_ids,
+        [repo.store().root_commit_id().clone()]
+    );
+}
+
+#[test]
+fn test_diff_and_status() {
+    let test_workspace = TestWorkspace::init();
+    let repo = &test_workspace.repo;
+    let workspace = &test_workspace.workspace;
+
+    let tree1 = create_tree(
+        repo,
+        &[(repo_path("kept"), "a"), (repo_path("removed"), "b")],
+    );
+    let tree2 = create_tree(
+        repo,
+        &[(repo_path("kept"), "c"), (repo_path("dir/added"), "d")],
+    );
+    let mut tx = repo.start_transaction();
+    let root_commit_id = repo.store().root_commit_id().clone();
+    let commit1 = tx
+        .repo_mut()
+        .new_commit(vec![root_commit_id], tree1.id())
+        .write()
+        .unwrap();
+    let commit2 = tx
+        .repo_mut()
+        .new_commit(vec![commit1.id().clone()], tree2.id())
+        .write()
+        .unwrap();
+    tx.repo_mut()
+        .edit(workspace.workspace_name().to_owned(), &commit2)
+        .unwrap();
+    tx.repo_mut().rebase_descendants().unwrap();
+    let repo = tx.commit("test").unwrap();
+
+    let changes = api::diff(repo.as_ref(), &commit2).unwrap();
+    let changes = changes
+        .iter()
+        .map(|change| (change.path.as_str(), change.kind, change.has_conflict))
+        .collect_vec();
+    assert_eq!(
+        changes,
+        [
+            ("dir/added", api::FileChangeKind::Added, false),
+            ("kept", api::FileChangeKind::Modified, false),
+            ("removed", api::FileChangeKind::Removed, false),
+        ]
+    );
+
+    let status = api::status(repo.as_ref(), workspace).unwrap();
+    assert_eq!(status.working_copy.commit_id, *commit2.id());
+    assert_eq!(status.changes.len(), 3);
+}
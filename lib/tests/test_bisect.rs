@@ -16,10 +16,16 @@ use std::sync::Arc;
 
 use assert_matches::assert_matches;
 use jj_lib::backend::CommitId;
+use jj_lib::bisect::BISECT_RANGE_TAG;
+use jj_lib::bisect::BISECT_TAG;
+use jj_lib::bisect::BISECT_TARGET_TAG;
+use jj_lib::bisect::BisectSession;
 use jj_lib::bisect::BisectionResult;
 use jj_lib::bisect::Bisector;
 use jj_lib::bisect::Evaluation;
 use jj_lib::bisect::NextStep;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo;
 use jj_lib::revset::ResolvedRevsetExpression;
 use testutils::TestRepo;
@@ -265,3 +271,53 @@ fn test_bisect_disjoint_sets() {
         BisectionResult::Found(vec![commit2.clone(), commit1.clone()])
     );
 }
+
+#[test]
+fn test_bisect_session_in_op_log() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let commit1 = write_random_commit(tx.repo_mut());
+    let commit2 = write_random_commit(tx.repo_mut());
+    let repo = tx.commit("test").unwrap();
+    assert!(BisectSession::find(repo.operation()).unwrap().is_none());
+
+    let mut tx = repo.start_transaction();
+    tx.set_tag(BISECT_TAG.to_owned(), "start".to_owned());
+    tx.set_tag(BISECT_RANGE_TAG.to_owned(), "..".to_owned());
+    tx.set_tag(BISECT_TARGET_TAG.to_owned(), commit1.id().hex());
+    let repo = tx.commit("start").unwrap();
+    let start_op_id = repo.op_id().clone();
+
+    let mark = |repo: &Arc<ReadonlyRepo>, id: &CommitId, evaluation, target: String| {
+        let mut tx = repo.start_transaction();
+        tx.set_tag(
+            BISECT_TAG.to_owned(),
+            BisectSession::mark_tag_value(id, evaluation),
+        );
+        tx.set_tag(BISECT_TARGET_TAG.to_owned(), target);
+        tx.commit("mark").unwrap()
+    };
+    let repo = mark(&repo, commit1.id(), Evaluation::Bad, commit2.id().hex());
+    let repo = mark(&repo, commit2.id(), Evaluation::Skip, String::new());
+    // The latest mark of a commit wins
+    let repo = mark(&repo, commit1.id(), Evaluation::Good, commit2.id().hex());
+
+    let session = BisectSession::find(repo.operation()).unwrap().unwrap();
+    assert_eq!(session.start_operation.id(), &start_op_id);
+    assert_eq!(session.range, "..");
+    assert_eq!(session.target.as_ref(), Some(commit2.id()));
+    assert_eq!(
+        session.marks,
+        vec![
+            (commit2.id().clone(), Evaluation::Skip),
+            (commit1.id().clone(), Evaluation::Good),
+        ]
+    );
+
+    let mut tx = repo.start_transaction();
+    tx.set_tag(BISECT_TAG.to_owned(), "reset".to_owned());
+    let repo = tx.commit("reset").unwrap();
+    assert!(BisectSession::find(repo.operation()).unwrap().is_none());
+}
@@ -15,11 +15,15 @@
 use std::cmp::max;
 use std::sync::Arc;
 use std::thread;
+use std::time::Duration;
+use std::time::Instant;
 
+use itertools::Itertools as _;
 use jj_lib::dag_walk;
 use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo as _;
 use test_case::test_case;
+use testutils::TestRepo;
 use testutils::TestRepoBackend;
 use testutils::TestWorkspace;
 use testutils::write_random_commit;
@@ -101,3 +105,27 @@ fn test_commit_parallel_instances(backend: TestRepoBackend) {
     // initial commit.
     assert_eq!(count_non_merge_operations(&repo), num_threads + 2);
 }
+
+#[test]
+fn test_prefetch_commits() {
+    // The test backend reports concurrency > 1, so commits are prefetched
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    let store = repo.store();
+    assert!(store.concurrency() > 1);
+
+    let mut tx = repo.start_transaction();
+    let commit_ids = (0..5)
+        .map(|_| write_random_commit(tx.repo_mut()).id().clone())
+        .collect_vec();
+    tx.commit("test").unwrap();
+    store.clear_caches();
+    assert!(!commit_ids.iter().any(|id| store.is_commit_cached(id)));
+
+    store.prefetch_commits(commit_ids.clone());
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !commit_ids.iter().all(|id| store.is_commit_cached(id)) {
+        assert!(Instant::now() < deadline, "commits weren't prefetched");
+        thread::sleep(Duration::from_millis(10));
+    }
+}
@@ -14,14 +14,18 @@
 
 use indoc::indoc;
 use itertools::Itertools as _;
+use jj_lib::backend::CopyId;
 use jj_lib::backend::FileId;
+use jj_lib::backend::TreeValue;
 use jj_lib::conflicts::ConflictMarkerStyle;
 use jj_lib::conflicts::ConflictMaterializeOptions;
 use jj_lib::conflicts::MIN_CONFLICT_MARKER_LEN;
 use jj_lib::conflicts::choose_materialized_conflict_marker_len;
 use jj_lib::conflicts::extract_as_single_hunk;
+use jj_lib::conflicts::materialize_file_conflict_to_bytes;
 use jj_lib::conflicts::materialize_merge_result_to_bytes;
 use jj_lib::conflicts::parse_conflict;
+use jj_lib::conflicts::try_materialize_file_conflict_value;
 use jj_lib::conflicts::update_from_content;
 use jj_lib::files::FileMergeHunkLevel;
 use jj_lib::merge::Merge;
@@ -2377,6 +2381,76 @@ fn test_update_from_content_malformed_conflict() {
     assert_eq!(second_snapshot, new_conflict);
 }
 
+#[test]
+fn test_materialize_file_conflict_cached() {
+    let test_repo = TestRepo::init();
+    let store = test_repo.repo.store();
+
+    let path = repo_path("file");
+    let base_id = testutils::write_file(store, path, "base\n");
+    let left_id = testutils::write_file(store, path, "left\n");
+    let right_id = testutils::write_file(store, path, "right\n");
+    let conflict = Merge::from_removes_adds(
+        vec![Some(TreeValue::File {
+            id: base_id,
+            executable: false,
+            copy_id: CopyId::placeholder(),
+        })],
+        vec![
+            Some(TreeValue::File {
+                id: left_id,
+                executable: false,
+                copy_id: CopyId::placeholder(),
+            }),
+            Some(TreeValue::File {
+                id: right_id,
+                executable: false,
+                copy_id: CopyId::placeholder(),
+            }),
+        ],
+    );
+    let file = try_materialize_file_conflict_value(store, path, &conflict)
+        .block_on()
+        .unwrap()
+        .unwrap();
+    let options = |marker_style| ConflictMaterializeOptions {
+        marker_style,
+        marker_len: None,
+        merge: store.merge_options().clone(),
+    };
+    let diff_options = options(ConflictMarkerStyle::Diff);
+    let cache = store.conflict_cache();
+    assert_eq!(cache.get(&file.ids, &diff_options), None);
+
+    // The rendering is recorded on first use
+    let contents = materialize_file_conflict_to_bytes(store, &file, &diff_options);
+    assert_eq!(
+        contents,
+        materialize_merge_result_to_bytes(&file.contents, &diff_options)
+    );
+    assert_eq!(cache.get(&file.ids, &diff_options), Some(contents.clone()));
+
+    // The cached rendering is reused
+    cache.insert(&file.ids, &diff_options, "cached\n".into());
+    assert_eq!(
+        materialize_file_conflict_to_bytes(store, &file, &diff_options),
+        "cached\n"
+    );
+
+    // Other options don't share the entry
+    let git_options = options(ConflictMarkerStyle::Git);
+    assert_eq!(cache.get(&file.ids, &git_options), None);
+    assert_ne!(
+        materialize_file_conflict_to_bytes(store, &file, &git_options),
+        "cached\n"
+    );
+
+    // Entries are dropped with the other cached objects
+    store.clear_caches();
+    assert_eq!(cache.get(&file.ids, &diff_options), None);
+    assert_eq!(cache.get(&file.ids, &git_options), None);
+}
+
 fn materialize_conflict_string(
     store: &Store,
     path: &RepoPath,
@@ -34,6 +34,7 @@ use jj_lib::store::Store;
 use jj_lib::transaction::Transaction;
 use maplit::hashmap;
 use maplit::hashset;
+use pollster::FutureExt as _;
 use testutils::TestRepo;
 use testutils::TestRepoBackend;
 use testutils::commit_with_tree;
@@ -43,6 +44,7 @@ use testutils::create_tree;
 use testutils::is_external_tool_installed;
 use testutils::repo_path;
 use testutils::repo_path_buf;
+use testutils::write_file;
 use testutils::write_random_commit;
 use testutils::write_random_commit_with_parents;
 
@@ -238,6 +240,18 @@ fn test_gc() {
     assert_eq!(collect_no_gc_refs(git_repo_path), hashset! {});
 }
 
+#[test]
+fn test_file_size() {
+    let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let store = test_repo.repo.store();
+
+    let path = repo_path("file");
+    let id = write_file(store, path, "contents");
+    assert_eq!(store.file_size(path, &id).block_on().unwrap(), 8);
+    let id = write_file(store, path, "");
+    assert_eq!(store.file_size(path, &id).block_on().unwrap(), 0);
+}
+
 #[test]
 fn test_copy_detection() {
     let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
@@ -4,11 +4,14 @@ use std::io::Write as _;
 #[cfg(unix)]
 use std::os::unix::prelude::PermissionsExt as _;
 use std::process::Stdio;
+use std::sync::Arc;
 
 use assert_matches::assert_matches;
 use insta::assert_debug_snapshot;
 use jj_lib::gpg_signing::GpgBackend;
 use jj_lib::gpg_signing::GpgsmBackend;
+use jj_lib::secret_provider::SecretProvider;
+use jj_lib::secret_provider::SecretProviderError;
 use jj_lib::signing::SigStatus;
 use jj_lib::signing::SignError;
 use jj_lib::signing::SigningBackend as _;
@@ -288,6 +291,37 @@ fn gpg_signing_roundtrip_explicit_key() {
     "#);
 }
 
+#[derive(Debug)]
+struct FixedSecretProvider(&'static str);
+
+impl SecretProvider for FixedSecretProvider {
+    fn name(&self) -> &str {
+        "fixed"
+    }
+
+    fn get_secret(&self, _key: &str) -> Result<Option<String>, SecretProviderError> {
+        Ok(Some(self.0.to_owned()))
+    }
+}
+
+#[test]
+#[cfg_attr(windows, ignore = "stuck randomly on Windows CI #3140")] // FIXME
+fn gpg_signing_roundtrip_with_passphrase() {
+    gpg_guard!();
+
+    // The test key isn't protected, but the passphrase shouldn't be mixed up
+    // with the data to sign.
+    let env = GpgEnvironment::new().unwrap();
+    let backend =
+        gpg_backend(&env).with_secret_provider(Some(Arc::new(FixedSecretProvider("passphrase"))));
+    let data = b"hello world";
+    let signature = backend.sign(data, None).unwrap();
+
+    let check = backend.verify(data, &signature).unwrap();
+    assert_eq!(check.status, SigStatus::Good);
+    assert_eq!(check.key.unwrap(), "638785CB16FEA061");
+}
+
 #[test]
 #[cfg_attr(windows, ignore = "stuck randomly on Windows CI #3140")] // FIXME
 fn gpg_unknown_key() {
@@ -764,6 +764,41 @@ fn test_reindex_missing_commit() {
     assert_matches!(err, DefaultIndexStoreError::IndexCommits { op_id, .. } if op_id == *bad_op_id);
 }
 
+#[test]
+fn test_index_loaded_lazily() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let commit_a = write_random_commit(tx.repo_mut());
+    let repo = tx.commit("test").unwrap();
+
+    // Remove the index of the operation so it would have to be rebuilt
+    let op_link_path = test_repo
+        .repo_path()
+        .join("index")
+        .join("op_links")
+        .join(repo.op_id().hex());
+    let legacy_op_link_path = test_repo
+        .repo_path()
+        .join("index")
+        .join("operations")
+        .join(repo.op_id().hex());
+    fs::remove_file(&op_link_path).unwrap();
+    fs::remove_file(&legacy_op_link_path).unwrap();
+
+    // Loading the repo without the index doesn't build it
+    let repo = repo
+        .loader()
+        .load_at_without_index(repo.operation())
+        .unwrap();
+    assert!(!op_link_path.exists());
+
+    // The index is built on first access
+    assert!(repo.load_index().unwrap().as_index().has_id(commit_a.id()));
+    assert!(op_link_path.exists());
+}
+
 /// Test that .jj/repo/index/type is created when the repo is created.
 #[test]
 fn test_index_store_type() {
@@ -30,6 +30,7 @@ use jj_lib::backend::TreeId;
 use jj_lib::backend::TreeValue;
 use jj_lib::file_util::check_symlink_support;
 use jj_lib::file_util::try_symlink;
+use jj_lib::fileset::FilesetExpression;
 use jj_lib::fsmonitor::FsmonitorSettings;
 use jj_lib::gitignore::GitIgnoreFile;
 use jj_lib::local_working_copy::LocalWorkingCopy;
@@ -648,7 +649,8 @@ fn test_conflicting_changes_on_disk() {
             updated_files: 0,
             added_files: 3,
             removed_files: 0,
-            skipped_files: 3
+            skipped_files: 3,
+            skipped_reserved_paths: vec![],
         }
     );
 
@@ -859,7 +861,8 @@ fn test_materialize_snapshot_conflicted_files() {
             updated_files: 0,
             added_files: 2,
             removed_files: 0,
-            skipped_files: 0
+            skipped_files: 0,
+            skipped_reserved_paths: vec![],
         }
     );
 
@@ -1500,6 +1503,64 @@ fn test_check_out_existing_file_replaced_with_directory() {
     assert!(file_path.to_fs_path_unchecked(&workspace_root).is_dir());
 }
 
+#[test]
+fn test_check_out_reserved_file_name() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    let workspace_root = test_repo.env.root().join("workspace");
+    let state_path = test_repo.env.root().join("state");
+    std::fs::create_dir(&workspace_root).unwrap();
+    std::fs::create_dir(&state_path).unwrap();
+
+    let reserved_path = repo_path("dir/CON/file");
+    let normal_path = repo_path("dir/file");
+    let tree = create_tree(
+        repo,
+        &[(reserved_path, "reserved"), (normal_path, "normal")],
+    );
+
+    let check_out = |skip_reserved_names: bool| {
+        let tree_state_settings = TreeStateSettings {
+            skip_reserved_names,
+            ..TreeStateSettings::try_from_user_settings(repo.settings()).unwrap()
+        };
+        let mut tree_state = TreeState::init(
+            repo.store().clone(),
+            workspace_root.clone(),
+            state_path.clone(),
+            &tree_state_settings,
+        )
+        .unwrap();
+        tree_state.check_out(&tree)
+    };
+
+    let stats = check_out(true).unwrap();
+    assert!(normal_path.to_fs_path_unchecked(&workspace_root).is_file());
+    if cfg!(windows) {
+        // The reserved path is skipped, but the other file is checked out.
+        assert_eq!(stats.skipped_files, 1);
+        assert_eq!(stats.skipped_reserved_paths, [reserved_path.to_owned()]);
+    } else {
+        assert_eq!(stats.added_files, 2);
+        assert_eq!(stats.skipped_files, 0);
+        assert!(stats.skipped_reserved_paths.is_empty());
+        assert!(
+            reserved_path
+                .to_fs_path_unchecked(&workspace_root)
+                .is_file()
+        );
+    }
+
+    std::fs::remove_dir_all(&workspace_root).unwrap();
+    std::fs::create_dir(&workspace_root).unwrap();
+    let result = check_out(false);
+    if cfg!(windows) {
+        assert_matches!(result, Err(CheckoutError::InvalidRepoPath(_)));
+    } else {
+        assert!(result.is_ok());
+    }
+}
+
 #[test]
 fn test_check_out_existing_directory_symlink() {
     if !check_symlink_support().unwrap() {
@@ -2107,3 +2168,60 @@ fn test_snapshot_max_new_file_size() {
         UntrackedReason::FileTooLarge { .. }
     );
 }
+
+#[test]
+fn test_snapshot_max_new_file_size_overrides() {
+    let mut test_workspace = TestWorkspace::init();
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+    let small_path = repo_path("small");
+    let large_path = repo_path("large");
+    let asset_path = repo_path("assets/large");
+    let limit: usize = 1024;
+    std::fs::create_dir(workspace_root.join("assets")).unwrap();
+    std::fs::write(
+        small_path.to_fs_path_unchecked(&workspace_root),
+        vec![0; 10],
+    )
+    .unwrap();
+    for path in [large_path, asset_path] {
+        std::fs::write(
+            path.to_fs_path_unchecked(&workspace_root),
+            vec![0; limit + 1],
+        )
+        .unwrap();
+    }
+    // The largest of the matching limits applies, and it may be lower than the
+    // default limit.
+    let options = SnapshotOptions {
+        max_new_file_size: limit as u64,
+        max_new_file_size_overrides: vec![
+            (FilesetExpression::prefix_path(repo_path_buf("assets")), 1),
+            (
+                FilesetExpression::prefix_path(repo_path_buf("assets")),
+                u64::MAX,
+            ),
+            (FilesetExpression::file_path(small_path.to_owned()), 1),
+        ],
+        ..empty_snapshot_options()
+    };
+    let (tree, stats) = test_workspace.snapshot_with_options(&options).unwrap();
+    assert!(tree.path_value(asset_path).unwrap().is_present());
+    assert!(tree.path_value(large_path).unwrap().is_absent());
+    assert!(tree.path_value(small_path).unwrap().is_absent());
+    assert_eq!(
+        stats
+            .untracked_paths
+            .keys()
+            .map(AsRef::as_ref)
+            .collect_vec(),
+        [large_path, small_path]
+    );
+    assert_matches!(
+        stats.untracked_paths[large_path],
+        UntrackedReason::FileTooLarge { max_size: 1024, .. }
+    );
+    assert_matches!(
+        stats.untracked_paths[small_path],
+        UntrackedReason::FileTooLarge { max_size: 1, .. }
+    );
+}
@@ -80,6 +80,7 @@ fn test_sparse_checkout() {
             added_files: 0,
             removed_files: 3,
             skipped_files: 0,
+            skipped_reserved_paths: vec![],
         }
     );
     assert_eq!(
@@ -153,6 +154,7 @@ fn test_sparse_checkout() {
             added_files: 2,
             removed_files: 2,
             skipped_files: 0,
+            skipped_reserved_paths: vec![],
         }
     );
     assert_eq!(locked_wc.sparse_patterns().unwrap(), sparse_patterns);
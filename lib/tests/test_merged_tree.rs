@@ -66,6 +66,35 @@ fn diff_stream_equals_iter(tree1: &MergedTree, tree2: &MergedTree, matcher: &dyn
             .collect()
             .block_on();
     assert_eq!(stream_diff, iter_diff);
+    tree1.store().clear_caches();
+    let parallel_diff: Vec<_> = TreeDiffStreamImpl::new_parallel(trees1, trees2, matcher)
+        .map(|diff| (diff.path, diff.values.unwrap()))
+        .collect()
+        .block_on();
+    assert_eq!(parallel_diff, iter_diff);
+}
+
+#[test]
+fn test_get_trees_batch() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    let store = repo.store();
+
+    let path = repo_path("file");
+    let tree1 = create_single_tree(repo, &[(path, "1")]);
+    let tree2 = create_single_tree(repo, &[(path, "2")]);
+    let tree3 = create_single_tree(repo, &[(path, "3")]);
+
+    // Trees are returned in the requested order, whether cached or not
+    store.clear_caches();
+    store.get_tree(RepoPathBuf::root(), tree2.id()).unwrap();
+    let ids = [tree3.id(), tree2.id(), tree1.id(), tree3.id()];
+    let trees = store
+        .get_trees_async(RepoPath::root(), &ids)
+        .block_on()
+        .unwrap();
+    assert_eq!(trees.iter().map(|tree| tree.id()).collect_vec(), ids);
+    assert_eq!(trees, [&tree3, &tree2, &tree1, &tree3].map(Clone::clone));
 }
 
 /// Test that a tree built with no changes on top of an add/add conflict gets
@@ -54,6 +54,7 @@ use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::RevsetExtensions;
 use jj_lib::revset::RevsetFilterPredicate;
 use jj_lib::revset::RevsetParseContext;
+use jj_lib::revset::RevsetParseErrorKind;
 use jj_lib::revset::RevsetResolutionError;
 use jj_lib::revset::RevsetWorkspaceContext;
 use jj_lib::revset::SymbolResolver;
@@ -509,7 +510,7 @@ fn test_resolve_working_copy() {
     assert_matches!(
         RevsetExpression::working_copy(ws1.clone())
             .resolve_user_expression(tx.repo(), &symbol_resolver),
-        Err(RevsetResolutionError::WorkspaceMissingWorkingCopy { name }) if name == "ws1"
+        Err(RevsetResolutionError::WorkspaceMissingWorkingCopy { name, .. }) if name == "ws1"
     );
 
     // The error can be suppressed by present()
@@ -4648,6 +4649,68 @@ fn test_evaluate_expression_conflict() {
     );
 }
 
+#[test]
+fn test_evaluate_expression_custom_function() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let commit1 = write_random_commit(mut_repo);
+    let commit2 = write_random_commit_with_parents(mut_repo, &[&commit1]);
+    let commit3 = write_random_commit_with_parents(mut_repo, &[&commit2]);
+
+    // Function backed by state captured by the closure, like a client of an
+    // external service
+    let submitted_ids = Arc::new(vec![commit1.id().clone(), commit3.id().clone()]);
+    let mut extensions = RevsetExtensions::new();
+    extensions.add_custom_function("submitted", {
+        let submitted_ids = submitted_ids.clone();
+        move |_diagnostics, function, _context| {
+            function.expect_no_arguments()?;
+            Ok(RevsetExpression::commits(submitted_ids.to_vec()))
+        }
+    });
+
+    let settings = testutils::user_settings();
+    let context = RevsetParseContext {
+        aliases_map: &RevsetAliasesMap::default(),
+        local_variables: HashMap::new(),
+        user_email: settings.user_email(),
+        date_pattern_context: chrono::Utc::now().fixed_offset().into(),
+        extensions: &extensions,
+        workspace: None,
+    };
+    let resolve = |revset_str: &str| -> Vec<CommitId> {
+        let expression = parse(&mut RevsetDiagnostics::new(), revset_str, &context).unwrap();
+        let symbol_resolver = default_symbol_resolver(mut_repo);
+        expression
+            .resolve_user_expression(mut_repo, &symbol_resolver)
+            .unwrap()
+            .evaluate(mut_repo)
+            .unwrap()
+            .iter()
+            .map(Result::unwrap)
+            .collect()
+    };
+    assert_eq!(
+        resolve("submitted()"),
+        vec![commit3.id().clone(), commit1.id().clone()]
+    );
+    assert_eq!(
+        resolve(&format!("::{} ~ submitted() ~ root()", commit3.id())),
+        vec![commit2.id().clone()]
+    );
+
+    // Custom functions are suggested as well as the built-in ones
+    let err = parse(&mut RevsetDiagnostics::new(), "submited()", &context).unwrap_err();
+    assert_matches!(
+        err.kind(),
+        RevsetParseErrorKind::NoSuchFunction { name, candidates }
+            if name == "submited" && candidates.contains(&"submitted".to_owned())
+    );
+}
+
 #[test]
 fn test_reverse_graph() {
     let test_repo = TestRepo::init();
@@ -0,0 +1,125 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt as _;
+use std::path::Path;
+
+use indoc::indoc;
+use jj_lib::backend::CommitId;
+use jj_lib::config::ConfigLayer;
+use jj_lib::config::ConfigSource;
+use jj_lib::settings::UserSettings;
+use jj_lib::signing::SigStatus;
+use jj_lib::signing::Signer;
+use jj_lib::signing::SigningBackend as _;
+use jj_lib::sigstore_signing::SigstoreBackend;
+
+// Stand-in for gitsign which "signs" by embedding the arguments and the data in
+// the signature.
+static FAKE_GITSIGN: &str = r#"#!/bin/sh
+if [ "$2" = "--verify" ]; then
+    data=$(cat)
+    if [ "$data" = "$(sed -n 3p "$3")" ]; then
+        echo "[GNUPG:] GOODSIG 0123456789ABCDEF test@example.com"
+    else
+        echo "[GNUPG:] BADSIG 0123456789ABCDEF test@example.com"
+    fi
+else
+    data=$(cat)
+    echo "-----BEGIN SIGNED MESSAGE-----"
+    echo "$*"
+    echo "$data"
+    echo "-----END SIGNED MESSAGE-----"
+fi
+"#;
+
+fn write_fake_gitsign(dir: &Path) -> SigstoreBackend {
+    let path = dir.join("gitsign");
+    fs::write(&path, FAKE_GITSIGN).unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    SigstoreBackend::new(path.into())
+}
+
+#[test]
+fn sigstore_signing_roundtrip() {
+    let temp_dir = testutils::new_temp_dir();
+    let backend = write_fake_gitsign(temp_dir.path());
+    let data = b"hello world";
+
+    let signature = backend.sign(data, None).unwrap();
+    assert!(backend.can_read(&signature));
+    insta::assert_snapshot!(String::from_utf8(signature.clone()).unwrap(), @r"
+    -----BEGIN SIGNED MESSAGE-----
+    -bsa
+    hello world
+    -----END SIGNED MESSAGE-----
+    ");
+
+    let check = backend.verify(data, &signature).unwrap();
+    assert_eq!(check.status, SigStatus::Good);
+    assert_eq!(check.key.unwrap(), "0123456789ABCDEF");
+    assert_eq!(check.display.unwrap(), "test@example.com");
+
+    let check = backend.verify(b"invalid-commit-data", &signature).unwrap();
+    assert_eq!(check.status, SigStatus::Bad);
+}
+
+#[test]
+fn sigstore_signing_with_key() {
+    let temp_dir = testutils::new_temp_dir();
+    let backend = write_fake_gitsign(temp_dir.path());
+
+    let signature = backend
+        .sign(b"hello world", Some("someone@example.com"))
+        .unwrap();
+    insta::assert_snapshot!(String::from_utf8(signature).unwrap(), @r"
+    -----BEGIN SIGNED MESSAGE-----
+    -bsa -u someone@example.com
+    hello world
+    -----END SIGNED MESSAGE-----
+    ");
+}
+
+#[test]
+fn sigstore_signing_program_failure() {
+    let backend = SigstoreBackend::new("false".into());
+    assert!(backend.sign(b"hello world", None).is_err());
+}
+
+#[test]
+fn sigstore_not_consulted_unless_configured() {
+    let mut config = testutils::base_user_config();
+    config.add_layer(
+        ConfigLayer::parse(
+            ConfigSource::User,
+            indoc! {r#"
+                signing.backends.gpgsm.program = "true"
+                signing.backends.sigstore.program = "/nonexistent/gitsign"
+            "#},
+        )
+        .unwrap(),
+    );
+    let settings = UserSettings::from_config(config).unwrap();
+    let signer = Signer::from_settings(&settings).unwrap();
+    let signature = b"-----BEGIN SIGNED MESSAGE-----\n-----END SIGNED MESSAGE-----\n";
+
+    // gpgsm doesn't know the signer, and gitsign isn't run
+    let check = signer
+        .verify(&CommitId::from_hex("00"), b"hello world", signature)
+        .unwrap();
+    assert_eq!(check.status, SigStatus::Unknown);
+}
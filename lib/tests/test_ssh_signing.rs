@@ -221,3 +221,27 @@ fn ssh_signing_revocation_unrevoked() {
     let check = backend.verify(data, &signature).unwrap();
     assert_eq!(check.status, SigStatus::Good);
 }
+
+#[cfg(unix)]
+#[test]
+fn ssh_signing_default_key_command() {
+    let env = SshEnvironment::new().unwrap();
+    let data = b"hello world";
+
+    // Without key nor default key command
+    assert!(backend(&env).sign(data, None).is_err());
+
+    let key_path = env.private_key_path.to_str().unwrap();
+    let key_backend = backend(&env)
+        .with_default_key_command(vec!["echo".to_owned(), format!("\n{key_path}\nignored")]);
+    let signature = key_backend.sign(data, None).unwrap();
+    let check = key_backend.verify(data, &signature).unwrap();
+    assert_eq!(check.status, SigStatus::Good);
+    assert_eq!(check.display.unwrap(), "test@example.com");
+
+    let key_backend = backend(&env).with_default_key_command(vec!["true".to_owned()]);
+    assert!(key_backend.sign(data, None).is_err());
+
+    let key_backend = backend(&env).with_default_key_command(vec!["false".to_owned()]);
+    assert!(key_backend.sign(data, None).is_err());
+}
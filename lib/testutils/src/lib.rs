@@ -134,6 +134,7 @@ pub fn empty_snapshot_options() -> SnapshotOptions<'static> {
         progress: None,
         start_tracking_matcher: &EverythingMatcher,
         max_new_file_size: u64::MAX,
+        max_new_file_size_overrides: vec![],
     }
 }
 
@@ -0,0 +1,66 @@
+import os
+import pathlib
+
+class JjError(Exception): ...
+
+class Signature:
+    @property
+    def name(self) -> str: ...
+    @property
+    def email(self) -> str: ...
+    @property
+    def timestamp_millis(self) -> int: ...
+    @property
+    def tz_offset_minutes(self) -> int: ...
+
+class Commit:
+    @property
+    def commit_id(self) -> str: ...
+    @property
+    def change_id(self) -> str: ...
+    @property
+    def parent_ids(self) -> list[str]: ...
+    @property
+    def description(self) -> str: ...
+    @property
+    def author(self) -> Signature: ...
+    @property
+    def committer(self) -> Signature: ...
+    @property
+    def bookmarks(self) -> list[str]: ...
+    @property
+    def tags(self) -> list[str]: ...
+    @property
+    def working_copies(self) -> list[str]: ...
+    @property
+    def has_conflict(self) -> bool: ...
+
+class Repo:
+    @property
+    def operation_id(self) -> str: ...
+    @property
+    def workspace_root(self) -> pathlib.Path: ...
+    def reload(self) -> Repo: ...
+    def evaluate_revset(self, revset: str) -> list[str]: ...
+    def log(self, revset: str) -> list[Commit]: ...
+    def get_commit(self, commit_id: str) -> Commit: ...
+    def start_transaction(self) -> Transaction: ...
+
+class Transaction:
+    def describe(self, commit_id: str, description: str) -> str: ...
+    def abandon(self, commit_ids: list[str]) -> None: ...
+    def evaluate_revset(self, revset: str) -> list[str]: ...
+    def commit(self, description: str) -> Repo: ...
+
+def open(
+    path: str | os.PathLike[str],
+    *,
+    user_name: str | None = None,
+    user_email: str | None = None,
+) -> Repo: ...
+def init(
+    path: str | os.PathLike[str],
+    *,
+    user_name: str | None = None,
+    user_email: str | None = None,
+) -> Repo: ...
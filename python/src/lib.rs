@@ -0,0 +1,326 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Python bindings for jj-lib, built on top of [`jj_lib::api`].
+//!
+//! The extension module is imported as `jj._jj`, and re-exported by the `jj`
+//! package.
+
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use jj_lib::api;
+use jj_lib::api::Repo as _;
+use jj_lib::config::ConfigLayer;
+use jj_lib::config::ConfigSource;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::workspace::Workspace;
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+
+create_exception!(jj, JjError, PyException, "Error raised by jj operations.");
+
+fn to_py_err(err: impl std::error::Error) -> PyErr {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+        message.push_str(": ");
+        message.push_str(&err.to_string());
+        source = err.source();
+    }
+    JjError::new_err(message)
+}
+
+fn load_settings(
+    user_name: Option<String>,
+    user_email: Option<String>,
+) -> PyResult<api::UserSettings> {
+    let mut config = api::StackedConfig::with_defaults();
+    let mut layer = ConfigLayer::empty(ConfigSource::CommandArg);
+    if let Some(name) = user_name {
+        layer.set_value("user.name", name).map_err(to_py_err)?;
+    }
+    if let Some(email) = user_email {
+        layer.set_value("user.email", email).map_err(to_py_err)?;
+    }
+    config.add_layer(layer);
+    api::UserSettings::from_config(config).map_err(to_py_err)
+}
+
+fn parse_commit_id(hex: &str) -> PyResult<api::CommitId> {
+    api::CommitId::try_from_hex(hex)
+        .ok_or_else(|| JjError::new_err(format!("Invalid commit id: {hex}")))
+}
+
+/// Loads the workspace at `path` and its repo at the latest operation.
+///
+/// The user configuration isn't loaded. `user_name` and `user_email` are
+/// recorded in the commits and operations created by transactions.
+#[pyfunction]
+#[pyo3(signature = (path, *, user_name = None, user_email = None))]
+fn open(path: PathBuf, user_name: Option<String>, user_email: Option<String>) -> PyResult<Repo> {
+    let settings = load_settings(user_name, user_email)?;
+    let (workspace, repo) = api::open(&settings, &path).map_err(to_py_err)?;
+    Ok(Repo {
+        workspace: Rc::new(workspace),
+        repo,
+    })
+}
+
+/// Creates a new workspace at `path` backed by an internal Git repo, and
+/// returns its repo.
+#[pyfunction]
+#[pyo3(signature = (path, *, user_name = None, user_email = None))]
+fn init(path: PathBuf, user_name: Option<String>, user_email: Option<String>) -> PyResult<Repo> {
+    let settings = load_settings(user_name, user_email)?;
+    let (workspace, repo) = Workspace::init_internal_git(&settings, &path).map_err(to_py_err)?;
+    Ok(Repo {
+        workspace: Rc::new(workspace),
+        repo,
+    })
+}
+
+/// Author or committer of a commit.
+#[pyclass(module = "jj", frozen, get_all)]
+#[derive(Clone)]
+struct Signature {
+    name: String,
+    email: String,
+    /// Milliseconds since the Unix epoch.
+    timestamp_millis: i64,
+    /// Time zone offset in minutes.
+    tz_offset_minutes: i32,
+}
+
+#[pymethods]
+impl Signature {
+    fn __repr__(&self) -> String {
+        format!("Signature(name={:?}, email={:?})", self.name, self.email)
+    }
+}
+
+impl From<api::Signature> for Signature {
+    fn from(signature: api::Signature) -> Self {
+        Self {
+            name: signature.name,
+            email: signature.email,
+            timestamp_millis: signature.timestamp.timestamp.0,
+            tz_offset_minutes: signature.timestamp.tz_offset,
+        }
+    }
+}
+
+/// Metadata of a commit as shown by `jj log`.
+#[pyclass(module = "jj", frozen, get_all)]
+#[derive(Clone)]
+struct Commit {
+    commit_id: String,
+    change_id: String,
+    parent_ids: Vec<String>,
+    description: String,
+    author: Signature,
+    committer: Signature,
+    bookmarks: Vec<String>,
+    tags: Vec<String>,
+    working_copies: Vec<String>,
+    has_conflict: bool,
+}
+
+#[pymethods]
+impl Commit {
+    fn __repr__(&self) -> String {
+        format!(
+            "Commit(commit_id={:?}, change_id={:?})",
+            self.commit_id, self.change_id
+        )
+    }
+}
+
+impl From<api::LogEntry> for Commit {
+    fn from(entry: api::LogEntry) -> Self {
+        Self {
+            commit_id: entry.commit_id.hex(),
+            change_id: entry.change_id.reverse_hex(),
+            parent_ids: entry.parent_ids.iter().map(|id| id.hex()).collect(),
+            description: entry.description,
+            author: entry.author.into(),
+            committer: entry.committer.into(),
+            bookmarks: entry.bookmarks,
+            tags: entry.tags,
+            working_copies: entry.working_copies,
+            has_conflict: entry.has_conflict,
+        }
+    }
+}
+
+/// Workspace and its repo loaded at a certain operation.
+#[pyclass(module = "jj", unsendable)]
+struct Repo {
+    workspace: Rc<Workspace>,
+    repo: Arc<api::ReadonlyRepo>,
+}
+
+#[pymethods]
+impl Repo {
+    /// Id of the operation the repo is loaded at.
+    #[getter]
+    fn operation_id(&self) -> String {
+        self.repo.op_id().hex()
+    }
+
+    /// Path to the root of the workspace.
+    #[getter]
+    fn workspace_root(&self) -> PathBuf {
+        self.workspace.workspace_root().to_owned()
+    }
+
+    /// Loads the repo at the latest operation.
+    fn reload(&self) -> PyResult<Self> {
+        let repo = self
+            .workspace
+            .repo_loader()
+            .load_at_head()
+            .map_err(to_py_err)?;
+        Ok(Self {
+            workspace: self.workspace.clone(),
+            repo,
+        })
+    }
+
+    /// Evaluates `revset` and returns the commit ids, children before parents.
+    ///
+    /// Only the built-in revset functions are available.
+    fn evaluate_revset(&self, revset: &str) -> PyResult<Vec<String>> {
+        let ids = api::evaluate_revset(self.repo.as_ref(), Some(&self.workspace), revset)
+            .map_err(to_py_err)?;
+        Ok(ids.iter().map(|id| id.hex()).collect())
+    }
+
+    /// Evaluates `revset` and returns the commits, children before parents.
+    fn log(&self, revset: &str) -> PyResult<Vec<Commit>> {
+        let entries =
+            api::log(self.repo.as_ref(), Some(&self.workspace), revset).map_err(to_py_err)?;
+        Ok(entries.into_iter().map(Commit::from).collect())
+    }
+
+    /// Returns the commit with the full hex `commit_id`.
+    fn get_commit(&self, commit_id: &str) -> PyResult<Commit> {
+        let repo = self.repo.as_ref();
+        let commit = repo
+            .store()
+            .get_commit(&parse_commit_id(commit_id)?)
+            .map_err(to_py_err)?;
+        let entry = api::LogEntry::from_commit(repo, &commit).map_err(to_py_err)?;
+        Ok(entry.into())
+    }
+
+    /// Starts a transaction on top of the loaded operation.
+    fn start_transaction(&self) -> Transaction {
+        Transaction {
+            workspace: self.workspace.clone(),
+            tx: Some(self.repo.start_transaction()),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Repo(workspace_root={:?}, operation_id={:?})",
+            self.workspace.workspace_root(),
+            self.operation_id()
+        )
+    }
+}
+
+/// Pending changes to a repo. Nothing is recorded until `commit()` is called.
+#[pyclass(module = "jj", unsendable)]
+struct Transaction {
+    workspace: Rc<Workspace>,
+    tx: Option<api::Transaction>,
+}
+
+impl Transaction {
+    fn tx_mut(&mut self) -> PyResult<&mut api::Transaction> {
+        self.tx
+            .as_mut()
+            .ok_or_else(|| JjError::new_err("Transaction has already been committed"))
+    }
+
+    fn get_commit(&mut self, commit_id: &str) -> PyResult<api::Commit> {
+        let id = parse_commit_id(commit_id)?;
+        self.tx_mut()?
+            .repo()
+            .store()
+            .get_commit(&id)
+            .map_err(to_py_err)
+    }
+}
+
+#[pymethods]
+impl Transaction {
+    /// Sets the description of the commit, rebases its descendants, and
+    /// returns the id of the rewritten commit.
+    fn describe(&mut self, commit_id: &str, description: String) -> PyResult<String> {
+        let commit = self.get_commit(commit_id)?;
+        let new_commit = api::describe_commit(self.tx_mut()?.repo_mut(), &commit, description)
+            .map_err(to_py_err)?;
+        Ok(new_commit.id().hex())
+    }
+
+    /// Abandons the commits, and rebases their descendants onto their parents.
+    fn abandon(&mut self, commit_ids: Vec<String>) -> PyResult<()> {
+        let commits: Vec<_> = commit_ids
+            .iter()
+            .map(|id| self.get_commit(id))
+            .collect::<PyResult<_>>()?;
+        api::abandon_commits(self.tx_mut()?.repo_mut(), &commits).map_err(to_py_err)
+    }
+
+    /// Evaluates `revset` against the pending state of the repo.
+    fn evaluate_revset(&mut self, revset: &str) -> PyResult<Vec<String>> {
+        let workspace = self.workspace.clone();
+        let tx = self.tx_mut()?;
+        let ids = api::evaluate_revset(tx.repo(), Some(&workspace), revset).map_err(to_py_err)?;
+        Ok(ids.iter().map(|id| id.hex()).collect())
+    }
+
+    /// Records the changes as a new operation, and returns the repo loaded at
+    /// it.
+    ///
+    /// The working copy on disk isn't updated.
+    fn commit(&mut self, description: String) -> PyResult<Repo> {
+        let tx = self
+            .tx
+            .take()
+            .ok_or_else(|| JjError::new_err("Transaction has already been committed"))?;
+        let repo = tx.commit(description).map_err(to_py_err)?;
+        Ok(Repo {
+            workspace: self.workspace.clone(),
+            repo,
+        })
+    }
+}
+
+#[pymodule]
+fn _jj(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("JjError", m.py().get_type::<JjError>())?;
+    m.add_class::<Signature>()?;
+    m.add_class::<Commit>()?;
+    m.add_class::<Repo>()?;
+    m.add_class::<Transaction>()?;
+    m.add_function(wrap_pyfunction!(open, m)?)?;
+    m.add_function(wrap_pyfunction!(init, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,69 @@
+# Copyright 2025 The Jujutsu Authors
+#
+# Licensed under the Apache License, Version 2.0 (the "License");
+# you may not use this file except in compliance with the License.
+# You may obtain a copy of the License at
+#
+# https://www.apache.org/licenses/LICENSE-2.0
+#
+# Unless required by applicable law or agreed to in writing, software
+# distributed under the License is distributed on an "AS IS" BASIS,
+# WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+# See the License for the specific language governing permissions and
+# limitations under the License.
+
+import pytest
+
+import jj
+
+ROOT_COMMIT_ID = "0" * 40
+
+
+@pytest.fixture
+def repo(tmp_path):
+    return jj.init(tmp_path, user_name="Test User", user_email="test@example.com")
+
+
+def test_open_and_log(repo):
+    reopened = jj.open(repo.workspace_root)
+    assert reopened.operation_id == repo.operation_id
+
+    [wc_commit, root_commit] = reopened.log("::@")
+    assert wc_commit.working_copies == ["default"]
+    assert wc_commit.parent_ids == [ROOT_COMMIT_ID]
+    assert wc_commit.author.name == "Test User"
+    assert root_commit.commit_id == ROOT_COMMIT_ID
+    assert reopened.get_commit(wc_commit.commit_id).change_id == wc_commit.change_id
+    assert reopened.evaluate_revset("@") == [wc_commit.commit_id]
+
+
+def test_errors(repo, tmp_path):
+    with pytest.raises(jj.JjError):
+        repo.log("nonexistent")
+    with pytest.raises(jj.JjError):
+        repo.get_commit("not hex")
+    with pytest.raises(jj.JjError):
+        jj.open(tmp_path / "missing")
+
+
+def test_transaction(repo):
+    [wc_commit_id] = repo.evaluate_revset("@")
+    tx = repo.start_transaction()
+    new_commit_id = tx.describe(wc_commit_id, "new description\n")
+    assert tx.evaluate_revset("@") == [new_commit_id]
+    new_repo = tx.commit("describe commit")
+    with pytest.raises(jj.JjError):
+        tx.commit("twice")
+
+    assert new_repo.operation_id != repo.operation_id
+    assert new_repo.get_commit(new_commit_id).description == "new description\n"
+    assert repo.reload().operation_id == new_repo.operation_id
+    # The old repo object still sees the old operation
+    assert repo.evaluate_revset("@") == [wc_commit_id]
+
+    tx = new_repo.start_transaction()
+    tx.abandon([new_commit_id])
+    new_repo = tx.commit("abandon commit")
+    [wc_commit] = new_repo.log("@")
+    assert wc_commit.commit_id != new_commit_id
+    assert wc_commit.description == ""